@@ -17,7 +17,8 @@ use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_node::metrics;
 
 use sui_benchmark::benchmark_setup::Env;
-use sui_benchmark::options::Opts;
+use sui_benchmark::options::{Opts, RunSpec};
+use sui_benchmark::scenario::{PhaseStats, Scenario, ScenarioStats};
 
 use sui_benchmark::workloads::workload_configuration::WorkloadConfiguration;
 
@@ -49,9 +50,18 @@ use tokio::sync::Barrier;
 /// --in-flight-ratio 2 \
 /// --shared-counter 50 \
 /// --transfer-object 50```
+/// To run the phases of a scenario file one after the other and
+/// report each of them separately, use it something like:
+/// ```cargo run  --release  --package sui-benchmark
+/// --bin stress -- --num-client-threads 12 \
+/// --num-server-threads 10 \
+/// --num-transfer-accounts 2 \
+/// scenario crates/sui-benchmark/scenarios/example.yaml```
 #[tokio::main]
 async fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
+    let scenario = Scenario::from_opts(&opts)?;
+    let is_scenario = matches!(opts.run_spec, RunSpec::Scenario { .. });
 
     // TODO: query the network for the current protocol version.
     let protocol_config = match opts.protocol_version {
@@ -119,29 +129,48 @@ async fn main() -> Result<()> {
     let registry_clone = registry.clone();
     let handle = std::thread::spawn(move || {
         client_runtime.block_on(async move {
-            let workloads = WorkloadConfiguration::configure(
-                bench_setup.bank,
-                &opts,
-                system_state_observer.clone(),
-            )
-            .await?;
-            let interval = opts.run_duration;
-            // We only show continuous progress in stderr
-            // if benchmark is running in unbounded mode,
-            // otherwise summarized benchmark results are
-            // published in the end
-            let show_progress = interval.is_unbounded();
+            let mut bank = bench_setup.bank;
             let driver = BenchDriver::new(opts.stat_collection_interval, stress_stat_collection);
-            driver
-                .run(
-                    bench_setup.proxies,
-                    workloads,
-                    system_state_observer,
-                    &registry_clone,
-                    show_progress,
-                    interval,
+            let mut results = vec![];
+            for phase in scenario.phases {
+                // Stop at the current phase on ctrl-c.
+                if driver.is_terminated() {
+                    break;
+                }
+                if is_scenario {
+                    eprintln!("Running phase {}", phase.name);
+                }
+                let workloads = WorkloadConfiguration::configure(
+                    &mut bank,
+                    &opts,
+                    &phase,
+                    system_state_observer.clone(),
                 )
-                .await
+                .await?;
+                // We only show continuous progress in stderr
+                // if benchmark is running in unbounded mode,
+                // otherwise summarized benchmark results are
+                // published in the end
+                let show_progress = phase.duration.is_unbounded();
+                let (stats, stress_stats) = driver
+                    .run(
+                        bench_setup.proxies.clone(),
+                        workloads,
+                        system_state_observer.clone(),
+                        &registry_clone,
+                        show_progress,
+                        phase.duration,
+                    )
+                    .await?;
+                results.push((
+                    PhaseStats {
+                        name: phase.name,
+                        stats,
+                    },
+                    stress_stats,
+                ));
+            }
+            Ok::<_, anyhow::Error>(results)
         })
     });
     let joined = handle.join();
@@ -159,33 +188,72 @@ async fn main() -> Result<()> {
             .expect("Failed to join the server handle");
         match joined {
             Ok(result) => match result {
-                Ok((benchmark_stats, stress_stats)) => {
-                    let benchmark_table = benchmark_stats.to_table();
-                    eprintln!("Benchmark Report:");
-                    eprintln!("{}", benchmark_table);
-
-                    if stress_stat_collection {
-                        eprintln!("Stress Performance Report:");
-                        let stress_stats_table = stress_stats.to_table();
-                        eprintln!("{}", stress_stats_table);
+                Ok(results) => {
+                    for (PhaseStats { name, stats }, stress_stats) in results.iter() {
+                        let benchmark_table = stats.to_table();
+                        if is_scenario {
+                            eprintln!("Benchmark Report[{}]:", name);
+                        } else {
+                            eprintln!("Benchmark Report:");
+                        }
+                        eprintln!("{}", benchmark_table);
+
+                        if stress_stat_collection {
+                            eprintln!("Stress Performance Report:");
+                            let stress_stats_table = stress_stats.to_table();
+                            eprintln!("{}", stress_stats_table);
+                        }
                     }
+                    let scenario_stats = ScenarioStats {
+                        phases: results.into_iter().map(|(phase, _)| phase).collect(),
+                    };
 
                     if !prev_benchmark_stats_path.is_empty() {
                         let data = std::fs::read_to_string(&prev_benchmark_stats_path)?;
-                        let prev_stats: BenchmarkStats = serde_json::from_str(&data)?;
-                        let cmp = BenchmarkCmp {
-                            new: &benchmark_stats,
-                            old: &prev_stats,
-                        };
-                        let cmp_table = cmp.to_table();
-                        eprintln!(
-                            "Benchmark Comparison Report[{}]:",
-                            prev_benchmark_stats_path
-                        );
-                        eprintln!("{}", cmp_table);
+                        if is_scenario {
+                            let prev_stats: ScenarioStats = serde_json::from_str(&data)?;
+                            for PhaseStats { name, stats } in scenario_stats.phases.iter() {
+                                match prev_stats.phase(name) {
+                                    Some(prev_stats) => {
+                                        let cmp = BenchmarkCmp {
+                                            new: stats,
+                                            old: prev_stats,
+                                        };
+                                        eprintln!(
+                                            "Benchmark Comparison Report[{}][{}]:",
+                                            prev_benchmark_stats_path, name
+                                        );
+                                        eprintln!("{}", cmp.to_table());
+                                    }
+                                    None => eprintln!(
+                                        "No phase {} in {} to compare with",
+                                        name, prev_benchmark_stats_path
+                                    ),
+                                }
+                            }
+                        } else if let Some(PhaseStats { stats, .. }) = scenario_stats.phases.first()
+                        {
+                            let prev_stats: BenchmarkStats = serde_json::from_str(&data)?;
+                            let cmp = BenchmarkCmp {
+                                new: stats,
+                                old: &prev_stats,
+                            };
+                            let cmp_table = cmp.to_table();
+                            eprintln!(
+                                "Benchmark Comparison Report[{}]:",
+                                prev_benchmark_stats_path
+                            );
+                            eprintln!("{}", cmp_table);
+                        }
                     }
                     if !curr_benchmark_stats_path.is_empty() {
-                        let serialized = serde_json::to_string(&benchmark_stats)?;
+                        // Single benchmark runs keep writing a plain `BenchmarkStats`, so that
+                        // they can still be compared with the results of previous versions.
+                        let serialized = if is_scenario {
+                            serde_json::to_string(&scenario_stats)?
+                        } else {
+                            serde_json::to_string(&scenario_stats.phases[0].stats)?
+                        };
                         std::fs::write(curr_benchmark_stats_path, serialized)?;
                     }
                 }
@@ -155,13 +155,13 @@ enum NextOp {
     Retry(RetryType),
 }
 
-async fn print_and_start_benchmark() -> &'static Instant {
-    static ONCE: OnceCell<Instant> = OnceCell::const_new();
-    ONCE.get_or_init(|| async move {
-        info!("Starting benchmark!");
-        Instant::now()
-    })
-    .await
+async fn print_and_start_benchmark(start_time: &OnceCell<Instant>) -> Instant {
+    *start_time
+        .get_or_init(|| async move {
+            info!("Starting benchmark!");
+            Instant::now()
+        })
+        .await
 }
 
 pub struct BenchWorker {
@@ -175,6 +175,9 @@ pub struct BenchDriver {
     pub stress_stat_collection: bool,
     pub start_time: Instant,
     pub token: CancellationToken,
+    // Registered on the first run, so that the driver can be run several times against the
+    // same registry e.g. once per phase of a scenario.
+    metrics: OnceCell<Arc<BenchMetrics>>,
 }
 
 impl BenchDriver {
@@ -184,11 +187,15 @@ impl BenchDriver {
             stress_stat_collection,
             start_time: Instant::now(),
             token: CancellationToken::new(),
+            metrics: OnceCell::new(),
         }
     }
     pub fn terminate(&self) {
         self.token.cancel()
     }
+    pub fn is_terminated(&self) -> bool {
+        self.token.is_cancelled()
+    }
     pub fn update_progress(
         start_time: Instant,
         interval: Interval,
@@ -288,7 +295,12 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
             return Err(anyhow!("No workers to run benchmark!"));
         }
         let stat_delay_micros = 1_000_000 * self.stat_collection_interval;
-        let metrics = Arc::new(BenchMetrics::new(registry));
+        let metrics = self
+            .metrics
+            .get_or_init(|| async { Arc::new(BenchMetrics::new(registry)) })
+            .await
+            .clone();
+        let benchmark_start_time = Arc::new(OnceCell::new());
         let barrier = Arc::new(Barrier::new(num_workers as usize));
         info!("Setting up {:?} workers...", num_workers);
         let progress = Arc::new(match run_duration {
@@ -312,10 +324,11 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
             let tx_cloned = tx.clone();
             let cloned_barrier = barrier.clone();
             let metrics_cloned = metrics.clone();
+            let benchmark_start_time = benchmark_start_time.clone();
 
             let runner = tokio::spawn(async move {
                 cloned_barrier.wait().await;
-                let start_time = print_and_start_benchmark().await;
+                let start_time = print_and_start_benchmark(&benchmark_start_time).await;
                 let mut num_success_txes = 0;
                 let mut num_error_txes = 0;
                 let mut num_success_cmds = 0;
@@ -467,7 +480,7 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                             match op {
                                 NextOp::Retry(b) => {
                                     retry_queue.push_back(b);
-                                    BenchDriver::update_progress(*start_time, run_duration, progress_cloned.clone());
+                                    BenchDriver::update_progress(start_time, run_duration, progress_cloned.clone());
                                     if progress_cloned.is_finished() {
                                         break;
                                     }
@@ -478,7 +491,7 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                                     num_in_flight -= 1;
                                     free_pool.push(payload);
                                     latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
-                                    BenchDriver::update_progress(*start_time, run_duration, progress_cloned.clone());
+                                    BenchDriver::update_progress(start_time, run_duration, progress_cloned.clone());
                                     if progress_cloned.is_finished() {
                                         break;
                                     }
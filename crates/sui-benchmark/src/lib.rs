@@ -60,6 +60,7 @@ pub mod embedded_reconfig_observer;
 pub mod fullnode_reconfig_observer;
 pub mod in_memory_wallet;
 pub mod options;
+pub mod scenario;
 pub mod system_state_observer;
 pub mod util;
 pub mod workloads;
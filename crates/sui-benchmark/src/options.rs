@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::*;
+use std::path::PathBuf;
 
 use strum_macros::EnumString;
 
@@ -70,7 +71,8 @@ pub struct Opts {
     /// Otherwise use EmbeddedReconfigObserver,
     #[clap(long, parse(try_from_str), default_value = "false", global = true)]
     pub use_fullnode_for_reconfig: bool,
-    /// Default workload is 100% transfer object
+    /// Default workload is 100% transfer object. Use the `scenario`
+    /// subcommand to run several workload phases from a file.
     #[clap(subcommand)]
     pub run_spec: RunSpec,
     #[clap(long, default_value = "9091", global = true)]
@@ -148,6 +150,14 @@ pub enum RunSpec {
         // relative weight of adversarial transactions in the benchmark workload
         #[clap(long, default_value = "0")]
         adversarial: u32,
+        // relative weight of transactions creating, mutating, wrapping and
+        // deleting an object, each depending on the previous one
+        #[clap(long, default_value = "0")]
+        object_lifecycle: u32,
+        // relative weight of transactions adding, mutating and removing a
+        // dynamic field, each depending on the previous one
+        #[clap(long, default_value = "0")]
+        dynamic_fields: u32,
 
         // --- workload-specific options --- (TODO: use subcommands or similar)
         // 100 for max hotness i.e all requests target
@@ -161,6 +171,19 @@ pub enum RunSpec {
         // batch size use for batch payment workload
         #[clap(long, default_value = "15")]
         batch_payment_size: u32,
+        // relative weights of the payload types of the adversarial workload, see `PayloadType`
+        #[clap(long, default_value = "1")]
+        adversarial_max_objects: u32,
+        #[clap(long, default_value = "0")]
+        adversarial_max_reads: u32,
+        #[clap(long, default_value = "0")]
+        adversarial_max_dynamic_fields: u32,
+        #[clap(long, default_value = "0")]
+        adversarial_max_effects: u32,
+        #[clap(long, default_value = "0")]
+        adversarial_max_events: u32,
+        #[clap(long, default_value = "0")]
+        adversarial_max_event_size: u32,
 
         // --- generic options ---
         // Target qps
@@ -173,4 +196,11 @@ pub enum RunSpec {
         #[clap(long, default_value = "5", global = true)]
         in_flight_ratio: u64,
     },
+    // Run the phases of a scenario file in sequence, each with its own
+    // duration, target qps and workload mix, see `Scenario`. The phases
+    // are reported and compared separately.
+    Scenario {
+        // path to a YAML (.yaml/.yml) or TOML (.toml) scenario file
+        path: PathBuf,
+    },
 }
@@ -0,0 +1,454 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative benchmark scenarios. A scenario is a sequence of phases, each running its own
+//! workload mix at its own rate for a bounded number of transactions or amount of time, so that
+//! a run can be checked in, reproduced and compared with a previous run phase by phase.
+//!
+//! Scenarios are written in YAML or TOML (picked from the extension of the file), e.g.
+//! ```yaml
+//! phases:
+//!   - name: warmup
+//!     duration: 30s
+//!     target_qps: 100
+//!     workloads:
+//!       transfer_object: 1
+//!   - name: contention
+//!     duration: 2m
+//!     target_qps: 500
+//!     shared_counter_hotness_factor: 90
+//!     workloads:
+//!       shared_counter: 3
+//!       transfer_object: 1
+//!   - name: contract
+//!     duration: 2m
+//!     workloads:
+//!       move_call: 1
+//!     move_call:
+//!       package_path: path/to/my/package
+//!       calls:
+//!         - module: my_module
+//!           function: create
+//!         - module: my_module
+//!           function: update
+//!           arguments: [{ created: 0 }, { u64: 42 }]
+//! ```
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+use crate::drivers::{BenchmarkStats, Interval};
+use crate::options::{Opts, RunSpec};
+use crate::workloads::adversarial::PayloadType;
+use crate::workloads::move_call_chain::MoveCallChainCfg;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub phases: Vec<Phase>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    /// Name of the phase, used to label its reports and to match it with the same phase of a
+    /// previous run when comparing.
+    pub name: String,
+    /// Number of transactions or duration to run the phase for, in the same format as
+    /// `--run-duration`. Only the last phase can be "unbounded".
+    #[serde(deserialize_with = "deserialize_interval")]
+    pub duration: Interval,
+    #[serde(default = "default_target_qps")]
+    pub target_qps: u64,
+    #[serde(default = "default_num_workers")]
+    pub num_workers: u64,
+    #[serde(default = "default_in_flight_ratio")]
+    pub in_flight_ratio: u64,
+    /// Relative weights of the workloads run in this phase.
+    #[serde(default)]
+    pub workloads: WorkloadWeights,
+    #[serde(default = "default_shared_counter_hotness_factor")]
+    pub shared_counter_hotness_factor: u32,
+    #[serde(default = "default_batch_payment_size")]
+    pub batch_payment_size: u32,
+    /// Relative weights of the payload types of the adversarial workload.
+    #[serde(default)]
+    pub adversarial_payloads: AdversarialPayloadWeights,
+    /// Chain of calls to a user package run by the `move_call` workload.
+    #[serde(default)]
+    pub move_call: Option<MoveCallChainCfg>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkloadWeights {
+    pub shared_counter: u32,
+    pub transfer_object: u32,
+    pub delegation: u32,
+    pub batch_payment: u32,
+    pub adversarial: u32,
+    pub object_lifecycle: u32,
+    pub dynamic_fields: u32,
+    pub move_call: u32,
+}
+
+impl WorkloadWeights {
+    pub fn total(&self) -> u32 {
+        self.shared_counter
+            + self.transfer_object
+            + self.delegation
+            + self.batch_payment
+            + self.adversarial
+            + self.object_lifecycle
+            + self.dynamic_fields
+            + self.move_call
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdversarialPayloadWeights {
+    pub max_objects: u32,
+    pub max_reads: u32,
+    pub max_dynamic_fields: u32,
+    pub max_effects: u32,
+    pub max_events: u32,
+    pub max_event_size: u32,
+}
+
+impl Default for AdversarialPayloadWeights {
+    fn default() -> Self {
+        Self {
+            max_objects: 1,
+            max_reads: 0,
+            max_dynamic_fields: 0,
+            max_effects: 0,
+            max_events: 0,
+            max_event_size: 0,
+        }
+    }
+}
+
+impl AdversarialPayloadWeights {
+    pub fn to_payload_weights(&self) -> Vec<(PayloadType, u32)> {
+        vec![
+            (PayloadType::MaxObjects, self.max_objects),
+            (PayloadType::MaxReads, self.max_reads),
+            (PayloadType::MaxDynamicFields, self.max_dynamic_fields),
+            (PayloadType::MaxEffects, self.max_effects),
+            (PayloadType::MaxEvents, self.max_events),
+            (PayloadType::MaxEventSize, self.max_event_size),
+        ]
+    }
+}
+
+// Keep in sync with the defaults of `RunSpec::Bench`.
+fn default_target_qps() -> u64 {
+    1000
+}
+
+fn default_num_workers() -> u64 {
+    12
+}
+
+fn default_in_flight_ratio() -> u64 {
+    5
+}
+
+fn default_shared_counter_hotness_factor() -> u32 {
+    50
+}
+
+fn default_batch_payment_size() -> u32 {
+    15
+}
+
+/// Accepts a transaction count either as a number or a string, a duration such as "60s", or
+/// "unbounded".
+fn deserialize_interval<'de, D>(deserializer: D) -> Result<Interval, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawInterval {
+        Count(u64),
+        Str(String),
+    }
+    match RawInterval::deserialize(deserializer)? {
+        RawInterval::Count(count) => Ok(Interval::Count(count)),
+        RawInterval::Str(s) => s.parse().map_err(D::Error::custom),
+    }
+}
+
+impl Scenario {
+    /// The scenario to run for the given command line: the phases of the scenario file for
+    /// `RunSpec::Scenario`, or a single phase made of the `RunSpec::Bench` flags.
+    pub fn from_opts(opts: &Opts) -> Result<Self> {
+        match &opts.run_spec {
+            RunSpec::Bench {
+                shared_counter,
+                transfer_object,
+                delegation,
+                batch_payment,
+                adversarial,
+                object_lifecycle,
+                dynamic_fields,
+                shared_counter_hotness_factor,
+                batch_payment_size,
+                adversarial_max_objects,
+                adversarial_max_reads,
+                adversarial_max_dynamic_fields,
+                adversarial_max_effects,
+                adversarial_max_events,
+                adversarial_max_event_size,
+                target_qps,
+                num_workers,
+                in_flight_ratio,
+            } => Ok(Self {
+                phases: vec![Phase {
+                    name: "bench".to_string(),
+                    duration: opts.run_duration,
+                    target_qps: *target_qps,
+                    num_workers: *num_workers,
+                    in_flight_ratio: *in_flight_ratio,
+                    workloads: WorkloadWeights {
+                        shared_counter: *shared_counter,
+                        transfer_object: *transfer_object,
+                        delegation: *delegation,
+                        batch_payment: *batch_payment,
+                        adversarial: *adversarial,
+                        object_lifecycle: *object_lifecycle,
+                        dynamic_fields: *dynamic_fields,
+                        move_call: 0,
+                    },
+                    shared_counter_hotness_factor: *shared_counter_hotness_factor,
+                    batch_payment_size: *batch_payment_size,
+                    adversarial_payloads: AdversarialPayloadWeights {
+                        max_objects: *adversarial_max_objects,
+                        max_reads: *adversarial_max_reads,
+                        max_dynamic_fields: *adversarial_max_dynamic_fields,
+                        max_effects: *adversarial_max_effects,
+                        max_events: *adversarial_max_events,
+                        max_event_size: *adversarial_max_event_size,
+                    },
+                    move_call: None,
+                }],
+            }),
+            RunSpec::Scenario { path } => Self::load(path),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read scenario file {}", path.display()))?;
+        let scenario: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&data)
+                .with_context(|| format!("Failed to parse scenario file {}", path.display()))?,
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&data)
+                .with_context(|| format!("Failed to parse scenario file {}", path.display()))?,
+            _ => bail!(
+                "Scenario file {} must have a .yaml, .yml or .toml extension",
+                path.display()
+            ),
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.phases.is_empty() {
+            bail!("Scenario must have at least one phase");
+        }
+        let mut names = HashSet::new();
+        for (i, phase) in self.phases.iter().enumerate() {
+            if !names.insert(&phase.name) {
+                bail!("Duplicate phase name {:?}", phase.name);
+            }
+            if phase.workloads.total() == 0 {
+                bail!(
+                    "Phase {:?} must give a non-zero weight to at least one workload",
+                    phase.name
+                );
+            }
+            match &phase.move_call {
+                Some(move_call) => move_call
+                    .validate()
+                    .with_context(|| format!("Invalid move call of phase {:?}", phase.name))?,
+                None if phase.workloads.move_call > 0 => bail!(
+                    "Phase {:?} gives a weight to the move call workload but no move call",
+                    phase.name
+                ),
+                None => (),
+            }
+            if phase.duration.is_unbounded() && i != self.phases.len() - 1 {
+                bail!(
+                    "Phase {:?} is unbounded, only the last phase can be unbounded",
+                    phase.name
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The benchmark statistics of one phase of a scenario.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PhaseStats {
+    pub name: String,
+    pub stats: BenchmarkStats,
+}
+
+/// The benchmark statistics of a scenario run, written to `--benchmark-stats-path` for
+/// `RunSpec::Scenario` so that a later run of the same scenario can be compared phase by phase.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScenarioStats {
+    pub phases: Vec<PhaseStats>,
+}
+
+impl ScenarioStats {
+    pub fn phase(&self, name: &str) -> Option<&BenchmarkStats> {
+        self.phases
+            .iter()
+            .find(|phase| phase.name == name)
+            .map(|phase| &phase.stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn load_str(data: &str, file_name: &str) -> Result<Scenario> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        std::fs::write(&path, data).unwrap();
+        Scenario::load(&path)
+    }
+
+    fn assert_load_error(data: &str, file_name: &str, expected: &str) {
+        let err = load_str(data, file_name).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains(expected),
+            "expected {:?} in {:#}",
+            expected,
+            err
+        );
+    }
+
+    #[test]
+    fn test_load_example() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("scenarios/example.yaml");
+        let scenario = Scenario::load(&path).unwrap();
+
+        let names: Vec<_> = scenario.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["warmup", "mixed", "contention", "dependent"]);
+
+        let warmup = &scenario.phases[0];
+        assert!(matches!(warmup.duration, Interval::Time(d) if d == Duration::from_secs(30)));
+        assert_eq!(warmup.target_qps, 100);
+        assert_eq!(warmup.num_workers, 4);
+        assert_eq!(warmup.in_flight_ratio, default_in_flight_ratio());
+        assert_eq!(warmup.workloads.transfer_object, 1);
+        assert_eq!(warmup.workloads.total(), 1);
+
+        let mixed = &scenario.phases[1];
+        assert!(matches!(mixed.duration, Interval::Time(d) if d == Duration::from_secs(120)));
+        assert_eq!(mixed.num_workers, default_num_workers());
+        assert_eq!(mixed.workloads.total(), 4);
+        assert_eq!(mixed.batch_payment_size, 50);
+
+        let contention = &scenario.phases[2];
+        assert_eq!(contention.shared_counter_hotness_factor, 95);
+        assert_eq!(contention.workloads.shared_counter, 1);
+
+        let dependent = &scenario.phases[3];
+        assert_eq!(dependent.workloads.object_lifecycle, 1);
+        assert_eq!(dependent.workloads.dynamic_fields, 1);
+        assert_eq!(
+            dependent.adversarial_payloads.to_payload_weights(),
+            AdversarialPayloadWeights::default().to_payload_weights()
+        );
+        assert!(dependent.move_call.is_none());
+    }
+
+    #[test]
+    fn test_load_toml() {
+        let scenario = load_str(
+            r#"
+            [[phases]]
+            name = "count"
+            duration = 1000
+            workloads = { adversarial = 1 }
+            adversarial_payloads = { max_objects = 0, max_reads = 2 }
+
+            [[phases]]
+            name = "rest"
+            duration = "unbounded"
+            workloads = { delegation = 1 }
+            "#,
+            "scenario.toml",
+        )
+        .unwrap();
+        assert!(matches!(scenario.phases[0].duration, Interval::Count(1000)));
+        assert_eq!(scenario.phases[0].adversarial_payloads.max_objects, 0);
+        assert_eq!(scenario.phases[0].adversarial_payloads.max_reads, 2);
+        assert!(scenario.phases[1].duration.is_unbounded());
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        assert_load_error(
+            "phases: []",
+            "scenario.json",
+            "must have a .yaml, .yml or .toml extension",
+        );
+        assert_load_error("phases: []", "scenario.yaml", "at least one phase");
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: 10s\n    workloads:\n      shared_counter: 1\n    target_qsp: 10\n",
+            "scenario.yaml",
+            "unknown field `target_qsp`",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: 10s\n    workloads:\n      shared_countr: 1\n",
+            "scenario.yaml",
+            "unknown field `shared_countr`",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: soon\n    workloads:\n      shared_counter: 1\n",
+            "scenario.yaml",
+            "Required integer number of cycles or time duration",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: 10s\n",
+            "scenario.yaml",
+            "non-zero weight",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: 10s\n    workloads:\n      shared_counter: 1\n  - name: a\n    duration: 10s\n    workloads:\n      shared_counter: 1\n",
+            "scenario.yaml",
+            "Duplicate phase name",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: unbounded\n    workloads:\n      shared_counter: 1\n  - name: b\n    duration: 10s\n    workloads:\n      shared_counter: 1\n",
+            "scenario.yaml",
+            "only the last phase can be unbounded",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: 10s\n    workloads:\n      move_call: 1\n",
+            "scenario.yaml",
+            "no move call",
+        );
+        assert_load_error(
+            "phases:\n  - name: a\n    duration: 10s\n    workloads:\n      move_call: 1\n    move_call:\n      package_path: p\n      calls:\n        - module: m\n          function: f\n          arguments: [{ created: 0 }]\n",
+            "scenario.yaml",
+            "only take objects created by earlier calls",
+        );
+    }
+}
@@ -64,6 +64,18 @@ pub async fn publish_basics_package(
 ) -> ObjectRef {
     let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     path.push("../../sui_programmability/examples/basics");
+    publish_package(path, gas, proxy, sender, keypair, gas_price).await
+}
+
+/// Build and publish the Move package at `path`, returning the reference of the package object.
+pub async fn publish_package(
+    path: PathBuf,
+    gas: ObjectRef,
+    proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+    sender: SuiAddress,
+    keypair: &AccountKeyPair,
+    gas_price: u64,
+) -> ObjectRef {
     let transaction =
         create_publish_move_package_transaction(gas, path, sender, keypair, Some(gas_price));
     let effects = proxy.execute_transaction(transaction.into()).await.unwrap();
@@ -2,13 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use async_trait::async_trait;
+use move_core_types::identifier::Identifier;
+use rand::distributions::{Distribution, WeightedIndex};
+use sui_protocol_config::ProtocolConfig;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::{base_types::ObjectID, object::Owner};
 use test_utils::messages::create_publish_move_package_transaction;
 
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use sui_types::{base_types::SuiAddress, crypto::get_key_pair, messages::VerifiedTransaction};
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::get_key_pair,
+    messages::{CallArg, ObjectArg, TransactionData, VerifiedTransaction},
+};
 
 use crate::in_memory_wallet::InMemoryWallet;
 use crate::system_state_observer::SystemStateObserver;
@@ -30,15 +38,79 @@ const NUM_OBJECTS: u64 = 32;
 // TODO: make this big once https://github.com/MystenLabs/sui/pull/9394 lands
 //const NUM_OBJECTS: u64 = 2048;
 
-/*enum PayloadType {
+/// Size in bytes of the shared objects read by the max reads payload
+const SHARED_OBJECT_SIZE: u64 = 256;
+/// Number of dynamic fields added per transaction when initializing the max dynamic fields payload
+const DYNAMIC_FIELDS_BATCH_SIZE: u64 = 100;
+/// Bytes left under the event size limit for the type tag of the event and the overhead of its
+/// contents, as the size of an event is measured on its abstract memory size
+const EVENT_SIZE_MARGIN: u64 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadType {
     /// create NUM_OBJECTS objects with the max object size. This will write out a lot of object data
     MaxObjects,
-    // TODO:
-    // - MaxReads (by creating a bunch of shared objects in the module init for adversarial, then taking them all as input)
-    // - MaxDynamicFields (by reading a bunch of dynamic fields at runtime)
-    // - MaxEffects (by creating a bunch of small objects)
-    // - MaxEvents (max out VM's event size limit)
-}*/
+    /// read as many shared objects as a transaction can take as input, two per command, created
+    /// when the workload is initialized (`max_input_objects`, `max_programmable_tx_commands`)
+    MaxReads,
+    /// read as many dynamic fields of a shared object as can be loaded at runtime
+    /// (`object_runtime_max_num_cached_objects`)
+    MaxDynamicFields,
+    /// create as many small objects as a transaction can create, to maximize the size of the
+    /// effects (`max_num_new_move_object_ids`, `max_num_transferred_move_object_ids`)
+    MaxEffects,
+    /// emit as many events as a transaction can emit (`max_num_event_emit`)
+    MaxEvents,
+    /// emit one event of the max event size (`max_event_emit_size`)
+    MaxEventSize,
+}
+
+/// Configuration of the adversarial workload: the relative weight of each payload type, and the
+/// protocol limits each payload type pushes.
+#[derive(Debug, Clone)]
+pub struct AdversarialPayloadCfg {
+    payload_weights: Vec<(PayloadType, u32)>,
+    num_shared_objects: u64,
+    num_dynamic_fields: u64,
+    num_created_objects: u64,
+    num_events: u64,
+    event_size: u64,
+}
+
+impl AdversarialPayloadCfg {
+    pub fn new(payload_weights: Vec<(PayloadType, u32)>, protocol_config: &ProtocolConfig) -> Self {
+        Self {
+            payload_weights,
+            // one input is the gas coin, and every command reads two shared objects
+            num_shared_objects: (protocol_config.max_input_objects() - 1)
+                .min(2 * protocol_config.max_programmable_tx_commands() as u64),
+            num_dynamic_fields: protocol_config.object_runtime_max_num_cached_objects(),
+            num_created_objects: protocol_config
+                .max_num_new_move_object_ids()
+                .min(protocol_config.max_num_transferred_move_object_ids()),
+            num_events: protocol_config.max_num_event_emit(),
+            event_size: protocol_config.max_event_emit_size() - EVENT_SIZE_MARGIN,
+        }
+    }
+
+    fn weight(&self, payload_type: PayloadType) -> u32 {
+        self.payload_weights
+            .iter()
+            .filter(|(t, _)| *t == payload_type)
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+}
+
+impl Default for AdversarialPayloadCfg {
+    /// Only max object payloads, for the max protocol version
+    fn default() -> Self {
+        Self::new(
+            vec![(PayloadType::MaxObjects, 1)],
+            &ProtocolConfig::get_for_max_version(),
+        )
+    }
+}
 
 #[derive(Debug)]
 pub struct AdversarialTestPayload {
@@ -48,6 +120,12 @@ pub struct AdversarialTestPayload {
     sender: SuiAddress,
     state: InMemoryWallet,
     system_state_observer: Arc<SystemStateObserver>,
+    cfg: Arc<AdversarialPayloadCfg>,
+    /// shared objects read by the max reads payload
+    shared_objects: Arc<Vec<ObjectArg>>,
+    /// shared object with the dynamic fields read by the max dynamic fields payload
+    dynamic_fields_object: Option<ObjectArg>,
+    payload_types: WeightedIndex<u32>,
 }
 
 impl std::fmt::Display for AdversarialTestPayload {
@@ -71,16 +149,93 @@ impl Payload for AdversarialTestPayload {
     fn make_transaction(&mut self) -> VerifiedTransaction {
         // TODO: default benchmarking gas coins are too small to use MAX_TX_GAS. But we will want to be able to use that much to hit some limits
         let gas_budget = MAX_TX_GAS / 100;
-        // TODO: generate random number, convert it to a PayloadType, call the appropriate function
+        let gas_price = *self.system_state_observer.reference_gas_price.borrow();
+        let payload_type =
+            self.cfg.payload_weights[self.payload_types.sample(&mut rand::thread_rng())].0;
+        match payload_type {
+            PayloadType::MaxObjects => self.move_call(
+                "create_max_size_owned_objects",
+                vec![NUM_OBJECTS.into()],
+                gas_budget,
+                gas_price,
+            ),
+            PayloadType::MaxReads => {
+                let mut builder = ProgrammableTransactionBuilder::new();
+                for shared_objects in self.shared_objects.chunks(2) {
+                    let arguments = shared_objects
+                        .iter()
+                        .map(|shared_object| builder.obj(*shared_object).unwrap())
+                        .collect::<Vec<_>>();
+                    let function = if arguments.len() == 2 {
+                        "read_objects"
+                    } else {
+                        "read_object"
+                    };
+                    builder.programmable_move_call(
+                        self.package_id,
+                        Identifier::new("adversarial").unwrap(),
+                        Identifier::new(function).unwrap(),
+                        vec![],
+                        arguments,
+                    );
+                }
+                let gas = *self.state.gas(&self.sender).unwrap();
+                self.state.create_tx(TransactionData::new_programmable(
+                    self.sender,
+                    vec![gas],
+                    builder.finish(),
+                    gas_budget,
+                    gas_price,
+                ))
+            }
+            PayloadType::MaxDynamicFields => self.move_call(
+                "read_dynamic_fields",
+                vec![
+                    CallArg::Object(self.dynamic_fields_object.unwrap()),
+                    self.cfg.num_dynamic_fields.into(),
+                ],
+                gas_budget,
+                gas_price,
+            ),
+            PayloadType::MaxEffects => self.move_call(
+                "create_min_size_owned_objects",
+                vec![self.cfg.num_created_objects.into()],
+                gas_budget,
+                gas_price,
+            ),
+            PayloadType::MaxEvents => self.move_call(
+                "emit_events",
+                vec![self.cfg.num_events.into(), 0u64.into()],
+                gas_budget,
+                gas_price,
+            ),
+            PayloadType::MaxEventSize => self.move_call(
+                "emit_events",
+                vec![1u64.into(), self.cfg.event_size.into()],
+                gas_budget,
+                gas_price,
+            ),
+        }
+    }
+}
+
+impl AdversarialTestPayload {
+    fn move_call(
+        &self,
+        function: &str,
+        arguments: Vec<CallArg>,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> VerifiedTransaction {
         self.state.move_call(
             self.sender,
             self.package_id,
             "adversarial",
-            "create_max_size_owned_objects",
+            function,
             vec![],
-            vec![NUM_OBJECTS.into()],
+            arguments,
             gas_budget,
-            *self.system_state_observer.reference_gas_price.borrow(),
+            gas_price,
         )
     }
 }
@@ -88,6 +243,7 @@ impl Payload for AdversarialTestPayload {
 #[derive(Debug)]
 pub struct AdversarialWorkloadBuilder {
     num_payloads: u64,
+    cfg: AdversarialPayloadCfg,
 }
 
 #[async_trait]
@@ -125,6 +281,9 @@ impl WorkloadBuilder<dyn Payload> for AdversarialWorkloadBuilder {
             package_id: ObjectID::ZERO,
             init_gas: init_gas.pop().unwrap(),
             payload_gas,
+            cfg: Arc::new(self.cfg.clone()),
+            shared_objects: Arc::new(vec![]),
+            dynamic_fields_object: None,
         }))
     }
 }
@@ -135,6 +294,7 @@ impl AdversarialWorkloadBuilder {
         target_qps: u64,
         num_workers: u64,
         in_flight_ratio: u64,
+        cfg: AdversarialPayloadCfg,
     ) -> Option<WorkloadBuilderInfo> {
         let target_qps = (workload_weight * target_qps as f32) as u64;
         let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
@@ -150,6 +310,7 @@ impl AdversarialWorkloadBuilder {
             let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
                 AdversarialWorkloadBuilder {
                     num_payloads: max_ops,
+                    cfg,
                 },
             ));
             let builder_info = WorkloadBuilderInfo {
@@ -167,6 +328,66 @@ pub struct AdversarialWorkload {
     package_id: ObjectID,
     pub init_gas: Gas,
     pub payload_gas: Vec<Gas>,
+    cfg: Arc<AdversarialPayloadCfg>,
+    /// shared objects read by the max reads payload
+    shared_objects: Arc<Vec<ObjectArg>>,
+    /// shared object with the dynamic fields read by the max dynamic fields payload
+    dynamic_fields_object: Option<ObjectArg>,
+}
+
+impl AdversarialWorkload {
+    async fn execute_init_transaction(
+        &self,
+        proxy: &Arc<dyn ValidatorProxy + Sync + Send>,
+        state: &mut InMemoryWallet,
+        transaction: VerifiedTransaction,
+    ) -> ExecutionEffects {
+        let effects = proxy.execute_transaction(transaction.into()).await.unwrap();
+        assert!(
+            effects.is_ok(),
+            "Adversarial workload initialization failed"
+        );
+        state.update(&effects);
+        effects
+    }
+
+    /// Create `n` shared objects of `size` bytes, returning them as mutable transaction inputs
+    async fn create_shared_objects(
+        &self,
+        proxy: &Arc<dyn ValidatorProxy + Sync + Send>,
+        state: &mut InMemoryWallet,
+        gas_price: u64,
+        n: u64,
+        size: u64,
+    ) -> Vec<ObjectArg> {
+        let transaction = state.move_call(
+            self.init_gas.1,
+            self.package_id,
+            "adversarial",
+            "create_shared_objects",
+            vec![],
+            vec![n.into(), size.into()],
+            MAX_TX_GAS,
+            gas_price,
+        );
+        let effects = self
+            .execute_init_transaction(proxy, state, transaction)
+            .await;
+        effects
+            .created()
+            .into_iter()
+            .filter_map(|((id, _, _), owner)| match owner {
+                Owner::Shared {
+                    initial_shared_version,
+                } => Some(ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    mutable: true,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -177,12 +398,15 @@ impl Workload<dyn Payload> for AdversarialWorkload {
         system_state_observer: Arc<SystemStateObserver>,
     ) {
         let gas = &self.init_gas;
+        let mut state = InMemoryWallet::new(gas);
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         path.push("src/workloads/data/adversarial");
         let gas_price = *system_state_observer.reference_gas_price.borrow();
         let transaction =
             create_publish_move_package_transaction(gas.0, path, gas.1, &gas.2, Some(gas_price));
-        let effects = proxy.execute_transaction(transaction.into()).await.unwrap();
+        let effects = self
+            .execute_init_transaction(&proxy, &mut state, transaction)
+            .await;
         let created = effects.created();
         // should only create the package object + upgrade cap. otherwise, there are some object initializers running and we will need to disambiguate
         assert_eq!(created.len(), 2);
@@ -191,6 +415,75 @@ impl Workload<dyn Payload> for AdversarialWorkload {
             .find(|o| matches!(o.1, Owner::Immutable))
             .unwrap();
         self.package_id = package_obj.0 .0;
+
+        if self.cfg.weight(PayloadType::MaxReads) > 0 {
+            let shared_objects = self
+                .create_shared_objects(
+                    &proxy,
+                    &mut state,
+                    gas_price,
+                    self.cfg.num_shared_objects,
+                    SHARED_OBJECT_SIZE,
+                )
+                .await;
+            // read the shared objects without mutating them, so that transactions reading them
+            // do not need to be ordered with respect to each other
+            self.shared_objects = Arc::new(
+                shared_objects
+                    .into_iter()
+                    .map(|shared_object| match shared_object {
+                        ObjectArg::SharedObject {
+                            id,
+                            initial_shared_version,
+                            ..
+                        } => ObjectArg::SharedObject {
+                            id,
+                            initial_shared_version,
+                            mutable: false,
+                        },
+                        _ => unreachable!(),
+                    })
+                    .collect(),
+            );
+        }
+
+        if self.cfg.weight(PayloadType::MaxDynamicFields) > 0 {
+            // smallest object, see `create_object_with_size`
+            let object = self
+                .create_shared_objects(&proxy, &mut state, gas_price, 1, 34)
+                .await
+                .pop()
+                .unwrap();
+            let mut start = 0;
+            while start < self.cfg.num_dynamic_fields {
+                let n = DYNAMIC_FIELDS_BATCH_SIZE.min(self.cfg.num_dynamic_fields - start);
+                let transaction = state.move_call(
+                    self.init_gas.1,
+                    self.package_id,
+                    "adversarial",
+                    "add_dynamic_fields",
+                    vec![],
+                    vec![CallArg::Object(object), start.into(), n.into()],
+                    MAX_TX_GAS,
+                    gas_price,
+                );
+                self.execute_init_transaction(&proxy, &mut state, transaction)
+                    .await;
+                start += n;
+            }
+            self.dynamic_fields_object = Some(match object {
+                ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    ..
+                } => ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version,
+                    mutable: false,
+                },
+                _ => unreachable!(),
+            });
+        }
     }
 
     async fn make_test_payloads(
@@ -206,6 +499,13 @@ impl Workload<dyn Payload> for AdversarialWorkload {
                 sender: gas.1,
                 state: InMemoryWallet::new(gas),
                 system_state_observer: system_state_observer.clone(),
+                cfg: self.cfg.clone(),
+                shared_objects: self.shared_objects.clone(),
+                dynamic_fields_object: self.dynamic_fields_object,
+                payload_types: WeightedIndex::new(
+                    self.cfg.payload_weights.iter().map(|(_, weight)| *weight),
+                )
+                .expect("Adversarial payload weights should not all be zero"),
             })
         }
         payloads
@@ -214,3 +514,106 @@ impl Workload<dyn Payload> for AdversarialWorkload {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bank::BenchmarkBank;
+    use crate::util::get_ed25519_keypair_from_keystore;
+    use crate::LocalValidatorAggregatorProxy;
+    use sui_config::SUI_KEYSTORE_FILENAME;
+    use sui_types::messages::{TransactionDataAPI, TransactionKind};
+    use test_utils::messages::get_sui_gas_object_with_wallet_context;
+    use test_utils::network::TestClusterBuilder;
+
+    const ALL_PAYLOAD_TYPES: [PayloadType; 6] = [
+        PayloadType::MaxObjects,
+        PayloadType::MaxReads,
+        PayloadType::MaxDynamicFields,
+        PayloadType::MaxEffects,
+        PayloadType::MaxEvents,
+        PayloadType::MaxEventSize,
+    ];
+
+    #[test]
+    fn test_payload_cfg_limits() {
+        let cfg = AdversarialPayloadCfg::new(vec![], &ProtocolConfig::get_for_max_version());
+        // 2048 input objects including the gas coin, read by 1024 commands
+        assert_eq!(cfg.num_shared_objects, 2047);
+        assert_eq!(cfg.num_dynamic_fields, 1000);
+        assert_eq!(cfg.num_created_objects, 2048);
+        assert_eq!(cfg.num_events, 256);
+        assert_eq!(cfg.event_size, 250 * 1024 - EVENT_SIZE_MARGIN);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_adversarial_payloads_hit_limits() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let swarm = &test_cluster.swarm;
+        let sender = test_cluster.get_address_0();
+        let keystore_path = swarm.dir().join(SUI_KEYSTORE_FILENAME);
+        let keypair = Arc::new(get_ed25519_keypair_from_keystore(keystore_path, &sender).unwrap());
+        let all_gas = get_sui_gas_object_with_wallet_context(&test_cluster.wallet, &sender).await;
+        let primary_gas = (all_gas[0].1, sender, keypair.clone());
+        let pay_coin = (all_gas[1].1, sender, keypair);
+
+        let registry = prometheus::Registry::new();
+        let proxy: Arc<dyn ValidatorProxy + Send + Sync> = Arc::new(
+            LocalValidatorAggregatorProxy::from_genesis(&swarm.config().genesis, &registry, None)
+                .await,
+        );
+        let mut system_state_observer = SystemStateObserver::new(proxy.clone());
+        system_state_observer
+            .reference_gas_price
+            .changed()
+            .await
+            .unwrap();
+        let system_state_observer = Arc::new(system_state_observer);
+        let gas_price = *system_state_observer.reference_gas_price.borrow();
+        let mut bank = BenchmarkBank::new(proxy.clone(), primary_gas, pay_coin);
+
+        let protocol_config = ProtocolConfig::get_for_max_version();
+        for payload_type in ALL_PAYLOAD_TYPES {
+            let cfg = AdversarialPayloadCfg::new(vec![(payload_type, 1)], &protocol_config);
+            let builder = AdversarialWorkloadBuilder::from(1.0, 1, 1, 1, cfg).unwrap();
+            let mut workloads = bank
+                .generate(vec![builder.workload_builder], gas_price, 100)
+                .await
+                .unwrap();
+            let mut workload = workloads.pop().unwrap();
+            workload
+                .init(proxy.clone(), system_state_observer.clone())
+                .await;
+            let mut payload = workload
+                .make_test_payloads(proxy.clone(), system_state_observer.clone())
+                .await
+                .pop()
+                .unwrap();
+
+            let transaction = payload.make_transaction();
+            if payload_type == PayloadType::MaxReads {
+                let data = transaction.data().transaction_data();
+                assert_eq!(data.input_objects().unwrap().len(), 2048);
+                let TransactionKind::ProgrammableTransaction(pt) = data.kind() else {
+                    panic!("max reads payload should be a programmable transaction");
+                };
+                assert_eq!(pt.commands.len(), 1024);
+            }
+
+            let effects = proxy.execute_transaction(transaction.into()).await.unwrap();
+            assert!(effects.is_ok(), "{payload_type:?} payload failed");
+            let ExecutionEffects::CertifiedTransactionEffects(_, events) = &effects else {
+                panic!("local validator proxy should return certified effects");
+            };
+            match payload_type {
+                PayloadType::MaxEvents => assert_eq!(events.data.len(), 256),
+                PayloadType::MaxEventSize => {
+                    assert_eq!(events.data.len(), 1);
+                    assert!(events.data[0].contents.len() as u64 > 250 * 1024 - EVENT_SIZE_MARGIN);
+                }
+                _ => (),
+            }
+            payload.make_new_payload(&effects);
+        }
+    }
+}
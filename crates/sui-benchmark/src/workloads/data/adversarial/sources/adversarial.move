@@ -4,6 +4,8 @@
 module adversarial::adversarial {
     use std::vector;
     use sui::bcs;
+    use sui::dynamic_field;
+    use sui::event;
     use sui::object::{Self, UID};
     use sui::tx_context::{Self, TxContext};
     use sui::transfer;
@@ -18,6 +20,10 @@ module adversarial::adversarial {
         s: S,
     }
 
+    struct Event has copy, drop {
+        contents: vector<u8>
+    }
+
     const MAX_OBJ_SIZE: u64 = 25600;
 
     // create an object whose Move BCS representation is `n` bytes
@@ -66,4 +72,68 @@ module adversarial::adversarial {
             i = i + 1
         }
     }
+
+    /// Create `n` shared objects of `size` bytes
+    public fun create_shared_objects(n: u64, size: u64, ctx: &mut TxContext) {
+        let i = 0;
+        while (i < n) {
+            transfer::public_share_object(create_object_with_size(size, ctx));
+            i = i + 1
+        }
+    }
+
+    /// Read the contents of `s`, for transactions taking as many objects as possible as input
+    public fun read_object(s: &S): u64 {
+        vector::length(&s.contents)
+    }
+
+    /// Read the contents of `s1` and `s2`, so that a transaction can take twice as many objects
+    /// as input as it has commands
+    public fun read_objects(s1: &S, s2: &S): u64 {
+        vector::length(&s1.contents) + vector::length(&s2.contents)
+    }
+
+    /// Add the dynamic fields `start`..`start + n` to `s`
+    public fun add_dynamic_fields(s: &mut S, start: u64, n: u64) {
+        let i = start;
+        while (i < start + n) {
+            dynamic_field::add(&mut s.id, i, i);
+            i = i + 1
+        }
+    }
+
+    /// Read the dynamic fields `0`..`n` of `s`, which loads `n` child objects at runtime
+    public fun read_dynamic_fields(s: &S, n: u64) {
+        let i = 0;
+        while (i < n) {
+            let _: &u64 = dynamic_field::borrow(&s.id, i);
+            i = i + 1
+        }
+    }
+
+    /// Create `n` objects without contents and transfer them to the tx sender, which writes the
+    /// largest number of objects to the effects
+    public fun create_min_size_owned_objects(n: u64, ctx: &mut TxContext) {
+        let i = 0;
+        let sender = tx_context::sender(ctx);
+        while (i < n) {
+            transfer::public_transfer(S { id: object::new(ctx), contents: vector[] }, sender);
+            i = i + 1
+        }
+    }
+
+    /// Emit `n` events whose contents are `size` bytes
+    public fun emit_events(n: u64, size: u64) {
+        let contents = vector[];
+        let i = 0;
+        while (i < size) {
+            vector::push_back(&mut contents, 9);
+            i = i + 1
+        };
+        let i = 0;
+        while (i < n) {
+            event::emit(Event { contents: copy contents });
+            i = i + 1
+        }
+    }
 }
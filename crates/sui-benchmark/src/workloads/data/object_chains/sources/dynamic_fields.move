@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Steps of the life of a dynamic field of an owned object, each taking the parent object as
+/// mutated by the previous one as input: create parent -> add -> mutate -> remove -> delete parent.
+module object_chains::dynamic_fields {
+    use sui::dynamic_field;
+    use sui::object::{Self, UID};
+    use sui::tx_context::{Self, TxContext};
+    use sui::transfer;
+
+    struct Parent has key {
+        id: UID,
+    }
+
+    public entry fun create_parent(ctx: &mut TxContext) {
+        transfer::transfer(Parent { id: object::new(ctx) }, tx_context::sender(ctx))
+    }
+
+    public entry fun add_field(parent: &mut Parent, name: u64, value: u64) {
+        dynamic_field::add(&mut parent.id, name, value)
+    }
+
+    public entry fun mutate_field(parent: &mut Parent, name: u64) {
+        let value = dynamic_field::borrow_mut<u64, u64>(&mut parent.id, name);
+        *value = *value + 1
+    }
+
+    public entry fun remove_field(parent: &mut Parent, name: u64) {
+        let _: u64 = dynamic_field::remove(&mut parent.id, name);
+    }
+
+    public entry fun delete_parent(parent: Parent) {
+        let Parent { id } = parent;
+        object::delete(id)
+    }
+}
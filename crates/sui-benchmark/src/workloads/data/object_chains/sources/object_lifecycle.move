@@ -0,0 +1,39 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Steps of the life of an owned object, each taking the output of the previous one as input:
+/// create -> mutate -> wrap -> unwrap and delete.
+module object_chains::object_lifecycle {
+    use sui::object::{Self, UID};
+    use sui::tx_context::{Self, TxContext};
+    use sui::transfer;
+
+    struct Obj has key, store {
+        id: UID,
+        value: u64,
+    }
+
+    struct Wrapper has key {
+        id: UID,
+        obj: Obj,
+    }
+
+    public entry fun create(ctx: &mut TxContext) {
+        transfer::transfer(Obj { id: object::new(ctx), value: 0 }, tx_context::sender(ctx))
+    }
+
+    public entry fun mutate(obj: &mut Obj) {
+        obj.value = obj.value + 1
+    }
+
+    public entry fun wrap(obj: Obj, ctx: &mut TxContext) {
+        transfer::transfer(Wrapper { id: object::new(ctx), obj }, tx_context::sender(ctx))
+    }
+
+    public entry fun unwrap_and_delete(wrapper: Wrapper) {
+        let Wrapper { id, obj } = wrapper;
+        object::delete(id);
+        let Obj { id, value: _ } = obj;
+        object::delete(id);
+    }
+}
@@ -4,6 +4,7 @@
 pub mod adversarial;
 pub mod batch_payment;
 pub mod delegation;
+pub mod move_call_chain;
 pub mod payload;
 pub mod shared_counter;
 pub mod transfer_object;
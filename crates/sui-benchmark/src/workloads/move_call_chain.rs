@@ -0,0 +1,518 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use move_core_types::language_storage::TypeTag;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    crypto::get_key_pair,
+    messages::{CallArg, ObjectArg, VerifiedTransaction},
+    object::Owner,
+    parse_sui_type_tag,
+};
+use tracing::info;
+
+use crate::in_memory_wallet::InMemoryWallet;
+use crate::system_state_observer::SystemStateObserver;
+use crate::util::publish_package;
+use crate::workloads::payload::Payload;
+use crate::workloads::{Gas, GasCoinConfig};
+use crate::{ExecutionEffects, ValidatorProxy};
+
+use super::{
+    workload::{Workload, WorkloadBuilder, MAX_GAS_FOR_TESTING},
+    WorkloadBuilderInfo, WorkloadParams,
+};
+
+/// Gas budget of every call of a chain
+const GAS_BUDGET: u64 = 100_000_000;
+
+/// An argument of a call of a chain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveCallArg {
+    U8(u8),
+    U64(u64),
+    U128(u128),
+    Bool(bool),
+    Address(SuiAddress),
+    String(String),
+    /// The address sending the chain
+    Sender,
+    /// The first object owned by the sender that was created by the call at this index of the
+    /// chain, in its latest version. The call must come earlier in the chain.
+    Created(usize),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveCall {
+    pub module: String,
+    pub function: String,
+    /// Type arguments, e.g. "0x2::sui::SUI"
+    #[serde(default)]
+    pub type_arguments: Vec<String>,
+    #[serde(default)]
+    pub arguments: Vec<MoveCallArg>,
+}
+
+impl MoveCall {
+    fn new(module: &str, function: &str, arguments: Vec<MoveCallArg>) -> Self {
+        Self {
+            module: module.to_string(),
+            function: function.to_string(),
+            type_arguments: vec![],
+            arguments,
+        }
+    }
+}
+
+/// A chain of Move calls to functions of a package published when the workload is initialized.
+/// Every payload executes the calls of the chain one after the other, each call taking the
+/// objects created by the previous ones as input, and starts over from the first call once the
+/// chain is done or when a call fails.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveCallChainCfg {
+    /// Path of the Move package to publish
+    pub package_path: PathBuf,
+    pub calls: Vec<MoveCall>,
+}
+
+impl MoveCallChainCfg {
+    /// create -> mutate -> wrap -> unwrap and delete an owned object
+    pub fn object_lifecycle() -> Self {
+        use MoveCallArg::*;
+        let module = "object_lifecycle";
+        Self {
+            package_path: object_chains_package_path(),
+            calls: vec![
+                MoveCall::new(module, "create", vec![]),
+                MoveCall::new(module, "mutate", vec![Created(0)]),
+                MoveCall::new(module, "wrap", vec![Created(0)]),
+                MoveCall::new(module, "unwrap_and_delete", vec![Created(2)]),
+            ],
+        }
+    }
+
+    /// add -> mutate -> remove a dynamic field of an owned object
+    pub fn dynamic_fields() -> Self {
+        use MoveCallArg::*;
+        let module = "dynamic_fields";
+        Self {
+            package_path: object_chains_package_path(),
+            calls: vec![
+                MoveCall::new(module, "create_parent", vec![]),
+                MoveCall::new(module, "add_field", vec![Created(0), U64(0), U64(0)]),
+                MoveCall::new(module, "mutate_field", vec![Created(0), U64(0)]),
+                MoveCall::new(module, "remove_field", vec![Created(0), U64(0)]),
+                MoveCall::new(module, "delete_parent", vec![Created(0)]),
+            ],
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.calls.is_empty() {
+            bail!("Move call chain must have at least one call");
+        }
+        for (i, call) in self.calls.iter().enumerate() {
+            for type_argument in &call.type_arguments {
+                parse_sui_type_tag(type_argument).with_context(|| {
+                    format!(
+                        "Invalid type argument {} of call {}::{}",
+                        type_argument, call.module, call.function
+                    )
+                })?;
+            }
+            for argument in &call.arguments {
+                if let MoveCallArg::Created(j) = argument {
+                    if *j >= i {
+                        bail!(
+                            "Call {}::{} can only take objects created by earlier calls, not by call {}",
+                            call.module,
+                            call.function,
+                            j
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn type_arguments(&self) -> Vec<Vec<TypeTag>> {
+        self.calls
+            .iter()
+            .map(|call| {
+                call.type_arguments
+                    .iter()
+                    .map(|t| parse_sui_type_tag(t).expect("Invalid type argument"))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn object_chains_package_path() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/workloads/data/object_chains");
+    path
+}
+
+#[derive(Debug)]
+pub struct MoveCallChainTestPayload {
+    /// Name of the workload, used to label metrics
+    name: Arc<str>,
+    package_id: ObjectID,
+    sender: SuiAddress,
+    state: InMemoryWallet,
+    cfg: Arc<MoveCallChainCfg>,
+    type_arguments: Arc<Vec<Vec<TypeTag>>>,
+    /// Index of the next call of the chain to execute
+    next_call: usize,
+    /// Object created by each call of the current run of the chain, see `MoveCallArg::Created`
+    created: Vec<Option<ObjectID>>,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for MoveCallChainTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Payload for MoveCallChainTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        self.state.update(effects);
+        if effects.is_ok() {
+            let sender = self.sender;
+            self.created[self.next_call] =
+                effects
+                    .created()
+                    .into_iter()
+                    .find_map(|((id, _, _), owner)| match owner {
+                        Owner::AddressOwner(a) if a == sender => Some(id),
+                        _ => None,
+                    });
+            self.next_call = (self.next_call + 1) % self.cfg.calls.len();
+        } else {
+            self.next_call = 0;
+        }
+        // start over if the chain is done or the next call is missing one of its inputs
+        if self.next_call == 0 || !self.has_inputs(self.next_call) {
+            self.next_call = 0;
+            self.created.iter_mut().for_each(|created| *created = None);
+        }
+    }
+
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = *self.system_state_observer.reference_gas_price.borrow();
+        let call = &self.cfg.calls[self.next_call];
+        let arguments = call
+            .arguments
+            .iter()
+            .map(|argument| self.call_arg(argument))
+            .collect();
+        self.state.move_call(
+            self.sender,
+            self.package_id,
+            &call.module,
+            &call.function,
+            self.type_arguments[self.next_call].clone(),
+            arguments,
+            GAS_BUDGET,
+            gas_price,
+        )
+    }
+}
+
+impl MoveCallChainTestPayload {
+    fn created_object(&self, call: usize) -> Option<ObjectArg> {
+        let id = self.created[call]?;
+        self.state
+            .owned_object(&self.sender, &id)
+            .map(|object_ref| ObjectArg::ImmOrOwnedObject(*object_ref))
+    }
+
+    fn has_inputs(&self, call: usize) -> bool {
+        self.cfg.calls[call]
+            .arguments
+            .iter()
+            .all(|argument| match argument {
+                MoveCallArg::Created(i) => self.created_object(*i).is_some(),
+                _ => true,
+            })
+    }
+
+    fn call_arg(&self, argument: &MoveCallArg) -> CallArg {
+        let pure = match argument {
+            MoveCallArg::U8(v) => bcs::to_bytes(v),
+            MoveCallArg::U64(v) => bcs::to_bytes(v),
+            MoveCallArg::U128(v) => bcs::to_bytes(v),
+            MoveCallArg::Bool(v) => bcs::to_bytes(v),
+            MoveCallArg::Address(v) => bcs::to_bytes(v),
+            MoveCallArg::String(v) => bcs::to_bytes(v),
+            MoveCallArg::Sender => bcs::to_bytes(&self.sender),
+            MoveCallArg::Created(i) => {
+                return CallArg::Object(
+                    self.created_object(*i)
+                        .expect("Input of a move call chain should have been created"),
+                )
+            }
+        };
+        CallArg::Pure(pure.unwrap())
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveCallChainWorkloadBuilder {
+    name: Arc<str>,
+    num_payloads: u64,
+    cfg: MoveCallChainCfg,
+}
+
+impl MoveCallChainWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        name: &str,
+        cfg: MoveCallChainCfg,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                MoveCallChainWorkloadBuilder {
+                    name: name.into(),
+                    num_payloads: max_ops,
+                    cfg,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for MoveCallChainWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Gas coin for publishing the package
+        let (address, keypair) = get_key_pair();
+        vec![GasCoinConfig {
+            amount: MAX_GAS_FOR_TESTING,
+            address,
+            keypair: Arc::new(keypair),
+        }]
+    }
+
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount: MAX_GAS_FOR_TESTING,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+
+    async fn build(
+        &self,
+        mut init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(MoveCallChainWorkload {
+            name: self.name.clone(),
+            package_id: None,
+            init_gas: init_gas.pop().unwrap(),
+            payload_gas,
+            cfg: Arc::new(self.cfg.clone()),
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveCallChainWorkload {
+    name: Arc<str>,
+    /// ID of the package whose functions are called, once published
+    package_id: Option<ObjectID>,
+    pub init_gas: Gas,
+    pub payload_gas: Vec<Gas>,
+    cfg: Arc<MoveCallChainCfg>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for MoveCallChainWorkload {
+    async fn init(
+        &mut self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.package_id.is_some() {
+            return;
+        }
+        let gas_price = *system_state_observer.reference_gas_price.borrow();
+        let (gas, sender, keypair) = &self.init_gas;
+        info!("Publishing package {:?}", self.cfg.package_path);
+        self.package_id = Some(
+            publish_package(
+                self.cfg.package_path.clone(),
+                *gas,
+                proxy,
+                *sender,
+                keypair,
+                gas_price,
+            )
+            .await
+            .0,
+        );
+    }
+
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        let type_arguments = Arc::new(self.cfg.type_arguments());
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::<dyn Payload>::from(Box::new(MoveCallChainTestPayload {
+                    name: self.name.clone(),
+                    package_id: self.package_id.unwrap(),
+                    sender: gas.1,
+                    state: InMemoryWallet::new(gas),
+                    cfg: self.cfg.clone(),
+                    type_arguments: type_arguments.clone(),
+                    next_call: 0,
+                    created: vec![None; self.cfg.calls.len()],
+                    system_state_observer: system_state_observer.clone(),
+                }))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bank::BenchmarkBank;
+    use crate::util::get_ed25519_keypair_from_keystore;
+    use crate::LocalValidatorAggregatorProxy;
+    use sui_config::SUI_KEYSTORE_FILENAME;
+    use sui_types::messages::TransactionDataAPI;
+    use test_utils::messages::get_sui_gas_object_with_wallet_context;
+    use test_utils::network::TestClusterBuilder;
+
+    /// A chain of calls to a user package, as configured in a scenario.
+    fn user_chain() -> MoveCallChainCfg {
+        let mut cfg: MoveCallChainCfg = serde_yaml::from_str(
+            r#"
+            package_path: ""
+            calls:
+              - module: dynamic_fields
+                function: create_parent
+              - module: dynamic_fields
+                function: add_field
+                arguments: [{ created: 0 }, { u64: 1 }, { u64: 42 }]
+              - module: object_lifecycle
+                function: create
+              - module: object_lifecycle
+                function: wrap
+                arguments: [{ created: 2 }]
+            "#,
+        )
+        .unwrap();
+        cfg.package_path = object_chains_package_path();
+        cfg
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_move_call_chains() {
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+        let swarm = &test_cluster.swarm;
+        let sender = test_cluster.get_address_0();
+        let keystore_path = swarm.dir().join(SUI_KEYSTORE_FILENAME);
+        let keypair = Arc::new(get_ed25519_keypair_from_keystore(keystore_path, &sender).unwrap());
+        let all_gas = get_sui_gas_object_with_wallet_context(&test_cluster.wallet, &sender).await;
+        let primary_gas = (all_gas[0].1, sender, keypair.clone());
+        let pay_coin = (all_gas[1].1, sender, keypair);
+
+        let registry = prometheus::Registry::new();
+        let proxy: Arc<dyn ValidatorProxy + Send + Sync> = Arc::new(
+            LocalValidatorAggregatorProxy::from_genesis(&swarm.config().genesis, &registry, None)
+                .await,
+        );
+        let mut system_state_observer = SystemStateObserver::new(proxy.clone());
+        system_state_observer
+            .reference_gas_price
+            .changed()
+            .await
+            .unwrap();
+        let system_state_observer = Arc::new(system_state_observer);
+        let gas_price = *system_state_observer.reference_gas_price.borrow();
+        let mut bank = BenchmarkBank::new(proxy.clone(), primary_gas, pay_coin);
+
+        for (name, cfg) in [
+            ("object_lifecycle", MoveCallChainCfg::object_lifecycle()),
+            ("dynamic_fields", MoveCallChainCfg::dynamic_fields()),
+            ("move_call", user_chain()),
+        ] {
+            cfg.validate().unwrap();
+            let builder =
+                MoveCallChainWorkloadBuilder::from(1.0, 1, 1, 1, name, cfg.clone()).unwrap();
+            let mut workloads = bank
+                .generate(vec![builder.workload_builder], gas_price, 100)
+                .await
+                .unwrap();
+            let mut workload = workloads.pop().unwrap();
+            workload
+                .init(proxy.clone(), system_state_observer.clone())
+                .await;
+            let mut payload = workload
+                .make_test_payloads(proxy.clone(), system_state_observer.clone())
+                .await
+                .pop()
+                .unwrap();
+
+            // run every call of the chain once, then the chain starts over
+            for call in cfg.calls.iter().chain(cfg.calls.first()) {
+                let transaction = payload.make_transaction();
+                let move_calls = transaction.data().transaction_data().move_calls();
+                assert_eq!(move_calls.len(), 1);
+                assert_eq!(move_calls[0].1.as_str(), call.module);
+                assert_eq!(move_calls[0].2.as_str(), call.function);
+
+                let effects = proxy.execute_transaction(transaction.into()).await.unwrap();
+                assert!(
+                    effects.is_ok(),
+                    "{name} call {}::{} failed",
+                    call.module,
+                    call.function
+                );
+                payload.make_new_payload(&effects);
+            }
+        }
+    }
+}
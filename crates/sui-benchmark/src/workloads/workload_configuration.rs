@@ -2,85 +2,95 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::bank::BenchmarkBank;
-use crate::options::{Opts, RunSpec};
+use crate::options::Opts;
+use crate::scenario::{Phase, WorkloadWeights};
 use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::batch_payment::BatchPaymentWorkloadBuilder;
 use crate::workloads::delegation::DelegationWorkloadBuilder;
+use crate::workloads::move_call_chain::{MoveCallChainCfg, MoveCallChainWorkloadBuilder};
 use crate::workloads::shared_counter::SharedCounterWorkloadBuilder;
 use crate::workloads::transfer_object::TransferObjectWorkloadBuilder;
 use crate::workloads::WorkloadInfo;
 use anyhow::Result;
 use std::sync::Arc;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 
-use super::adversarial::AdversarialWorkloadBuilder;
+use super::adversarial::{AdversarialPayloadCfg, AdversarialWorkloadBuilder};
 
 pub struct WorkloadConfiguration;
 
+/// Configuration of the workloads run together: their relative weights, the rate and concurrency
+/// they share, and the parameters of each workload.
+#[derive(Debug, Clone)]
+pub struct WorkloadsCfg {
+    pub num_workers: u64,
+    pub target_qps: u64,
+    pub in_flight_ratio: u64,
+    pub weights: WorkloadWeights,
+    pub num_transfer_accounts: u64,
+    pub batch_payment_size: u32,
+    pub shared_counter_hotness_factor: u32,
+    pub adversarial_cfg: AdversarialPayloadCfg,
+    pub move_call_cfg: Option<MoveCallChainCfg>,
+}
+
 impl WorkloadConfiguration {
+    /// Build the workloads of one phase of the scenario, using `bank` to generate their gas.
     pub async fn configure(
-        bank: BenchmarkBank,
+        bank: &mut BenchmarkBank,
         opts: &Opts,
+        phase: &Phase,
         system_state_observer: Arc<SystemStateObserver>,
     ) -> Result<Vec<WorkloadInfo>> {
-        match opts.run_spec {
-            RunSpec::Bench {
-                target_qps,
-                num_workers,
-                in_flight_ratio,
-                shared_counter,
-                transfer_object,
-                delegation,
-                batch_payment,
-                adversarial,
-                batch_payment_size,
-                shared_counter_hotness_factor,
-                ..
-            } => {
-                Self::build_workloads(
-                    num_workers,
-                    opts.num_transfer_accounts,
-                    shared_counter,
-                    transfer_object,
-                    delegation,
-                    batch_payment,
-                    adversarial,
-                    batch_payment_size,
-                    shared_counter_hotness_factor,
-                    target_qps,
-                    in_flight_ratio,
-                    bank,
-                    system_state_observer,
-                    opts.gas_request_chunk_size,
-                )
-                .await
-            }
-        }
+        // TODO: query the network for the current protocol version.
+        let protocol_config = match opts.protocol_version {
+            Some(v) => ProtocolConfig::get_for_version(ProtocolVersion::new(v)),
+            None => ProtocolConfig::get_for_max_version(),
+        };
+        let adversarial_cfg = AdversarialPayloadCfg::new(
+            phase.adversarial_payloads.to_payload_weights(),
+            &protocol_config,
+        );
+        Self::build_workloads(
+            WorkloadsCfg {
+                num_workers: phase.num_workers,
+                target_qps: phase.target_qps,
+                in_flight_ratio: phase.in_flight_ratio,
+                weights: phase.workloads.clone(),
+                num_transfer_accounts: opts.num_transfer_accounts,
+                batch_payment_size: phase.batch_payment_size,
+                shared_counter_hotness_factor: phase.shared_counter_hotness_factor,
+                adversarial_cfg,
+                move_call_cfg: phase.move_call.clone(),
+            },
+            bank,
+            system_state_observer,
+            opts.gas_request_chunk_size,
+        )
+        .await
     }
 
     pub async fn build_workloads(
-        num_workers: u64,
-        num_transfer_accounts: u64,
-        shared_counter_weight: u32,
-        transfer_object_weight: u32,
-        delegation_weight: u32,
-        batch_payment_weight: u32,
-        adversarial_weight: u32,
-        batch_payment_size: u32,
-        shared_counter_hotness_factor: u32,
-        target_qps: u64,
-        in_flight_ratio: u64,
-        mut bank: BenchmarkBank,
+        cfg: WorkloadsCfg,
+        bank: &mut BenchmarkBank,
         system_state_observer: Arc<SystemStateObserver>,
         chunk_size: u64,
     ) -> Result<Vec<WorkloadInfo>> {
-        let total_weight = shared_counter_weight
-            + transfer_object_weight
-            + delegation_weight
-            + batch_payment_weight
-            + adversarial_weight;
+        let WorkloadsCfg {
+            num_workers,
+            target_qps,
+            in_flight_ratio,
+            weights,
+            num_transfer_accounts,
+            batch_payment_size,
+            shared_counter_hotness_factor,
+            adversarial_cfg,
+            move_call_cfg,
+        } = cfg;
+        let total_weight = weights.total();
         let mut workload_builders = vec![];
         let shared_workload = SharedCounterWorkloadBuilder::from(
-            shared_counter_weight as f32 / total_weight as f32,
+            weights.shared_counter as f32 / total_weight as f32,
             target_qps,
             num_workers,
             in_flight_ratio,
@@ -88,7 +98,7 @@ impl WorkloadConfiguration {
         );
         workload_builders.push(shared_workload);
         let transfer_workload = TransferObjectWorkloadBuilder::from(
-            transfer_object_weight as f32 / total_weight as f32,
+            weights.transfer_object as f32 / total_weight as f32,
             target_qps,
             num_workers,
             in_flight_ratio,
@@ -96,14 +106,14 @@ impl WorkloadConfiguration {
         );
         workload_builders.push(transfer_workload);
         let delegation_workload = DelegationWorkloadBuilder::from(
-            delegation_weight as f32 / total_weight as f32,
+            weights.delegation as f32 / total_weight as f32,
             target_qps,
             num_workers,
             in_flight_ratio,
         );
         workload_builders.push(delegation_workload);
         let batch_payment_workload = BatchPaymentWorkloadBuilder::from(
-            batch_payment_weight as f32 / total_weight as f32,
+            weights.batch_payment as f32 / total_weight as f32,
             target_qps,
             num_workers,
             in_flight_ratio,
@@ -111,12 +121,42 @@ impl WorkloadConfiguration {
         );
         workload_builders.push(batch_payment_workload);
         let adversarial_workload = AdversarialWorkloadBuilder::from(
-            adversarial_weight as f32 / total_weight as f32,
+            weights.adversarial as f32 / total_weight as f32,
             target_qps,
             num_workers,
             in_flight_ratio,
+            adversarial_cfg,
         );
         workload_builders.push(adversarial_workload);
+        let object_lifecycle_workload = MoveCallChainWorkloadBuilder::from(
+            weights.object_lifecycle as f32 / total_weight as f32,
+            target_qps,
+            num_workers,
+            in_flight_ratio,
+            "object_lifecycle",
+            MoveCallChainCfg::object_lifecycle(),
+        );
+        workload_builders.push(object_lifecycle_workload);
+        let dynamic_fields_workload = MoveCallChainWorkloadBuilder::from(
+            weights.dynamic_fields as f32 / total_weight as f32,
+            target_qps,
+            num_workers,
+            in_flight_ratio,
+            "dynamic_fields",
+            MoveCallChainCfg::dynamic_fields(),
+        );
+        workload_builders.push(dynamic_fields_workload);
+        if let Some(move_call_cfg) = move_call_cfg {
+            let move_call_workload = MoveCallChainWorkloadBuilder::from(
+                weights.move_call as f32 / total_weight as f32,
+                target_qps,
+                num_workers,
+                in_flight_ratio,
+                "move_call",
+                move_call_cfg,
+            );
+            workload_builders.push(move_call_workload);
+        }
         let (workload_params, workload_builders): (Vec<_>, Vec<_>) = workload_builders
             .into_iter()
             .flatten()
@@ -9,8 +9,10 @@ mod test {
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, Instant};
     use sui_benchmark::bank::BenchmarkBank;
+    use sui_benchmark::scenario::WorkloadWeights;
     use sui_benchmark::system_state_observer::SystemStateObserver;
-    use sui_benchmark::workloads::workload_configuration::WorkloadConfiguration;
+    use sui_benchmark::workloads::adversarial::AdversarialPayloadCfg;
+    use sui_benchmark::workloads::workload_configuration::{WorkloadConfiguration, WorkloadsCfg};
     use sui_benchmark::{
         drivers::{bench_driver::BenchDriver, driver::Driver, Interval},
         util::get_ed25519_keypair_from_keystore,
@@ -233,7 +235,7 @@ mod test {
                 .await,
         );
 
-        let bank = BenchmarkBank::new(proxy.clone(), primary_gas, pay_coin);
+        let mut bank = BenchmarkBank::new(proxy.clone(), primary_gas, pay_coin);
         let system_state_observer = {
             let mut system_state_observer = SystemStateObserver::new(proxy.clone());
             if let Ok(_) = system_state_observer.reference_gas_price.changed().await {
@@ -248,31 +250,32 @@ mod test {
         let num_workers = get_var("SIM_STRESS_TEST_WORKERS", 10);
         let in_flight_ratio = get_var("SIM_STRESS_TEST_IFR", 2);
         let batch_payment_size = get_var("SIM_BATCH_PAYMENT_SIZE", 15);
-        let shared_counter_weight = 1;
-        let transfer_object_weight = 1;
-        let num_transfer_accounts = 2;
-        let delegation_weight = 1;
-        let batch_payment_weight = 1;
-
-        // TODO: re-enable this when we figure out why it is causing connection errors and making
-        // tests run for ever
-        let adversarial_weight = 0;
-
-        let shared_counter_hotness_factor = 50;
+        let weights = WorkloadWeights {
+            shared_counter: 1,
+            transfer_object: 1,
+            delegation: 1,
+            batch_payment: 1,
+            // TODO: re-enable this when we figure out why it is causing connection errors and
+            // making tests run for ever
+            adversarial: 0,
+            object_lifecycle: 0,
+            dynamic_fields: 0,
+            move_call: 0,
+        };
 
         let workloads = WorkloadConfiguration::build_workloads(
-            num_workers,
-            num_transfer_accounts,
-            shared_counter_weight,
-            transfer_object_weight,
-            delegation_weight,
-            batch_payment_weight,
-            adversarial_weight,
-            batch_payment_size,
-            shared_counter_hotness_factor,
-            target_qps,
-            in_flight_ratio,
-            bank,
+            WorkloadsCfg {
+                num_workers,
+                target_qps,
+                in_flight_ratio,
+                weights,
+                num_transfer_accounts: 2,
+                batch_payment_size,
+                shared_counter_hotness_factor: 50,
+                adversarial_cfg: AdversarialPayloadCfg::default(),
+                move_call_cfg: None,
+            },
+            &mut bank,
             system_state_observer.clone(),
             100,
         )
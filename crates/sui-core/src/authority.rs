@@ -46,11 +46,12 @@ use sui_config::genesis::Genesis;
 use sui_config::node::{AuthorityStorePruningConfig, DBCheckpointConfig};
 use sui_json_rpc_types::{
     Checkpoint, DevInspectResults, DryRunTransactionResponse, EventFilter, SuiEvent, SuiMoveValue,
-    SuiObjectDataFilter, SuiTransactionEvents,
+    SuiObjectDataFilter, SuiTransaction, SuiTransactionEffects, SuiTransactionEvents,
+    SuiTransactionResponse,
 };
 use sui_macros::{fail_point, fail_point_async, nondeterministic};
 use sui_protocol_config::SupportedProtocolVersions;
-use sui_storage::indexes::{ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
+use sui_storage::indexes::{CheckpointIndex, ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
 use sui_storage::write_ahead_log::WriteAheadLog;
 use sui_storage::{
     write_ahead_log::{DBTxGuard, TxGuard},
@@ -1017,11 +1018,21 @@ impl AuthorityState {
             .certificate_executed(digest, epoch_store)
     }
 
+    /// Execute `transaction` without committing its effects. Besides the response, returns the
+    /// objects read and written by the transaction and its effects, so that callers can derive
+    /// the object and balance changes, which are left empty in the response.
     pub async fn dry_exec_transaction(
         &self,
         transaction: TransactionData,
         transaction_digest: TransactionDigest,
-    ) -> Result<DryRunTransactionResponse, anyhow::Error> {
+    ) -> Result<
+        (
+            DryRunTransactionResponse,
+            InnerTemporaryStore,
+            TransactionEffects,
+        ),
+        anyhow::Error,
+    > {
         let epoch_store = self.load_epoch_store_one_call_per_task();
         if !self.is_fullnode(&epoch_store) {
             return Err(anyhow!("dry-exec is only supported on fullnodes"));
@@ -1095,15 +1106,18 @@ impl AuthorityState {
         let module_cache =
             TemporaryModuleResolver::new(&inner_temp_store, epoch_store.module_cache().clone());
 
-        Ok(DryRunTransactionResponse {
-            effects: effects.try_into()?,
+        let response = DryRunTransactionResponse {
+            effects: effects.clone().try_into()?,
             events: SuiTransactionEvents::try_from(
                 inner_temp_store.events.clone(),
                 tx_digest,
                 None,
                 &module_cache,
             )?,
-        })
+            object_changes: vec![],
+            balance_changes: vec![],
+        };
+        Ok((response, inner_temp_store, effects))
     }
 
     /// The object ID for gas can be any object ID, even for an uncreated object
@@ -1448,16 +1462,15 @@ impl AuthorityState {
 
             // Emit events
             if res.is_ok() {
+                let sui_effects: SuiTransactionEffects = effects.clone().try_into()?;
+                let sui_events = SuiTransactionEvents::try_from(
+                    events.clone(),
+                    *tx_digest,
+                    Some(timestamp_ms),
+                    epoch_store.module_cache(),
+                )?;
                 self.event_handler
-                    .process_events(
-                        &effects.clone().try_into()?,
-                        &SuiTransactionEvents::try_from(
-                            events.clone(),
-                            *tx_digest,
-                            Some(timestamp_ms),
-                            epoch_store.module_cache(),
-                        )?,
-                    )
+                    .process_events(&sui_effects, &sui_events)
                     .await
                     .tap_ok(|_| {
                         self.metrics
@@ -1474,6 +1487,32 @@ impl AuthorityState {
                 self.metrics
                     .post_processing_total_events_emitted
                     .inc_by(events.data.len() as u64);
+
+                // Emit transaction, skipping the conversion when nobody is subscribed
+                if self.event_handler.has_transaction_subscribers().await {
+                    let transaction = SuiTransaction::try_from(
+                        certificate.data().clone(),
+                        epoch_store.module_cache(),
+                    )
+                    .map_err(|e| SuiError::GenericAuthorityError {
+                        error: e.to_string(),
+                    })?;
+                    self.event_handler
+                        .process_transaction(SuiTransactionResponse {
+                            transaction: Some(transaction),
+                            effects: Some(sui_effects),
+                            events: Some(sui_events),
+                            timestamp_ms: Some(timestamp_ms),
+                            ..SuiTransactionResponse::new(*tx_digest)
+                        })
+                        .await
+                        .tap_err(|e| {
+                            warn!(
+                                ?tx_digest,
+                                "Post processing - Couldn't process transaction for tx: {}", e
+                            )
+                        })?;
+                }
             }
         };
         Ok(())
@@ -2367,7 +2406,7 @@ impl AuthorityState {
         reverse: bool,
     ) -> Result<Vec<TransactionDigest>, anyhow::Error> {
         self.get_indexes()?
-            .get_transactions(filter, cursor, limit, reverse)
+            .get_transactions(filter, cursor, limit, reverse, self)
     }
 
     fn get_checkpoint_store(&self) -> Arc<CheckpointStore> {
@@ -3400,6 +3439,39 @@ impl AuthorityState {
     }
 }
 
+impl CheckpointIndex for AuthorityState {
+    fn checkpoint_of(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self
+            .database
+            .get_transaction_checkpoint(digest)?
+            .map(|(_, checkpoint)| checkpoint))
+    }
+
+    fn checkpoint_transactions(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<Vec<TransactionDigest>>> {
+        let executed = self
+            .checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()?;
+        if !matches!(executed, Some(executed) if checkpoint <= executed) {
+            return Ok(None);
+        }
+        let Some(summary) = self
+            .checkpoint_store
+            .get_checkpoint_by_sequence_number(checkpoint)? else {
+            return Ok(None);
+        };
+        Ok(self
+            .checkpoint_store
+            .get_checkpoint_contents(&summary.content_digest)?
+            .map(|contents| contents.iter().map(|digests| digests.transaction).collect()))
+    }
+}
+
 fn calculate_checkpoint_numbers(
     // If `Some`, the query will start from the next item after the specified cursor
     cursor: Option<CheckpointSequenceNumber>,
@@ -17,8 +17,8 @@ use typed_store::rocks::{
 use typed_store::traits::{Map, TableSummary, TypedStoreDebug};
 
 use crate::authority::authority_store_types::{
-    MigratedStoreObjectPair, ObjectContentDigest, StoreData, StoreMoveObjectWrapper,
-    StoreObjectWrapper,
+    get_store_object_pair, MigratedStoreObjectPair, ObjectContentDigest, StoreData,
+    StoreMoveObjectWrapper, StoreObjectPair, StoreObjectWrapper,
 };
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
 use typed_store_derive::DBMapUtils;
@@ -222,6 +222,43 @@ impl AuthorityPerpetualTables {
         }
     }
 
+    /// Insert live objects, e.g. restored from a state snapshot, along with their parent
+    /// entries, and their locks for the objects which are not child objects.
+    pub fn insert_live_objects(
+        &self,
+        objects: &[Object],
+        indirect_objects_threshold: usize,
+    ) -> SuiResult {
+        let mut batch = self.objects.batch();
+        for object in objects {
+            let object_ref = object.compute_object_reference();
+            let StoreObjectPair(store_object, indirect_object) =
+                get_store_object_pair(object.clone(), indirect_objects_threshold);
+            batch = batch.insert_batch(
+                &self.objects,
+                std::iter::once((ObjectKey::from(object_ref), store_object)),
+            )?;
+            if let Some(indirect_object) = indirect_object {
+                batch = batch.insert_batch(
+                    &self.indirect_move_objects,
+                    std::iter::once((indirect_object.inner().digest(), indirect_object)),
+                )?;
+            }
+            batch = batch.insert_batch(
+                &self.parent_sync,
+                std::iter::once((object_ref, object.previous_transaction)),
+            )?;
+            if !object.is_child_object() {
+                batch = batch.insert_batch(
+                    &self.owned_object_transaction_locks,
+                    std::iter::once((object_ref, None)),
+                )?;
+            }
+        }
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
         // This checkpoints the entire db and not just objects table
         self.objects
@@ -230,6 +267,21 @@ impl AuthorityPerpetualTables {
     }
 }
 
+impl AuthorityPerpetualTablesReadOnly {
+    /// Same as [AuthorityPerpetualTables::object], for tools inspecting the database of a node.
+    pub fn object(&self, store_object: StoreObjectWrapper) -> Result<Object, SuiError> {
+        let store_object = store_object.migrate().into_inner();
+        let indirect_object = match store_object.data {
+            StoreData::IndirectObject(ref metadata) => self
+                .indirect_move_objects
+                .get(&metadata.digest)?
+                .map(|o| o.migrate().into_inner()),
+            _ => None,
+        };
+        MigratedStoreObjectPair(store_object, indirect_object).try_into()
+    }
+}
+
 impl ObjectStore for AuthorityPerpetualTables {
     /// Read an object and return it, or Ok(None) if the object was not found.
     fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
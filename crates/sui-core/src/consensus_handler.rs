@@ -30,6 +30,8 @@ use sui_types::messages::{
 };
 use sui_types::storage::ParentSync;
 
+#[cfg(feature = "fault-injection")]
+use tokio::sync::watch;
 use tracing::{debug, error, instrument};
 
 pub struct ConsensusHandler<T> {
@@ -50,10 +52,53 @@ pub struct ConsensusHandler<T> {
     /// Lru cache to quickly discard transactions processed by consensus
     processed_cache: Mutex<LruCache<SequencedConsensusTransactionKey, ()>>,
     transaction_scheduler: AsyncTransactionScheduler,
+    /// Consensus output is not processed while paused, used by tests to pause consensus.
+    pause: ConsensusPause,
 }
 
 const PROCESSED_CACHE_CAP: usize = 1024 * 1024;
 
+/// Pauses the processing of consensus output by the `ConsensusHandler` of a validator, across
+/// epochs, so that tests can stall the execution of transactions with shared objects and the
+/// creation of checkpoints. Consensus can only be paused with the `fault-injection` feature.
+#[derive(Clone, Debug)]
+pub struct ConsensusPause {
+    #[cfg(feature = "fault-injection")]
+    paused: Arc<watch::Sender<bool>>,
+}
+
+impl Default for ConsensusPause {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "fault-injection")]
+            paused: Arc::new(watch::channel(false).0),
+        }
+    }
+}
+
+impl ConsensusPause {
+    #[cfg(feature = "fault-injection")]
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    async fn wait_until_resumed(&self) {
+        #[cfg(feature = "fault-injection")]
+        {
+            let mut paused = self.paused.subscribe();
+            while *paused.borrow_and_update() {
+                // cannot fail, the sender is owned by self
+                let _ = paused.changed().await;
+            }
+        }
+    }
+}
+
 impl<T> ConsensusHandler<T> {
     pub fn new(
         epoch_store: Arc<AuthorityPerEpochStore>,
@@ -63,6 +108,7 @@ impl<T> ConsensusHandler<T> {
         low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
         committee: Arc<Committee>,
         metrics: Arc<AuthorityMetrics>,
+        pause: ConsensusPause,
     ) -> Self {
         let last_seen = Mutex::new(Default::default());
         let transaction_scheduler =
@@ -79,6 +125,7 @@ impl<T> ConsensusHandler<T> {
                 NonZeroUsize::new(PROCESSED_CACHE_CAP).unwrap(),
             )),
             transaction_scheduler,
+            pause,
         }
     }
 }
@@ -117,6 +164,10 @@ impl<T: ParentSync + Send + Sync> ExecutionState for ConsensusHandler<T> {
     /// This function will be called by Narwhal, after Narwhal sequenced this certificate.
     #[instrument(level = "trace", skip_all)]
     async fn handle_consensus_output(&self, consensus_output: ConsensusOutput) {
+        // Narwhal does not deliver the next output until this one is handled, so waiting here
+        // pauses the processing of all consensus output.
+        self.pause.wait_until_resumed().await;
+
         let _scope = monitored_scope("HandleConsensusOutput");
         let mut sequenced_transactions = Vec::new();
 
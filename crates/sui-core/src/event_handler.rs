@@ -5,8 +5,9 @@ use tokio_stream::Stream;
 use tracing::{error, instrument, trace};
 
 use sui_json_rpc_types::{EventFilter, SuiTransactionEffects, SuiTransactionEvents};
-use sui_json_rpc_types::{SuiEvent, SuiTransactionEffectsAPI};
+use sui_json_rpc_types::{SuiEvent, SuiTransactionEffectsAPI, SuiTransactionResponse};
 use sui_types::error::SuiResult;
+use sui_types::query::TransactionFilter;
 
 use crate::streamer::Streamer;
 
@@ -18,13 +19,14 @@ pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
 
 pub struct EventHandler {
     event_streamer: Streamer<SuiEvent, EventFilter>,
+    transaction_streamer: Streamer<SuiTransactionResponse, TransactionFilter>,
 }
 
 impl Default for EventHandler {
     fn default() -> Self {
-        let streamer = Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE);
         Self {
-            event_streamer: streamer,
+            event_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE),
+            transaction_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE),
         }
     }
 }
@@ -51,7 +53,26 @@ impl EventHandler {
         Ok(())
     }
 
+    #[instrument(level = "debug", skip_all, fields(tx_digest=?transaction.digest), err)]
+    pub async fn process_transaction(&self, transaction: SuiTransactionResponse) -> SuiResult {
+        if let Err(e) = self.transaction_streamer.send(transaction).await {
+            error!(error =? e, "Failed to send transaction to dispatch");
+        }
+        Ok(())
+    }
+
+    pub async fn has_transaction_subscribers(&self) -> bool {
+        self.transaction_streamer.has_subscribers().await
+    }
+
     pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = SuiEvent> {
         self.event_streamer.subscribe(filter)
     }
+
+    pub fn subscribe_transactions(
+        &self,
+        filter: TransactionFilter,
+    ) -> impl Stream<Item = SuiTransactionResponse> {
+        self.transaction_streamer.subscribe(filter)
+    }
 }
@@ -25,6 +25,7 @@ pub mod safe_client;
 mod scoring_decision;
 mod stake_aggregator;
 pub mod state_accumulator;
+pub mod state_snapshot;
 pub mod storage;
 pub mod streamer;
 pub mod test_utils;
@@ -0,0 +1,568 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! State snapshots hold the live object set at the end of an epoch, in a versioned format which
+//! does not depend on the database layout, so that a new node can start from the end of an epoch
+//! instead of syncing from genesis.
+//!
+//! A snapshot of epoch `N` is stored in the `epoch_N` directory of an object store:
+//! - `objects/<index>.obj`: BCS serialized vectors of the live objects,
+//! - `MANIFEST`: the BCS serialized [StateSnapshotManifest],
+//! - `_SUCCESS`: written once the snapshot is complete.
+
+use anyhow::{anyhow, Context, Result};
+use bytes::Bytes;
+use fastcrypto::hash::{HashFunction, MultisetHash, Sha3_256};
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use sui_config::genesis::Genesis;
+use sui_storage::object_store::util::put;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::accumulator::Accumulator;
+use sui_types::committee::{Committee, EpochId};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointCommitment, ECMHLiveObjectSetDigest, VerifiedCheckpoint,
+};
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
+use tracing::info;
+use typed_store::Map;
+
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+use crate::authority::epoch_start_configuration::EpochStartConfiguration;
+use crate::checkpoints::CheckpointStore;
+use crate::db_checkpoint_handler::SUCCESS_MARKER;
+use crate::epoch::committee_store::CommitteeStore;
+
+pub const STATE_SNAPSHOT_VERSION: u8 = 1;
+pub const MANIFEST_FILE_NAME: &str = "MANIFEST";
+const OBJECTS_DIR_NAME: &str = "objects";
+/// Objects are split into files of about this size.
+const MAX_OBJECTS_FILE_SIZE_BYTES: usize = 128 * 1024 * 1024;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StateSnapshotManifest {
+    pub version: u8,
+    pub epoch: EpochId,
+    /// The last checkpoint of the epoch, certified by the committee of the epoch.
+    pub last_checkpoint: CertifiedCheckpointSummary,
+    /// The last checkpoints of the previous epochs, which carry the committees of the following
+    /// epochs, to verify `last_checkpoint` starting from the genesis committee.
+    pub previous_end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+    /// The root state hash of the epoch as computed by the StateAccumulator. It is restored so
+    /// that the node keeps accumulating the following epochs.
+    pub root_state_hash: Accumulator,
+    /// The digest of the accumulator of the object digests in the snapshot.
+    pub live_object_set_digest: ECMHLiveObjectSetDigest,
+    pub num_objects: u64,
+    pub files: Vec<SnapshotFileMetadata>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotFileMetadata {
+    pub name: String,
+    pub num_objects: u64,
+    /// Sha3-256 digest of the content of the file.
+    pub sha3_digest: [u8; 32],
+}
+
+pub fn snapshot_dir(epoch: EpochId) -> Path {
+    Path::from(format!("epoch_{epoch}"))
+}
+
+/// Export the live object set at the end of the last accumulated epoch of the node database at
+/// `db_path`. The database must not have executed any checkpoint of the next epoch, which is the
+/// case of the database checkpoints taken at the end of an epoch, see
+/// `DBCheckpointConfig::perform_db_checkpoints_at_epoch_end`.
+pub async fn export_state_snapshot(
+    db_path: &std::path::Path,
+    output: &ObjectStoreConfig,
+) -> Result<StateSnapshotManifest> {
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+
+    let (epoch, (last_checkpoint_seq, root_state_hash)) = perpetual_tables
+        .root_state_hash_by_epoch
+        .iter()
+        .skip_to_last()
+        .next()
+        .ok_or_else(|| anyhow!("No epoch has been accumulated in {}", db_path.display()))?;
+    let highest_executed = checkpoint_store.get_highest_executed_checkpoint_seq_number()?;
+    if highest_executed != Some(last_checkpoint_seq) {
+        return Err(anyhow!(
+            "Database is not at the end of epoch {epoch}: highest executed checkpoint is {:?}, \
+             last checkpoint of the epoch is {last_checkpoint_seq}",
+            highest_executed
+        ));
+    }
+    let last_checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(last_checkpoint_seq)?
+        .ok_or_else(|| anyhow!("Missing last checkpoint {last_checkpoint_seq} of epoch {epoch}"))?;
+    let previous_end_of_epoch_checkpoints = (0..epoch)
+        .map(|previous| {
+            checkpoint_store
+                .get_epoch_last_checkpoint(previous)?
+                .map(VerifiedCheckpoint::into_inner)
+                .ok_or_else(|| anyhow!("Missing last checkpoint of epoch {previous}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let store = output.make()?;
+    let dir = snapshot_dir(epoch);
+    info!("Exporting state snapshot of epoch {epoch} to {dir}");
+
+    let mut live_object_set = Accumulator::default();
+    let mut files = vec![];
+    let mut objects = vec![];
+    let mut objects_size = 0;
+    let mut num_objects = 0;
+    for object_ref in perpetual_tables.iter_live_object_set() {
+        let store_object = perpetual_tables
+            .objects
+            .get(&ObjectKey::from(object_ref))?
+            .ok_or_else(|| anyhow!("Missing live object {:?}", object_ref))?;
+        let object = perpetual_tables.object(store_object)?;
+        live_object_set.insert(object_ref.2);
+        objects_size += bcs::serialized_size(&object)?;
+        objects.push(object);
+        num_objects += 1;
+        if objects_size >= MAX_OBJECTS_FILE_SIZE_BYTES {
+            files.push(write_objects_file(&store, &dir, files.len(), &objects).await?);
+            objects.clear();
+            objects_size = 0;
+        }
+    }
+    if !objects.is_empty() {
+        files.push(write_objects_file(&store, &dir, files.len(), &objects).await?);
+    }
+
+    let manifest = StateSnapshotManifest {
+        version: STATE_SNAPSHOT_VERSION,
+        epoch,
+        last_checkpoint: last_checkpoint.into_inner(),
+        previous_end_of_epoch_checkpoints,
+        root_state_hash,
+        live_object_set_digest: live_object_set.digest().into(),
+        num_objects,
+        files,
+    };
+    put(
+        &dir.child(MANIFEST_FILE_NAME),
+        Bytes::from(bcs::to_bytes(&manifest)?),
+        store.clone(),
+    )
+    .await?;
+    put(
+        &dir.child(SUCCESS_MARKER),
+        Bytes::from_static(b"success"),
+        store,
+    )
+    .await?;
+    info!("Exported {num_objects} objects of epoch {epoch}");
+    Ok(manifest)
+}
+
+/// Restore the state snapshot of `epoch` into the empty node database at `db_path`. Besides the
+/// live objects, the root state hash of the epoch, the committee and start configuration of the
+/// next epoch and the last checkpoint of the epoch are restored, so that the node starts syncing
+/// from the first checkpoint of the next epoch.
+///
+/// The last checkpoint of the snapshot is verified from the genesis committee and the objects
+/// against its live object set commitment before anything is written to the database.
+pub async fn restore_state_snapshot(
+    input: &ObjectStoreConfig,
+    epoch: EpochId,
+    db_path: &std::path::Path,
+    genesis: &Genesis,
+    indirect_objects_threshold: usize,
+) -> Result<StateSnapshotManifest> {
+    let store = input.make()?;
+    let dir = snapshot_dir(epoch);
+    store
+        .head(&dir.child(SUCCESS_MARKER))
+        .await
+        .context(format!("No complete state snapshot of epoch {epoch}"))?;
+    let manifest: StateSnapshotManifest =
+        bcs::from_bytes(&get(&store, &dir.child(MANIFEST_FILE_NAME)).await?)?;
+    if manifest.version != STATE_SNAPSHOT_VERSION {
+        return Err(anyhow!(
+            "Unsupported state snapshot version {}",
+            manifest.version
+        ));
+    }
+    let last_checkpoint = &manifest.last_checkpoint;
+    if manifest.epoch != epoch || last_checkpoint.epoch() != epoch {
+        return Err(anyhow!("State snapshot is not a snapshot of epoch {epoch}"));
+    }
+    let committee = verify_committee_chain(genesis, &manifest.previous_end_of_epoch_checkpoints)?;
+    last_checkpoint
+        .verify_signature(&committee)
+        .context("Checkpoint of the state snapshot is not certified by its epoch committee")?;
+    let next_epoch_committee = last_checkpoint.next_epoch_committee().ok_or_else(|| {
+        anyhow!("Checkpoint of the state snapshot is not the last checkpoint of its epoch")
+    })?;
+    let commitments = last_checkpoint
+        .end_of_epoch_data
+        .iter()
+        .flat_map(|data| &data.epoch_commitments)
+        .map(|commitment| {
+            let CheckpointCommitment::ECMHLiveObjectSetDigest(digest) = commitment;
+            digest.clone()
+        })
+        .collect::<Vec<_>>();
+    let live_object_set_commitment = match commitments.as_slice() {
+        [commitment] => commitment.clone(),
+        _ => {
+            return Err(anyhow!(
+                "Checkpoint of the state snapshot must commit to exactly one live object set, \
+                 found {}",
+                commitments.len()
+            ))
+        }
+    };
+    let root_state_digest: ECMHLiveObjectSetDigest = manifest.root_state_hash.digest().into();
+    if root_state_digest != live_object_set_commitment {
+        return Err(anyhow!(
+            "Root state hash of the state snapshot does not match the checkpoint"
+        ));
+    }
+
+    let perpetual_tables = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    if !perpetual_tables.database_is_empty()? {
+        return Err(anyhow!(
+            "Cannot restore a state snapshot into the non empty database {}",
+            db_path.display()
+        ));
+    }
+
+    // Verify all objects before writing any, the files are read again to be written rather than
+    // held in memory, their digests ensure that the same objects are written.
+    let mut live_object_set = Accumulator::default();
+    let mut num_objects = 0;
+    for file in &manifest.files {
+        let objects = read_objects_file(&store, &dir, file).await?;
+        live_object_set.insert_all(objects.iter().map(|object| object.digest()));
+        num_objects += objects.len() as u64;
+    }
+    let live_object_set_digest: ECMHLiveObjectSetDigest = live_object_set.digest().into();
+    if live_object_set_digest != live_object_set_commitment || num_objects != manifest.num_objects {
+        return Err(anyhow!(
+            "Objects of the state snapshot do not match the live object set of the checkpoint"
+        ));
+    }
+
+    info!(
+        "Restoring state snapshot of epoch {epoch} into {}",
+        db_path.display()
+    );
+    for file in &manifest.files {
+        let objects = read_objects_file(&store, &dir, file).await?;
+        perpetual_tables.insert_live_objects(&objects, indirect_objects_threshold)?;
+    }
+
+    perpetual_tables.root_state_hash_by_epoch.insert(
+        &epoch,
+        &(
+            *last_checkpoint.sequence_number(),
+            manifest.root_state_hash.clone(),
+        ),
+    )?;
+    let system_state = get_sui_system_state(&perpetual_tables)?;
+    perpetual_tables
+        .set_epoch_start_configuration(&EpochStartConfiguration::new_v1(
+            system_state.into_epoch_start_state(),
+            *last_checkpoint.digest(),
+        ))
+        .await?;
+
+    let committee_store = CommitteeStore::new(db_path.join("epochs"), &genesis.committee()?, None);
+    committee_store.insert_new_committee(&Committee::new(
+        epoch + 1,
+        next_epoch_committee
+            .iter()
+            .cloned()
+            .collect::<BTreeMap<_, _>>(),
+    ))?;
+
+    let checkpoint_store = CheckpointStore::new(&db_path.join("checkpoints"));
+    let last_checkpoint = VerifiedCheckpoint::new_unchecked(last_checkpoint.clone());
+    checkpoint_store.insert_verified_checkpoint(last_checkpoint.clone())?;
+    checkpoint_store.update_highest_synced_checkpoint(&last_checkpoint)?;
+    checkpoint_store.update_highest_executed_checkpoint(&last_checkpoint)?;
+
+    info!(
+        "Restored {} objects of epoch {epoch}, last checkpoint {} with digest {}",
+        manifest.num_objects,
+        last_checkpoint.sequence_number(),
+        last_checkpoint.digest()
+    );
+    Ok(manifest)
+}
+
+/// Returns the committee of the epoch following `end_of_epoch_checkpoints`, verifying each of
+/// them with the committee of its epoch, starting from the genesis committee.
+fn verify_committee_chain(
+    genesis: &Genesis,
+    end_of_epoch_checkpoints: &[CertifiedCheckpointSummary],
+) -> Result<Committee> {
+    let mut committee = genesis.committee()?;
+    for checkpoint in end_of_epoch_checkpoints {
+        if checkpoint.epoch() != committee.epoch {
+            return Err(anyhow!(
+                "Expected the last checkpoint of epoch {}, found a checkpoint of epoch {}",
+                committee.epoch,
+                checkpoint.epoch()
+            ));
+        }
+        checkpoint.verify_signature(&committee).context(format!(
+            "Last checkpoint of epoch {} is not certified by its committee",
+            committee.epoch
+        ))?;
+        let next_epoch_committee = checkpoint.next_epoch_committee().ok_or_else(|| {
+            anyhow!(
+                "Checkpoint {} is not the last checkpoint of epoch {}",
+                checkpoint.sequence_number(),
+                committee.epoch
+            )
+        })?;
+        committee = Committee::new(
+            committee.epoch + 1,
+            next_epoch_committee
+                .iter()
+                .cloned()
+                .collect::<BTreeMap<_, _>>(),
+        );
+    }
+    Ok(committee)
+}
+
+async fn read_objects_file(
+    store: &Arc<DynObjectStore>,
+    dir: &Path,
+    file: &SnapshotFileMetadata,
+) -> Result<Vec<Object>> {
+    let bytes = get(
+        store,
+        &dir.child(OBJECTS_DIR_NAME).child(file.name.as_str()),
+    )
+    .await?;
+    if Sha3_256::digest(&bytes).digest != file.sha3_digest {
+        return Err(anyhow!("State snapshot file {} is corrupted", file.name));
+    }
+    Ok(bcs::from_bytes(&bytes)?)
+}
+
+async fn write_objects_file(
+    store: &Arc<DynObjectStore>,
+    dir: &Path,
+    index: usize,
+    objects: &[Object],
+) -> Result<SnapshotFileMetadata> {
+    let name = format!("{index}.obj");
+    let bytes = bcs::to_bytes(objects)?;
+    let sha3_digest = Sha3_256::digest(&bytes).digest;
+    put(
+        &dir.child(OBJECTS_DIR_NAME).child(name.as_str()),
+        Bytes::from(bytes),
+        store.clone(),
+    )
+    .await?;
+    Ok(SnapshotFileMetadata {
+        name,
+        num_objects: objects.len() as u64,
+        sha3_digest,
+    })
+}
+
+async fn get(store: &Arc<DynObjectStore>, location: &Path) -> Result<Bytes> {
+    Ok(store.get(location).await?.bytes().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_config::NetworkConfig;
+    use sui_protocol_config::ProtocolVersion;
+    use sui_storage::object_store::ObjectStoreType;
+    use sui_types::crypto::KeypairTraits;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{
+        CheckpointContents, CheckpointSummary, EndOfEpochData, SignedCheckpointSummary,
+    };
+    use tempfile::TempDir;
+
+    /// A node database at the end of epoch 0 with `objects` as live objects, whose last
+    /// checkpoint commits to `root_state_hash` and is signed by the validators of `signers`.
+    fn end_of_epoch_database(
+        genesis: &Genesis,
+        signers: &NetworkConfig,
+        objects: &[Object],
+        root_state_hash: &Accumulator,
+    ) -> Result<TempDir> {
+        let committee = signers.genesis.committee()?;
+        let summary = CheckpointSummary::new(
+            0,
+            1,
+            1,
+            &CheckpointContents::new_with_causally_ordered_transactions([]),
+            Some(*genesis.checkpoint().digest()),
+            GasCostSummary::default(),
+            Some(EndOfEpochData {
+                next_epoch_committee: committee.voting_rights.clone(),
+                next_epoch_protocol_version: ProtocolVersion::MAX,
+                epoch_commitments: vec![CheckpointCommitment::ECMHLiveObjectSetDigest(
+                    root_state_hash.digest().into(),
+                )],
+            }),
+            0,
+        );
+        let signatures = signers
+            .validator_configs
+            .iter()
+            .map(|config| {
+                let key = config.protocol_key_pair();
+                SignedCheckpointSummary::sign(0, &summary, key, key.public().into())
+            })
+            .collect();
+        let checkpoint = VerifiedCheckpoint::new_unchecked(CertifiedCheckpointSummary::new(
+            summary, signatures, &committee,
+        )?);
+
+        let dir = TempDir::new()?;
+        let perpetual_tables = AuthorityPerpetualTables::open(&dir.path().join("store"), None);
+        perpetual_tables.insert_live_objects(objects, 0)?;
+        perpetual_tables
+            .root_state_hash_by_epoch
+            .insert(&0, &(1, root_state_hash.clone()))?;
+        let checkpoint_store = CheckpointStore::new(&dir.path().join("checkpoints"));
+        checkpoint_store.insert_verified_checkpoint(checkpoint.clone())?;
+        checkpoint_store.update_highest_executed_checkpoint(&checkpoint)?;
+        Ok(dir)
+    }
+
+    fn file_store_config(dir: &TempDir) -> ObjectStoreConfig {
+        ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_and_restore() -> Result<()> {
+        let config_dir = TempDir::new()?;
+        let network_config = sui_config::builder::ConfigBuilder::new(&config_dir).build();
+        let genesis = &network_config.genesis;
+
+        // A node database at the end of epoch 0, with the genesis objects as live objects.
+        let mut root_state_hash = Accumulator::default();
+        root_state_hash.insert_all(genesis.objects().iter().map(|object| object.digest()));
+        let source_dir = end_of_epoch_database(
+            genesis,
+            &network_config,
+            genesis.objects(),
+            &root_state_hash,
+        )?;
+
+        let snapshot_dir = TempDir::new()?;
+        let store_config = file_store_config(&snapshot_dir);
+        let exported = export_state_snapshot(source_dir.path(), &store_config).await?;
+        assert_eq!(exported.epoch, 0);
+        assert_eq!(exported.num_objects, genesis.objects().len() as u64);
+        assert!(exported.previous_end_of_epoch_checkpoints.is_empty());
+        assert!(snapshot_dir
+            .path()
+            .join("epoch_0")
+            .join(SUCCESS_MARKER)
+            .exists());
+
+        let target_dir = TempDir::new()?;
+        restore_state_snapshot(&store_config, 0, target_dir.path(), genesis, 0).await?;
+
+        {
+            let perpetual_tables =
+                AuthorityPerpetualTables::open(&target_dir.path().join("store"), None);
+            let mut live_object_set = Accumulator::default();
+            live_object_set.insert_all(perpetual_tables.iter_live_object_set().map(|o| o.2));
+            assert_eq!(live_object_set.digest(), root_state_hash.digest());
+            let (_, (last_checkpoint, restored_hash)) = perpetual_tables
+                .root_state_hash_by_epoch
+                .iter()
+                .next()
+                .unwrap();
+            assert_eq!(last_checkpoint, 1);
+            assert_eq!(restored_hash.digest(), root_state_hash.digest());
+            assert_eq!(perpetual_tables.get_recovery_epoch_at_restart()?, 0);
+            let checkpoint_store = CheckpointStore::new(&target_dir.path().join("checkpoints"));
+            assert_eq!(
+                checkpoint_store.get_highest_executed_checkpoint_seq_number()?,
+                Some(1)
+            );
+        }
+
+        // A database can only be restored once.
+        assert!(
+            restore_state_snapshot(&store_config, 0, target_dir.path(), genesis, 0)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_unverified_snapshot() -> Result<()> {
+        let config_dir = TempDir::new()?;
+        let network_config = sui_config::builder::ConfigBuilder::new(&config_dir).build();
+        let genesis = &network_config.genesis;
+        let mut root_state_hash = Accumulator::default();
+        root_state_hash.insert_all(genesis.objects().iter().map(|object| object.digest()));
+
+        let assert_not_restored = |target_dir: &TempDir| -> Result<()> {
+            let perpetual_tables =
+                AuthorityPerpetualTables::open(&target_dir.path().join("store"), None);
+            assert!(perpetual_tables.database_is_empty()?);
+            Ok(())
+        };
+
+        // A checkpoint signed by the validators of another network.
+        let other_config_dir = TempDir::new()?;
+        let other_network_config =
+            sui_config::builder::ConfigBuilder::new(&other_config_dir).build();
+        let source_dir = end_of_epoch_database(
+            genesis,
+            &other_network_config,
+            genesis.objects(),
+            &root_state_hash,
+        )?;
+        let snapshot_dir = TempDir::new()?;
+        let store_config = file_store_config(&snapshot_dir);
+        export_state_snapshot(source_dir.path(), &store_config).await?;
+        let target_dir = TempDir::new()?;
+        assert!(
+            restore_state_snapshot(&store_config, 0, target_dir.path(), genesis, 0)
+                .await
+                .is_err()
+        );
+        assert_not_restored(&target_dir)?;
+
+        // Objects which do not match the live object set the checkpoint commits to.
+        let (_, objects) = genesis.objects().split_last().unwrap();
+        let source_dir =
+            end_of_epoch_database(genesis, &network_config, objects, &root_state_hash)?;
+        let snapshot_dir = TempDir::new()?;
+        let store_config = file_store_config(&snapshot_dir);
+        export_state_snapshot(source_dir.path(), &store_config).await?;
+        let target_dir = TempDir::new()?;
+        assert!(
+            restore_state_snapshot(&store_config, 0, target_dir.path(), genesis, 0)
+                .await
+                .is_err()
+        );
+        assert_not_restored(&target_dir)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::Path;
 use std::sync::Arc;
 
+use move_core_types::language_storage::ModuleId;
+use move_core_types::resolver::ModuleResolver;
+
 use sui_types::base_types::TransactionDigest;
+use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::committee::Committee;
 use sui_types::committee::EpochId;
 use sui_types::digests::{TransactionEffectsDigest, TransactionEventsDigest};
+use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::VerifiedTransaction;
 use sui_types::messages::{TransactionEffects, TransactionEvents};
 use sui_types::messages_checkpoint::CheckpointContentsDigest;
@@ -16,13 +22,22 @@ use sui_types::messages_checkpoint::EndOfEpochData;
 use sui_types::messages_checkpoint::FullCheckpointContents;
 use sui_types::messages_checkpoint::VerifiedCheckpoint;
 use sui_types::messages_checkpoint::VerifiedCheckpointContents;
+use sui_types::object::Object;
 use sui_types::storage::ReadStore;
 use sui_types::storage::WriteStore;
+use sui_types::storage::{get_module, BackingPackageStore, ObjectKey};
+use sui_types::{fp_bail, fp_ensure};
+use typed_store::rocks::{MetricConf, TypedStoreError};
 use typed_store::Map;
 
+use crate::authority::authority_store_tables::{
+    AuthorityPerpetualTables, AuthorityPerpetualTablesReadOnly,
+};
 use crate::authority::AuthorityStore;
-use crate::checkpoints::CheckpointStore;
-use crate::epoch::committee_store::CommitteeStore;
+use crate::checkpoints::{CheckpointStore, CheckpointStoreReadOnly, CheckpointWatermark};
+use crate::epoch::committee_store::{
+    CommitteeStore, CommitteeStoreTables, CommitteeStoreTablesReadOnly,
+};
 
 #[derive(Clone)]
 pub struct RocksDbStore {
@@ -158,3 +173,330 @@ impl WriteStore for RocksDbStore {
         Ok(())
     }
 }
+
+/// A [ReadStore] over the databases of a node, opened as RocksDB secondary instances so that
+/// another process can read them while the node is running. Writes of the node are only visible
+/// after [SecondaryRocksDbStore::try_catch_up_with_primary].
+#[derive(Clone)]
+pub struct SecondaryRocksDbStore {
+    perpetual_tables: Arc<AuthorityPerpetualTablesReadOnly>,
+    committee_store: Arc<CommitteeStoreTablesReadOnly>,
+    checkpoint_store: Arc<CheckpointStoreReadOnly>,
+}
+
+impl SecondaryRocksDbStore {
+    /// `db_path` is the database directory of the node, which contains the `store`, `epochs` and
+    /// `checkpoints` directories.
+    pub fn open(db_path: &Path) -> Self {
+        Self {
+            perpetual_tables: Arc::new(AuthorityPerpetualTables::open_readonly(
+                &db_path.join("store"),
+            )),
+            committee_store: Arc::new(CommitteeStoreTables::get_read_only_handle(
+                db_path.join("epochs"),
+                None,
+                None,
+                MetricConf::default(),
+            )),
+            checkpoint_store: Arc::new(CheckpointStore::open_readonly(
+                &db_path.join("checkpoints"),
+            )),
+        }
+    }
+
+    pub fn try_catch_up_with_primary(&self) -> Result<(), TypedStoreError> {
+        // The tables of a store share one RocksDB instance, so catching up one table catches up
+        // all of them. Checkpoints are caught up first, so that the objects written by every
+        // checkpoint below the executed watermark are visible.
+        self.checkpoint_store
+            .watermarks
+            .try_catch_up_with_primary()?;
+        self.committee_store
+            .committee_map
+            .try_catch_up_with_primary()?;
+        self.perpetual_tables.objects.try_catch_up_with_primary()
+    }
+
+    pub fn get_highest_executed_checkpoint_seq_number(
+        &self,
+    ) -> Result<Option<CheckpointSequenceNumber>, TypedStoreError> {
+        Ok(self
+            .checkpoint_store
+            .watermarks
+            .get(&CheckpointWatermark::HighestExecuted)?
+            .map(|(sequence_number, _)| sequence_number))
+    }
+
+    /// Get an object at a version, None if the object version does not exist or was pruned.
+    pub fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        self.perpetual_tables
+            .objects
+            .get(&ObjectKey(*object_id, version))?
+            .map(|store_object| self.perpetual_tables.object(store_object))
+            .transpose()
+    }
+
+    fn get_watermark_checkpoint(
+        &self,
+        watermark: CheckpointWatermark,
+    ) -> Result<VerifiedCheckpoint, TypedStoreError> {
+        let (_, digest) = self
+            .checkpoint_store
+            .watermarks
+            .get(&watermark)?
+            .expect("storage should have been initialized with genesis checkpoint");
+        self.get_checkpoint_by_digest(&digest)
+            .map(|maybe_checkpoint| {
+                maybe_checkpoint.expect("watermark should point to a stored checkpoint")
+            })
+    }
+}
+
+impl ReadStore for SecondaryRocksDbStore {
+    type Error = TypedStoreError;
+
+    fn get_checkpoint_by_digest(
+        &self,
+        digest: &CheckpointDigest,
+    ) -> Result<Option<VerifiedCheckpoint>, Self::Error> {
+        self.checkpoint_store
+            .checkpoint_by_digest
+            .get(digest)
+            .map(|maybe_checkpoint| maybe_checkpoint.map(|c| c.into()))
+    }
+
+    fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<VerifiedCheckpoint>, Self::Error> {
+        self.checkpoint_store
+            .certified_checkpoints
+            .get(&sequence_number)
+            .map(|maybe_checkpoint| maybe_checkpoint.map(|c| c.into()))
+    }
+
+    fn get_highest_verified_checkpoint(&self) -> Result<VerifiedCheckpoint, Self::Error> {
+        self.get_watermark_checkpoint(CheckpointWatermark::HighestVerified)
+    }
+
+    fn get_highest_synced_checkpoint(&self) -> Result<VerifiedCheckpoint, Self::Error> {
+        self.get_watermark_checkpoint(CheckpointWatermark::HighestSynced)
+    }
+
+    fn get_full_checkpoint_contents(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Result<Option<FullCheckpointContents>, Self::Error> {
+        self.checkpoint_store
+            .checkpoint_content
+            .get(digest)?
+            .map(|contents| FullCheckpointContents::from_checkpoint_contents(&self, contents))
+            .transpose()
+            .map(|contents| contents.flatten())
+    }
+
+    fn get_committee(&self, epoch: EpochId) -> Result<Option<Arc<Committee>>, Self::Error> {
+        self.committee_store
+            .committee_map
+            .get(&epoch)
+            .map(|maybe_committee| maybe_committee.map(Arc::new))
+    }
+
+    fn get_transaction(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Result<Option<VerifiedTransaction>, Self::Error> {
+        self.perpetual_tables
+            .transactions
+            .get(digest)
+            .map(|v| v.map(|v| v.into()))
+    }
+
+    fn get_transaction_effects(
+        &self,
+        digest: &TransactionEffectsDigest,
+    ) -> Result<Option<TransactionEffects>, Self::Error> {
+        self.perpetual_tables.effects.get(digest)
+    }
+
+    fn get_transaction_events(
+        &self,
+        digest: &TransactionEventsDigest,
+    ) -> Result<Option<TransactionEvents>, Self::Error> {
+        let data = self
+            .perpetual_tables
+            .events
+            .iter()
+            .skip_to(&(*digest, 0))?
+            .take_while(|((events_digest, _), _)| events_digest == digest)
+            .map(|(_, event)| event)
+            .collect::<Vec<_>>();
+        Ok((!data.is_empty()).then_some(TransactionEvents { data }))
+    }
+}
+
+impl BackingPackageStore for SecondaryRocksDbStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        let package = self
+            .perpetual_tables
+            .objects
+            .iter()
+            .skip_prior_to(&ObjectKey::max_for_id(package_id))?
+            .next()
+            .filter(|(key, _)| key.0 == *package_id)
+            .map(|(_, store_object)| self.perpetual_tables.object(store_object))
+            .transpose()?;
+        if let Some(obj) = &package {
+            fp_ensure!(
+                obj.is_package(),
+                SuiError::BadObjectType {
+                    error: format!("Package expected, Move object found: {package_id}"),
+                }
+            );
+        }
+        Ok(package)
+    }
+}
+
+impl ModuleResolver for SecondaryRocksDbStore {
+    type Error = SuiError;
+
+    fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
+        get_module(self, module_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::crypto::KeypairTraits;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, SignedCheckpointSummary,
+    };
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_secondary_store_catches_up_with_primary() -> anyhow::Result<()> {
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let genesis = &network_config.genesis;
+        let committee = genesis.committee()?;
+
+        // The databases of a node, with the genesis checkpoint executed.
+        let dir = TempDir::new()?;
+        let authority_store = AuthorityStore::open_with_committee_for_testing(
+            &dir.path().join("store"),
+            None,
+            &committee,
+            genesis,
+            0,
+        )
+        .await?;
+        let committee_store = CommitteeStore::new(dir.path().join("epochs"), &committee, None);
+        let checkpoint_store = CheckpointStore::new(&dir.path().join("checkpoints"));
+        let genesis_checkpoint = genesis.checkpoint();
+        checkpoint_store.insert_verified_checkpoint(genesis_checkpoint.clone())?;
+        checkpoint_store.insert_checkpoint_contents(genesis.checkpoint_contents().clone())?;
+        checkpoint_store.update_highest_synced_checkpoint(&genesis_checkpoint)?;
+        checkpoint_store.update_highest_executed_checkpoint(&genesis_checkpoint)?;
+
+        let secondary = SecondaryRocksDbStore::open(dir.path());
+        assert_eq!(
+            secondary.get_highest_executed_checkpoint_seq_number()?,
+            Some(0)
+        );
+        assert_eq!(
+            secondary.get_highest_synced_checkpoint()?.digest(),
+            genesis_checkpoint.digest()
+        );
+        let contents = secondary
+            .get_full_checkpoint_contents(&genesis_checkpoint.content_digest)?
+            .unwrap();
+        assert_eq!(
+            contents.iter().next().unwrap().transaction.digest(),
+            genesis.transaction().digest()
+        );
+        let genesis_object = &genesis.objects()[0];
+        assert_eq!(
+            secondary
+                .get_object_by_key(&genesis_object.id(), genesis_object.version())?
+                .as_ref(),
+            Some(genesis_object)
+        );
+        assert!(secondary.get_committee(0)?.is_some());
+
+        // The node executes a checkpoint, writing an object, and starts a new epoch.
+        let contents = CheckpointContents::new_with_causally_ordered_transactions([]);
+        let summary = CheckpointSummary::new(
+            0,
+            1,
+            1,
+            &contents,
+            Some(*genesis_checkpoint.digest()),
+            GasCostSummary::default(),
+            None,
+            0,
+        );
+        let signatures = network_config
+            .validator_configs
+            .iter()
+            .map(|config| {
+                let key = config.protocol_key_pair();
+                SignedCheckpointSummary::sign(0, &summary, key, key.public().into())
+            })
+            .collect();
+        let checkpoint = VerifiedCheckpoint::new_unchecked(CertifiedCheckpointSummary::new(
+            summary, signatures, &committee,
+        )?);
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        authority_store
+            .perpetual_tables
+            .insert_live_objects(&[object.clone()], 0)?;
+        checkpoint_store.insert_verified_checkpoint(checkpoint.clone())?;
+        checkpoint_store.insert_checkpoint_contents(contents)?;
+        checkpoint_store.update_highest_synced_checkpoint(&checkpoint)?;
+        checkpoint_store.update_highest_executed_checkpoint(&checkpoint)?;
+        let next_committee = Committee::new(1, committee.voting_rights.iter().cloned().collect());
+        committee_store.insert_new_committee(&next_committee)?;
+
+        // The writes of the node are not visible until the secondary catches up.
+        assert_eq!(
+            secondary.get_highest_executed_checkpoint_seq_number()?,
+            Some(0)
+        );
+        assert!(secondary.get_checkpoint_by_sequence_number(1)?.is_none());
+        assert!(secondary
+            .get_object_by_key(&object.id(), object.version())?
+            .is_none());
+        assert!(secondary.get_committee(1)?.is_none());
+
+        secondary.try_catch_up_with_primary()?;
+        assert_eq!(
+            secondary.get_highest_executed_checkpoint_seq_number()?,
+            Some(1)
+        );
+        assert_eq!(
+            secondary.get_highest_synced_checkpoint()?.digest(),
+            checkpoint.digest()
+        );
+        assert_eq!(
+            secondary
+                .get_checkpoint_by_sequence_number(1)?
+                .map(|checkpoint| *checkpoint.digest()),
+            Some(*checkpoint.digest())
+        );
+        assert_eq!(
+            secondary.get_object_by_key(&object.id(), object.version())?,
+            Some(object)
+        );
+        assert_eq!(
+            secondary.get_committee(1)?.as_deref(),
+            Some(&next_committee)
+        );
+        Ok(())
+    }
+}
@@ -77,6 +77,12 @@ where
         ReceiverStream::new(rx)
     }
 
+    /// Returns true if at least one subscriber is registered, so callers can skip building data
+    /// nobody will receive.
+    pub async fn has_subscribers(&self) -> bool {
+        !self.subscribers.read().await.is_empty()
+    }
+
     pub async fn send(&self, data: T) -> Result<(), SuiError> {
         self.streamer_queue
             .send(data)
@@ -237,7 +237,8 @@ async fn test_dry_run_transaction() {
             transaction_digest,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .0;
     assert_eq!(*response.effects.status(), SuiExecutionStatus::Success);
     let gas_usage = response.effects.gas_used();
 
@@ -268,7 +269,8 @@ async fn test_dry_run_transaction() {
     let response = fullnode
         .dry_exec_transaction(txn_data, transaction_digest)
         .await
-        .unwrap();
+        .unwrap()
+        .0;
     let gas_usage_no_gas = response.effects.gas_used();
     assert_eq!(*response.effects.status(), SuiExecutionStatus::Success);
     assert_eq!(gas_usage, gas_usage_no_gas);
@@ -301,7 +303,8 @@ async fn test_dry_run_no_gas_big_transfer() {
             *signed.digest(),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .0;
     assert_eq!(*dry_run_res.effects.status(), SuiExecutionStatus::Success);
 }
 
@@ -5285,7 +5288,8 @@ async fn test_for_inc_201_dry_run() {
             *signed.digest(),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .0;
 
     assert_eq!(1, events.data.len());
     assert_eq!(
@@ -683,7 +683,7 @@ mod tests {
         let res = SuiClientCommands::PayAllSui {
             input_coins: vec![*bad_gas.id()],
             recipient: SuiAddress::random_for_testing_only(),
-            gas_budget: 50000,
+            gas_budget: Some(50000),
         }
         .execute(faucet.wallet_mut())
         .await
@@ -738,7 +738,7 @@ mod tests {
         let res = SuiClientCommands::SplitCoin {
             coin_id: *gases[0].id(),
             amounts: Some(vec![tiny_value + DEFAULT_GAS_COMPUTATION_BUCKET]),
-            gas_budget: 50000,
+            gas_budget: Some(50000),
             gas: None,
             count: None,
         }
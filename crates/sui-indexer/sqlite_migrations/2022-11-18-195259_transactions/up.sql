@@ -0,0 +1,44 @@
+-- SQLite has no array type, the TEXT[] columns of the PostgreSQL schema
+-- are JSON arrays of strings.
+CREATE TABLE transactions (
+    id                          INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_digest          TEXT    NOT NULL,
+    sender                      TEXT    NOT NULL,
+    recipients                  TEXT    NOT NULL,
+    checkpoint_sequence_number  BIGINT  NOT NULL,
+    timestamp_ms                BIGINT  NOT NULL,
+    transaction_kind            TEXT    NOT NULL,
+    -- object related
+    created                     TEXT    NOT NULL,
+    mutated                     TEXT    NOT NULL,
+    deleted                     TEXT    NOT NULL,
+    unwrapped                   TEXT    NOT NULL,
+    wrapped                     TEXT    NOT NULL,
+    -- each move call is <package>::<module>::<function>
+    move_calls                  TEXT    NOT NULL,
+    -- gas object related
+    gas_object_id               TEXT    NOT NULL,
+    gas_object_sequence         BIGINT  NOT NULL,
+    gas_object_digest           TEXT    NOT NULL,
+    -- gas budget & cost related
+    gas_budget                  BIGINT  NOT NULL,
+    total_gas_cost              BIGINT  NOT NULL,
+    computation_cost            BIGINT  NOT NULL,
+    storage_cost                BIGINT  NOT NULL,
+    storage_rebate              BIGINT  NOT NULL,
+    -- gas price from transaction data,
+    -- not the reference gas price
+    gas_price                   BIGINT  NOT NULL,
+    -- BCS serialized SenderSignedData
+    raw_transaction             BLOB    NOT NULL,
+    transaction_content         TEXT    NOT NULL,
+    transaction_effects_content TEXT    NOT NULL,
+    confirmed_local_execution   BOOLEAN,
+    UNIQUE (transaction_digest)
+);
+
+CREATE INDEX transactions_transaction_digest ON transactions (transaction_digest);
+CREATE INDEX transactions_timestamp_ms ON transactions (timestamp_ms);
+CREATE INDEX transactions_sender ON transactions (sender);
+CREATE INDEX transactions_gas_object_id ON transactions (gas_object_id);
+CREATE INDEX transactions_checkpoint_sequence_number ON transactions (checkpoint_sequence_number);
@@ -0,0 +1,8 @@
+CREATE TABLE addresses
+(
+    account_address       TEXT   PRIMARY KEY,
+    first_appearance_tx   TEXT   NOT NULL,
+    first_appearance_time BIGINT NOT NULL
+);
+CREATE INDEX addresses_first_appearance_tx ON addresses (first_appearance_tx);
+CREATE INDEX addresses_first_appearance_time ON addresses (first_appearance_time);
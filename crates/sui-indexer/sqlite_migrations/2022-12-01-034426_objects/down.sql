@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS objects;
+DROP TABLE IF EXISTS objects_history;
@@ -0,0 +1,81 @@
+-- The owner_type and object_status enums of the PostgreSQL schema are TEXT
+-- columns with the same values, and the bcs_bytes[] columns are the BCS
+-- serialized list of (name, bytes) pairs.
+CREATE TABLE objects
+(
+    epoch                  BIGINT  NOT NULL,
+    checkpoint             BIGINT  NOT NULL,
+    object_id              TEXT    PRIMARY KEY,
+    version                BIGINT  NOT NULL,
+    object_digest          TEXT    NOT NULL,
+    -- owner related
+    owner_type             TEXT    NOT NULL CHECK (owner_type IN ('address_owner', 'object_owner', 'shared', 'immutable')),
+    -- only non-null for objects with an owner,
+    -- the owner can be an account or an object.
+    owner_address          TEXT,
+    -- only non-null for shared objects
+    initial_shared_version BIGINT,
+    previous_transaction   TEXT    NOT NULL,
+    object_type            TEXT    NOT NULL,
+    object_status          TEXT    NOT NULL CHECK (object_status IN ('created', 'mutated', 'deleted', 'wrapped', 'unwrapped', 'unwrapped_then_deleted')),
+    has_public_transfer    BOOLEAN NOT NULL,
+    storage_rebate         BIGINT  NOT NULL,
+    bcs                    BLOB    NOT NULL
+);
+CREATE INDEX objects_owner_address ON objects (owner_type, owner_address);
+CREATE INDEX objects_tx_digest ON objects (previous_transaction);
+
+-- SQLite has no table partitioning, the history of all epochs is in one table.
+CREATE TABLE objects_history
+(
+    epoch                  BIGINT  NOT NULL,
+    checkpoint             BIGINT  NOT NULL,
+    object_id              TEXT    NOT NULL,
+    version                BIGINT  NOT NULL,
+    object_digest          TEXT    NOT NULL,
+    owner_type             TEXT    NOT NULL,
+    owner_address          TEXT,
+    initial_shared_version BIGINT,
+    previous_transaction   TEXT    NOT NULL,
+    object_type            TEXT    NOT NULL,
+    object_status          TEXT    NOT NULL,
+    has_public_transfer    BOOLEAN NOT NULL,
+    storage_rebate         BIGINT  NOT NULL,
+    bcs                    BLOB    NOT NULL,
+    CONSTRAINT objects_history_pk PRIMARY KEY (epoch, object_id, version)
+);
+
+CREATE TRIGGER objects_history_insert
+    AFTER INSERT
+    ON objects
+    FOR EACH ROW
+BEGIN
+    INSERT OR IGNORE INTO objects_history
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address,
+            NEW.initial_shared_version,
+            NEW.previous_transaction, NEW.object_type, NEW.object_status, NEW.has_public_transfer,
+            NEW.storage_rebate, NEW.bcs);
+END;
+
+CREATE TRIGGER objects_history_update
+    AFTER UPDATE
+    ON objects
+    FOR EACH ROW
+BEGIN
+    INSERT OR IGNORE INTO objects_history
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address,
+            NEW.initial_shared_version,
+            NEW.previous_transaction, NEW.object_type, NEW.object_status, NEW.has_public_transfer,
+            NEW.storage_rebate, NEW.bcs);
+END;
+
+-- object deleted from the main table, archive the history for that object
+CREATE TRIGGER objects_history_delete
+    AFTER DELETE
+    ON objects
+    FOR EACH ROW
+BEGIN
+    DELETE FROM objects_history WHERE object_id = OLD.object_id;
+END;
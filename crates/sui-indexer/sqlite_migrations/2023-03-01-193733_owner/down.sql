@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS owner_insert;
+DROP TRIGGER IF EXISTS owner_update;
+DROP TRIGGER IF EXISTS owner_delete;
+
+DROP TABLE IF EXISTS owner;
+DROP TABLE IF EXISTS owner_history;
@@ -0,0 +1,105 @@
+CREATE TABLE owner
+(
+    epoch         BIGINT NOT NULL,
+    checkpoint    BIGINT NOT NULL,
+    object_id     TEXT PRIMARY KEY,
+    version       BIGINT NOT NULL,
+    object_digest TEXT   NOT NULL,
+    owner_type    TEXT   NOT NULL,
+    owner_address TEXT,
+    object_status TEXT   NOT NULL
+);
+CREATE INDEX owner_epoch_index ON owner (epoch);
+CREATE INDEX owner_owner_index ON owner (owner_type, owner_address);
+
+-- triggers updating owner table from objects table, ignoring shared and immutable objects
+CREATE TRIGGER owner_insert
+    AFTER INSERT
+    ON objects
+    FOR EACH ROW
+    WHEN NEW.owner_type IN ('address_owner', 'object_owner')
+BEGIN
+    INSERT OR IGNORE INTO owner
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address, NEW.object_status);
+END;
+
+CREATE TRIGGER owner_update
+    AFTER UPDATE
+    ON objects
+    FOR EACH ROW
+    WHEN NEW.owner_type IN ('address_owner', 'object_owner')
+BEGIN
+    UPDATE owner
+    SET epoch         = NEW.epoch,
+        checkpoint    = NEW.checkpoint,
+        version       = NEW.version,
+        object_digest = NEW.object_digest,
+        owner_type    = NEW.owner_type,
+        owner_address = NEW.owner_address,
+        object_status = NEW.object_status
+    WHERE object_id = NEW.object_id;
+END;
+
+CREATE TRIGGER owner_delete
+    AFTER DELETE
+    ON objects
+    FOR EACH ROW
+BEGIN
+    DELETE
+    FROM owner
+    WHERE owner_type = OLD.owner_type
+      AND owner_address = OLD.owner_address
+      AND object_id = OLD.object_id;
+END;
+
+CREATE TABLE owner_history
+(
+    epoch             BIGINT NOT NULL,
+    checkpoint        BIGINT NOT NULL,
+    object_id         TEXT   NOT NULL,
+    version           BIGINT NOT NULL,
+    object_digest     TEXT   NOT NULL,
+    owner_type        TEXT,
+    owner_address     TEXT,
+    old_owner_type    TEXT,
+    old_owner_address TEXT,
+    object_status     TEXT   NOT NULL,
+    CONSTRAINT owner_history_pk PRIMARY KEY (epoch, object_id, version)
+);
+CREATE INDEX owner_history_new_owner_index ON owner_history (owner_type, owner_address);
+CREATE INDEX owner_history_old_owner_index ON owner_history (old_owner_type, old_owner_address);
+
+-- triggers updating owner_history table from owner table
+CREATE TRIGGER owner_history_insert
+    AFTER INSERT
+    ON owner
+    FOR EACH ROW
+BEGIN
+    INSERT OR IGNORE INTO owner_history
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address, NULL, NULL, NEW.object_status);
+END;
+
+CREATE TRIGGER owner_history_update
+    AFTER UPDATE
+    ON owner
+    FOR EACH ROW
+BEGIN
+    INSERT OR IGNORE INTO owner_history
+    VALUES (NEW.epoch, NEW.checkpoint, NEW.object_id, NEW.version, NEW.object_digest, NEW.owner_type,
+            NEW.owner_address, OLD.owner_type,
+            OLD.owner_address, NEW.object_status);
+END;
+
+-- owner info deleted from the main table, archive the owner history
+CREATE TRIGGER owner_history_delete
+    AFTER DELETE
+    ON owner
+    FOR EACH ROW
+BEGIN
+    DELETE FROM owner_history WHERE object_id = OLD.object_id;
+END;
+
+-- NOTE: SQLite has no stored functions, the object_owned_at_checkpoint function
+-- of the PostgreSQL schema is not available.
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS epochs;
\ No newline at end of file
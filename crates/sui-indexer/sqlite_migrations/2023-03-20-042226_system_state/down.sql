@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS system_states;
+DROP TABLE IF EXISTS validators;
+DROP TABLE IF EXISTS at_risk_validators;
\ No newline at end of file
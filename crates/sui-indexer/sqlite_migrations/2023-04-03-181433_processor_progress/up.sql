@@ -0,0 +1,6 @@
+-- last checkpoint processed by each custom processor
+CREATE TABLE processor_progress
+(
+    processor_name             TEXT PRIMARY KEY,
+    checkpoint_sequence_number BIGINT NOT NULL
+);
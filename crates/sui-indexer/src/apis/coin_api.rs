@@ -8,7 +8,9 @@ use jsonrpsee::RpcModule;
 use sui_json_rpc::api::CoinReadApiClient;
 use sui_json_rpc::api::CoinReadApiServer;
 use sui_json_rpc::SuiRpcModule;
-use sui_json_rpc_types::{Balance, CoinPage, SuiCoinMetadata};
+use sui_json_rpc_types::{
+    Balance, CheckpointId, Coin, CoinPage, CoinSelectionStrategy, SuiCoinMetadata,
+};
 use sui_open_rpc::Module;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -48,16 +50,36 @@ impl CoinReadApiServer for CoinReadApi {
         self.fullnode.get_all_coins(owner, cursor, limit).await
     }
 
+    async fn select_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        amount: u128,
+        exclude: Option<Vec<ObjectID>>,
+        strategy: Option<CoinSelectionStrategy>,
+    ) -> RpcResult<Vec<Coin>> {
+        self.fullnode
+            .select_coins(owner, coin_type, amount, exclude, strategy)
+            .await
+    }
+
     async fn get_balance(
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
+        at_checkpoint: Option<CheckpointId>,
     ) -> RpcResult<Balance> {
-        self.fullnode.get_balance(owner, coin_type).await
+        self.fullnode
+            .get_balance(owner, coin_type, at_checkpoint)
+            .await
     }
 
-    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
-        self.fullnode.get_all_balances(owner).await
+    async fn get_all_balances(
+        &self,
+        owner: SuiAddress,
+        at_checkpoint: Option<CheckpointId>,
+    ) -> RpcResult<Vec<Balance>> {
+        self.fullnode.get_all_balances(owner, at_checkpoint).await
     }
 
     async fn get_coin_metadata(&self, coin_type: String) -> RpcResult<SuiCoinMetadata> {
@@ -3,9 +3,11 @@
 
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
 
@@ -18,6 +20,7 @@ use sui_json_rpc_types::{EventFilter, EventPage, SuiEvent};
 use sui_open_rpc::Module;
 use sui_types::digests::TransactionDigest;
 use sui_types::event::EventID;
+use sui_types::query::TransactionFilter;
 
 use crate::errors::IndexerError;
 use crate::store::IndexerStore;
@@ -81,6 +84,23 @@ where
         spawn_subscription(sink, self.event_handler.subscribe(filter));
         Ok(())
     }
+
+    fn subscribe_transaction(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: TransactionFilter,
+    ) -> SubscriptionResult {
+        if filter.uses_checkpoint() {
+            sink.reject(CallError::InvalidParams(anyhow!(
+                "CheckpointRange filters are not supported by subscriptions, transactions are \
+                 streamed before they are included in a checkpoint"
+            )))?;
+            return Ok(());
+        }
+        spawn_subscription(sink, self.event_handler.subscribe_transactions(filter));
+        Ok(())
+    }
+
     async fn get_events(&self, transaction_digest: TransactionDigest) -> RpcResult<Vec<SuiEvent>> {
         self.fullnode.get_events(transaction_digest).await
     }
@@ -7,7 +7,9 @@ use crate::types::SuiTransactionFullResponse;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
-use jsonrpsee::RpcModule;
+use jsonrpsee::types::error::CallError;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
 use std::collections::BTreeMap;
 use sui_json_rpc::api::{cap_page_limit, ReadApiClient, ReadApiServer};
 use sui_json_rpc::SuiRpcModule;
@@ -168,6 +170,12 @@ impl<S: IndexerStore> ReadApi<S> {
                     is_descending,
                 )
             }
+            Some(filter) => {
+                return Err(IndexerError::NotImplementedError(format!(
+                    "filter {:?} has not been implemented on indexer for queryTransactions",
+                    filter
+                )));
+            }
         }?;
 
         // digests here are of size (limit + 1), where the last one is the cursor for the next page
@@ -464,6 +472,20 @@ where
             .get_checkpoints(cursor, limit, descending_order)
             .await;
     }
+
+    fn subscribe_checkpoint(
+        &self,
+        mut sink: SubscriptionSink,
+        _options: Option<SuiTransactionResponseOptions>,
+    ) -> SubscriptionResult {
+        sink.reject(CallError::Failed(
+            IndexerError::NotImplementedError(
+                "subscribeCheckpoint has not been implemented on indexer".to_string(),
+            )
+            .into(),
+        ))?;
+        Ok(())
+    }
 }
 
 impl<S> SuiRpcModule for ReadApi<S>
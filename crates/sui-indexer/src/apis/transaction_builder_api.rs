@@ -36,9 +36,10 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         gas: Option<ObjectID>,
         gas_budget: u64,
         recipient: SuiAddress,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .transfer_object(signer, object_id, gas, gas_budget, recipient)
+            .transfer_object(signer, object_id, gas, gas_budget, recipient, sponsor)
             .await
     }
 
@@ -63,9 +64,18 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         amounts: Vec<BigInt>,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .pay(signer, input_coins, recipients, amounts, gas, gas_budget)
+            .pay(
+                signer,
+                input_coins,
+                recipients,
+                amounts,
+                gas,
+                gas_budget,
+                sponsor,
+            )
             .await
     }
 
@@ -101,9 +111,10 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         dep_ids: Vec<ObjectID>,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .publish(sender, compiled_modules, dep_ids, gas, gas_budget)
+            .publish(sender, compiled_modules, dep_ids, gas, gas_budget, sponsor)
             .await
     }
 
@@ -114,9 +125,17 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .split_coin(signer, coin_object_id, split_amounts, gas, gas_budget)
+            .split_coin(
+                signer,
+                coin_object_id,
+                split_amounts,
+                gas,
+                gas_budget,
+                sponsor,
+            )
             .await
     }
 
@@ -127,9 +146,17 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         split_count: u64,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .split_coin_equal(signer, coin_object_id, split_count, gas, gas_budget)
+            .split_coin_equal(
+                signer,
+                coin_object_id,
+                split_count,
+                gas,
+                gas_budget,
+                sponsor,
+            )
             .await
     }
 
@@ -140,9 +167,17 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .merge_coin(signer, primary_coin, coin_to_merge, gas, gas_budget)
+            .merge_coin(
+                signer,
+                primary_coin,
+                coin_to_merge,
+                gas,
+                gas_budget,
+                sponsor,
+            )
             .await
     }
 
@@ -157,6 +192,7 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         gas: Option<ObjectID>,
         gas_budget: u64,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
             .move_call(
@@ -169,6 +205,7 @@ impl TransactionBuilderServer for TransactionBuilderApi {
                 gas,
                 gas_budget,
                 txn_builder_mode,
+                sponsor,
             )
             .await
     }
@@ -180,9 +217,10 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         gas: Option<ObjectID>,
         gas_budget: u64,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .batch_transaction(signer, params, gas, gas_budget, txn_builder_mode)
+            .batch_transaction(signer, params, gas, gas_budget, txn_builder_mode, sponsor)
             .await
     }
 
@@ -194,9 +232,10 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         validator: SuiAddress,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .request_add_stake(signer, coins, amount, validator, gas, gas_budget)
+            .request_add_stake(signer, coins, amount, validator, gas, gas_budget, sponsor)
             .await
     }
 
@@ -206,9 +245,10 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         staked_sui: ObjectID,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .request_withdraw_stake(signer, staked_sui, gas, gas_budget)
+            .request_withdraw_stake(signer, staked_sui, gas, gas_budget, sponsor)
             .await
     }
 }
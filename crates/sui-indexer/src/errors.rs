@@ -45,6 +45,21 @@ pub enum IndexerError {
     #[error(transparent)]
     PostgresError(#[from] diesel::result::Error),
 
+    #[error("Indexer failed to build SQLite connection pool with error: `{0}`")]
+    SqliteConnectionPoolInitError(String),
+
+    #[error("Indexer failed to get a pool connection from SQLite pool with error: `{0}`")]
+    SqlitePoolConnectionError(String),
+
+    #[error("Indexer failed to read SQLite DB with error: `{0}`")]
+    SqliteReadError(String),
+
+    #[error("Indexer failed to reset SQLite DB with error: `{0}`")]
+    SqliteResetError(String),
+
+    #[error("Indexer failed to commit changes to SQLite DB with error: `{0}`")]
+    SqliteWriteError(String),
+
     #[error("Indexer failed to initialize fullnode RPC client with error: `{0}`")]
     RpcClientInitError(String),
 
@@ -88,6 +103,13 @@ impl IndexerError {
             IndexerError::SerdeError(_) => "SerdeError".into(),
             IndexerError::NotImplementedError(_) => "NotImplementedError".into(),
             IndexerError::PostgresError(_) => "PostgresError".into(),
+            IndexerError::SqliteConnectionPoolInitError(_) => {
+                "SqliteConnectionPoolInitError".into()
+            }
+            IndexerError::SqlitePoolConnectionError(_) => "SqlitePoolConnectionError".into(),
+            IndexerError::SqliteReadError(_) => "SqliteReadError".into(),
+            IndexerError::SqliteResetError(_) => "SqliteResetError".into(),
+            IndexerError::SqliteWriteError(_) => "SqliteWriteError".into(),
             IndexerError::UncategorizedError(_) => "UncategorizedError".into(),
             IndexerError::ObjectIdParseError(_) => "ObjectIdParseError".into(),
             IndexerError::SuiError(_) => "SuiError".into(),
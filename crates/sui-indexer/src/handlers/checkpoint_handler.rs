@@ -9,7 +9,7 @@ use futures::FutureExt;
 use move_core_types::ident_str;
 use prometheus::Registry;
 use tokio::task::JoinHandle;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use mysten_metrics::spawn_monitored_task;
 use sui_core::event_handler::EventHandler;
@@ -25,11 +25,13 @@ use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 use sui_types::SUI_FRAMEWORK_ADDRESS;
 
 use crate::errors::IndexerError;
+use crate::handlers::local_checkpoint_reader::LocalCheckpointReader;
 use crate::metrics::IndexerCheckpointHandlerMetrics;
 use crate::models::checkpoints::Checkpoint;
 use crate::models::epoch::{DBEpochInfo, SystemEpochInfoEvent};
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::packages::Package;
+use crate::processors::processor::Processor;
 use crate::store::{
     CheckpointData, IndexerStore, TemporaryCheckpointStore, TemporaryEpochStore,
     TransactionObjectChanges,
@@ -39,10 +41,13 @@ use crate::utils::multi_get_full_transactions;
 
 const HANDLER_RETRY_INTERVAL_IN_SECS: u64 = 10;
 const MULTI_GET_CHUNK_SIZE: usize = 500;
+const LOCAL_READER_POLL_INTERVAL_IN_MILLIS: u64 = 100;
 
-pub struct CheckpointHandler<S> {
+pub struct CheckpointHandler<S: IndexerStore> {
     state: S,
     rpc_client: SuiClient,
+    local_reader: Option<LocalCheckpointReader>,
+    processors: Vec<Arc<dyn Processor<S::Connection>>>,
     event_handler: Arc<EventHandler>,
     metrics: IndexerCheckpointHandlerMetrics,
 }
@@ -54,12 +59,16 @@ where
     pub fn new(
         state: S,
         rpc_client: SuiClient,
+        local_reader: Option<LocalCheckpointReader>,
+        processors: Vec<Arc<dyn Processor<S::Connection>>>,
         event_handler: Arc<EventHandler>,
         prometheus_registry: &Registry,
     ) -> Self {
         Self {
             state,
             rpc_client,
+            local_reader,
+            processors,
             event_handler,
             metrics: IndexerCheckpointHandlerMetrics::new(prometheus_registry),
         }
@@ -84,60 +93,122 @@ where
 
     async fn start(&self) -> Result<(), IndexerError> {
         info!("Indexer checkpoint handler started...");
-        let mut next_cursor_sequence_number = self.state.get_latest_checkpoint_sequence_number()?;
+        let latest_committed_sequence_number =
+            self.state.get_latest_checkpoint_sequence_number()?;
+        let mut next_cursor_sequence_number = latest_committed_sequence_number;
         if next_cursor_sequence_number > 0 {
             info!("Resuming from checkpoint {next_cursor_sequence_number}");
         }
+        // Processors behind the committed checkpoints catch up on checkpoints that are already
+        // committed, which are then only passed to the processors.
+        for processor in &self.processors {
+            self.state.initialize_processor(processor.as_ref())?;
+            let progress = self
+                .state
+                .get_processor_progress(processor.name())?
+                .unwrap_or(-1);
+            if progress < next_cursor_sequence_number {
+                info!(
+                    "Processor {} is behind at checkpoint {progress}, catching up",
+                    processor.name()
+                );
+                next_cursor_sequence_number = progress;
+            }
+        }
         next_cursor_sequence_number += 1;
 
         loop {
             self.metrics.total_checkpoint_requested.inc();
             let request_guard = self.metrics.full_node_read_request_latency.start_timer();
 
-            let checkpoint = self
-                .download_checkpoint_data(next_cursor_sequence_number as u64)
-                .await.map_err(|e| {
-                    error!(
-                        "Failed to download checkpoint data with checkpoint sequence number {} and error {:?}, retrying...",
-                        next_cursor_sequence_number, e
-                    );
-                    e
-                })?;
+            let checkpoints = match &self.local_reader {
+                Some(local_reader) => {
+                    let checkpoints = local_reader
+                        .read_checkpoints(next_cursor_sequence_number as u64)
+                        .await?;
+                    if checkpoints.is_empty() {
+                        // The node has not executed the next checkpoint yet.
+                        request_guard.stop_and_discard();
+                        tokio::time::sleep(std::time::Duration::from_millis(
+                            LOCAL_READER_POLL_INTERVAL_IN_MILLIS,
+                        ))
+                        .await;
+                        continue;
+                    }
+                    checkpoints
+                }
+                None => vec![self
+                    .download_checkpoint_data(next_cursor_sequence_number as u64)
+                    .await.map_err(|e| {
+                        error!(
+                            "Failed to download checkpoint data with checkpoint sequence number {} and error {:?}, retrying...",
+                            next_cursor_sequence_number, e
+                        );
+                        e
+                    })?],
+            };
             request_guard.stop_and_record();
-            self.metrics.total_checkpoint_received.inc();
 
-            // Index checkpoint data
-            // TODO: Metrics
-            let (indexed_checkpoint, indexed_epoch) = self.index_checkpoint(&checkpoint)?;
-
-            if let Some(indexed_epoch) = indexed_epoch {
-                self.state.persist_epoch(&indexed_epoch)?;
+            for checkpoint in checkpoints {
+                self.metrics.total_checkpoint_received.inc();
+                if next_cursor_sequence_number > latest_committed_sequence_number {
+                    self.process_checkpoint(&checkpoint).await?;
+                }
+                self.run_processors(&checkpoint)?;
+                next_cursor_sequence_number += 1;
             }
+        }
+    }
 
-            // Write to DB
-            let db_guard = self.metrics.db_write_request_latency.start_timer();
-            let tx_count = indexed_checkpoint.transactions.len();
-            let object_count = indexed_checkpoint.objects_changes.len();
+    /// Index and persist one checkpoint, then notify subscribers of its transactions and events.
+    async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> Result<(), IndexerError> {
+        // Index checkpoint data
+        // TODO: Metrics
+        let (indexed_checkpoint, indexed_epoch) = self.index_checkpoint(checkpoint)?;
 
-            self.state.persist_checkpoint(&indexed_checkpoint)?;
-            info!(
-                "Checkpoint {} committed with {tx_count} transactions and {object_count} objects.",
-                next_cursor_sequence_number
-            );
-            self.metrics.total_checkpoint_processed.inc();
-            db_guard.stop_and_record();
-
-            // Process websocket subscription
-            let db_guard = self.metrics.db_write_request_latency.start_timer();
-            for tx in &checkpoint.transactions {
-                self.event_handler
-                    .process_events(&tx.effects, &tx.events)
-                    .await?;
-            }
-            db_guard.stop_and_record();
+        if let Some(indexed_epoch) = indexed_epoch {
+            self.state.persist_epoch(&indexed_epoch)?;
+        }
+
+        // Write to DB
+        let db_guard = self.metrics.db_write_request_latency.start_timer();
+        let tx_count = indexed_checkpoint.transactions.len();
+        let object_count = indexed_checkpoint.objects_changes.len();
+
+        self.state.persist_checkpoint(&indexed_checkpoint)?;
+        info!(
+            "Checkpoint {} committed with {tx_count} transactions and {object_count} objects.",
+            checkpoint.checkpoint.sequence_number
+        );
+        self.metrics.total_checkpoint_processed.inc();
+        db_guard.stop_and_record();
+
+        // Process websocket subscription
+        let db_guard = self.metrics.db_write_request_latency.start_timer();
+        for tx in &checkpoint.transactions {
+            self.event_handler
+                .process_events(&tx.effects, &tx.events)
+                .await?;
+            self.event_handler
+                .process_transaction(tx.clone().into())
+                .await?;
+        }
+        db_guard.stop_and_record();
+        Ok(())
+    }
 
-            next_cursor_sequence_number += 1;
+    /// Run the custom processors which have not processed the checkpoint yet.
+    fn run_processors(&self, checkpoint: &CheckpointData) -> Result<(), IndexerError> {
+        for processor in &self.processors {
+            if self.state.run_processor(processor.as_ref(), checkpoint)? {
+                debug!(
+                    "Processor {} processed checkpoint {}.",
+                    processor.name(),
+                    checkpoint.checkpoint.sequence_number
+                );
+            }
         }
+        Ok(())
     }
 
     /// Download all the data we need for one checkpoint.
@@ -0,0 +1,256 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+use std::sync::Arc;
+
+use futures::future::join_all;
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use tracing::info;
+
+use sui_core::storage::SecondaryRocksDbStore;
+use sui_json_rpc_types::{
+    Checkpoint as RpcCheckpoint, SuiObjectData, SuiObjectDataOptions, SuiTransaction,
+    SuiTransactionEffects, SuiTransactionEvents,
+};
+use sui_types::base_types::ExecutionData;
+use sui_types::messages::{TransactionEffectsAPI, TransactionEvents};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::ObjectFormatOptions;
+use sui_types::storage::ReadStore;
+
+use crate::errors::IndexerError;
+use crate::models::objects::ObjectStatus;
+use crate::store::CheckpointData;
+use crate::types::SuiTransactionFullResponse;
+
+/// Reads checkpoint data directly from the database of a fullnode running on the same machine,
+/// instead of downloading it from the fullnode over JSON-RPC.
+///
+/// The database is opened as a RocksDB secondary instance, so only checkpoints the node has
+/// finished executing are read, and historical checkpoints are read `concurrency` at a time.
+#[derive(Clone)]
+pub struct LocalCheckpointReader {
+    store: SecondaryRocksDbStore,
+    module_cache: Arc<SyncModuleCache<SecondaryRocksDbStore>>,
+    concurrency: usize,
+}
+
+impl LocalCheckpointReader {
+    pub fn new(node_db_path: &Path, concurrency: usize) -> Self {
+        info!(
+            "Reading checkpoints from node database at {:?}",
+            node_db_path
+        );
+        let store = SecondaryRocksDbStore::open(node_db_path);
+        Self {
+            module_cache: Arc::new(SyncModuleCache::new(store.clone())),
+            store,
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    /// Read the next batch of executed checkpoints starting at `from`, in sequence order.
+    /// Returns an empty batch if the node has not executed checkpoint `from` yet.
+    pub async fn read_checkpoints(
+        &self,
+        from: CheckpointSequenceNumber,
+    ) -> Result<Vec<CheckpointData>, IndexerError> {
+        self.store
+            .try_catch_up_with_primary()
+            .map_err(|e| read_error(from, e))?;
+        let highest_executed = match self
+            .store
+            .get_highest_executed_checkpoint_seq_number()
+            .map_err(|e| read_error(from, e))?
+        {
+            Some(highest_executed) if highest_executed >= from => highest_executed,
+            _ => return Ok(vec![]),
+        };
+        let to = highest_executed.min(from + self.concurrency as u64 - 1);
+
+        join_all((from..=to).map(|seq| {
+            let reader = self.clone();
+            tokio::task::spawn_blocking(move || reader.get_checkpoint_data(seq))
+        }))
+        .await
+        .into_iter()
+        .map(|result| {
+            result.map_err(|e| {
+                IndexerError::FullNodeReadingError(format!(
+                    "Checkpoint reading task failed with error {:?}",
+                    e
+                ))
+            })?
+        })
+        .collect()
+    }
+
+    fn get_checkpoint_data(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<CheckpointData, IndexerError> {
+        let checkpoint = self
+            .store
+            .get_checkpoint_by_sequence_number(seq)
+            .map_err(|e| read_error(seq, e))?
+            .ok_or_else(|| read_error(seq, "checkpoint not found"))?;
+        let contents = self
+            .store
+            .get_full_checkpoint_contents(&checkpoint.content_digest)
+            .map_err(|e| read_error(seq, e))?
+            .ok_or_else(|| read_error(seq, "checkpoint contents not found"))?;
+        let timestamp_ms = checkpoint.timestamp_ms;
+
+        let mut transactions = vec![];
+        let mut changed_objects = vec![];
+        for ExecutionData {
+            transaction,
+            effects,
+        } in contents.iter()
+        {
+            let events = match effects.events_digest() {
+                Some(digest) => self
+                    .store
+                    .get_transaction_events(digest)
+                    .map_err(|e| read_error(seq, e))?
+                    .ok_or_else(|| read_error(seq, format!("events {digest} not found")))?,
+                None => TransactionEvents::default(),
+            };
+
+            let created = effects.created().iter();
+            let created = created.map(|(o, _)| (o, ObjectStatus::Created));
+            let mutated = effects.mutated().iter();
+            let mutated = mutated.map(|(o, _)| (o, ObjectStatus::Mutated));
+            let unwrapped = effects.unwrapped().iter();
+            let unwrapped = unwrapped.map(|(o, _)| (o, ObjectStatus::Unwrapped));
+            for (object_ref, status) in created.chain(mutated).chain(unwrapped) {
+                let (object_id, version, _) = object_ref;
+                let object = self
+                    .store
+                    .get_object_by_key(object_id, *version)
+                    .map_err(|e| read_error(seq, e))?
+                    .ok_or_else(|| {
+                        read_error(
+                            seq,
+                            format!(
+                                "object {object_id} at version {version} not found, the node may have pruned it"
+                            ),
+                        )
+                    })?;
+                let layout = object
+                    .get_layout(ObjectFormatOptions::default(), self.module_cache.as_ref())
+                    .map_err(|e| read_error(seq, e))?;
+                let object_data = SuiObjectData::try_from((
+                    *object_ref,
+                    object,
+                    layout,
+                    SuiObjectDataOptions::bcs_lossless(),
+                ))
+                .map_err(|e| read_error(seq, e))?;
+                changed_objects.push((status, object_data));
+            }
+
+            let digest = *transaction.digest();
+            transactions.push(SuiTransactionFullResponse {
+                digest,
+                transaction: SuiTransaction::try_from(
+                    transaction.data().clone(),
+                    self.module_cache.as_ref(),
+                )
+                .map_err(|e| read_error(seq, e))?,
+                raw_transaction: bcs::to_bytes(transaction.data())
+                    .map_err(|e| read_error(seq, e))?,
+                effects: SuiTransactionEffects::try_from(effects.clone())
+                    .map_err(|e| read_error(seq, e))?,
+                // Same as the fullnode JSON-RPC, which does not set the timestamp of events.
+                events: SuiTransactionEvents::try_from(
+                    events,
+                    digest,
+                    None,
+                    self.module_cache.as_ref(),
+                )
+                .map_err(|e| read_error(seq, e))?,
+                object_changes: None,
+                balance_changes: None,
+                timestamp_ms,
+                confirmed_local_execution: None,
+                checkpoint: seq,
+            });
+        }
+
+        let checkpoint: RpcCheckpoint = (
+            checkpoint.into_inner().into_data(),
+            contents.into_checkpoint_contents(),
+        )
+            .into();
+        Ok(CheckpointData {
+            checkpoint,
+            transactions,
+            changed_objects,
+        })
+    }
+}
+
+fn read_error(seq: CheckpointSequenceNumber, e: impl std::fmt::Display) -> IndexerError {
+    IndexerError::FullNodeReadingError(format!(
+        "Failed to read checkpoint with sequence number {} from node database: {}",
+        seq, e
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_core::authority::AuthorityStore;
+    use sui_core::checkpoints::CheckpointStore;
+    use sui_core::epoch::committee_store::CommitteeStore;
+    use sui_types::messages::TransactionEffectsAPI;
+    use tempfile::TempDir;
+
+    use super::LocalCheckpointReader;
+
+    #[tokio::test]
+    async fn test_read_executed_checkpoints() -> Result<(), anyhow::Error> {
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let genesis = &network_config.genesis;
+        let committee = genesis.committee()?;
+
+        let dir = TempDir::new()?;
+        let _authority_store = AuthorityStore::open_with_committee_for_testing(
+            &dir.path().join("store"),
+            None,
+            &committee,
+            genesis,
+            0,
+        )
+        .await?;
+        let _committee_store = CommitteeStore::new(dir.path().join("epochs"), &committee, None);
+        let checkpoint_store = CheckpointStore::new(&dir.path().join("checkpoints"));
+        let checkpoint = genesis.checkpoint();
+        checkpoint_store.insert_verified_checkpoint(checkpoint.clone())?;
+        checkpoint_store.insert_checkpoint_contents(genesis.checkpoint_contents().clone())?;
+
+        // The genesis checkpoint is stored but not executed yet.
+        let reader = LocalCheckpointReader::new(dir.path(), 2);
+        assert!(reader.read_checkpoints(0).await?.is_empty());
+
+        // The reader catches up with the node once it has executed the checkpoint.
+        checkpoint_store.update_highest_executed_checkpoint(&checkpoint)?;
+        let checkpoints = reader.read_checkpoints(0).await?;
+        assert_eq!(checkpoints.len(), 1);
+        let data = &checkpoints[0];
+        assert_eq!(data.checkpoint.sequence_number, 0);
+        assert_eq!(data.checkpoint.digest, *checkpoint.digest());
+        assert_eq!(data.transactions.len(), 1);
+        assert_eq!(data.transactions[0].digest, *genesis.transaction().digest());
+        assert_eq!(data.transactions[0].checkpoint, 0);
+        let effects = genesis.effects();
+        assert_eq!(
+            data.changed_objects.len(),
+            effects.created().len() + effects.mutated().len() + effects.unwrapped().len()
+        );
+
+        assert!(reader.read_checkpoints(1).await?.is_empty());
+        Ok(())
+    }
+}
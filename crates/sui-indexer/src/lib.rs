@@ -3,6 +3,7 @@
 #![recursion_limit = "256"]
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -10,9 +11,11 @@ use anyhow::Result;
 use backoff::retry;
 use backoff::ExponentialBackoff;
 use clap::Parser;
+use diesel::connection::SimpleConnection;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
-use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
+use diesel::r2d2::{ConnectionManager, CustomizeConnection, Pool, PooledConnection};
+use diesel::sqlite::SqliteConnection;
 use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClientBuilder};
 use prometheus::Registry;
 use tracing::{info, warn};
@@ -24,7 +27,9 @@ use apis::{
 };
 use errors::IndexerError;
 use handlers::checkpoint_handler::CheckpointHandler;
+use handlers::local_checkpoint_reader::LocalCheckpointReader;
 use mysten_metrics::spawn_monitored_task;
+use processors::processor::Processor;
 use store::IndexerStore;
 use sui_core::event_handler::EventHandler;
 use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle, CLIENT_SDK_TYPE_HEADER};
@@ -44,6 +49,10 @@ pub mod utils;
 
 pub type PgConnectionPool = Pool<ConnectionManager<PgConnection>>;
 pub type PgPoolConnection = PooledConnection<ConnectionManager<PgConnection>>;
+pub type SqliteConnectionPool = Pool<ConnectionManager<SqliteConnection>>;
+pub type SqlitePoolConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
+
+const SQLITE_URL_PREFIX: &str = "sqlite://";
 
 pub const MIGRATED_METHODS: [&str; 7] = [
     "get_checkpoint",
@@ -62,6 +71,7 @@ pub const MIGRATED_METHODS: [&str; 7] = [
     rename_all = "kebab-case"
 )]
 pub struct IndexerConfig {
+    /// PostgreSQL URL, or `sqlite://<path>` to use a SQLite database file instead
     #[clap(long)]
     pub db_url: String,
     #[clap(long)]
@@ -78,6 +88,13 @@ pub struct IndexerConfig {
     pub migrated_methods: Vec<String>,
     #[clap(long)]
     pub reset_db: bool,
+    /// Database directory of a fullnode running on the same machine, to read checkpoints from
+    /// directly instead of downloading them over JSON-RPC
+    #[clap(long)]
+    pub node_db_path: Option<PathBuf>,
+    /// Number of checkpoints read in parallel from `node_db_path` when catching up
+    #[clap(long, default_value = "16")]
+    pub backfill_concurrency: usize,
 }
 
 impl IndexerConfig {
@@ -94,6 +111,12 @@ impl IndexerConfig {
         )
     }
 
+    /// returns the path of the SQLite database file if the indexer is configured to use SQLite
+    /// instead of PostgreSQL
+    pub fn sqlite_db_path(&self) -> Option<&str> {
+        self.db_url.strip_prefix(SQLITE_URL_PREFIX)
+    }
+
     /// returns all endpoints for which we have implemented on the indexer
     /// NOTE: we only use this for integration testing
     pub fn all_migrated_methods() -> Vec<String> {
@@ -112,6 +135,8 @@ impl Default for IndexerConfig {
             rpc_server_port: 9000,
             migrated_methods: vec![],
             reset_db: false,
+            node_db_path: None,
+            backfill_concurrency: 16,
         }
     }
 }
@@ -123,6 +148,16 @@ impl Indexer {
         config: &IndexerConfig,
         registry: &Registry,
         store: S,
+    ) -> Result<(), IndexerError> {
+        Self::start_with_processors(config, registry, store, vec![]).await
+    }
+
+    /// Same as [Indexer::start], and runs the custom processors on every checkpoint.
+    pub async fn start_with_processors<S: IndexerStore + Sync + Send + Clone + 'static>(
+        config: &IndexerConfig,
+        registry: &Registry,
+        store: S,
+        processors: Vec<Arc<dyn Processor<S::Connection>>>,
     ) -> Result<(), IndexerError> {
         let event_handler = Arc::new(EventHandler::default());
         let handle = build_json_rpc_server(registry, store.clone(), event_handler.clone(), config)
@@ -135,6 +170,11 @@ impl Indexer {
             env!("CARGO_PKG_VERSION")
         );
 
+        let local_reader = config
+            .node_db_path
+            .as_ref()
+            .map(|path| LocalCheckpointReader::new(path, config.backfill_concurrency));
+
         backoff::future::retry(ExponentialBackoff::default(), || async {
             let event_handler_clone = event_handler.clone();
             let rpc_client = new_rpc_client(config.rpc_client_url.as_str()).await?;
@@ -143,6 +183,8 @@ impl Indexer {
             let cp = CheckpointHandler::new(
                 store.clone(),
                 rpc_client.clone(),
+                local_reader.clone(),
+                processors.clone(),
                 event_handler_clone,
                 registry,
             );
@@ -197,6 +239,50 @@ pub fn get_pg_pool_connection(pool: &PgConnectionPool) -> Result<PgPoolConnectio
     })
 }
 
+/// SQLite connections wait for the lock instead of failing when another pool connection is
+/// writing, and use write-ahead logging so that reads do not block on writes.
+#[derive(Debug)]
+struct SqliteConnectionCustomizer;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for SqliteConnectionCustomizer {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        conn.batch_execute(
+            "PRAGMA busy_timeout = 5000; PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;",
+        )
+        .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+pub async fn new_sqlite_connection_pool(
+    db_path: &str,
+) -> Result<SqliteConnectionPool, IndexerError> {
+    let manager = ConnectionManager::<SqliteConnection>::new(db_path);
+    Pool::builder()
+        .connection_customizer(Box::new(SqliteConnectionCustomizer))
+        .build(manager)
+        .map_err(|e| {
+            IndexerError::SqliteConnectionPoolInitError(format!(
+                "Failed to initialize connection pool with error: {:?}",
+                e
+            ))
+        })
+}
+
+pub fn get_sqlite_pool_connection(
+    pool: &SqliteConnectionPool,
+) -> Result<SqlitePoolConnection, IndexerError> {
+    retry(ExponentialBackoff::default(), || {
+        let pool_conn = pool.get()?;
+        Ok(pool_conn)
+    })
+    .map_err(|e| {
+        IndexerError::SqlitePoolConnectionError(format!(
+            "Failed to get pool connection from SQLite connection pool with error: {:?}",
+            e
+        ))
+    })
+}
+
 pub async fn build_json_rpc_server<S: IndexerStore + Sync + Send + 'static + Clone>(
     prometheus_registry: &Registry,
     state: S,
@@ -5,9 +5,12 @@ use clap::Parser;
 use tracing::info;
 
 use sui_indexer::errors::IndexerError;
-use sui_indexer::store::PgIndexerStore;
-use sui_indexer::utils::reset_database;
-use sui_indexer::{get_pg_pool_connection, new_pg_connection_pool, Indexer, IndexerConfig};
+use sui_indexer::store::{PgIndexerStore, SqliteIndexerStore};
+use sui_indexer::utils::{reset_database, reset_sqlite_database, run_sqlite_migrations};
+use sui_indexer::{
+    get_pg_pool_connection, get_sqlite_pool_connection, new_pg_connection_pool,
+    new_sqlite_connection_pool, Indexer, IndexerConfig,
+};
 use sui_node::metrics::start_prometheus_server;
 
 #[tokio::main]
@@ -31,6 +34,25 @@ async fn main() -> Result<(), IndexerError> {
     );
 
     let registry = registry_service.default_registry();
+    if let Some(db_path) = indexer_config.sqlite_db_path() {
+        let sqlite_connection_pool = new_sqlite_connection_pool(db_path).await?;
+        let mut conn = get_sqlite_pool_connection(&sqlite_connection_pool)?;
+        if indexer_config.reset_db {
+            reset_sqlite_database(&mut conn)
+        } else {
+            run_sqlite_migrations(&mut conn)
+        }
+        .map_err(|e| {
+            IndexerError::SqliteResetError(format!(
+                "unable to set up SQLite database at path: {:?} and err: {:?}",
+                db_path, e
+            ))
+        })?;
+        drop(conn);
+        let store = SqliteIndexerStore::new(sqlite_connection_pool);
+        return Indexer::start(&indexer_config, &registry, store).await;
+    }
+
     let pg_connection_pool = new_pg_connection_pool(&indexer_config.db_url).await?;
     if indexer_config.reset_db {
         let mut conn = get_pg_pool_connection(&pg_connection_pool)?;
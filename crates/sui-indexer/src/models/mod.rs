@@ -8,6 +8,7 @@ pub mod events;
 pub mod objects;
 pub mod owners;
 pub mod packages;
+pub mod processor_progress;
 pub mod system_state;
 pub mod transaction_index;
 pub mod transactions;
@@ -0,0 +1,13 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::processor_progress;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = processor_progress, primary_key(processor_name))]
+pub struct ProcessorProgress {
+    pub processor_name: String,
+    pub checkpoint_sequence_number: i64,
+}
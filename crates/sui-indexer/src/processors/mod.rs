@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod object_processor;
+pub mod processor;
 pub mod processor_orchestrator;
@@ -0,0 +1,30 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::IndexerError;
+use crate::store::CheckpointData;
+
+/// A custom processor, run by the checkpoint handler on every checkpoint after the checkpoint is
+/// committed to the indexer store, e.g. to index events of a given type into a custom table.
+///
+/// `C` is the connection type of the store, `PgConnection` or `SqliteConnection`. The progress
+/// of each processor is tracked in the `processor_progress` table under its [Processor::name];
+/// a processor without progress, like a newly registered one, starts from the genesis checkpoint.
+pub trait Processor<C>: Send + Sync {
+    /// Unique name of the processor, renaming a processor makes it process all checkpoints again.
+    fn name(&self) -> &str;
+
+    /// Called once when the indexer starts, to create the tables of the processor.
+    fn initialize(&self, _conn: &mut C) -> Result<(), IndexerError> {
+        Ok(())
+    }
+
+    /// Process one checkpoint, with its transactions, events and changed objects. Checkpoints are
+    /// processed in order, in the same database transaction that records the progress of the
+    /// processor, so the writes of every checkpoint are committed exactly once.
+    fn process_checkpoint(
+        &self,
+        conn: &mut C,
+        checkpoint: &CheckpointData,
+    ) -> Result<(), IndexerError>;
+}
@@ -213,6 +213,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    processor_progress (processor_name) {
+        processor_name -> Varchar,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
 diesel::table! {
     recipients (id) {
         id -> Int8,
@@ -339,6 +346,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     owner,
     owner_history,
     packages,
+    processor_progress,
     recipients,
     system_states,
     transactions,
@@ -22,11 +22,13 @@ use crate::models::packages::Package;
 use crate::models::system_state::{DBSystemStateSummary, DBValidatorSummary};
 use crate::models::transaction_index::{InputObject, MoveCall, Recipient};
 use crate::models::transactions::Transaction;
+use crate::processors::processor::Processor;
 use crate::types::SuiTransactionFullResponse;
 
 #[async_trait]
 pub trait IndexerStore {
     type ModuleCache;
+    type Connection: diesel::Connection + 'static;
 
     fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError>;
     fn get_checkpoint(&self, id: CheckpointId) -> Result<Checkpoint, IndexerError>;
@@ -148,6 +150,20 @@ pub trait IndexerStore {
     fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError>;
 
     fn module_cache(&self) -> &Self::ModuleCache;
+
+    /// Returns the last checkpoint processed by the processor, None if it has not processed any.
+    fn get_processor_progress(&self, processor_name: &str) -> Result<Option<i64>, IndexerError>;
+    fn initialize_processor(
+        &self,
+        processor: &dyn Processor<Self::Connection>,
+    ) -> Result<(), IndexerError>;
+    /// Runs the processor on the checkpoint and records its progress in one transaction. Returns
+    /// false without running the processor if it has already processed the checkpoint.
+    fn run_processor(
+        &self,
+        processor: &dyn Processor<Self::Connection>,
+        checkpoint: &CheckpointData,
+    ) -> Result<bool, IndexerError>;
 }
 
 #[derive(Clone, Debug)]
@@ -3,7 +3,12 @@
 
 pub use indexer_store::*;
 pub use pg_indexer_store::PgIndexerStore;
+pub use sqlite_indexer_store::SqliteIndexerStore;
 
 mod indexer_store;
 mod module_resolver;
 mod pg_indexer_store;
+mod query;
+mod sqlite_indexer_store;
+mod sqlite_models;
+mod sqlite_schema;
@@ -2,11 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
-use crate::{get_pg_pool_connection, PgConnectionPool};
+use crate::store::sqlite_models::package_modules;
+use crate::store::sqlite_schema::packages;
+use crate::{
+    get_pg_pool_connection, get_sqlite_pool_connection, PgConnectionPool, SqliteConnectionPool,
+};
 use diesel::pg::sql_types::Bytea;
 use diesel::sql_types::Text;
 use diesel::QueryableByName;
-use diesel::RunQueryDsl;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use move_core_types::language_storage::ModuleId;
 use move_core_types::resolver::ModuleResolver;
 use sui_types::base_types::ObjectID;
@@ -57,3 +61,41 @@ impl ModuleResolver for IndexerModuleResolver {
         Ok(Some(module_bytes.data))
     }
 }
+
+/// Module resolver of the SQLite store, which has no composite types to select a single module
+/// from, so modules are looked up in the deserialized modules of the latest package version.
+pub struct SqliteModuleResolver {
+    cp: SqliteConnectionPool,
+}
+
+impl SqliteModuleResolver {
+    pub fn new(cp: SqliteConnectionPool) -> Self {
+        Self { cp }
+    }
+}
+
+impl ModuleResolver for SqliteModuleResolver {
+    type Error = IndexerError;
+
+    fn get_module(&self, id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
+        let package_id = ObjectID::from(*id.address()).to_string();
+        let module_name = id.name().to_string();
+
+        let mut sqlite_pool_conn = get_sqlite_pool_connection(&self.cp)?;
+        let package_data = packages::table
+            .filter(packages::package_id.eq(package_id))
+            .order(packages::version.desc())
+            .select(packages::data)
+            .first::<Vec<u8>>(&mut sqlite_pool_conn)
+            .optional()
+            .map_err(|e| IndexerError::SqliteReadError(e.to_string()))?;
+
+        Ok(match package_data {
+            Some(data) => package_modules(&data)?
+                .into_iter()
+                .find(|module| module.0 == module_name)
+                .map(|module| module.1),
+            None => None,
+        })
+    }
+}
@@ -11,7 +11,7 @@ use diesel::dsl::{count, max};
 use diesel::query_builder::AsQuery;
 use diesel::sql_types::{BigInt, VarChar};
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, PgArrayExpressionMethods};
+use diesel::{ExpressionMethods, PgArrayExpressionMethods, PgConnection};
 use diesel::{OptionalExtension, QueryableByName};
 use diesel::{QueryDsl, RunQueryDsl};
 use move_bytecode_utils::module_cache::SyncModuleCache;
@@ -28,18 +28,24 @@ use crate::models::checkpoints::Checkpoint;
 use crate::models::epoch::DBEpochInfo;
 use crate::models::events::Event;
 use crate::models::objects::Object;
+use crate::models::processor_progress::ProcessorProgress;
 use crate::models::system_state::DBValidatorSummary;
 use crate::models::transactions::Transaction;
+use crate::processors::processor::Processor;
 use crate::schema::{
     addresses, checkpoints, checkpoints::dsl as checkpoints_dsl, epochs, epochs::dsl as epochs_dsl,
     events, input_objects, input_objects::dsl as input_objects_dsl, move_calls,
     move_calls::dsl as move_calls_dsl, objects, objects::dsl as objects_dsl, objects_history,
-    packages, recipients, recipients::dsl as recipients_dsl, system_states, transactions,
-    transactions::dsl as transactions_dsl, validators,
+    packages, processor_progress, recipients, recipients::dsl as recipients_dsl, system_states,
+    transactions, transactions::dsl as transactions_dsl, validators,
 };
 use crate::store::indexer_store::TemporaryCheckpointStore;
 use crate::store::module_resolver::IndexerModuleResolver;
-use crate::store::{IndexerStore, TemporaryEpochStore};
+use crate::store::query::{
+    input_object_digest_page_query, move_call_digest_page_query, recipient_digest_page_query,
+    TempDigestTable,
+};
+use crate::store::{CheckpointData, IndexerStore, TemporaryEpochStore};
 use crate::{get_pg_pool_connection, PgConnectionPool};
 
 const MAX_EVENT_PAGE_SIZE: usize = 1000;
@@ -57,12 +63,6 @@ WHERE parent.relkind = 'p'
 GROUP BY table_name;
 "#;
 
-#[derive(QueryableByName, Debug, Clone)]
-struct TempDigestTable {
-    #[diesel(sql_type = VarChar)]
-    digest_name: String,
-}
-
 #[derive(Clone)]
 pub struct PgIndexerStore {
     cp: PgConnectionPool,
@@ -84,6 +84,7 @@ impl PgIndexerStore {
 #[async_trait]
 impl IndexerStore for PgIndexerStore {
     type ModuleCache = SyncModuleCache<IndexerModuleResolver>;
+    type Connection = PgConnection;
 
     fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
@@ -613,31 +614,12 @@ impl IndexerStore for PgIndexerStore {
         is_descending: bool,
     ) -> Result<Vec<String>, IndexerError> {
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name FROM (
-                SELECT transaction_digest, max(id) AS max_id 
-                FROM input_objects
-                WHERE object_id = '{}' {} {} 
-                GROUP BY transaction_digest 
-                ORDER BY max_id {} LIMIT {}
-            ) AS t",
-            object_id,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if let Some(version) = version {
-                format!("AND version = {}", version)
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
+        let sql_query = input_object_digest_page_query(
+            &object_id,
+            version,
+            start_sequence,
+            limit,
+            is_descending,
         );
         Ok(pg_pool_conn.build_transaction()
             .read_only()
@@ -662,36 +644,13 @@ impl IndexerStore for PgIndexerStore {
         is_descending: bool,
     ) -> Result<Vec<String>, IndexerError> {
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name FROM (
-                SELECT transaction_digest, max(id) AS max_id 
-                FROM move_calls
-                WHERE move_package = '{}' {} {} {}
-                GROUP BY transaction_digest 
-                ORDER BY max_id {} LIMIT {}
-            ) AS t",
-            package_name,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if let Some(module_name) = module_name.clone() {
-                format!("AND move_module = '{}'", module_name)
-            } else {
-                "".to_string()
-            },
-            if let Some(function_name) = function_name.clone() {
-                format!("AND move_function = '{}'", function_name)
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
+        let sql_query = move_call_digest_page_query(
+            &package_name,
+            module_name.as_deref(),
+            function_name.as_deref(),
+            start_sequence,
+            limit,
+            is_descending,
         );
         Ok(pg_pool_conn.build_transaction()
             .read_only()
@@ -714,26 +673,8 @@ impl IndexerStore for PgIndexerStore {
         is_descending: bool,
     ) -> Result<Vec<String>, IndexerError> {
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
-        let sql_query = format!(
-            "SELECT transaction_digest as digest_name FROM (
-                SELECT transaction_digest, max(id) AS max_id
-                FROM recipients
-                WHERE recipient = '{}' {} GROUP BY transaction_digest
-                ORDER BY max_id {} LIMIT {}
-            ) AS t",
-            recipient_address,
-            if let Some(start_sequence) = start_sequence {
-                if is_descending {
-                    format!("AND id < {}", start_sequence)
-                } else {
-                    format!("AND id > {}", start_sequence)
-                }
-            } else {
-                "".to_string()
-            },
-            if is_descending { "DESC" } else { "ASC" },
-            limit
-        );
+        let sql_query =
+            recipient_digest_page_query(&recipient_address, start_sequence, limit, is_descending);
         Ok(pg_pool_conn.build_transaction()
             .read_only()
             .run(|conn| {
@@ -1105,6 +1046,67 @@ WHERE e1.epoch = e2.epoch
         &self.module_cache
     }
 
+    fn get_processor_progress(&self, processor_name: &str) -> Result<Option<i64>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        processor_progress::table
+            .filter(processor_progress::processor_name.eq(processor_name))
+            .select(processor_progress::checkpoint_sequence_number)
+            .first::<i64>(&mut pg_pool_conn)
+            .optional()
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading progress of processor {} in PostgresDB with error {:?}",
+                    processor_name, e
+                ))
+            })
+    }
+
+    fn initialize_processor(
+        &self,
+        processor: &dyn Processor<PgConnection>,
+    ) -> Result<(), IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_write()
+            .run(|conn| processor.initialize(conn))
+    }
+
+    fn run_processor(
+        &self,
+        processor: &dyn Processor<PgConnection>,
+        checkpoint: &CheckpointData,
+    ) -> Result<bool, IndexerError> {
+        let sequence_number = checkpoint.checkpoint.sequence_number as i64;
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .serializable()
+            .read_write()
+            .run(|conn| {
+                let progress = processor_progress::table
+                    .filter(processor_progress::processor_name.eq(processor.name()))
+                    .select(processor_progress::checkpoint_sequence_number)
+                    .for_update()
+                    .first::<i64>(conn)
+                    .optional()?;
+                if matches!(progress, Some(progress) if progress >= sequence_number) {
+                    return Ok(false);
+                }
+                processor.process_checkpoint(conn, checkpoint)?;
+                diesel::insert_into(processor_progress::table)
+                    .values(ProcessorProgress {
+                        processor_name: processor.name().to_string(),
+                        checkpoint_sequence_number: sequence_number,
+                    })
+                    .on_conflict(processor_progress::processor_name)
+                    .do_update()
+                    .set(processor_progress::checkpoint_sequence_number.eq(sequence_number))
+                    .execute(conn)?;
+                Ok(true)
+            })
+    }
+
     fn get_epochs(
         &self,
         cursor: Option<EpochId>,
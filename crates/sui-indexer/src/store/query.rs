@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Raw SQL queries shared by the PostgreSQL and SQLite stores.
+
+use diesel::sql_types::VarChar;
+use diesel::QueryableByName;
+
+#[derive(QueryableByName, Debug, Clone)]
+pub(crate) struct TempDigestTable {
+    #[diesel(sql_type = VarChar)]
+    pub digest_name: String,
+}
+
+pub(crate) fn input_object_digest_page_query(
+    object_id: &str,
+    version: Option<i64>,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    format!(
+        "SELECT transaction_digest as digest_name FROM (
+            SELECT transaction_digest, max(id) AS max_id
+            FROM input_objects
+            WHERE object_id = '{}' {} {}
+            GROUP BY transaction_digest
+            ORDER BY max_id {} LIMIT {}
+        ) AS t",
+        object_id,
+        start_sequence_filter(start_sequence, is_descending),
+        if let Some(version) = version {
+            format!("AND version = {}", version)
+        } else {
+            "".to_string()
+        },
+        order(is_descending),
+        limit
+    )
+}
+
+pub(crate) fn move_call_digest_page_query(
+    package_name: &str,
+    module_name: Option<&str>,
+    function_name: Option<&str>,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    format!(
+        "SELECT transaction_digest as digest_name FROM (
+            SELECT transaction_digest, max(id) AS max_id
+            FROM move_calls
+            WHERE move_package = '{}' {} {} {}
+            GROUP BY transaction_digest
+            ORDER BY max_id {} LIMIT {}
+        ) AS t",
+        package_name,
+        start_sequence_filter(start_sequence, is_descending),
+        if let Some(module_name) = module_name {
+            format!("AND move_module = '{}'", module_name)
+        } else {
+            "".to_string()
+        },
+        if let Some(function_name) = function_name {
+            format!("AND move_function = '{}'", function_name)
+        } else {
+            "".to_string()
+        },
+        order(is_descending),
+        limit
+    )
+}
+
+pub(crate) fn recipient_digest_page_query(
+    recipient_address: &str,
+    start_sequence: Option<i64>,
+    limit: usize,
+    is_descending: bool,
+) -> String {
+    format!(
+        "SELECT transaction_digest as digest_name FROM (
+            SELECT transaction_digest, max(id) AS max_id
+            FROM recipients
+            WHERE recipient = '{}' {} GROUP BY transaction_digest
+            ORDER BY max_id {} LIMIT {}
+        ) AS t",
+        recipient_address,
+        start_sequence_filter(start_sequence, is_descending),
+        order(is_descending),
+        limit
+    )
+}
+
+fn start_sequence_filter(start_sequence: Option<i64>, is_descending: bool) -> String {
+    if let Some(start_sequence) = start_sequence {
+        if is_descending {
+            format!("AND id < {}", start_sequence)
+        } else {
+            format!("AND id > {}", start_sequence)
+        }
+    } else {
+        "".to_string()
+    }
+}
+
+fn order(is_descending: bool) -> &'static str {
+    if is_descending {
+        "DESC"
+    } else {
+        "ASC"
+    }
+}
@@ -0,0 +1,970 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::dsl::{count, max};
+use diesel::sql_types::{BigInt, Text};
+use diesel::upsert::excluded;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
+use move_bytecode_utils::module_cache::SyncModuleCache;
+
+use sui_json_rpc_types::{CheckpointId, EpochInfo, EventFilter, EventPage, SuiEvent};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::committee::EpochId;
+use sui_types::event::EventID;
+use sui_types::object::ObjectRead;
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::epoch::DBEpochInfo;
+use crate::models::events::Event;
+use crate::models::objects::Object;
+use crate::models::processor_progress::ProcessorProgress;
+use crate::models::system_state::DBValidatorSummary;
+use crate::models::transactions::Transaction;
+use crate::processors::processor::Processor;
+use crate::schema::{
+    addresses, epochs, input_objects, move_calls, processor_progress, recipients, validators,
+};
+use crate::store::indexer_store::TemporaryCheckpointStore;
+use crate::store::module_resolver::SqliteModuleResolver;
+use crate::store::query::{
+    input_object_digest_page_query, move_call_digest_page_query, recipient_digest_page_query,
+    TempDigestTable,
+};
+use crate::store::sqlite_models::{
+    SqliteCheckpoint, SqliteEvent, SqliteObject, SqlitePackage, SqliteSystemStateSummary,
+    SqliteTransaction,
+};
+use crate::store::sqlite_schema::{
+    checkpoints, events, objects, objects_history, packages, system_states, transactions,
+};
+use crate::store::{CheckpointData, IndexerStore, TemporaryEpochStore};
+use crate::{get_sqlite_pool_connection, SqliteConnectionPool};
+
+const MAX_EVENT_PAGE_SIZE: usize = 1000;
+
+const MUTATED_OBJECT_FILTER_SQL: &str =
+    "(EXISTS (SELECT 1 FROM json_each(transactions.mutated) WHERE value = ?)
+    OR EXISTS (SELECT 1 FROM json_each(transactions.created) WHERE value = ?)
+    OR EXISTS (SELECT 1 FROM json_each(transactions.unwrapped) WHERE value = ?))";
+
+/// An `IndexerStore` over a SQLite database file, for running the indexer and its JSON-RPC APIs
+/// without a PostgreSQL server. Arrays and enums of the PostgreSQL schema are stored as text, see
+/// `sqlite_models`, and all data of a checkpoint is committed in a single transaction.
+#[derive(Clone)]
+pub struct SqliteIndexerStore {
+    cp: SqliteConnectionPool,
+    module_cache: Arc<SyncModuleCache<SqliteModuleResolver>>,
+}
+
+impl SqliteIndexerStore {
+    pub fn new(cp: SqliteConnectionPool) -> Self {
+        let module_cache = Arc::new(SyncModuleCache::new(SqliteModuleResolver::new(cp.clone())));
+        SqliteIndexerStore { cp, module_cache }
+    }
+}
+
+#[async_trait]
+impl IndexerStore for SqliteIndexerStore {
+    type ModuleCache = SyncModuleCache<SqliteModuleResolver>;
+    type Connection = SqliteConnection;
+
+    fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        checkpoints::table
+            .select(max(checkpoints::sequence_number))
+            .first::<Option<i64>>(&mut conn)
+            // -1 to differentiate between no checkpoints and the first checkpoint
+            .map(|o| o.unwrap_or(-1))
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading latest checkpoint sequence number in SQLite with error {:?}",
+                    e
+                ))
+            })
+    }
+
+    fn get_checkpoint(&self, id: CheckpointId) -> Result<Checkpoint, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        match id {
+            CheckpointId::SequenceNumber(seq) => checkpoints::table
+                .filter(checkpoints::sequence_number.eq(seq as i64))
+                .first::<SqliteCheckpoint>(&mut conn),
+            CheckpointId::Digest(digest) => checkpoints::table
+                .filter(checkpoints::checkpoint_digest.eq(digest.base58_encode()))
+                .first::<SqliteCheckpoint>(&mut conn),
+        }
+        .map_err(|e| {
+            IndexerError::SqliteReadError(format!(
+                "Failed reading checkpoint in SQLite with error {:?}",
+                e
+            ))
+        })?
+        .try_into()
+    }
+
+    fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        events::table
+            .filter(events::transaction_digest.eq(id.tx_digest.base58_encode()))
+            .filter(events::event_sequence.eq(id.event_seq as i64))
+            .first::<SqliteEvent>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading event in SQLite with error {:?}",
+                    e
+                ))
+            })?
+            .try_into()
+    }
+
+    fn get_events(
+        &self,
+        query: EventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        let mut boxed_query = events::table.into_boxed();
+        match query {
+            EventFilter::All(..) => {}
+            EventFilter::Transaction(digest) => {
+                boxed_query =
+                    boxed_query.filter(events::transaction_digest.eq(digest.base58_encode()));
+            }
+            EventFilter::MoveModule { package, module } => {
+                boxed_query = boxed_query
+                    .filter(events::package.eq(package.to_string()))
+                    .filter(events::module.eq(module.to_string()));
+            }
+            EventFilter::MoveEventType(struct_name) => {
+                boxed_query = boxed_query.filter(events::event_type.eq(struct_name.to_string()));
+            }
+            EventFilter::Sender(sender) => {
+                boxed_query = boxed_query.filter(events::sender.eq(sender.to_string()));
+            }
+            EventFilter::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                boxed_query = boxed_query
+                    .filter(events::event_time_ms.ge(start_time as i64))
+                    .filter(events::event_time_ms.lt(end_time as i64));
+            }
+            _ => {
+                return Err(IndexerError::NotImplementedError(format!(
+                    "Filter type [{query:?}] not supported by the Indexer."
+                )))
+            }
+        }
+
+        let mut page_limit = limit.unwrap_or(MAX_EVENT_PAGE_SIZE);
+        if page_limit > MAX_EVENT_PAGE_SIZE {
+            Err(IndexerError::InvalidArgumentError(format!(
+                "Limit {} exceeds the maximum page size {}",
+                page_limit, MAX_EVENT_PAGE_SIZE
+            )))?;
+        }
+        // fetch one more item to tell if there is next page
+        page_limit += 1;
+
+        let sqlite_cursor = cursor
+            .map(|c| {
+                self.get_event(c)?
+                    .id
+                    .ok_or_else(|| IndexerError::SqliteReadError("Event ID is None".to_string()))
+            })
+            .transpose()?;
+        if let Some(sqlite_cursor) = sqlite_cursor {
+            if descending_order {
+                boxed_query = boxed_query.filter(events::id.lt(sqlite_cursor));
+            } else {
+                boxed_query = boxed_query.filter(events::id.gt(sqlite_cursor));
+            }
+        }
+        if descending_order {
+            boxed_query = boxed_query.order(events::id.desc());
+        } else {
+            boxed_query = boxed_query.order(events::id.asc());
+        }
+
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let events_vec: Vec<SqliteEvent> = boxed_query
+            .limit(page_limit as i64)
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading events in SQLite with error {:?}",
+                    e
+                ))
+            })?;
+
+        let mut sui_event_vec = events_vec
+            .into_iter()
+            .map(|event| Event::try_from(event)?.try_into())
+            .collect::<Result<Vec<SuiEvent>, _>>()?;
+        // reset to original limit for checking and truncating
+        page_limit -= 1;
+        let has_next_page = sui_event_vec.len() > page_limit;
+        sui_event_vec.truncate(page_limit);
+        let next_cursor = sui_event_vec.last().map(|e| e.id.clone());
+        Ok(EventPage {
+            data: sui_event_vec,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_total_transaction_number(&self) -> Result<i64, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        transactions::table
+            .select(count(transactions::id))
+            .first::<i64>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading total transaction number with err: {:?}",
+                    e
+                ))
+            })
+    }
+
+    fn get_transaction_by_digest(&self, txn_digest: &str) -> Result<Transaction, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        transactions::table
+            .filter(transactions::transaction_digest.eq(txn_digest))
+            .first::<SqliteTransaction>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transaction with digest {} and err: {:?}",
+                    txn_digest, e
+                ))
+            })?
+            .try_into()
+    }
+
+    fn multi_get_transactions_by_digests(
+        &self,
+        txn_digests: &[String],
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        transactions::table
+            .filter(transactions::transaction_digest.eq_any(txn_digests))
+            .load::<SqliteTransaction>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transactions with digests {:?} and err: {:?}",
+                    txn_digests, e
+                ))
+            })?
+            .into_iter()
+            .map(Transaction::try_from)
+            .collect()
+    }
+
+    fn get_transaction_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                let mut conn = get_sqlite_pool_connection(&self.cp)?;
+                let mut boxed_query = transactions::table
+                    .filter(transactions::transaction_digest.eq(digest.clone()))
+                    .select(transactions::id)
+                    .into_boxed();
+                if is_descending {
+                    boxed_query = boxed_query.order(transactions::id.desc());
+                } else {
+                    boxed_query = boxed_query.order(transactions::id.asc());
+                }
+                boxed_query.first::<i64>(&mut conn).map_err(|e| {
+                    IndexerError::SqliteReadError(format!(
+                        "Failed reading transaction sequence with digest {} and err: {:?}",
+                        digest, e
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    fn get_object(
+        &self,
+        object_id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> Result<ObjectRead, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let object = if let Some(version) = version {
+            objects_history::table
+                .filter(objects_history::object_id.eq(object_id.to_string()))
+                .filter(objects_history::version.eq(version.value() as i64))
+                .first::<SqliteObject>(&mut conn)
+                .optional()
+        } else {
+            objects::table
+                .filter(objects::object_id.eq(object_id.to_string()))
+                .first::<SqliteObject>(&mut conn)
+                .optional()
+        }
+        .map_err(|e| {
+            IndexerError::SqliteReadError(format!(
+                "Failed reading object with id {} and err: {:?}",
+                object_id, e
+            ))
+        })?;
+
+        match object {
+            None => Ok(ObjectRead::NotExists(object_id)),
+            Some(o) => Object::try_from(o)?.try_into_object_read(&self.module_cache),
+        }
+    }
+
+    fn get_move_call_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                let mut conn = get_sqlite_pool_connection(&self.cp)?;
+                let mut boxed_query = move_calls::table
+                    .filter(move_calls::transaction_digest.eq(digest.clone()))
+                    .into_boxed();
+                if is_descending {
+                    boxed_query = boxed_query.order(move_calls::id.desc());
+                } else {
+                    boxed_query = boxed_query.order(move_calls::id.asc());
+                }
+                boxed_query
+                    .select(move_calls::id)
+                    .first::<i64>(&mut conn)
+                    .map_err(|e| {
+                        IndexerError::SqliteReadError(format!(
+                            "Failed reading move call sequence with digest {} and err: {:?}",
+                            digest, e
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    fn get_input_object_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                let mut conn = get_sqlite_pool_connection(&self.cp)?;
+                let mut boxed_query = input_objects::table
+                    .filter(input_objects::transaction_digest.eq(digest.clone()))
+                    .into_boxed();
+                if is_descending {
+                    boxed_query = boxed_query.order(input_objects::id.desc());
+                } else {
+                    boxed_query = boxed_query.order(input_objects::id.asc());
+                }
+                boxed_query
+                    .select(input_objects::id)
+                    .first::<i64>(&mut conn)
+                    .map_err(|e| {
+                        IndexerError::SqliteReadError(format!(
+                            "Failed reading input object sequence with digest {} and err: {:?}",
+                            digest, e
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    fn get_recipient_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|txn_digest| {
+                let mut conn = get_sqlite_pool_connection(&self.cp)?;
+                let mut boxed_query = recipients::table
+                    .filter(recipients::transaction_digest.eq(&txn_digest))
+                    .into_boxed();
+                if is_descending {
+                    boxed_query = boxed_query.order(recipients::id.desc());
+                } else {
+                    boxed_query = boxed_query.order(recipients::id.asc());
+                }
+                boxed_query
+                    .select(recipients::id)
+                    .first::<i64>(&mut conn)
+                    .map_err(|e| {
+                        IndexerError::SqliteReadError(format!(
+                            "Failed reading recipients sequence with digest {} and err: {:?}",
+                            txn_digest, e
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    fn get_all_transaction_digest_page(
+        &self,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let mut boxed_query = transactions::table.into_boxed();
+        if let Some(start_sequence) = start_sequence {
+            if is_descending {
+                boxed_query = boxed_query.filter(transactions::id.lt(start_sequence));
+            } else {
+                boxed_query = boxed_query.filter(transactions::id.gt(start_sequence));
+            }
+        }
+        if is_descending {
+            boxed_query = boxed_query.order(transactions::id.desc());
+        } else {
+            boxed_query = boxed_query.order(transactions::id.asc());
+        }
+        boxed_query
+            .limit(limit as i64)
+            .select(transactions::transaction_digest)
+            .load::<String>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading all transaction digests with start_sequence {:?} and limit {} and err: {:?}",
+                    start_sequence, limit, e
+                ))
+            })
+    }
+
+    fn get_transaction_digest_page_by_mutated_object(
+        &self,
+        object_id: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let sql_query = format!(
+            "SELECT transaction_digest AS digest_name FROM transactions
+            WHERE {MUTATED_OBJECT_FILTER_SQL} {}
+            ORDER BY id {} LIMIT {}",
+            if let Some(start_sequence) = start_sequence {
+                if is_descending {
+                    format!("AND id < {}", start_sequence)
+                } else {
+                    format!("AND id > {}", start_sequence)
+                }
+            } else {
+                "".to_string()
+            },
+            if is_descending { "DESC" } else { "ASC" },
+            limit
+        );
+        Ok(diesel::sql_query(sql_query)
+            .bind::<Text, _>(&object_id)
+            .bind::<Text, _>(&object_id)
+            .bind::<Text, _>(&object_id)
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transaction digests by mutated object id {} with start_sequence {:?} and limit {} and err: {:?}",
+                    object_id, start_sequence, limit, e
+                ))
+            })?
+            .into_iter()
+            .map(|table: TempDigestTable| table.digest_name)
+            .collect())
+    }
+
+    fn get_transaction_digest_page_by_sender_address(
+        &self,
+        sender_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let mut boxed_query = transactions::table
+            .filter(transactions::sender.eq(sender_address.clone()))
+            .into_boxed();
+        if let Some(start_sequence) = start_sequence {
+            if is_descending {
+                boxed_query = boxed_query.filter(transactions::id.lt(start_sequence));
+            } else {
+                boxed_query = boxed_query.filter(transactions::id.gt(start_sequence));
+            }
+        }
+        if is_descending {
+            boxed_query = boxed_query.order(transactions::id.desc());
+        } else {
+            boxed_query = boxed_query.order(transactions::id.asc());
+        }
+        boxed_query
+            .limit(limit as i64)
+            .select(transactions::transaction_digest)
+            .load::<String>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transaction digests by sender address {} with start_sequence {:?} and limit {} and err: {:?}",
+                    sender_address, start_sequence, limit, e
+                ))
+            })
+    }
+
+    fn get_transaction_digest_page_by_recipient_address(
+        &self,
+        recipient_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let sql_query =
+            recipient_digest_page_query(&recipient_address, start_sequence, limit, is_descending);
+        Ok(diesel::sql_query(sql_query)
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transaction digests by recipient address {} with start_sequence {:?} and limit {} and err: {:?}",
+                    recipient_address, start_sequence, limit, e
+                ))
+            })?
+            .into_iter()
+            .map(|table: TempDigestTable| table.digest_name)
+            .collect())
+    }
+
+    fn get_transaction_digest_page_by_input_object(
+        &self,
+        object_id: String,
+        version: Option<i64>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let sql_query = input_object_digest_page_query(
+            &object_id,
+            version,
+            start_sequence,
+            limit,
+            is_descending,
+        );
+        Ok(diesel::sql_query(sql_query)
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transaction digests by input object ID {} and version {:?} with start_sequence {:?} and limit {} and err: {:?}",
+                    object_id, version, start_sequence, limit, e
+                ))
+            })?
+            .into_iter()
+            .map(|table: TempDigestTable| table.digest_name)
+            .collect())
+    }
+
+    fn get_transaction_digest_page_by_move_call(
+        &self,
+        package_name: String,
+        module_name: Option<String>,
+        function_name: Option<String>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let sql_query = move_call_digest_page_query(
+            &package_name,
+            module_name.as_deref(),
+            function_name.as_deref(),
+            start_sequence,
+            limit,
+            is_descending,
+        );
+        Ok(diesel::sql_query(sql_query)
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transaction digests with package_name {} module_name {:?} and function_name {:?} and start_sequence {:?} and limit {} and err: {:?}",
+                    package_name, module_name, function_name, start_sequence, limit, e
+                ))
+            })?
+            .into_iter()
+            .map(|table: TempDigestTable| table.digest_name)
+            .collect())
+    }
+
+    fn read_transactions(
+        &self,
+        last_processed_id: i64,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        transactions::table
+            .filter(transactions::id.gt(last_processed_id))
+            .order(transactions::id.asc())
+            .limit(limit as i64)
+            .load::<SqliteTransaction>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading transactions with last_processed_id {} and err: {:?}",
+                    last_processed_id, e
+                ))
+            })?
+            .into_iter()
+            .map(Transaction::try_from)
+            .collect()
+    }
+
+    fn get_total_address_number(&self) -> Result<u64, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let total_addresses = addresses::table
+            .select(count(addresses::account_address))
+            .first::<i64>(&mut conn)
+            .map_err(|e| IndexerError::SqliteReadError(e.to_string()))?;
+        Ok(total_addresses as u64)
+    }
+
+    fn persist_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError> {
+        let TemporaryCheckpointStore {
+            checkpoint,
+            transactions,
+            events,
+            objects_changes,
+            addresses,
+            packages,
+            input_objects,
+            move_calls,
+            recipients,
+        } = data;
+
+        // Convert before writing, SQLite serializes writers so the transaction is kept short.
+        let transactions = transactions
+            .iter()
+            .map(SqliteTransaction::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let events = events
+            .iter()
+            .map(SqliteEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mutated_objects = objects_changes
+            .iter()
+            .flat_map(|changes| &changes.mutated_objects)
+            .map(SqliteObject::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let deleted_objects = objects_changes
+            .iter()
+            .flat_map(|changes| &changes.deleted_objects)
+            .map(|deleted_object| {
+                let object: Object = deleted_object.clone().into();
+                SqliteObject::try_from(&object)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let packages = packages
+            .iter()
+            .map(SqlitePackage::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let sqlite_checkpoint = SqliteCheckpoint::try_from(checkpoint)?;
+
+        let mut sqlite_pool_conn = get_sqlite_pool_connection(&self.cp)?;
+        // Unlike PostgreSQL, all data of the checkpoint is committed in one transaction, so the
+        // checkpoint is committed if and only if all related data is.
+        sqlite_pool_conn
+            .immediate_transaction(|conn| {
+                for transaction in &transactions {
+                    diesel::insert_into(transactions::table)
+                        .values(transaction)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                for event in &events {
+                    diesel::insert_into(events::table)
+                        .values(event)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                for object in &mutated_objects {
+                    diesel::insert_into(objects::table)
+                        .values(object)
+                        .on_conflict(objects::object_id)
+                        .do_update()
+                        .set((
+                            objects::epoch.eq(excluded(objects::epoch)),
+                            objects::checkpoint.eq(excluded(objects::checkpoint)),
+                            objects::version.eq(excluded(objects::version)),
+                            objects::object_digest.eq(excluded(objects::object_digest)),
+                            objects::owner_type.eq(excluded(objects::owner_type)),
+                            objects::owner_address.eq(excluded(objects::owner_address)),
+                            objects::initial_shared_version
+                                .eq(excluded(objects::initial_shared_version)),
+                            objects::previous_transaction
+                                .eq(excluded(objects::previous_transaction)),
+                            objects::object_type.eq(excluded(objects::object_type)),
+                            objects::object_status.eq(excluded(objects::object_status)),
+                            objects::has_public_transfer
+                                .eq(excluded(objects::has_public_transfer)),
+                            objects::storage_rebate.eq(excluded(objects::storage_rebate)),
+                            objects::bcs.eq(excluded(objects::bcs)),
+                        ))
+                        .execute(conn)?;
+                }
+                for object in &deleted_objects {
+                    diesel::insert_into(objects::table)
+                        .values(object)
+                        .on_conflict(objects::object_id)
+                        .do_update()
+                        .set((
+                            objects::epoch.eq(excluded(objects::epoch)),
+                            objects::checkpoint.eq(excluded(objects::checkpoint)),
+                            objects::version.eq(excluded(objects::version)),
+                            objects::previous_transaction
+                                .eq(excluded(objects::previous_transaction)),
+                            objects::object_status.eq(excluded(objects::object_status)),
+                        ))
+                        .execute(conn)?;
+                }
+                for address in addresses {
+                    diesel::insert_into(addresses::table)
+                        .values(address)
+                        .on_conflict(addresses::account_address)
+                        .do_nothing()
+                        .execute(conn)?;
+                }
+                for package in &packages {
+                    diesel::insert_into(packages::table)
+                        .values(package)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                for move_call in move_calls {
+                    diesel::insert_into(move_calls::table)
+                        .values(move_call)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                for input_object in input_objects {
+                    diesel::insert_into(input_objects::table)
+                        .values(input_object)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                for recipient in recipients {
+                    diesel::insert_into(recipients::table)
+                        .values(recipient)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+
+                // update epoch transaction count
+                diesel::sql_query(
+                    "UPDATE epochs SET epoch_total_transactions = epoch_total_transactions + ? WHERE epoch = ?",
+                )
+                .bind::<BigInt, _>(checkpoint.transactions.len() as i64)
+                .bind::<BigInt, _>(checkpoint.epoch)
+                .execute(conn)?;
+
+                diesel::insert_into(checkpoints::table)
+                    .values(&sqlite_checkpoint)
+                    .on_conflict_do_nothing()
+                    .execute(conn)
+            })
+            .map_err(|e: diesel::result::Error| {
+                IndexerError::SqliteWriteError(format!(
+                    "Failed writing checkpoint to SQLite with error: {:?}",
+                    e
+                ))
+            })
+    }
+
+    fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<(), IndexerError> {
+        let system_state = SqliteSystemStateSummary::try_from(&data.system_state)?;
+        let mut sqlite_pool_conn = get_sqlite_pool_connection(&self.cp)?;
+        sqlite_pool_conn
+            .immediate_transaction(|conn| {
+                if let Some(last_epoch) = &data.last_epoch {
+                    diesel::insert_into(epochs::table)
+                        .values(last_epoch)
+                        .on_conflict(epochs::epoch)
+                        .do_update()
+                        .set((
+                            epochs::last_checkpoint_id.eq(excluded(epochs::last_checkpoint_id)),
+                            epochs::epoch_end_timestamp.eq(excluded(epochs::epoch_end_timestamp)),
+                            epochs::protocol_version.eq(excluded(epochs::protocol_version)),
+                            epochs::reference_gas_price.eq(excluded(epochs::reference_gas_price)),
+                            epochs::total_stake.eq(excluded(epochs::total_stake)),
+                            epochs::storage_fund_reinvestment
+                                .eq(excluded(epochs::storage_fund_reinvestment)),
+                            epochs::storage_charge.eq(excluded(epochs::storage_charge)),
+                            epochs::storage_rebate.eq(excluded(epochs::storage_rebate)),
+                            epochs::storage_fund_balance.eq(excluded(epochs::storage_fund_balance)),
+                            epochs::stake_subsidy_amount.eq(excluded(epochs::stake_subsidy_amount)),
+                            epochs::total_gas_fees.eq(excluded(epochs::total_gas_fees)),
+                            epochs::total_stake_rewards_distributed
+                                .eq(excluded(epochs::total_stake_rewards_distributed)),
+                            epochs::leftover_storage_fund_inflow
+                                .eq(excluded(epochs::leftover_storage_fund_inflow)),
+                        ))
+                        .execute(conn)?;
+                }
+                diesel::insert_into(epochs::table)
+                    .values(&data.new_epoch)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+
+                diesel::insert_into(system_states::table)
+                    .values(&system_state)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+
+                for validator in &data.validators {
+                    diesel::insert_into(validators::table)
+                        .values(validator)
+                        .on_conflict_do_nothing()
+                        .execute(conn)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+            .map_err(|e| {
+                IndexerError::SqliteWriteError(format!(
+                    "Failed writing epoch to SQLite with error: {:?}",
+                    e
+                ))
+            })
+    }
+
+    fn module_cache(&self) -> &Self::ModuleCache {
+        &self.module_cache
+    }
+
+    fn get_processor_progress(&self, processor_name: &str) -> Result<Option<i64>, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        processor_progress::table
+            .filter(processor_progress::processor_name.eq(processor_name))
+            .select(processor_progress::checkpoint_sequence_number)
+            .first::<i64>(&mut conn)
+            .optional()
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading progress of processor {} in SQLite with error {:?}",
+                    processor_name, e
+                ))
+            })
+    }
+
+    fn initialize_processor(
+        &self,
+        processor: &dyn Processor<SqliteConnection>,
+    ) -> Result<(), IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        conn.immediate_transaction(|conn| processor.initialize(conn))
+    }
+
+    fn run_processor(
+        &self,
+        processor: &dyn Processor<SqliteConnection>,
+        checkpoint: &CheckpointData,
+    ) -> Result<bool, IndexerError> {
+        let sequence_number = checkpoint.checkpoint.sequence_number as i64;
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        // The immediate transaction locks the database, so no other writer can record progress of
+        // the processor between the read and the write below.
+        conn.immediate_transaction(|conn| {
+            let progress = processor_progress::table
+                .filter(processor_progress::processor_name.eq(processor.name()))
+                .select(processor_progress::checkpoint_sequence_number)
+                .first::<i64>(conn)
+                .optional()?;
+            if matches!(progress, Some(progress) if progress >= sequence_number) {
+                return Ok(false);
+            }
+            processor.process_checkpoint(conn, checkpoint)?;
+            diesel::insert_into(processor_progress::table)
+                .values(ProcessorProgress {
+                    processor_name: processor.name().to_string(),
+                    checkpoint_sequence_number: sequence_number,
+                })
+                .on_conflict(processor_progress::processor_name)
+                .do_update()
+                .set(processor_progress::checkpoint_sequence_number.eq(sequence_number))
+                .execute(conn)?;
+            Ok(true)
+        })
+    }
+
+    fn get_epochs(
+        &self,
+        cursor: Option<EpochId>,
+        limit: usize,
+    ) -> Result<Vec<EpochInfo>, IndexerError> {
+        let id = cursor.map(|id| id as i64).unwrap_or(-1);
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let epoch_info: Vec<DBEpochInfo> = epochs::table
+            .filter(epochs::epoch.gt(id))
+            .order_by(epochs::epoch.asc())
+            .limit(limit as i64)
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading epochs in SQLite with error {:?}",
+                    e
+                ))
+            })?;
+
+        let validators: Vec<DBValidatorSummary> = validators::table
+            .filter(validators::epoch.gt(id))
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading validators in SQLite with error {:?}",
+                    e
+                ))
+            })?;
+
+        let mut validators =
+            validators
+                .into_iter()
+                .fold(BTreeMap::<i64, Vec<_>>::new(), |mut acc, v| {
+                    acc.entry(v.epoch).or_default().push(v);
+                    acc
+                });
+
+        epoch_info
+            .into_iter()
+            .map(|info| {
+                let epoch = info.epoch;
+                info.to_epoch_info(validators.remove(&epoch).unwrap_or_default())
+            })
+            .collect()
+    }
+
+    fn get_current_epoch(&self) -> Result<EpochInfo, IndexerError> {
+        let mut conn = get_sqlite_pool_connection(&self.cp)?;
+        let epoch_info: DBEpochInfo = epochs::table
+            .order_by(epochs::epoch.desc())
+            .first::<DBEpochInfo>(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading current epoch in SQLite with error {:?}",
+                    e
+                ))
+            })?;
+
+        let validators: Vec<DBValidatorSummary> = validators::table
+            .filter(validators::epoch.eq(epoch_info.epoch))
+            .load(&mut conn)
+            .map_err(|e| {
+                IndexerError::SqliteReadError(format!(
+                    "Failed reading validators in SQLite with error {:?}",
+                    e
+                ))
+            })?;
+
+        epoch_info.to_epoch_info(validators)
+    }
+}
@@ -0,0 +1,536 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rows of the tables of `sqlite_schema`, converted from and to the models shared with the
+//! PostgreSQL store. Arrays are stored as JSON text, enums as the labels of the PostgreSQL enums
+//! and lists of named BCS bytes as their BCS serialization.
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::errors::IndexerError;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::events::Event;
+use crate::models::objects::{NamedBcsBytes, Object, ObjectStatus};
+use crate::models::owners::OwnerType;
+use crate::models::packages::Package;
+use crate::models::system_state::DBSystemStateSummary;
+use crate::models::transactions::Transaction;
+use crate::store::sqlite_schema::{
+    checkpoints, events, objects, packages, system_states, transactions,
+};
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = transactions)]
+pub(crate) struct SqliteTransaction {
+    #[diesel(deserialize_as = i64)]
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub sender: String,
+    pub recipients: String,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub transaction_kind: String,
+    pub created: String,
+    pub mutated: String,
+    pub deleted: String,
+    pub unwrapped: String,
+    pub wrapped: String,
+    pub move_calls: String,
+    pub gas_object_id: String,
+    pub gas_object_sequence: i64,
+    pub gas_object_digest: String,
+    pub gas_budget: i64,
+    pub total_gas_cost: i64,
+    pub computation_cost: i64,
+    pub storage_cost: i64,
+    pub storage_rebate: i64,
+    pub gas_price: i64,
+    pub raw_transaction: Vec<u8>,
+    pub transaction_content: String,
+    pub transaction_effects_content: String,
+    pub confirmed_local_execution: Option<bool>,
+}
+
+impl TryFrom<&Transaction> for SqliteTransaction {
+    type Error = IndexerError;
+
+    fn try_from(t: &Transaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: t.id,
+            transaction_digest: t.transaction_digest.clone(),
+            sender: t.sender.clone(),
+            recipients: to_json_array(&t.recipients)?,
+            checkpoint_sequence_number: t.checkpoint_sequence_number,
+            timestamp_ms: t.timestamp_ms,
+            transaction_kind: t.transaction_kind.clone(),
+            created: to_json_array(&t.created)?,
+            mutated: to_json_array(&t.mutated)?,
+            deleted: to_json_array(&t.deleted)?,
+            unwrapped: to_json_array(&t.unwrapped)?,
+            wrapped: to_json_array(&t.wrapped)?,
+            move_calls: to_json_array(&t.move_calls)?,
+            gas_object_id: t.gas_object_id.clone(),
+            gas_object_sequence: t.gas_object_sequence,
+            gas_object_digest: t.gas_object_digest.clone(),
+            gas_budget: t.gas_budget,
+            total_gas_cost: t.total_gas_cost,
+            computation_cost: t.computation_cost,
+            storage_cost: t.storage_cost,
+            storage_rebate: t.storage_rebate,
+            gas_price: t.gas_price,
+            raw_transaction: t.raw_transaction.clone(),
+            transaction_content: t.transaction_content.clone(),
+            transaction_effects_content: t.transaction_effects_content.clone(),
+            confirmed_local_execution: t.confirmed_local_execution,
+        })
+    }
+}
+
+impl TryFrom<SqliteTransaction> for Transaction {
+    type Error = IndexerError;
+
+    fn try_from(t: SqliteTransaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: t.id,
+            transaction_digest: t.transaction_digest,
+            sender: t.sender,
+            recipients: from_json_array(&t.recipients)?,
+            checkpoint_sequence_number: t.checkpoint_sequence_number,
+            timestamp_ms: t.timestamp_ms,
+            transaction_kind: t.transaction_kind,
+            created: from_json_array(&t.created)?,
+            mutated: from_json_array(&t.mutated)?,
+            deleted: from_json_array(&t.deleted)?,
+            unwrapped: from_json_array(&t.unwrapped)?,
+            wrapped: from_json_array(&t.wrapped)?,
+            move_calls: from_json_array(&t.move_calls)?,
+            gas_object_id: t.gas_object_id,
+            gas_object_sequence: t.gas_object_sequence,
+            gas_object_digest: t.gas_object_digest,
+            gas_budget: t.gas_budget,
+            total_gas_cost: t.total_gas_cost,
+            computation_cost: t.computation_cost,
+            storage_cost: t.storage_cost,
+            storage_rebate: t.storage_rebate,
+            gas_price: t.gas_price,
+            raw_transaction: t.raw_transaction,
+            transaction_content: t.transaction_content,
+            transaction_effects_content: t.transaction_effects_content,
+            confirmed_local_execution: t.confirmed_local_execution,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = checkpoints)]
+pub(crate) struct SqliteCheckpoint {
+    pub sequence_number: i64,
+    pub checkpoint_digest: String,
+    pub epoch: i64,
+    pub transactions: String,
+    pub previous_checkpoint_digest: Option<String>,
+    pub next_epoch_committee: Option<String>,
+    pub next_epoch_protocol_version: Option<i64>,
+    pub end_of_epoch_data: Option<String>,
+    pub total_gas_cost: i64,
+    pub total_computation_cost: i64,
+    pub total_storage_cost: i64,
+    pub total_storage_rebate: i64,
+    pub total_transactions: i64,
+    pub total_transactions_current_epoch: i64,
+    pub total_transactions_from_genesis: i64,
+    pub timestamp_ms: i64,
+    pub timestamp_ms_str: NaiveDateTime,
+    pub checkpoint_tps: f32,
+}
+
+impl TryFrom<&Checkpoint> for SqliteCheckpoint {
+    type Error = IndexerError;
+
+    fn try_from(c: &Checkpoint) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence_number: c.sequence_number,
+            checkpoint_digest: c.checkpoint_digest.clone(),
+            epoch: c.epoch,
+            transactions: to_json_array(&c.transactions)?,
+            previous_checkpoint_digest: c.previous_checkpoint_digest.clone(),
+            next_epoch_committee: c.next_epoch_committee.clone(),
+            next_epoch_protocol_version: c.next_epoch_protocol_version,
+            end_of_epoch_data: c.end_of_epoch_data.clone(),
+            total_gas_cost: c.total_gas_cost,
+            total_computation_cost: c.total_computation_cost,
+            total_storage_cost: c.total_storage_cost,
+            total_storage_rebate: c.total_storage_rebate,
+            total_transactions: c.total_transactions,
+            total_transactions_current_epoch: c.total_transactions_current_epoch,
+            total_transactions_from_genesis: c.total_transactions_from_genesis,
+            timestamp_ms: c.timestamp_ms,
+            timestamp_ms_str: c.timestamp_ms_str,
+            checkpoint_tps: c.checkpoint_tps,
+        })
+    }
+}
+
+impl TryFrom<SqliteCheckpoint> for Checkpoint {
+    type Error = IndexerError;
+
+    fn try_from(c: SqliteCheckpoint) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence_number: c.sequence_number,
+            checkpoint_digest: c.checkpoint_digest,
+            epoch: c.epoch,
+            transactions: from_json_array(&c.transactions)?,
+            previous_checkpoint_digest: c.previous_checkpoint_digest,
+            next_epoch_committee: c.next_epoch_committee,
+            next_epoch_protocol_version: c.next_epoch_protocol_version,
+            end_of_epoch_data: c.end_of_epoch_data,
+            total_gas_cost: c.total_gas_cost,
+            total_computation_cost: c.total_computation_cost,
+            total_storage_cost: c.total_storage_cost,
+            total_storage_rebate: c.total_storage_rebate,
+            total_transactions: c.total_transactions,
+            total_transactions_current_epoch: c.total_transactions_current_epoch,
+            total_transactions_from_genesis: c.total_transactions_from_genesis,
+            timestamp_ms: c.timestamp_ms,
+            timestamp_ms_str: c.timestamp_ms_str,
+            checkpoint_tps: c.checkpoint_tps,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = events)]
+pub(crate) struct SqliteEvent {
+    #[diesel(deserialize_as = i64)]
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub event_sequence: i64,
+    pub sender: String,
+    pub package: String,
+    pub module: String,
+    pub event_type: String,
+    pub event_time_ms: Option<i64>,
+    pub parsed_json: String,
+    pub event_bcs: Vec<u8>,
+}
+
+impl TryFrom<&Event> for SqliteEvent {
+    type Error = IndexerError;
+
+    fn try_from(e: &Event) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: e.id,
+            transaction_digest: e.transaction_digest.clone(),
+            event_sequence: e.event_sequence,
+            sender: e.sender.clone(),
+            package: e.package.clone(),
+            module: e.module.clone(),
+            event_type: e.event_type.clone(),
+            event_time_ms: e.event_time_ms,
+            parsed_json: serde_json::to_string(&e.parsed_json).map_err(|e| {
+                IndexerError::SerdeError(format!("Failed to serialize event JSON: {:?}", e))
+            })?,
+            event_bcs: e.event_bcs.clone(),
+        })
+    }
+}
+
+impl TryFrom<SqliteEvent> for Event {
+    type Error = IndexerError;
+
+    fn try_from(e: SqliteEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: e.id,
+            transaction_digest: e.transaction_digest,
+            event_sequence: e.event_sequence,
+            sender: e.sender,
+            package: e.package,
+            module: e.module,
+            event_type: e.event_type,
+            event_time_ms: e.event_time_ms,
+            parsed_json: serde_json::from_str(&e.parsed_json).map_err(|e| {
+                IndexerError::SerdeError(format!("Failed to deserialize event JSON: {:?}", e))
+            })?,
+            event_bcs: e.event_bcs,
+        })
+    }
+}
+
+// Rows of both the objects and the objects_history tables, which have the same columns.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = objects)]
+pub(crate) struct SqliteObject {
+    pub epoch: i64,
+    pub checkpoint: i64,
+    pub object_id: String,
+    pub version: i64,
+    pub object_digest: String,
+    pub owner_type: String,
+    pub owner_address: Option<String>,
+    pub initial_shared_version: Option<i64>,
+    pub previous_transaction: String,
+    pub object_type: String,
+    pub object_status: String,
+    pub has_public_transfer: bool,
+    pub storage_rebate: i64,
+    pub bcs: Vec<u8>,
+}
+
+impl TryFrom<&Object> for SqliteObject {
+    type Error = IndexerError;
+
+    fn try_from(o: &Object) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: o.epoch,
+            checkpoint: o.checkpoint,
+            object_id: o.object_id.clone(),
+            version: o.version,
+            object_digest: o.object_digest.clone(),
+            owner_type: owner_type_label(&o.owner_type).to_string(),
+            owner_address: o.owner_address.clone(),
+            initial_shared_version: o.initial_shared_version,
+            previous_transaction: o.previous_transaction.clone(),
+            object_type: o.object_type.clone(),
+            object_status: object_status_label(&o.object_status).to_string(),
+            has_public_transfer: o.has_public_transfer,
+            storage_rebate: o.storage_rebate,
+            bcs: to_bcs_bytes(&o.bcs)?,
+        })
+    }
+}
+
+impl TryFrom<SqliteObject> for Object {
+    type Error = IndexerError;
+
+    fn try_from(o: SqliteObject) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: o.epoch,
+            checkpoint: o.checkpoint,
+            object_id: o.object_id,
+            version: o.version,
+            object_digest: o.object_digest,
+            owner_type: owner_type_from_label(&o.owner_type)?,
+            owner_address: o.owner_address,
+            initial_shared_version: o.initial_shared_version,
+            previous_transaction: o.previous_transaction,
+            object_type: o.object_type,
+            object_status: object_status_from_label(&o.object_status)?,
+            has_public_transfer: o.has_public_transfer,
+            storage_rebate: o.storage_rebate,
+            bcs: from_bcs_bytes(&o.bcs)?,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = packages)]
+pub(crate) struct SqlitePackage {
+    pub package_id: String,
+    pub version: i64,
+    pub author: String,
+    pub data: Vec<u8>,
+}
+
+impl TryFrom<&Package> for SqlitePackage {
+    type Error = IndexerError;
+
+    fn try_from(p: &Package) -> Result<Self, Self::Error> {
+        Ok(Self {
+            package_id: p.package_id.clone(),
+            version: p.version,
+            author: p.author.clone(),
+            data: to_bcs_bytes(&p.data)?,
+        })
+    }
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = system_states)]
+pub(crate) struct SqliteSystemStateSummary {
+    pub epoch: i64,
+    pub protocol_version: i64,
+    pub system_state_version: i64,
+    pub storage_fund: i64,
+    pub reference_gas_price: i64,
+    pub safe_mode: bool,
+    pub epoch_start_timestamp_ms: i64,
+    pub governance_start_epoch: i64,
+    pub epoch_duration_ms: i64,
+    pub stake_subsidy_epoch_counter: i64,
+    pub stake_subsidy_balance: i64,
+    pub stake_subsidy_current_epoch_amount: i64,
+    pub total_stake: i64,
+    pub pending_active_validators_id: String,
+    pub pending_active_validators_size: i64,
+    pub pending_removals: String,
+    pub staking_pool_mappings_id: String,
+    pub staking_pool_mappings_size: i64,
+    pub inactive_pools_id: String,
+    pub inactive_pools_size: i64,
+    pub validator_candidates_id: String,
+    pub validator_candidates_size: i64,
+}
+
+impl TryFrom<&DBSystemStateSummary> for SqliteSystemStateSummary {
+    type Error = IndexerError;
+
+    fn try_from(s: &DBSystemStateSummary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: s.epoch,
+            protocol_version: s.protocol_version,
+            system_state_version: s.system_state_version,
+            storage_fund: s.storage_fund,
+            reference_gas_price: s.reference_gas_price,
+            safe_mode: s.safe_mode,
+            epoch_start_timestamp_ms: s.epoch_start_timestamp_ms,
+            governance_start_epoch: s.governance_start_epoch,
+            epoch_duration_ms: s.epoch_duration_ms,
+            stake_subsidy_epoch_counter: s.stake_subsidy_epoch_counter,
+            stake_subsidy_balance: s.stake_subsidy_balance,
+            stake_subsidy_current_epoch_amount: s.stake_subsidy_current_epoch_amount,
+            total_stake: s.total_stake,
+            pending_active_validators_id: s.pending_active_validators_id.clone(),
+            pending_active_validators_size: s.pending_active_validators_size,
+            pending_removals: to_json_array(&s.pending_removals)?,
+            staking_pool_mappings_id: s.staking_pool_mappings_id.clone(),
+            staking_pool_mappings_size: s.staking_pool_mappings_size,
+            inactive_pools_id: s.inactive_pools_id.clone(),
+            inactive_pools_size: s.inactive_pools_size,
+            validator_candidates_id: s.validator_candidates_id.clone(),
+            validator_candidates_size: s.validator_candidates_size,
+        })
+    }
+}
+
+/// The modules of the latest version of a package stored in the packages table.
+pub(crate) fn package_modules(data: &[u8]) -> Result<Vec<NamedBcsBytes>, IndexerError> {
+    from_bcs_bytes(data)
+}
+
+fn to_json_array<T: Serialize>(values: &[T]) -> Result<String, IndexerError> {
+    serde_json::to_string(values).map_err(|e| {
+        IndexerError::SerdeError(format!("Failed to serialize array to JSON: {:?}", e))
+    })
+}
+
+fn from_json_array<T: DeserializeOwned>(json: &str) -> Result<Vec<T>, IndexerError> {
+    serde_json::from_str(json).map_err(|e| {
+        IndexerError::SerdeError(format!(
+            "Failed to deserialize array from JSON {}: {:?}",
+            json, e
+        ))
+    })
+}
+
+fn to_bcs_bytes(values: &[NamedBcsBytes]) -> Result<Vec<u8>, IndexerError> {
+    let values: Vec<_> = values
+        .iter()
+        .map(|NamedBcsBytes(name, bytes)| (name, bytes))
+        .collect();
+    Ok(bcs::to_bytes(&values)?)
+}
+
+fn from_bcs_bytes(bytes: &[u8]) -> Result<Vec<NamedBcsBytes>, IndexerError> {
+    Ok(bcs::from_bytes::<Vec<(String, Vec<u8>)>>(bytes)?
+        .into_iter()
+        .map(|(name, bytes)| NamedBcsBytes(name, bytes))
+        .collect())
+}
+
+fn owner_type_label(owner_type: &OwnerType) -> &'static str {
+    match owner_type {
+        OwnerType::AddressOwner => "address_owner",
+        OwnerType::ObjectOwner => "object_owner",
+        OwnerType::Shared => "shared",
+        OwnerType::Immutable => "immutable",
+    }
+}
+
+fn owner_type_from_label(label: &str) -> Result<OwnerType, IndexerError> {
+    Ok(match label {
+        "address_owner" => OwnerType::AddressOwner,
+        "object_owner" => OwnerType::ObjectOwner,
+        "shared" => OwnerType::Shared,
+        "immutable" => OwnerType::Immutable,
+        _ => {
+            return Err(IndexerError::SerdeError(format!(
+                "Unknown owner type: {label}"
+            )))
+        }
+    })
+}
+
+fn object_status_label(object_status: &ObjectStatus) -> &'static str {
+    match object_status {
+        ObjectStatus::Created => "created",
+        ObjectStatus::Mutated => "mutated",
+        ObjectStatus::Deleted => "deleted",
+        ObjectStatus::Wrapped => "wrapped",
+        ObjectStatus::Unwrapped => "unwrapped",
+        ObjectStatus::UnwrappedThenDeleted => "unwrapped_then_deleted",
+    }
+}
+
+fn object_status_from_label(label: &str) -> Result<ObjectStatus, IndexerError> {
+    Ok(match label {
+        "created" => ObjectStatus::Created,
+        "mutated" => ObjectStatus::Mutated,
+        "deleted" => ObjectStatus::Deleted,
+        "wrapped" => ObjectStatus::Wrapped,
+        "unwrapped" => ObjectStatus::Unwrapped,
+        "unwrapped_then_deleted" => ObjectStatus::UnwrappedThenDeleted,
+        _ => {
+            return Err(IndexerError::SerdeError(format!(
+                "Unknown object status: {label}"
+            )))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_round_trip() {
+        let object = Object {
+            epoch: 1,
+            checkpoint: 2,
+            object_id: "0x5".to_string(),
+            version: 3,
+            object_digest: "digest".to_string(),
+            owner_type: OwnerType::Shared,
+            owner_address: None,
+            initial_shared_version: Some(1),
+            previous_transaction: "tx".to_string(),
+            object_type: "0x2::clock::Clock".to_string(),
+            object_status: ObjectStatus::UnwrappedThenDeleted,
+            has_public_transfer: false,
+            storage_rebate: 10,
+            bcs: vec![NamedBcsBytes("object".to_string(), vec![1, 2, 3])],
+        };
+        let row = SqliteObject::try_from(&object).unwrap();
+        assert_eq!(row.owner_type, "shared");
+        assert_eq!(row.object_status, "unwrapped_then_deleted");
+
+        let decoded = Object::try_from(row).unwrap();
+        assert!(matches!(decoded.owner_type, OwnerType::Shared));
+        assert!(matches!(
+            decoded.object_status,
+            ObjectStatus::UnwrappedThenDeleted
+        ));
+        assert_eq!(decoded.bcs[0].0, "object");
+        assert_eq!(decoded.bcs[0].1, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_json_array_round_trip() {
+        let values = vec![Some("0x1".to_string()), None];
+        let json = to_json_array(&values).unwrap();
+        assert_eq!(json, r#"["0x1",null]"#);
+        assert_eq!(from_json_array::<Option<String>>(&json).unwrap(), values);
+    }
+}
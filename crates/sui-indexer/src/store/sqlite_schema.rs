@@ -0,0 +1,149 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tables of the SQLite schema whose column types differ from the PostgreSQL schema in
+//! `crate::schema`, see `sqlite_migrations`. The other tables only use types supported by both
+//! backends and are shared.
+
+diesel::table! {
+    checkpoints (sequence_number) {
+        sequence_number -> BigInt,
+        checkpoint_digest -> Text,
+        epoch -> BigInt,
+        transactions -> Text,
+        previous_checkpoint_digest -> Nullable<Text>,
+        next_epoch_committee -> Nullable<Text>,
+        next_epoch_protocol_version -> Nullable<BigInt>,
+        end_of_epoch_data -> Nullable<Text>,
+        total_gas_cost -> BigInt,
+        total_computation_cost -> BigInt,
+        total_storage_cost -> BigInt,
+        total_storage_rebate -> BigInt,
+        total_transactions -> BigInt,
+        total_transactions_current_epoch -> BigInt,
+        total_transactions_from_genesis -> BigInt,
+        timestamp_ms -> BigInt,
+        timestamp_ms_str -> Timestamp,
+        checkpoint_tps -> Float,
+    }
+}
+
+diesel::table! {
+    events (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        event_sequence -> BigInt,
+        sender -> Text,
+        package -> Text,
+        module -> Text,
+        event_type -> Text,
+        event_time_ms -> Nullable<BigInt>,
+        parsed_json -> Text,
+        event_bcs -> Binary,
+    }
+}
+
+diesel::table! {
+    objects (object_id) {
+        epoch -> BigInt,
+        checkpoint -> BigInt,
+        object_id -> Text,
+        version -> BigInt,
+        object_digest -> Text,
+        owner_type -> Text,
+        owner_address -> Nullable<Text>,
+        initial_shared_version -> Nullable<BigInt>,
+        previous_transaction -> Text,
+        object_type -> Text,
+        object_status -> Text,
+        has_public_transfer -> Bool,
+        storage_rebate -> BigInt,
+        bcs -> Binary,
+    }
+}
+
+diesel::table! {
+    objects_history (epoch, object_id, version) {
+        epoch -> BigInt,
+        checkpoint -> BigInt,
+        object_id -> Text,
+        version -> BigInt,
+        object_digest -> Text,
+        owner_type -> Text,
+        owner_address -> Nullable<Text>,
+        initial_shared_version -> Nullable<BigInt>,
+        previous_transaction -> Text,
+        object_type -> Text,
+        object_status -> Text,
+        has_public_transfer -> Bool,
+        storage_rebate -> BigInt,
+        bcs -> Binary,
+    }
+}
+
+diesel::table! {
+    packages (package_id, version) {
+        package_id -> Text,
+        version -> BigInt,
+        author -> Text,
+        data -> Binary,
+    }
+}
+
+diesel::table! {
+    system_states (epoch) {
+        epoch -> BigInt,
+        protocol_version -> BigInt,
+        system_state_version -> BigInt,
+        storage_fund -> BigInt,
+        reference_gas_price -> BigInt,
+        safe_mode -> Bool,
+        epoch_start_timestamp_ms -> BigInt,
+        governance_start_epoch -> BigInt,
+        epoch_duration_ms -> BigInt,
+        stake_subsidy_epoch_counter -> BigInt,
+        stake_subsidy_balance -> BigInt,
+        stake_subsidy_current_epoch_amount -> BigInt,
+        total_stake -> BigInt,
+        pending_active_validators_id -> Text,
+        pending_active_validators_size -> BigInt,
+        pending_removals -> Text,
+        staking_pool_mappings_id -> Text,
+        staking_pool_mappings_size -> BigInt,
+        inactive_pools_id -> Text,
+        inactive_pools_size -> BigInt,
+        validator_candidates_id -> Text,
+        validator_candidates_size -> BigInt,
+    }
+}
+
+diesel::table! {
+    transactions (id) {
+        id -> BigInt,
+        transaction_digest -> Text,
+        sender -> Text,
+        recipients -> Text,
+        checkpoint_sequence_number -> BigInt,
+        timestamp_ms -> BigInt,
+        transaction_kind -> Text,
+        created -> Text,
+        mutated -> Text,
+        deleted -> Text,
+        unwrapped -> Text,
+        wrapped -> Text,
+        move_calls -> Text,
+        gas_object_id -> Text,
+        gas_object_sequence -> BigInt,
+        gas_object_digest -> Text,
+        gas_budget -> BigInt,
+        total_gas_cost -> BigInt,
+        computation_cost -> BigInt,
+        storage_cost -> BigInt,
+        storage_rebate -> BigInt,
+        gas_price -> BigInt,
+        raw_transaction -> Binary,
+        transaction_content -> Text,
+        transaction_effects_content -> Text,
+        confirmed_local_execution -> Nullable<Bool>,
+    }
+}
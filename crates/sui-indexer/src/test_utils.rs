@@ -1,14 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use anyhow::anyhow;
+use diesel::SqliteConnection;
 use prometheus::Registry;
 use tokio::task::JoinHandle;
 
 use crate::errors::IndexerError;
-use crate::store::PgIndexerStore;
-use crate::utils::reset_database;
-use crate::{new_pg_connection_pool, Indexer, IndexerConfig};
+use crate::processors::processor::Processor;
+use crate::store::{PgIndexerStore, SqliteIndexerStore};
+use crate::utils::{reset_database, reset_sqlite_database};
+use crate::{new_pg_connection_pool, new_sqlite_connection_pool, Indexer, IndexerConfig};
 
 /// Spawns an indexer thread with provided Postgres DB url
 pub async fn start_test_indexer(
@@ -32,3 +36,37 @@ pub async fn start_test_indexer(
     let handle = tokio::spawn(async move { Indexer::start(&config, &registry, store_clone).await });
     Ok((store, handle))
 }
+
+/// Spawns an indexer thread with a SQLite database at the path of the `sqlite://` DB url, reset
+/// if `reset_db` is set
+pub async fn start_test_sqlite_indexer(
+    config: IndexerConfig,
+) -> Result<(SqliteIndexerStore, JoinHandle<Result<(), IndexerError>>), anyhow::Error> {
+    start_test_sqlite_indexer_with_processors(config, vec![]).await
+}
+
+/// Same as [start_test_sqlite_indexer], and runs the custom processors on every checkpoint
+pub async fn start_test_sqlite_indexer_with_processors(
+    config: IndexerConfig,
+    processors: Vec<Arc<dyn Processor<SqliteConnection>>>,
+) -> Result<(SqliteIndexerStore, JoinHandle<Result<(), IndexerError>>), anyhow::Error> {
+    let db_path = config
+        .sqlite_db_path()
+        .ok_or_else(|| anyhow!("{} is not a SQLite DB url", config.db_url))?;
+    let sqlite_connection_pool = new_sqlite_connection_pool(db_path).await?;
+    if config.reset_db {
+        reset_sqlite_database(
+            &mut sqlite_connection_pool
+                .get()
+                .map_err(|e| anyhow!("Fail to get sqlite_connection_pool {e}"))?,
+        )?;
+    }
+    let store = SqliteIndexerStore::new(sqlite_connection_pool);
+
+    let registry = Registry::default();
+    let store_clone = store.clone();
+    let handle = tokio::spawn(async move {
+        Indexer::start_with_processors(&config, &registry, store_clone, processors).await
+    });
+    Ok((store, handle))
+}
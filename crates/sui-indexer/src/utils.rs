@@ -3,7 +3,7 @@
 
 use anyhow::anyhow;
 use diesel::migration::MigrationSource;
-use diesel::{PgConnection, RunQueryDsl};
+use diesel::{PgConnection, RunQueryDsl, SqliteConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use tracing::info;
 
@@ -16,6 +16,7 @@ use crate::types::SuiTransactionFullResponse;
 use crate::PgPoolConnection;
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+const SQLITE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("sqlite_migrations");
 
 /// Resets the database by reverting all migrations and reapplying them.
 ///
@@ -66,6 +67,31 @@ pub fn drop_all_tables(conn: &mut PgConnection) -> Result<(), diesel::result::Er
     Ok(())
 }
 
+/// Runs the pending migrations of the SQLite schema, creating the database on first use.
+pub fn run_sqlite_migrations(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    conn.run_pending_migrations(SQLITE_MIGRATIONS)
+        .map_err(|e| anyhow!("Failed to run SQLite migrations {e}"))?;
+    Ok(())
+}
+
+/// Resets the SQLite database by dropping all tables and reapplying the migrations.
+pub fn reset_sqlite_database(conn: &mut SqliteConnection) -> Result<(), anyhow::Error> {
+    info!("Resetting SQLite database ...");
+    let table_names: Vec<String> = diesel::dsl::sql::<diesel::sql_types::Text>(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+    )
+    .load(conn)
+    .map_err(|e| anyhow!("Encountering error when listing all tables {e}"))?;
+    for table_name in table_names {
+        diesel::sql_query(format!("DROP TABLE IF EXISTS {}", table_name))
+            .execute(conn)
+            .map_err(|e| anyhow!("Encountering error when dropping table {table_name} {e}"))?;
+    }
+    run_sqlite_migrations(conn)?;
+    info!("Reset SQLite database complete.");
+    Ok(())
+}
+
 pub async fn multi_get_full_transactions(
     read_api: &SuiReadApi,
     digests: Vec<TransactionDigest>,
@@ -96,7 +96,7 @@ pub mod pg_integration_test {
         gas: Option<ObjectID>,
     ) -> Result<SuiTransactionResponse, anyhow::Error> {
         let transaction_bytes: TransactionBytes = indexer_rpc_client
-            .transfer_object(*sender, object_id, gas, 2000, *recipient)
+            .transfer_object(*sender, object_id, gas, 2000, *recipient, None)
             .await?;
         let tx_response = sign_and_execute_transaction(
             test_cluster,
@@ -601,6 +601,7 @@ pub mod pg_integration_test {
                 post_transfer_full_obj_data.object_id, // coin to merge and delete
                 None,
                 2000,
+                None,
             )
             .await?;
         let tx_response = sign_and_execute_transaction(
@@ -778,3 +779,230 @@ pub mod pg_integration_test {
         EventFilter::MoveEventType(StructTag::from_str(event_type).unwrap())
     }
 }
+
+// integration test with a SQLite database file, which needs no database server
+pub mod sqlite_integration_test {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use diesel::sql_types::BigInt;
+    use diesel::{Connection, RunQueryDsl, SqliteConnection};
+    use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+    use tempfile::TempDir;
+    use tokio::task::JoinHandle;
+
+    use sui_config::utils::get_available_port;
+    use sui_indexer::errors::IndexerError;
+    use sui_indexer::processors::processor::Processor;
+    use sui_indexer::store::{CheckpointData, IndexerStore, SqliteIndexerStore};
+    use sui_indexer::test_utils::start_test_sqlite_indexer_with_processors;
+    use sui_indexer::IndexerConfig;
+    use sui_json_rpc::api::ReadApiClient;
+    use sui_json_rpc_types::SuiTransactionResponseOptions;
+    use sui_types::digests::TransactionDigest;
+    use sui_types::object::ObjectRead;
+    use sui_types::SUI_CLOCK_OBJECT_ID;
+    use test_utils::network::{TestCluster, TestClusterBuilder};
+
+    #[tokio::test]
+    async fn test_sqlite_genesis_sync() -> Result<(), anyhow::Error> {
+        let (_dir, _test_cluster, indexer_rpc_client, store, _handle) = start_test_cluster().await;
+        wait_until_next_checkpoint(&store).await;
+
+        let checkpoint = store.get_checkpoint(0.into())?;
+        assert!(!checkpoint.transactions.is_empty());
+        for tx in checkpoint.transactions {
+            let tx = tx.unwrap();
+            let transaction = store.get_transaction_by_digest(&tx)?;
+            assert_eq!(transaction.checkpoint_sequence_number, 0);
+            let tx_digest = TransactionDigest::from_str(&tx)?;
+            indexer_rpc_client
+                .get_transaction_with_options(tx_digest, Some(SuiTransactionResponseOptions::new()))
+                .await?;
+        }
+
+        assert_eq!(store.get_current_epoch()?.epoch, 0);
+        // the clock object is created at genesis
+        assert!(matches!(
+            store.get_object(SUI_CLOCK_OBJECT_ID, None)?,
+            ObjectRead::Exists(..)
+        ));
+        let digests = store.get_transaction_digest_page_by_mutated_object(
+            SUI_CLOCK_OBJECT_ID.to_string(),
+            None,
+            10,
+            false,
+        )?;
+        assert!(!digests.is_empty());
+        Ok(())
+    }
+
+    /// Counts the transactions of every checkpoint into a custom table, named after the processor.
+    struct TransactionCountProcessor(&'static str);
+
+    impl Processor<SqliteConnection> for TransactionCountProcessor {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn initialize(&self, conn: &mut SqliteConnection) -> Result<(), IndexerError> {
+            diesel::sql_query(format!(
+                "CREATE TABLE IF NOT EXISTS {} \
+                (checkpoint BIGINT PRIMARY KEY, transaction_count BIGINT NOT NULL)",
+                self.0
+            ))
+            .execute(conn)?;
+            Ok(())
+        }
+
+        fn process_checkpoint(
+            &self,
+            conn: &mut SqliteConnection,
+            checkpoint: &CheckpointData,
+        ) -> Result<(), IndexerError> {
+            // Fails on a duplicate checkpoint, which must never be processed twice.
+            diesel::sql_query(format!(
+                "INSERT INTO {} VALUES ({}, {})",
+                self.0,
+                checkpoint.checkpoint.sequence_number,
+                checkpoint.transactions.len()
+            ))
+            .execute(conn)?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_custom_processor() -> Result<(), anyhow::Error> {
+        let (dir, _test_cluster, _indexer_rpc_client, store, _handle) =
+            start_test_cluster_with_processors(vec![Arc::new(TransactionCountProcessor(
+                "transaction_counts",
+            ))])
+            .await;
+        wait_until_processor_progress(&store, "transaction_counts", 0).await;
+
+        let checkpoint = store.get_checkpoint(0.into())?;
+        let mut conn =
+            SqliteConnection::establish(&dir.path().join("indexer.db").display().to_string())?;
+        let transaction_count: i64 = diesel::dsl::sql::<BigInt>(
+            "SELECT transaction_count FROM transaction_counts WHERE checkpoint = 0",
+        )
+        .get_result(&mut conn)?;
+        assert_eq!(transaction_count, checkpoint.transactions.len() as i64);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_processor_restart() -> Result<(), anyhow::Error> {
+        let (dir, test_cluster, _indexer_rpc_client, store, handle) =
+            start_test_cluster_with_processors(vec![Arc::new(TransactionCountProcessor(
+                "transaction_counts",
+            ))])
+            .await;
+        wait_until_processor_progress(&store, "transaction_counts", 1).await;
+        handle.abort();
+        assert!(handle.await.unwrap_err().is_cancelled());
+        let progress = store.get_processor_progress("transaction_counts")?.unwrap();
+
+        // Restart the indexer on the same database with a new processor, which makes the handler
+        // rewind to the genesis checkpoint. The checkpoints already processed by the first
+        // processor must not be processed again, which would fail on the duplicate rows.
+        let config = IndexerConfig {
+            db_url: format!("sqlite://{}", dir.path().join("indexer.db").display()),
+            rpc_client_url: test_cluster.rpc_url().to_string(),
+            rpc_server_port: get_available_port("127.0.0.1"),
+            migrated_methods: IndexerConfig::all_migrated_methods(),
+            reset_db: false,
+            ..Default::default()
+        };
+        let (store, handle) = start_test_sqlite_indexer_with_processors(
+            config,
+            vec![
+                Arc::new(TransactionCountProcessor("transaction_counts")),
+                Arc::new(TransactionCountProcessor("new_transaction_counts")),
+            ],
+        )
+        .await?;
+        let new_progress =
+            wait_until_processor_progress(&store, "new_transaction_counts", progress + 1).await;
+        assert!(!handle.is_finished());
+
+        let mut conn =
+            SqliteConnection::establish(&dir.path().join("indexer.db").display().to_string())?;
+        for table in ["transaction_counts", "new_transaction_counts"] {
+            let progress = store.get_processor_progress(table)?.unwrap();
+            assert!(progress >= new_progress);
+            // Every checkpoint up to the progress of the processor was processed exactly once.
+            let checkpoints: i64 = diesel::dsl::sql::<BigInt>(&format!(
+                "SELECT COUNT(*) FROM {table} WHERE checkpoint <= {progress}"
+            ))
+            .get_result(&mut conn)?;
+            assert_eq!(checkpoints, progress + 1);
+        }
+        Ok(())
+    }
+
+    async fn start_test_cluster() -> (
+        TempDir,
+        TestCluster,
+        HttpClient,
+        SqliteIndexerStore,
+        JoinHandle<Result<(), IndexerError>>,
+    ) {
+        start_test_cluster_with_processors(vec![]).await
+    }
+
+    async fn start_test_cluster_with_processors(
+        processors: Vec<Arc<dyn Processor<SqliteConnection>>>,
+    ) -> (
+        TempDir,
+        TestCluster,
+        HttpClient,
+        SqliteIndexerStore,
+        JoinHandle<Result<(), IndexerError>>,
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        let db_url = format!("sqlite://{}", dir.path().join("indexer.db").display());
+        let test_cluster = TestClusterBuilder::new().build().await.unwrap();
+
+        let config = IndexerConfig {
+            db_url,
+            rpc_client_url: test_cluster.rpc_url().to_string(),
+            migrated_methods: IndexerConfig::all_migrated_methods(),
+            reset_db: true,
+            ..Default::default()
+        };
+        let http_addr_port = format!(
+            "http://{}:{}",
+            config.rpc_server_url, config.rpc_server_port
+        );
+        let http_client = HttpClientBuilder::default().build(http_addr_port).unwrap();
+
+        let (store, handle) = start_test_sqlite_indexer_with_processors(config, processors)
+            .await
+            .unwrap();
+        (dir, test_cluster, http_client, store, handle)
+    }
+
+    async fn wait_until_next_checkpoint(store: &SqliteIndexerStore) {
+        let mut cp = store.get_latest_checkpoint_sequence_number().unwrap();
+        let target = cp + 1;
+        while cp < target {
+            tokio::task::yield_now().await;
+            cp = store.get_latest_checkpoint_sequence_number().unwrap();
+        }
+    }
+
+    async fn wait_until_processor_progress(
+        store: &SqliteIndexerStore,
+        processor_name: &str,
+        target: i64,
+    ) -> i64 {
+        loop {
+            match store.get_processor_progress(processor_name).unwrap() {
+                Some(progress) if progress >= target => return progress,
+                _ => tokio::task::yield_now().await,
+            }
+        }
+    }
+}
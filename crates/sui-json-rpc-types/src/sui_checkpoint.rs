@@ -13,7 +13,7 @@ use sui_types::messages_checkpoint::{
     CheckpointTimestamp, EndOfEpochData,
 };
 
-use crate::Page;
+use crate::{Page, SuiTransactionResponse};
 
 pub type CheckpointPage = Page<Checkpoint, CheckpointSequenceNumber>;
 
@@ -81,6 +81,18 @@ impl From<(CheckpointSummary, CheckpointContents)> for Checkpoint {
     }
 }
 
+/// A checkpoint emitted by the checkpoint subscription, together with the content of its
+/// transactions if requested.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointWithTransactions {
+    pub checkpoint: Checkpoint,
+    /// Transactions of the checkpoint in execution order, only present if transaction
+    /// options were specified in the subscription.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<Vec<SuiTransactionResponse>>,
+}
+
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum CheckpointId {
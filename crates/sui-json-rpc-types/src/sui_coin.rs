@@ -45,6 +45,16 @@ impl Coin {
     }
 }
 
+/// Strategy used to select coins covering a requested amount.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CoinSelectionStrategy {
+    /// Select the largest coins first, minimizing the number of coins used.
+    LargestFirst,
+    /// Select the smallest coins first, consolidating small coins ("dust") owned by the address.
+    FewestDust,
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SuiCoinMetadata {
@@ -917,12 +917,93 @@ pub enum SuiObjectDataFilter {
         #[serde_as(as = "DisplayFromStr")]
         module: Identifier,
     },
-    /// Query by type
+    /// Query by type, type parameters are ignored if not specified,
+    /// e.g. `0x2::coin::Coin` matches `0x2::coin::Coin<0x2::sui::SUI>`
     StructType(
         #[schemars(with = "String")]
         #[serde_as(as = "DisplayFromStr")]
         StructTag,
     ),
+    /// Query by struct name, matching every instantiation of the struct regardless of its
+    /// type parameters, e.g. all `0x2::coin::Coin<_>`.
+    StructName {
+        /// the Move package ID
+        package: ObjectID,
+        /// the module name
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        module: Identifier,
+        /// the struct name
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        name: Identifier,
+    },
+    /// Query by a set of object IDs.
+    ObjectIds(Vec<ObjectID>),
+    /// Query by object version, which increases every time the object is modified. Both bounds
+    /// are inclusive and optional.
+    VersionRange {
+        min: Option<SequenceNumber>,
+        max: Option<SequenceNumber>,
+    },
+    /// Match objects matching all of the filters.
+    MatchAll(Vec<SuiObjectDataFilter>),
+    /// Match objects matching any of the filters.
+    MatchAny(Vec<SuiObjectDataFilter>),
+    /// Match objects matching none of the filters.
+    MatchNone(Vec<SuiObjectDataFilter>),
+}
+
+impl SuiObjectDataFilter {
+    pub fn matches(&self, object: &ObjectInfo) -> bool {
+        match self {
+            SuiObjectDataFilter::Package(package) => match &object.type_ {
+                ObjectType::Struct(s) => ObjectID::from(s.address()) == *package,
+                ObjectType::Package => false,
+            },
+            SuiObjectDataFilter::MoveModule { package, module } => match &object.type_ {
+                ObjectType::Struct(s) => {
+                    ObjectID::from(s.address()) == *package && s.module() == module.as_ident_str()
+                }
+                ObjectType::Package => false,
+            },
+            SuiObjectDataFilter::StructType(struct_tag) => {
+                let obj_tag: StructTag = match object.type_.clone().try_into() {
+                    Ok(tag) => tag,
+                    Err(_) => return false,
+                };
+                // If people do not provide type_params, we will match all type_params
+                if !struct_tag.type_params.is_empty()
+                    && struct_tag.type_params != obj_tag.type_params
+                {
+                    return false;
+                }
+                obj_tag.address == struct_tag.address
+                    && obj_tag.module == struct_tag.module
+                    && obj_tag.name == struct_tag.name
+            }
+            SuiObjectDataFilter::StructName {
+                package,
+                module,
+                name,
+            } => match &object.type_ {
+                ObjectType::Struct(s) => {
+                    ObjectID::from(s.address()) == *package
+                        && s.module() == module.as_ident_str()
+                        && s.name() == name.as_ident_str()
+                }
+                ObjectType::Package => false,
+            },
+            SuiObjectDataFilter::ObjectIds(ids) => ids.contains(&object.object_id),
+            SuiObjectDataFilter::VersionRange { min, max } => {
+                min.map_or(true, |min| object.version >= min)
+                    && max.map_or(true, |max| object.version <= max)
+            }
+            SuiObjectDataFilter::MatchAll(filters) => filters.iter().all(|f| f.matches(object)),
+            SuiObjectDataFilter::MatchAny(filters) => filters.iter().any(|f| f.matches(object)),
+            SuiObjectDataFilter::MatchNone(filters) => !filters.iter().any(|f| f.matches(object)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Default)]
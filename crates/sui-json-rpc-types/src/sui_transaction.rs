@@ -38,7 +38,7 @@ use sui_types::signature::GenericSignature;
 
 use crate::balance_changes::BalanceChange;
 use crate::object_changes::ObjectChange;
-use crate::{Page, SuiEvent, SuiMovePackage, SuiObjectRef};
+use crate::{Filter, Page, SuiEvent, SuiMovePackage, SuiObjectRef};
 
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, PartialEq, Eq, Copy)]
@@ -234,6 +234,72 @@ impl SuiTransactionResponse {
     }
 }
 
+impl SuiTransactionResponse {
+    /// Objects created, mutated or unwrapped by this transaction, requires `effects` to be present.
+    fn changed_objects(&self) -> impl Iterator<Item = &OwnedObjectRef> {
+        self.effects.iter().flat_map(|effects| {
+            effects
+                .created()
+                .iter()
+                .chain(effects.mutated())
+                .chain(effects.unwrapped())
+        })
+    }
+}
+
+impl Filter<SuiTransactionResponse> for TransactionFilter {
+    fn matches(&self, item: &SuiTransactionResponse) -> bool {
+        match self {
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } => item.transaction.as_ref().map_or(false, |tx| {
+                tx.data.move_calls().iter().any(|call| {
+                    &call.package == package
+                        && module.as_ref().map_or(true, |m| m == &call.module)
+                        && function.as_ref().map_or(true, |f| f == &call.function)
+                })
+            }),
+            TransactionFilter::InputObject(object_id) => {
+                item.transaction.as_ref().map_or(false, |tx| {
+                    let gas = tx.data.gas_data().payment.iter().map(|o| &o.object_id);
+                    let packages = tx.data.move_calls().into_iter().map(|c| &c.package);
+                    let inputs = match tx.data.transaction() {
+                        SuiTransactionKind::ProgrammableTransaction(pt) => {
+                            pt.inputs.iter().filter_map(SuiCallArg::object).collect()
+                        }
+                        _ => vec![],
+                    };
+                    gas.chain(packages).chain(inputs).any(|id| id == object_id)
+                })
+            }
+            TransactionFilter::ChangedObject(object_id) => item
+                .changed_objects()
+                .any(|o| &o.reference.object_id == object_id),
+            TransactionFilter::FromAddress(address) => item
+                .transaction
+                .as_ref()
+                .map_or(false, |tx| tx.data.sender() == address),
+            TransactionFilter::ToAddress(address) => item
+                .changed_objects()
+                .any(|o| matches!(o.owner.get_owner_address(), Ok(addr) if &addr == address)),
+            TransactionFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            } => {
+                matches!(item.checkpoint, Some(c) if *start_checkpoint <= c && c < *end_checkpoint)
+            }
+            TransactionFilter::TimeRange {
+                start_time,
+                end_time,
+            } => matches!(item.timestamp_ms, Some(t) if *start_time <= t && t < *end_time),
+            TransactionFilter::And(f1, f2) => f1.matches(item) && f2.matches(item),
+            TransactionFilter::Or(f1, f2) => f1.matches(item) || f2.matches(item),
+        }
+    }
+}
+
 /// We are specifically ignoring events for now until events become more stable.
 impl PartialEq for SuiTransactionResponse {
     fn eq(&self, other: &Self) -> bool {
@@ -557,9 +623,16 @@ impl Display for SuiTransactionEffects {
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct DryRunTransactionResponse {
     pub effects: SuiTransactionEffects,
     pub events: SuiTransactionEvents,
+    /// Empty in the responses of servers predating the field.
+    #[serde(default)]
+    pub object_changes: Vec<ObjectChange>,
+    /// Empty in the responses of servers predating the field.
+    #[serde(default)]
+    pub balance_changes: Vec<BalanceChange>,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
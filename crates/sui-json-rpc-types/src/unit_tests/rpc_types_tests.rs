@@ -10,12 +10,18 @@ use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveValue};
 
 use sui_types::base_types::SequenceNumber;
-use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
+use sui_types::digests::{ObjectDigest, TransactionDigest};
 use sui_types::gas_coin::GasCoin;
-use sui_types::object::MoveObject;
+use sui_types::object::{MoveObject, Object};
+use sui_types::query::TransactionFilter;
 use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
-use crate::{SuiMoveStruct, SuiMoveValue};
+use crate::{
+    Filter, SuiCommand, SuiGasData, SuiMoveStruct, SuiMoveValue, SuiObjectDataFilter, SuiObjectRef,
+    SuiProgrammableMoveCall, SuiProgrammableTransaction, SuiTransaction, SuiTransactionData,
+    SuiTransactionDataV1, SuiTransactionKind, SuiTransactionResponse,
+};
 
 #[test]
 fn test_move_value_to_sui_coin() {
@@ -143,3 +149,106 @@ fn test_serde() {
         )
     }
 }
+
+#[test]
+fn test_transaction_filter_matches() {
+    let sender = SuiAddress::random_for_testing_only();
+    let package = ObjectID::random();
+    let gas = ObjectID::random();
+    let response = SuiTransactionResponse {
+        transaction: Some(SuiTransaction {
+            data: SuiTransactionData::V1(SuiTransactionDataV1 {
+                transaction: SuiTransactionKind::ProgrammableTransaction(
+                    SuiProgrammableTransaction {
+                        inputs: vec![],
+                        commands: vec![SuiCommand::MoveCall(Box::new(SuiProgrammableMoveCall {
+                            package,
+                            module: "market".to_string(),
+                            function: "trade".to_string(),
+                            type_arguments: vec![],
+                            arguments: vec![],
+                        }))],
+                    },
+                ),
+                sender,
+                gas_data: SuiGasData {
+                    payment: vec![SuiObjectRef {
+                        object_id: gas,
+                        version: SequenceNumber::new(),
+                        digest: ObjectDigest::random(),
+                    }],
+                    owner: sender,
+                    price: 1,
+                    budget: 1000,
+                },
+            }),
+            tx_signatures: vec![],
+        }),
+        ..SuiTransactionResponse::new(TransactionDigest::random())
+    };
+
+    assert!(TransactionFilter::FromAddress(sender).matches(&response));
+    assert!(
+        !TransactionFilter::FromAddress(SuiAddress::random_for_testing_only()).matches(&response)
+    );
+    assert!(TransactionFilter::MoveFunction {
+        package,
+        module: Some("market".to_string()),
+        function: None,
+    }
+    .matches(&response));
+    assert!(!TransactionFilter::MoveFunction {
+        package,
+        module: Some("market".to_string()),
+        function: Some("cancel".to_string()),
+    }
+    .matches(&response));
+    assert!(TransactionFilter::InputObject(gas).matches(&response));
+    assert!(TransactionFilter::InputObject(package).matches(&response));
+    // Effects are not present, no changed objects or recipients can be matched.
+    assert!(!TransactionFilter::ChangedObject(gas).matches(&response));
+    assert!(!TransactionFilter::ToAddress(sender).matches(&response));
+}
+
+#[test]
+fn test_object_data_filter_matches() {
+    let id = ObjectID::random();
+    let object = Object::with_id_owner_version_for_testing(
+        id,
+        SequenceNumber::from_u64(5),
+        SuiAddress::random_for_testing_only(),
+    );
+    let info = ObjectInfo::new(&object.compute_object_reference(), &object);
+
+    let coin = SuiObjectDataFilter::StructName {
+        package: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        module: Identifier::from_str("coin").unwrap(),
+        name: Identifier::from_str("Coin").unwrap(),
+    };
+    let sui_module = SuiObjectDataFilter::MoveModule {
+        package: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+        module: Identifier::from_str("sui").unwrap(),
+    };
+    assert!(coin.matches(&info));
+    assert!(!sui_module.matches(&info));
+    assert!(SuiObjectDataFilter::StructType(GasCoin::type_()).matches(&info));
+    assert!(SuiObjectDataFilter::ObjectIds(vec![ObjectID::random(), id]).matches(&info));
+    assert!(!SuiObjectDataFilter::ObjectIds(vec![]).matches(&info));
+
+    assert!(SuiObjectDataFilter::VersionRange {
+        min: Some(SequenceNumber::from_u64(5)),
+        max: None,
+    }
+    .matches(&info));
+    assert!(!SuiObjectDataFilter::VersionRange {
+        min: None,
+        max: Some(SequenceNumber::from_u64(4)),
+    }
+    .matches(&info));
+
+    assert!(SuiObjectDataFilter::MatchAll(vec![coin.clone()]).matches(&info));
+    assert!(!SuiObjectDataFilter::MatchAll(vec![coin.clone(), sui_module.clone()]).matches(&info));
+    assert!(SuiObjectDataFilter::MatchAny(vec![coin.clone(), sui_module.clone()]).matches(&info));
+    assert!(SuiObjectDataFilter::MatchNone(vec![sui_module]).matches(&info));
+    assert!(!SuiObjectDataFilter::MatchNone(vec![coin]).matches(&info));
+}
@@ -3,7 +3,9 @@
 
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
-use sui_json_rpc_types::{Balance, CoinPage, SuiCoinMetadata};
+use sui_json_rpc_types::{
+    Balance, CheckpointId, Coin, CoinPage, CoinSelectionStrategy, SuiCoinMetadata,
+};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -37,6 +39,24 @@ pub trait CoinReadApi {
         limit: Option<usize>,
     ) -> RpcResult<CoinPage>;
 
+    /// Return a set of coins of one coin type owned by the address owner, with a total balance
+    /// covering the requested amount. Locked coins are never selected. Only the first 1000 coins
+    /// of the owner are considered, an error is returned if they cannot cover the amount.
+    #[method(name = "selectCoins")]
+    async fn select_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// the amount the selected coins must cover
+        amount: u128,
+        /// optional list of coins that must not be selected
+        exclude: Option<Vec<ObjectID>>,
+        /// optional selection strategy, default to largest-first if not specified.
+        strategy: Option<CoinSelectionStrategy>,
+    ) -> RpcResult<Vec<Coin>>;
+
     /// Return the total coin balance for one coin type, owned by the address owner.
     #[method(name = "getBalance")]
     async fn get_balance(
@@ -45,6 +65,8 @@ pub trait CoinReadApi {
         owner: SuiAddress,
         /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
         coin_type: Option<String>,
+        /// optional checkpoint to query the balance at, default to the latest state if not specified. Fails if the owner has more than 10000 transactions since the checkpoint, or if the versions of its coins at the checkpoint have been pruned by the node.
+        at_checkpoint: Option<CheckpointId>,
     ) -> RpcResult<Balance>;
 
     /// Return the total coin balance for all coin type, owned by the address owner.
@@ -53,6 +75,8 @@ pub trait CoinReadApi {
         &self,
         /// the owner's Sui address
         owner: SuiAddress,
+        /// optional checkpoint to query the balances at, default to the latest state if not specified. Fails if the owner has more than 10000 transactions since the checkpoint, or if the versions of its coins at the checkpoint have been pruned by the node.
+        at_checkpoint: Option<CheckpointId>,
     ) -> RpcResult<Vec<Balance>>;
 
     /// Return metadata(e.g., symbol, decimals) for a coin
@@ -4,10 +4,11 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 
-use sui_json_rpc_types::{EventFilter, EventPage, SuiEvent};
+use sui_json_rpc_types::{EventFilter, EventPage, SuiEvent, SuiTransactionResponse};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::digests::TransactionDigest;
 use sui_types::event::EventID;
+use sui_types::query::TransactionFilter;
 
 #[open_rpc(namespace = "sui", tag = "Event Read API")]
 #[rpc(server, client, namespace = "sui")]
@@ -41,4 +42,12 @@ pub trait EventReadApi {
         /// the filter criteria of the event stream, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
         filter: EventFilter,
     );
+
+    /// Subscribe to a stream of Sui transactions, with input, effects and events of each transaction
+    #[subscription(name = "subscribeTransaction", item = SuiTransactionResponse)]
+    fn subscribe_transaction(
+        &self,
+        /// the filter criteria of the transaction stream. `CheckpointRange` filters are rejected, as transactions are streamed before they are checkpointed.
+        filter: TransactionFilter,
+    );
 }
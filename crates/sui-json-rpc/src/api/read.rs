@@ -5,11 +5,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use std::collections::BTreeMap;
 use sui_json_rpc_types::{
-    Checkpoint, CheckpointId, CheckpointPage, DynamicFieldPage, MoveFunctionArgType, ObjectsPage,
-    SuiGetPastObjectRequest, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
-    SuiMoveNormalizedStruct, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionResponse, SuiTransactionResponseOptions,
-    SuiTransactionResponseQuery, TransactionsPage,
+    Checkpoint, CheckpointId, CheckpointPage, CheckpointWithTransactions, DynamicFieldPage,
+    MoveFunctionArgType, ObjectsPage, SuiGetPastObjectRequest, SuiMoveNormalizedFunction,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiPastObjectResponse, SuiTransactionResponse,
+    SuiTransactionResponseOptions, SuiTransactionResponseQuery, TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{
@@ -224,4 +224,12 @@ pub trait ReadApi {
         /// query result ordering, default to false (ascending order), oldest record first.
         descending_order: bool,
     ) -> RpcResult<CheckpointPage>;
+
+    /// Subscribe to a stream of checkpoints synced by this node, in sequence number order
+    #[subscription(name = "subscribeCheckpoint", item = CheckpointWithTransactions)]
+    fn subscribe_checkpoint(
+        &self,
+        /// options for specifying the content of the checkpoint's transactions to be returned, transactions are not returned if not specified
+        options: Option<SuiTransactionResponseOptions>,
+    );
 }
@@ -25,12 +25,14 @@ pub trait TransactionBuilder {
         signer: SuiAddress,
         /// the ID of the object to be transferred
         object_id: ObjectID,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
         /// the recipient's Sui address
         recipient: SuiAddress,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to send SUI coin object to a Sui address. The SUI object is also used as the gas object.
@@ -64,10 +66,12 @@ pub trait TransactionBuilder {
         recipients: Vec<SuiAddress>,
         /// the amounts to be transferred to recipients, following the same order
         amounts: Vec<BigInt>,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Send SUI coins to a list of addresses, following a list of amounts.
@@ -130,12 +134,14 @@ pub trait TransactionBuilder {
         type_arguments: Vec<SuiTypeTag>,
         /// the arguments to be passed into the Move function, in [SuiJson](https://docs.sui.io/build/sui-json) format
         arguments: Vec<SuiJsonValue>,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
         /// Whether this is a Normal transaction or a Dev Inspect Transaction. Default to be `SuiTransactionBuilderMode::Commit` when it's None.
         execution_mode: Option<SuiTransactionBuilderMode>,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to publish Move module.
@@ -148,10 +154,12 @@ pub trait TransactionBuilder {
         compiled_modules: Vec<Base64>,
         /// a list of transitive dependency addresses that this set of modules depends on.
         dependencies: Vec<ObjectID>,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to split a coin object into multiple coins.
@@ -164,10 +172,12 @@ pub trait TransactionBuilder {
         coin_object_id: ObjectID,
         /// the amounts to split out from the coin
         split_amounts: Vec<u64>,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to split a coin object into multiple equal-size coins.
@@ -180,10 +190,12 @@ pub trait TransactionBuilder {
         coin_object_id: ObjectID,
         /// the number of coins to split into
         split_count: u64,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to merge multiple coins into one coin.
@@ -196,10 +208,12 @@ pub trait TransactionBuilder {
         primary_coin: ObjectID,
         /// the coin object to be merged, this coin will be destroyed, the balance will be added to `primary_coin`
         coin_to_merge: ObjectID,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned batched transaction.
@@ -210,12 +224,14 @@ pub trait TransactionBuilder {
         signer: SuiAddress,
         /// list of transaction request parameters
         single_transaction_params: Vec<RPCTransactionRequestParams>,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
         /// Whether this is a regular transaction or a Dev Inspect Transaction
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Add stake to a validator's staking pool using multiple coins and amount.
@@ -230,10 +246,12 @@ pub trait TransactionBuilder {
         amount: Option<u64>,
         /// the validator's Sui address
         validator: SuiAddress,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Withdraw stake from a validator's staking pool.
@@ -244,9 +262,11 @@ pub trait TransactionBuilder {
         signer: SuiAddress,
         /// StakedSui object ID
         staked_sui: ObjectID,
-        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        /// gas object to be used in this transaction, node will pick one from the signer's (or the sponsor's) possession if not provided
         gas: Option<ObjectID>,
         /// the gas budget, the transaction will fail if the gas cost exceed the budget
         gas_budget: u64,
+        /// the sponsor's Sui address, paying for the gas of the transaction with its own gas coin. The transaction must be signed by both the signer and the sponsor
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes>;
 }
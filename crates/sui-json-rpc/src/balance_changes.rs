@@ -171,6 +171,20 @@ impl<P> ObjectProviderCache<P> {
             provider,
         }
     }
+
+    /// A cache pre-populated with `objects`, for objects which the provider does not know about,
+    /// e.g. the objects read and written by a dry run.
+    pub fn new_with_cache(provider: P, objects: impl IntoIterator<Item = Object>) -> Self {
+        let object_cache = objects
+            .into_iter()
+            .map(|o| ((o.id(), o.version()), o))
+            .collect();
+        Self {
+            object_cache: RwLock::new(object_cache),
+            last_version_cache: Default::default(),
+            provider,
+        }
+    }
 }
 
 #[async_trait]
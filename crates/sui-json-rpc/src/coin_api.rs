@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 use anyhow::anyhow;
@@ -12,22 +12,33 @@ use move_core_types::language_storage::{StructTag, TypeTag};
 use tracing::debug;
 
 use sui_core::authority::AuthorityState;
-use sui_json_rpc_types::{Balance, Coin as SuiCoin};
+use sui_json_rpc_types::{Balance, CheckpointId, Coin as SuiCoin, CoinSelectionStrategy};
 use sui_json_rpc_types::{CoinPage, SuiCoinMetadata};
 use sui_open_rpc::Module;
 use sui_types::balance::Supply;
-use sui_types::base_types::{MoveObjectType, ObjectID, ObjectRef, ObjectType, SuiAddress};
+use sui_types::base_types::{
+    MoveObjectType, ObjectDigest, ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress,
+};
 use sui_types::coin::{Coin, CoinMetadata, LockedCoin, TreasuryCap};
 use sui_types::error::SuiError;
 use sui_types::gas_coin::GAS;
 use sui_types::messages::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::{Object, Owner};
 use sui_types::parse_sui_struct_tag;
+use sui_types::query::TransactionFilter;
 
-use crate::api::{cap_page_limit, CoinReadApiServer};
+use crate::api::{cap_page_limit, CoinReadApiServer, QUERY_MAX_RESULT_LIMIT};
 use crate::error::Error;
 use crate::SuiRpcModule;
 
+/// Maximum number of transactions of an owner since a checkpoint read to reconstruct its coins at
+/// that checkpoint.
+const MAX_TRANSACTIONS_SINCE_CHECKPOINT: usize = 10 * QUERY_MAX_RESULT_LIMIT;
+
+/// Maximum number of coins of an owner considered by `select_coins`.
+pub const SELECT_COINS_MAX_SCANNED: usize = 1000;
+
 pub struct CoinReadApi {
     state: Arc<AuthorityState>,
 }
@@ -47,42 +58,7 @@ impl CoinReadApi {
     }
 
     async fn get_coin(&self, coin_id: &ObjectID) -> Result<SuiCoin, Error> {
-        let o = self.get_object(coin_id).await?;
-        if let Some(move_object) = o.data.try_as_move() {
-            let (balance, locked_until_epoch) = if move_object.type_().is_coin() {
-                let coin: Coin = bcs::from_bytes(move_object.contents())?;
-                (coin.balance.value(), None)
-            } else if move_object.type_().is_locked_coin() {
-                let locked_coin: LockedCoin = bcs::from_bytes(move_object.contents())?;
-                (
-                    locked_coin.balance.value(),
-                    Some(locked_coin.locked_until_epoch),
-                )
-            } else {
-                return Err(Error::SuiError(SuiError::ObjectDeserializationError {
-                    error: format!("{:?} is not a supported coin type", move_object.type_()),
-                }));
-            };
-
-            Ok(SuiCoin {
-                coin_type: move_object
-                    .type_()
-                    .type_params()
-                    .first()
-                    .unwrap()
-                    .to_string(),
-                coin_object_id: o.id(),
-                version: o.version(),
-                digest: o.digest(),
-                balance,
-                locked_until_epoch,
-                previous_transaction: o.previous_transaction,
-            })
-        } else {
-            Err(Error::UnexpectedError(format!(
-                "Provided object : [{coin_id}] is not a Move object."
-            )))
-        }
+        to_sui_coin(self.get_object(coin_id).await?)
     }
 
     async fn get_coins_internal(
@@ -130,6 +106,164 @@ impl CoinReadApi {
             .map(|info|info.object_id))
     }
 
+    async fn get_owner_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: &Option<StructTag>,
+        at_checkpoint: Option<CheckpointId>,
+    ) -> Result<Vec<SuiCoin>, Error> {
+        if let Some(checkpoint) = at_checkpoint {
+            return self
+                .get_owner_coins_at_checkpoint(owner, coin_type, checkpoint)
+                .await;
+        }
+        // TODO: Add index to improve performance?
+        let mut coins = vec![];
+        for coin in self.get_owner_coin_iterator(owner, coin_type)? {
+            coins.push(self.get_coin(&coin).await?);
+        }
+        Ok(coins)
+    }
+
+    /// Reconstruct the coins owned by `owner` at the end of `checkpoint`, by reading each coin the
+    /// owner owns or has transacted with since the checkpoint at its last version finalized in or
+    /// before the checkpoint.
+    ///
+    /// Fails if the owner has more than `MAX_TRANSACTIONS_SINCE_CHECKPOINT` transactions since the
+    /// checkpoint, or if the version of one of its coins at the checkpoint has been pruned.
+    async fn get_owner_coins_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: &Option<StructTag>,
+        checkpoint: CheckpointId,
+    ) -> Result<Vec<SuiCoin>, Error> {
+        let checkpoint = match checkpoint {
+            CheckpointId::SequenceNumber(seq) => seq,
+            CheckpointId::Digest(digest) => {
+                self.state
+                    .get_checkpoint_summary_by_digest(digest)?
+                    .sequence_number
+            }
+        };
+        let latest_checkpoint = self.state.get_latest_checkpoint_sequence_number()?;
+        if checkpoint > latest_checkpoint {
+            return Err(anyhow!(
+                "Checkpoint {checkpoint} has not been executed, latest executed checkpoint is {latest_checkpoint}"
+            )
+            .into());
+        }
+
+        // A coin owned at the checkpoint is either still owned, or was changed since by a
+        // transaction sent by the owner, or paid for with the owner's gas. Collect the objects of
+        // all of these, walking back from the latest transaction of the owner to the checkpoint.
+        let mut candidates = self
+            .state
+            .get_owner_objects_iterator(owner, None, None, None)?
+            .filter(
+                |o| matches!(&o.type_, ObjectType::Struct(type_) if is_coin_type(type_, coin_type)),
+            )
+            .map(|o| o.object_id)
+            .collect::<BTreeSet<_>>();
+        let filter = TransactionFilter::FromAddress(owner).or(TransactionFilter::ToAddress(owner));
+        let mut cursor = None;
+        let mut scanned = 0;
+        loop {
+            let digests = self.state.get_transactions(
+                Some(filter.clone()),
+                cursor,
+                Some(QUERY_MAX_RESULT_LIMIT),
+                true,
+            )?;
+            let finalized = self
+                .state
+                .multi_get_transaction_checkpoint(&digests)
+                .await?;
+            let effects = self.state.multi_get_executed_effects(&digests).await?;
+            let mut past_checkpoint = false;
+            for ((digest, finalized), effects) in digests.iter().zip(finalized).zip(effects) {
+                if matches!(finalized, Some((_, seq)) if seq <= checkpoint) {
+                    continue;
+                }
+                past_checkpoint = true;
+                let effects = effects.ok_or(SuiError::TransactionNotFound { digest: *digest })?;
+                candidates.extend(
+                    effects
+                        .all_changed_objects()
+                        .into_iter()
+                        .map(|(r, _, _)| r.0),
+                );
+                candidates.extend(effects.all_deleted().into_iter().map(|(r, _)| r.0));
+            }
+            // Transactions are indexed in execution order, which only roughly follows checkpoint
+            // order: stop after a whole page of transactions finalized before the checkpoint.
+            if !past_checkpoint || digests.len() < QUERY_MAX_RESULT_LIMIT {
+                break;
+            }
+            scanned += digests.len();
+            if scanned >= MAX_TRANSACTIONS_SINCE_CHECKPOINT {
+                return Err(anyhow!(
+                    "Address [{owner}] has more than {MAX_TRANSACTIONS_SINCE_CHECKPOINT} transactions since checkpoint {checkpoint}, query a more recent checkpoint"
+                )
+                .into());
+            }
+            cursor = digests.last().cloned();
+        }
+
+        let mut coins = vec![];
+        for id in candidates {
+            let Some((version, digest)) = self.object_version_at_checkpoint(id, checkpoint).await?
+            else {
+                continue;
+            };
+            if !digest.is_alive() {
+                continue;
+            }
+            let object = self
+                .state
+                .get_past_object_read(&id, version)
+                .await?
+                .into_object()
+                .map_err(SuiError::from)?;
+            if object.owner == Owner::AddressOwner(owner)
+                && matches!(object.type_(), Some(type_) if is_coin_type(type_, coin_type))
+            {
+                coins.push(to_sui_coin(object)?);
+            }
+        }
+        Ok(coins)
+    }
+
+    /// The high-water mark of object `id` at `checkpoint`: its last version written by a
+    /// transaction finalized in or before the checkpoint, if any.
+    async fn object_version_at_checkpoint(
+        &self,
+        id: ObjectID,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<Option<(SequenceNumber, ObjectDigest)>, Error> {
+        let versions = self
+            .state
+            .get_parent_iterator(id, None)
+            .await?
+            .collect::<Vec<_>>();
+        // The versions of an object are finalized in checkpoint order, binary search for the
+        // first version past the checkpoint.
+        let (mut low, mut high) = (0, versions.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let finalized = self
+                .state
+                .get_transaction_checkpoint_sequence(&versions[mid].1)?;
+            if matches!(finalized, Some((_, seq)) if seq <= checkpoint) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low
+            .checked_sub(1)
+            .map(|i| (versions[i].0 .1, versions[i].0 .2)))
+    }
+
     async fn find_package_object(
         &self,
         package_id: &ObjectID,
@@ -202,24 +336,73 @@ impl CoinReadApiServer for CoinReadApi {
         Ok(self.get_coins_internal(owner, None, cursor, limit).await?)
     }
 
+    async fn select_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        amount: u128,
+        exclude: Option<Vec<ObjectID>>,
+        strategy: Option<CoinSelectionStrategy>,
+    ) -> RpcResult<Vec<SuiCoin>> {
+        let coin_type = Some(match coin_type {
+            Some(c) => parse_sui_struct_tag(&c)?,
+            None => GAS::type_(),
+        });
+        let exclude = exclude.unwrap_or_default();
+
+        // Read at most `SELECT_COINS_MAX_SCANNED` coins, so that owners with many small coins
+        // cannot make a single call read all of them.
+        let mut coin_ids = self
+            .get_owner_coin_iterator(owner, &coin_type)?
+            .filter(|coin| !exclude.contains(coin));
+        let mut coins = vec![];
+        let mut total_balance = 0u128;
+        for coin in coin_ids.by_ref().take(SELECT_COINS_MAX_SCANNED) {
+            let coin = self.get_coin(&coin).await?;
+            if coin.locked_until_epoch.is_none() {
+                total_balance += coin.balance as u128;
+                coins.push(coin);
+            }
+        }
+
+        if total_balance < amount {
+            return Err(if coin_ids.next().is_some() {
+                anyhow!(
+                    "Cannot cover requested amount {amount} with the first {SELECT_COINS_MAX_SCANNED} coins of address [{owner}], available balance: {total_balance}, merge its coins first"
+                )
+            } else {
+                anyhow!(
+                    "Insufficient fund for address [{owner}], requested amount: {amount}, available balance: {total_balance}"
+                )
+            }
+            .into());
+        }
+        Ok(select_coins(
+            coins,
+            amount,
+            strategy.unwrap_or(CoinSelectionStrategy::LargestFirst),
+        ))
+    }
+
     async fn get_balance(
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
+        at_checkpoint: Option<CheckpointId>,
     ) -> RpcResult<Balance> {
         let coin_type = Some(match coin_type {
             Some(c) => parse_sui_struct_tag(&c)?,
             None => GAS::type_(),
         });
 
-        // TODO: Add index to improve performance?
-        let coins = self.get_owner_coin_iterator(owner, &coin_type)?;
+        let coins = self
+            .get_owner_coins(owner, &coin_type, at_checkpoint)
+            .await?;
         let mut total_balance = 0u128;
         let mut locked_balance = HashMap::new();
         let mut coin_object_count = 0;
 
         for coin in coins {
-            let coin = self.get_coin(&coin).await?;
             if let Some(lock) = coin.locked_until_epoch {
                 *locked_balance.entry(lock).or_default() += coin.balance as u128
             } else {
@@ -236,13 +419,15 @@ impl CoinReadApiServer for CoinReadApi {
         })
     }
 
-    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
-        // TODO: Add index to improve performance?
-        let coins = self.get_owner_coin_iterator(owner, &None)?;
+    async fn get_all_balances(
+        &self,
+        owner: SuiAddress,
+        at_checkpoint: Option<CheckpointId>,
+    ) -> RpcResult<Vec<Balance>> {
+        let coins = self.get_owner_coins(owner, &None, at_checkpoint).await?;
         let mut balances: HashMap<String, Balance> = HashMap::new();
 
         for coin in coins {
-            let coin = self.get_coin(&coin).await?;
             let balance = balances.entry(coin.coin_type.clone()).or_insert(Balance {
                 coin_type: coin.coin_type,
                 coin_object_count: 0,
@@ -321,3 +506,64 @@ fn is_coin_type(type_: &MoveObjectType, coin_type: &Option<StructTag>) -> bool {
     }
     false
 }
+
+/// Select coins covering `amount` according to `strategy`, the total balance of `coins` must be
+/// at least `amount`.
+fn select_coins(
+    mut coins: Vec<SuiCoin>,
+    amount: u128,
+    strategy: CoinSelectionStrategy,
+) -> Vec<SuiCoin> {
+    match strategy {
+        CoinSelectionStrategy::LargestFirst => coins.sort_by(|a, b| b.balance.cmp(&a.balance)),
+        CoinSelectionStrategy::FewestDust => coins.sort_by_key(|coin| coin.balance),
+    }
+    let mut total = 0u128;
+    coins
+        .into_iter()
+        .take_while(|coin| {
+            let take = total < amount;
+            total += coin.balance as u128;
+            take
+        })
+        .collect()
+}
+
+fn to_sui_coin(o: Object) -> Result<SuiCoin, Error> {
+    if let Some(move_object) = o.data.try_as_move() {
+        let (balance, locked_until_epoch) = if move_object.type_().is_coin() {
+            let coin: Coin = bcs::from_bytes(move_object.contents())?;
+            (coin.balance.value(), None)
+        } else if move_object.type_().is_locked_coin() {
+            let locked_coin: LockedCoin = bcs::from_bytes(move_object.contents())?;
+            (
+                locked_coin.balance.value(),
+                Some(locked_coin.locked_until_epoch),
+            )
+        } else {
+            return Err(Error::SuiError(SuiError::ObjectDeserializationError {
+                error: format!("{:?} is not a supported coin type", move_object.type_()),
+            }));
+        };
+
+        Ok(SuiCoin {
+            coin_type: move_object
+                .type_()
+                .type_params()
+                .first()
+                .unwrap()
+                .to_string(),
+            coin_object_id: o.id(),
+            version: o.version(),
+            digest: o.digest(),
+            balance,
+            locked_until_epoch,
+            previous_transaction: o.previous_transaction,
+        })
+    } else {
+        Err(Error::UnexpectedError(format!(
+            "Provided object : [{}] is not a Move object.",
+            o.id()
+        )))
+    }
+}
@@ -3,10 +3,12 @@
 
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::Stream;
 use jsonrpsee::core::error::SubscriptionClosed;
 use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee::{RpcModule, SubscriptionSink};
 use serde::Serialize;
@@ -19,6 +21,7 @@ use sui_open_rpc::Module;
 use sui_types::digests::TransactionDigest;
 use sui_types::event::EventID;
 use sui_types::messages::TransactionEffectsAPI;
+use sui_types::query::TransactionFilter;
 
 use crate::api::cap_page_limit;
 use crate::api::EventReadApiServer;
@@ -119,6 +122,25 @@ impl EventReadApiServer for EventReadApi {
         spawn_subscription(sink, self.state.event_handler.subscribe(filter));
         Ok(())
     }
+
+    fn subscribe_transaction(
+        &self,
+        mut sink: SubscriptionSink,
+        filter: TransactionFilter,
+    ) -> SubscriptionResult {
+        if filter.uses_checkpoint() {
+            sink.reject(CallError::InvalidParams(anyhow!(
+                "CheckpointRange filters are not supported by subscriptions, transactions are \
+                 streamed before they are included in a checkpoint"
+            )))?;
+            return Ok(());
+        }
+        spawn_subscription(
+            sink,
+            self.state.event_handler.subscribe_transactions(filter),
+        );
+        Ok(())
+    }
 }
 
 impl SuiRpcModule for EventReadApi {
@@ -8,9 +8,11 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use fastcrypto::encoding::Base64;
 use futures::future::join_all;
+use futures::{future, stream, StreamExt};
 use itertools::Itertools;
 use jsonrpsee::core::RpcResult;
-use jsonrpsee::RpcModule;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
 use linked_hash_map::LinkedHashMap;
 use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_bytecode_utils::module_cache::GetModule;
@@ -18,18 +20,21 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::StructTag;
 use move_core_types::value::{MoveStruct, MoveStructLayout, MoveValue};
 use tap::TapFallible;
-use tracing::debug;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
 
 use shared_crypto::intent::{AppId, Intent, IntentMessage, IntentScope, IntentVersion};
-use sui_core::authority::AuthorityState;
+use sui_core::authority::{AuthorityState, EffectsNotifyRead};
 use sui_json_rpc_types::{
-    BalanceChange, Checkpoint, CheckpointId, CheckpointPage, DynamicFieldPage, MoveFunctionArgType,
-    ObjectChange, ObjectValueKind, ObjectsPage, Page, SuiGetPastObjectRequest,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiMoveStruct,
-    SuiMoveValue, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransaction, SuiTransactionEvents, SuiTransactionResponse,
-    SuiTransactionResponseOptions, SuiTransactionResponseQuery, TransactionsPage,
+    BalanceChange, Checkpoint, CheckpointId, CheckpointPage, CheckpointWithTransactions,
+    DynamicFieldPage, MoveFunctionArgType, ObjectChange, ObjectValueKind, ObjectsPage, Page,
+    SuiGetPastObjectRequest, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct, SuiMoveStruct, SuiMoveValue, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiPastObjectResponse, SuiTransaction, SuiTransactionEvents,
+    SuiTransactionResponse, SuiTransactionResponseOptions, SuiTransactionResponseQuery,
+    TransactionsPage,
 };
+use sui_network::state_sync;
 use sui_open_rpc::Module;
 use sui_types::base_types::{
     ObjectID, SequenceNumber, SuiAddress, TransactionDigest, TxSequenceNumber,
@@ -55,6 +60,7 @@ use crate::api::{
     MAX_GET_OWNED_OBJECT_LIMIT, QUERY_MAX_RESULT_LIMIT, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
 };
 use crate::error::Error;
+use crate::event_api::spawn_subscription;
 use crate::{
     get_balance_change_from_effect, get_object_change_from_effect, ObjectProviderCache,
     SuiRpcModule,
@@ -64,8 +70,10 @@ const MAX_DISPLAY_NESTED_LEVEL: usize = 10;
 
 // An implementation of the read portion of the JSON-RPC interface intended for use in
 // Fullnodes.
+#[derive(Clone)]
 pub struct ReadApi {
     pub state: Arc<AuthorityState>,
+    state_sync: state_sync::Handle,
 }
 
 // Internal data structure to make it easy to work with data returned from
@@ -94,8 +102,8 @@ impl IntermediateTransactionResponse {
 }
 
 impl ReadApi {
-    pub fn new(state: Arc<AuthorityState>) -> Self {
-        Self { state }
+    pub fn new(state: Arc<AuthorityState>, state_sync: state_sync::Handle) -> Self {
+        Self { state, state_sync }
     }
 
     fn get_checkpoint_internal(&self, id: CheckpointId) -> Result<Checkpoint, Error> {
@@ -112,6 +120,40 @@ impl ReadApi {
             }
         })
     }
+
+    async fn get_checkpoint_with_transactions(
+        &self,
+        seq: CheckpointSequenceNumber,
+        options: Option<SuiTransactionResponseOptions>,
+    ) -> RpcResult<CheckpointWithTransactions> {
+        let checkpoint = self.get_checkpoint_internal(seq.into())?;
+        let transactions = match options {
+            Some(options) => {
+                // Synced checkpoints are not necessarily executed yet, wait for their effects.
+                self.state
+                    .database
+                    .notify_read_executed_effects(checkpoint.transactions.clone())
+                    .await
+                    .map_err(Error::SuiError)?;
+                let mut transactions = Vec::with_capacity(checkpoint.transactions.len());
+                for digests in checkpoint.transactions.chunks(QUERY_MAX_RESULT_LIMIT) {
+                    transactions.extend(
+                        self.multi_get_transactions_with_options(
+                            digests.to_vec(),
+                            Some(options.clone()),
+                        )
+                        .await?,
+                    );
+                }
+                Some(transactions)
+            }
+            None => None,
+        };
+        Ok(CheckpointWithTransactions {
+            checkpoint,
+            transactions,
+        })
+    }
 }
 
 #[async_trait]
@@ -856,6 +898,60 @@ impl ReadApiServer for ReadApi {
             has_next_page,
         })
     }
+
+    fn subscribe_checkpoint(
+        &self,
+        sink: SubscriptionSink,
+        options: Option<SuiTransactionResponseOptions>,
+    ) -> SubscriptionResult {
+        let sequence_numbers = stream::unfold(
+            (
+                self.state_sync.subscribe_to_synced_checkpoints(),
+                None::<CheckpointSequenceNumber>,
+            ),
+            |(mut receiver, last_seq)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(checkpoint) => {
+                            let seq = *checkpoint.sequence_number();
+                            // Catch up on checkpoints skipped if this subscriber lagged behind,
+                            // they have already been persisted by state sync.
+                            let start = last_seq.map_or(seq, |last_seq| last_seq + 1);
+                            return Some((stream::iter(start..=seq), (receiver, Some(seq))));
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            debug!("Checkpoint subscriber lagged behind by {skipped} checkpoints")
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )
+        .flatten();
+
+        let read_api = self.clone();
+        let checkpoints = sequence_numbers
+            .then(move |seq| {
+                let read_api = read_api.clone();
+                let options = options.clone();
+                async move {
+                    read_api
+                        .get_checkpoint_with_transactions(seq, options)
+                        .await
+                }
+            })
+            // Close the subscription rather than skipping a checkpoint.
+            .take_while(|result| {
+                if let Err(e) = result {
+                    warn!(error = ?e, "Failed to read synced checkpoint, closing subscription.");
+                }
+                future::ready(result.is_ok())
+            })
+            .filter_map(|result| future::ready(result.ok()));
+
+        spawn_subscription(sink, Box::pin(checkpoints));
+        Ok(())
+    }
 }
 
 impl SuiRpcModule for ReadApi {
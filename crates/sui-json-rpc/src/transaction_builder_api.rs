@@ -118,9 +118,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         gas: Option<ObjectID>,
         gas_budget: u64,
         recipient: SuiAddress,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .builder
+            .with_sponsor(sponsor)
             .transfer_object(signer, object_id, gas, gas_budget, recipient)
             .await?;
         Ok(TransactionBytes::from_data(data)?)
@@ -149,9 +151,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         amounts: Vec<BigInt>,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .builder
+            .with_sponsor(sponsor)
             .pay(
                 signer,
                 input_coins,
@@ -206,6 +210,7 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         dependencies: Vec<ObjectID>,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let compiled_modules = compiled_modules
             .into_iter()
@@ -213,6 +218,7 @@ impl TransactionBuilderServer for TransactionBuilderApi {
             .collect::<Result<Vec<_>, _>>()?;
         let data = self
             .builder
+            .with_sponsor(sponsor)
             .publish(sender, compiled_modules, dependencies, gas, gas_budget)
             .await?;
         Ok(TransactionBytes::from_data(data)?)
@@ -225,9 +231,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .builder
+            .with_sponsor(sponsor)
             .split_coin(signer, coin_object_id, split_amounts, gas, gas_budget)
             .await?;
         Ok(TransactionBytes::from_data(data)?)
@@ -240,9 +248,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         split_count: u64,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .builder
+            .with_sponsor(sponsor)
             .split_coin_equal(signer, coin_object_id, split_count, gas, gas_budget)
             .await?;
         Ok(TransactionBytes::from_data(data)?)
@@ -255,9 +265,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let data = self
             .builder
+            .with_sponsor(sponsor)
             .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
             .await?;
         Ok(TransactionBytes::from_data(data)?)
@@ -274,11 +286,13 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         gas: Option<ObjectID>,
         gas_budget: u64,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let mode = txn_builder_mode.unwrap_or(SuiTransactionBuilderMode::Commit);
         let data: TransactionData = match mode {
             SuiTransactionBuilderMode::DevInspect => {
                 self.dev_inspect_builder
+                    .with_sponsor(sponsor)
                     .move_call(
                         signer,
                         package_object_id,
@@ -293,6 +307,7 @@ impl TransactionBuilderServer for TransactionBuilderApi {
             }
             SuiTransactionBuilderMode::Commit => {
                 self.builder
+                    .with_sponsor(sponsor)
                     .move_call(
                         signer,
                         package_object_id,
@@ -316,16 +331,19 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         gas: Option<ObjectID>,
         gas_budget: u64,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         let mode = txn_builder_mode.unwrap_or(SuiTransactionBuilderMode::Commit);
         let data = match mode {
             SuiTransactionBuilderMode::DevInspect => {
                 self.dev_inspect_builder
+                    .with_sponsor(sponsor)
                     .batch_transaction(signer, params, gas, gas_budget)
                     .await?
             }
             SuiTransactionBuilderMode::Commit => {
                 self.builder
+                    .with_sponsor(sponsor)
                     .batch_transaction(signer, params, gas, gas_budget)
                     .await?
             }
@@ -341,9 +359,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         validator: SuiAddress,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         Ok(TransactionBytes::from_data(
             self.builder
+                .with_sponsor(sponsor)
                 .request_add_stake(signer, coins, amount, validator, gas, gas_budget)
                 .await?,
         )?)
@@ -355,9 +375,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         staked_sui: ObjectID,
         gas: Option<ObjectID>,
         gas_budget: u64,
+        sponsor: Option<SuiAddress>,
     ) -> RpcResult<TransactionBytes> {
         Ok(TransactionBytes::from_data(
             self.builder
+                .with_sponsor(sponsor)
                 .request_withdraw_stake(signer, staked_sui, gas, gas_budget)
                 .await?,
         )?)
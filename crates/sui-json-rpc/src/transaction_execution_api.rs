@@ -143,6 +143,31 @@ impl TransactionExecutionApi {
             }
         }
     }
+
+    async fn dry_run_transaction(
+        &self,
+        tx_bytes: Base64,
+    ) -> Result<DryRunTransactionResponse, Error> {
+        let (txn_data, txn_digest) = get_transaction_data_and_digest(tx_bytes)?;
+        let sender = txn_data.sender();
+        let (mut response, inner_temp_store, effects) = self
+            .state
+            .dry_exec_transaction(txn_data, txn_digest)
+            .await?;
+        // The objects read and written by the dry run are not in the store, serve them from the
+        // cache when rendering the changes.
+        let objects = inner_temp_store.objects.into_values().chain(
+            inner_temp_store
+                .written
+                .into_values()
+                .map(|(_, object, _)| object),
+        );
+        let object_cache = ObjectProviderCache::new_with_cache(self.state.clone(), objects);
+        response.balance_changes = get_balance_change_from_effect(&object_cache, &effects).await?;
+        response.object_changes =
+            get_object_change_from_effect(&object_cache, sender, &effects).await?;
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -175,11 +200,7 @@ impl WriteApiServer for TransactionExecutionApi {
     }
 
     async fn dry_run_transaction(&self, tx_bytes: Base64) -> RpcResult<DryRunTransactionResponse> {
-        let (txn_data, txn_digest) = get_transaction_data_and_digest(tx_bytes)?;
-        Ok(self
-            .state
-            .dry_exec_transaction(txn_data, txn_digest)
-            .await?)
+        Ok(self.dry_run_transaction(tx_bytes).await?)
     }
 }
 
@@ -4,6 +4,9 @@
 use std::path::Path;
 #[cfg(not(msim))]
 use std::str::FromStr;
+use std::time::Duration;
+
+use futures::StreamExt;
 
 use crate::api::{
     CoinReadApiClient, GovernanceReadApiClient, ReadApiClient, TransactionBuilderClient,
@@ -15,9 +18,10 @@ use sui_json::{call_args, type_args};
 use sui_json_rpc_types::ObjectChange;
 use sui_json_rpc_types::ObjectsPage;
 use sui_json_rpc_types::{
-    Balance, CoinPage, DelegatedStake, StakeStatus, SuiCoinMetadata, SuiExecutionStatus,
-    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionEffectsAPI,
-    SuiTransactionResponse, SuiTransactionResponseOptions, TransactionBytes,
+    Balance, CheckpointWithTransactions, CoinPage, CoinSelectionStrategy, DelegatedStake,
+    StakeStatus, SuiCoinMetadata, SuiExecutionStatus, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiTransactionEffectsAPI, SuiTransactionResponse,
+    SuiTransactionResponseOptions, TransactionBytes,
 };
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
 use sui_macros::sim_test;
@@ -25,10 +29,15 @@ use sui_types::balance::Supply;
 use sui_types::base_types::ObjectID;
 use sui_types::coin::{TreasuryCap, COIN_MODULE_NAME, LOCKED_COIN_MODULE_NAME};
 use sui_types::gas_coin::GAS;
-use sui_types::messages::ExecuteTransactionRequestType;
-use sui_types::utils::to_sender_signed_transaction;
+use sui_types::messages::{ExecuteTransactionRequestType, TransactionDataAPI};
+use sui_types::object::Owner;
+use sui_types::utils::{
+    to_sender_signed_transaction, to_sender_signed_transaction_with_multi_signers,
+};
 use sui_types::{parse_sui_struct_tag, SUI_FRAMEWORK_ADDRESS};
 use test_utils::network::TestClusterBuilder;
+use test_utils::transaction::transfer_sui;
+use tokio::time::timeout;
 
 #[sim_test]
 async fn test_get_objects() -> Result<(), anyhow::Error> {
@@ -90,7 +99,7 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     let gas = objects.clone().last().unwrap().object().unwrap().object_id;
 
     let transaction_bytes: TransactionBytes = http_client
-        .transfer_object(*address, obj, Some(gas), 1000, *address)
+        .transfer_object(*address, obj, Some(gas), 1000, *address, None)
         .await?;
 
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -117,6 +126,58 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_sponsored_transfer_object() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let sender = cluster.accounts[0];
+    let sponsor = cluster.accounts[1];
+
+    let objects = http_client
+        .get_owned_objects(
+            sender,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new(),
+            )),
+            None,
+            None,
+            None,
+        )
+        .await?
+        .data;
+    let obj = objects.first().unwrap().object().unwrap().object_id;
+
+    // The gas is selected from the coins of the sponsor.
+    let transaction_bytes: TransactionBytes = http_client
+        .transfer_object(sender, obj, None, 1000, sender, Some(sponsor))
+        .await?;
+    let data = transaction_bytes.to_data()?;
+    assert_eq!(data.sender(), sender);
+    assert_eq!(data.gas_owner(), sponsor);
+
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction_with_multi_signers(
+        data,
+        vec![keystore.get_key(&sender)?, keystore.get_key(&sponsor)?],
+    );
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+
+    let tx_response: SuiTransactionResponse = http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new().with_effects()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let effects = tx_response.effects.unwrap();
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+    assert_eq!(effects.gas_object().owner, Owner::AddressOwner(sponsor));
+    Ok(())
+}
+
 #[sim_test]
 async fn test_publish() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await?;
@@ -152,6 +213,7 @@ async fn test_publish() -> Result<(), anyhow::Error> {
             dependencies,
             Some(gas.object_id),
             10000,
+            None,
         )
         .await?;
 
@@ -213,6 +275,7 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
             Some(gas.object_id),
             10_000,
             None,
+            None,
         )
         .await?;
 
@@ -351,7 +414,7 @@ async fn test_get_balance() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client();
     let address = cluster.accounts.first().unwrap();
 
-    let result: Balance = http_client.get_balance(*address, None).await?;
+    let result: Balance = http_client.get_balance(*address, None, None).await?;
     assert_eq!("0x2::sui::SUI", result.coin_type);
     assert_eq!(500000000000000, result.total_balance);
     assert_eq!(5, result.coin_object_count);
@@ -359,6 +422,154 @@ async fn test_get_balance() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_get_balance_at_checkpoint() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let sender = cluster.accounts[0];
+    let recipient = cluster.accounts[1];
+
+    let sender_balance: Balance = http_client.get_balance(sender, None, None).await?;
+    let recipient_balance: Balance = http_client.get_balance(recipient, None, None).await?;
+    let checkpoint = http_client.get_latest_checkpoint_sequence_number().await?;
+
+    // transfer a coin after the checkpoint
+    let coins: CoinPage = http_client.get_coins(sender, None, None, None).await?;
+    let transaction_bytes: TransactionBytes = http_client
+        .transfer_object(
+            sender,
+            coins.data[0].coin_object_id,
+            Some(coins.data[1].coin_object_id),
+            10000,
+            recipient,
+            None,
+        )
+        .await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(transaction_bytes.to_data()?, keystore.get_key(&sender)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let result: Balance = http_client.get_balance(sender, None, None).await?;
+    assert_eq!(4, result.coin_object_count);
+    let result: Balance = http_client.get_balance(recipient, None, None).await?;
+    assert_eq!(6, result.coin_object_count);
+
+    // the coin transferred away after the checkpoint is still owned by the sender at the
+    // checkpoint, with the gas coin at its version before the transfer
+    let result: Balance = http_client
+        .get_balance(sender, None, Some(checkpoint.into()))
+        .await?;
+    assert_eq!(5, result.coin_object_count);
+    assert_eq!(sender_balance.total_balance, result.total_balance);
+
+    // the coin received after the checkpoint is not owned by the recipient at the checkpoint
+    let result: Balance = http_client
+        .get_balance(recipient, None, Some(checkpoint.into()))
+        .await?;
+    assert_eq!(5, result.coin_object_count);
+    assert_eq!(recipient_balance.total_balance, result.total_balance);
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_select_coins() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+
+    let coins: CoinPage = http_client.get_coins(*address, None, None, None).await?;
+    assert_eq!(5, coins.data.len());
+
+    // split two small coins out of the first coin, so that the coins have different balances
+    let transaction_bytes: TransactionBytes = http_client
+        .split_coin(
+            *address,
+            coins.data[0].coin_object_id,
+            vec![1000, 2000],
+            Some(coins.data[1].coin_object_id),
+            10000,
+            None,
+        )
+        .await?;
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(transaction_bytes.to_data()?, keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let coins: CoinPage = http_client.get_coins(*address, None, None, None).await?;
+    assert_eq!(7, coins.data.len());
+    let coin_with_balance = |balance| {
+        coins
+            .data
+            .iter()
+            .find(|coin| coin.balance == balance)
+            .unwrap()
+            .coin_object_id
+    };
+    let (small, medium) = (coin_with_balance(1000), coin_with_balance(2000));
+    let largest = coins.data.iter().map(|coin| coin.balance).max().unwrap();
+    let total_balance: u128 = coins.data.iter().map(|coin| coin.balance as u128).sum();
+
+    // the largest coin alone covers the amount
+    let selected = http_client
+        .select_coins(*address, None, 2500, None, None)
+        .await?;
+    assert_eq!(1, selected.len());
+    assert_eq!(largest, selected[0].balance);
+
+    // the two smallest coins cover the amount
+    let selected = http_client
+        .select_coins(
+            *address,
+            None,
+            2500,
+            None,
+            Some(CoinSelectionStrategy::FewestDust),
+        )
+        .await?;
+    let selected: Vec<_> = selected.iter().map(|coin| coin.coin_object_id).collect();
+    assert_eq!(vec![small, medium], selected);
+
+    // excluded coins are skipped, the next smallest coin is selected instead
+    let selected = http_client
+        .select_coins(
+            *address,
+            None,
+            2500,
+            Some(vec![small]),
+            Some(CoinSelectionStrategy::FewestDust),
+        )
+        .await?;
+    assert_eq!(2, selected.len());
+    assert_eq!(medium, selected[0].coin_object_id);
+    assert!(selected[1].balance > 2000);
+
+    let result = http_client
+        .select_coins(*address, None, total_balance + 1, None, None)
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_get_metadata() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await?;
@@ -398,6 +609,7 @@ async fn test_get_metadata() -> Result<(), anyhow::Error> {
             dependencies,
             Some(gas.object_id),
             10000,
+            None,
         )
         .await?;
 
@@ -481,6 +693,7 @@ async fn test_get_total_supply() -> Result<(), anyhow::Error> {
             dependencies,
             Some(gas.object_id),
             10000,
+            None,
         )
         .await?;
 
@@ -553,6 +766,7 @@ async fn test_get_total_supply() -> Result<(), anyhow::Error> {
             Some(gas.object_id),
             10_000,
             None,
+            None,
         )
         .await?;
 
@@ -605,7 +819,8 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
     assert_eq!(5, objects.len());
     // verify coins and balance before test
     let coins: CoinPage = http_client.get_coins(*address, None, None, None).await?;
-    let balance: Vec<Balance> = http_client.get_all_balances(*address).await?;
+    let balance: Vec<Balance> = http_client.get_all_balances(*address, None).await?;
+    let checkpoint = http_client.get_latest_checkpoint_sequence_number().await?;
 
     assert_eq!(5, coins.data.len());
     for coin in &coins.data {
@@ -614,6 +829,7 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
 
     assert_eq!(1, balance.len());
     assert!(balance[0].locked_balance.is_empty());
+    let total_balance = balance[0].total_balance;
 
     // lock one coin
     let transaction_bytes: TransactionBytes = http_client
@@ -627,6 +843,7 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
             None,
             2000,
             None,
+            None,
         )
         .await?;
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -644,7 +861,7 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
         )
         .await?;
 
-    let balances: Vec<Balance> = http_client.get_all_balances(*address).await?;
+    let balances: Vec<Balance> = http_client.get_all_balances(*address, None).await?;
 
     assert_eq!(1, balance.len());
 
@@ -654,6 +871,15 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
     assert_eq!(1, balance.locked_balance.len());
     assert!(balance.locked_balance.contains_key(&20));
 
+    // the coin was not locked yet at the checkpoint before the lock transaction
+    let balances: Vec<Balance> = http_client
+        .get_all_balances(*address, Some(checkpoint.into()))
+        .await?;
+    assert_eq!(1, balances.len());
+    assert_eq!(5, balances[0].coin_object_count);
+    assert_eq!(total_balance, balances[0].total_balance);
+    assert!(balances[0].locked_balance.is_empty());
+
     Ok(())
 }
 
@@ -693,7 +919,15 @@ async fn test_staking() -> Result<(), anyhow::Error> {
     let coin = objects.data[0].object()?.object_id;
     // Delegate some SUI
     let transaction_bytes: TransactionBytes = http_client
-        .request_add_stake(*address, vec![coin], Some(1000000), validator, None, 10000)
+        .request_add_stake(
+            *address,
+            vec![coin],
+            Some(1000000),
+            validator,
+            None,
+            10000,
+            None,
+        )
         .await?;
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
     let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
@@ -762,6 +996,7 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
             validator,
             None,
             10000,
+            None,
         )
         .await?;
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -802,3 +1037,40 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_subscribe_checkpoint() -> Result<(), anyhow::Error> {
+    let mut cluster = TestClusterBuilder::new().build().await?;
+    let mut subscription = cluster
+        .fullnode_handle
+        .ws_client
+        .subscribe_checkpoint(Some(SuiTransactionResponseOptions::new().with_effects()))
+        .await?;
+
+    let (_, _, _, digest) = transfer_sui(&mut cluster.wallet, None, None).await?;
+
+    // Checkpoints are streamed in sequence number order with their transactions, until the one
+    // including the transfer.
+    let mut last_sequence_number = None;
+    loop {
+        let CheckpointWithTransactions {
+            checkpoint,
+            transactions,
+        } = timeout(Duration::from_secs(30), subscription.next())
+            .await?
+            .unwrap()?;
+        if let Some(last_sequence_number) = last_sequence_number {
+            assert_eq!(checkpoint.sequence_number, last_sequence_number + 1);
+        }
+        last_sequence_number = Some(checkpoint.sequence_number);
+
+        let transactions = transactions.unwrap();
+        let digests: Vec<_> = transactions.iter().map(|tx| tx.digest).collect();
+        assert_eq!(digests, checkpoint.transactions);
+        assert!(transactions.iter().all(|tx| tx.effects.is_some()));
+        if digests.contains(&digest) {
+            break;
+        }
+    }
+    Ok(())
+}
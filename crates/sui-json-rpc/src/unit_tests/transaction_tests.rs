@@ -46,7 +46,7 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
     for obj in &objects[..objects.len() - 1] {
         let oref = obj.object().unwrap();
         let transaction_bytes: TransactionBytes = http_client
-            .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address)
+            .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address, None)
             .await?;
         let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
         let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
@@ -132,7 +132,7 @@ async fn test_get_raw_transaction() -> Result<(), anyhow::Error> {
 
     // Make a transfer transactions
     let transaction_bytes: TransactionBytes = http_client
-        .transfer_object(*address, object_to_transfer, None, 1000, *address)
+        .transfer_object(*address, object_to_transfer, None, 1000, *address, None)
         .await?;
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
     let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
@@ -195,7 +195,7 @@ async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
             let oref = obj.object().unwrap();
             let data = client
                 .transaction_builder()
-                .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address)
+                .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address, None)
                 .await?;
             let tx = to_sender_signed_transaction(data, keystore.get_key(address).unwrap());
 
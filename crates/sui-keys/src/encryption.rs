@@ -0,0 +1,137 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::anyhow;
+use argon2::{Algorithm, Argon2, Params, Version};
+use fastcrypto::encoding::{Base64, Encoding};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+pub const ENCRYPTED_KEYSTORE_VERSION: u8 = 1;
+
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// Parameters of the Argon2id key derivation function, `m_cost` is the memory size in KiB.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    /// Base64 encoded random salt.
+    pub salt: String,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 1,
+            salt: Base64::encode(random_bytes::<SALT_LENGTH>()),
+        }
+    }
+}
+
+impl KdfParams {
+    /// The same costs with a fresh random salt.
+    pub fn with_new_salt(&self) -> Self {
+        Self {
+            salt: Base64::encode(random_bytes::<SALT_LENGTH>()),
+            ..self.clone()
+        }
+    }
+
+    pub fn derive_key(&self, password: &str) -> Result<EncryptionKey, anyhow::Error> {
+        let salt = Base64::decode(&self.salt).map_err(|e| anyhow!("Invalid KDF salt: {e}"))?;
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(KEY_LENGTH))
+            .map_err(|e| anyhow!("Invalid KDF parameters: {e}"))?;
+        let mut key = [0u8; KEY_LENGTH];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("Cannot derive key from password: {e}"))?;
+        Ok(EncryptionKey(key))
+    }
+}
+
+/// Content of an encrypted keystore file. The ciphertext is the AES-256-GCM encryption of the
+/// content of a plain keystore file, the JSON list of Base64 encoded `flag || privkey`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedKeystoreFile {
+    pub version: u8,
+    pub kdf: KdfParams,
+    /// Base64 encoded 96-bit nonce.
+    pub nonce: String,
+    /// Base64 encoded ciphertext.
+    pub ciphertext: String,
+    /// Base64 encoded `flag || pk` of the encrypted keypairs, to list the addresses of a
+    /// locked keystore.
+    pub public_keys: Vec<String>,
+}
+
+/// A 256-bit key derived from the keystore password, zeroed when dropped.
+#[derive(Clone)]
+pub struct EncryptionKey([u8; KEY_LENGTH]);
+
+impl EncryptionKey {
+    /// A random key, e.g. to wrap the keystore key for a session.
+    pub fn generate() -> Self {
+        Self(random_bytes::<KEY_LENGTH>())
+    }
+
+    /// Encrypt `plaintext` with a fresh random nonce, returns the Base64 encoded nonce and
+    /// ciphertext.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<(String, String), anyhow::Error> {
+        let nonce = random_bytes::<NONCE_LENGTH>();
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|e| anyhow!("Cannot encrypt keystore: {e}"))?;
+        Ok((Base64::encode(nonce), Base64::encode(ciphertext)))
+    }
+
+    pub fn decrypt(&self, nonce: &str, ciphertext: &str) -> Result<Vec<u8>, anyhow::Error> {
+        let nonce = Base64::decode(nonce).map_err(|e| anyhow!("Invalid nonce: {e}"))?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(anyhow!("Invalid nonce length: {}", nonce.len()));
+        }
+        let ciphertext =
+            Base64::decode(ciphertext).map_err(|e| anyhow!("Invalid ciphertext: {e}"))?;
+        self.cipher()
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow!("Incorrect password or corrupted keystore"))
+    }
+
+    pub fn encode_base64(&self) -> String {
+        Base64::encode(self.0)
+    }
+
+    pub fn decode_base64(value: &str) -> Result<Self, anyhow::Error> {
+        let mut bytes = Base64::decode(value).map_err(|e| anyhow!("Invalid key: {e}"))?;
+        let key = <[u8; KEY_LENGTH]>::try_from(bytes.as_slice())
+            .map_err(|_| anyhow!("Invalid key length: {}", bytes.len()));
+        bytes.zeroize();
+        Ok(Self(key?))
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new_from_slice(&self.0).expect("Key has the AES-256 key length")
+    }
+}
+
+impl Drop for EncryptionKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
@@ -3,16 +3,21 @@
 
 use anyhow::anyhow;
 use fastcrypto::traits::EncodeDecodeBase64;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair, SuiKeyPair};
+use zeroize::Zeroizing;
 
-/// Write Base64 encoded `flag || privkey` to file.
+use crate::encryption::KdfParams;
+use crate::keystore::write_private_file;
+
+/// Write Base64 encoded `flag || privkey` to a file only readable by its owner.
 pub fn write_keypair_to_file<P: AsRef<std::path::Path>>(
     keypair: &SuiKeyPair,
     path: P,
 ) -> anyhow::Result<()> {
-    let contents = keypair.encode_base64();
-    std::fs::write(path, contents)?;
-    Ok(())
+    let contents = Zeroizing::new(keypair.encode_base64());
+    write_private_file(path.as_ref(), contents.as_bytes())
 }
 
 /// Write Base64 encoded `privkey` to file.
@@ -50,3 +55,132 @@ pub fn read_network_keypair_from_file<P: AsRef<std::path::Path>>(
         Err(anyhow!("Invalid scheme for network keypair"))
     }
 }
+
+pub const KEYSTORE_BACKUP_VERSION: u8 = 1;
+
+/// A backup of the keypairs of a keystore. The keystore does not record the mnemonic phrases or
+/// derivation paths the keypairs were generated from, so the keypairs themselves are backed up.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeystoreBackup {
+    pub version: u8,
+    #[serde(flatten)]
+    pub content: KeystoreBackupContent,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum KeystoreBackupContent {
+    Plain {
+        keys: Vec<BackupKey>,
+    },
+    /// The keys encrypted with AES-256-GCM, under a key derived from a password with Argon2id.
+    Encrypted {
+        kdf: KdfParams,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupKey {
+    pub address: SuiAddress,
+    pub scheme: String,
+    /// Base64 encoded `flag || privkey`.
+    pub keypair: String,
+}
+
+impl KeystoreBackup {
+    /// Back up `keypairs` in plain text, or encrypted with `password` if it is provided.
+    pub fn new(
+        keypairs: &[&SuiKeyPair],
+        password: Option<&str>,
+        kdf: KdfParams,
+    ) -> anyhow::Result<Self> {
+        let keys = keypairs
+            .iter()
+            .map(|kp| BackupKey {
+                address: (&kp.public()).into(),
+                scheme: kp.public().scheme().to_string(),
+                keypair: kp.encode_base64(),
+            })
+            .collect::<Vec<_>>();
+        let content = match password {
+            Some(password) => {
+                let key = kdf.derive_key(password)?;
+                let plaintext = Zeroizing::new(serde_json::to_vec(&keys)?);
+                let (nonce, ciphertext) = key.encrypt(&plaintext)?;
+                KeystoreBackupContent::Encrypted {
+                    kdf,
+                    nonce,
+                    ciphertext,
+                }
+            }
+            None => KeystoreBackupContent::Plain { keys },
+        };
+        Ok(Self {
+            version: KEYSTORE_BACKUP_VERSION,
+            content,
+        })
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self.content, KeystoreBackupContent::Encrypted { .. })
+    }
+
+    /// The backed up keypairs, the password is required if the backup is encrypted.
+    pub fn key_pairs(&self, password: Option<&str>) -> anyhow::Result<Vec<SuiKeyPair>> {
+        let decrypted;
+        let keys = match &self.content {
+            KeystoreBackupContent::Plain { keys } => keys,
+            KeystoreBackupContent::Encrypted {
+                kdf,
+                nonce,
+                ciphertext,
+            } => {
+                let password = password
+                    .ok_or_else(|| anyhow!("A password is required to restore the backup"))?;
+                let key = kdf.derive_key(password)?;
+                let plaintext = Zeroizing::new(key.decrypt(nonce, ciphertext)?);
+                decrypted = serde_json::from_slice::<Vec<BackupKey>>(&plaintext)?;
+                &decrypted
+            }
+        };
+        keys.iter()
+            .map(|key| {
+                let kp = SuiKeyPair::decode_base64(&key.keypair).map_err(|e| anyhow!(e))?;
+                let address: SuiAddress = (&kp.public()).into();
+                if address != key.address {
+                    return Err(anyhow!(
+                        "Keypair of address [{}] does not match its address [{address}]",
+                        key.address
+                    ));
+                }
+                Ok(kp)
+            })
+            .collect()
+    }
+}
+
+/// Write a keystore backup to file as JSON.
+pub fn write_keystore_backup_to_file<P: AsRef<std::path::Path>>(
+    backup: &KeystoreBackup,
+    path: P,
+) -> anyhow::Result<()> {
+    let contents = serde_json::to_string_pretty(backup)?;
+    write_private_file(path.as_ref(), contents.as_bytes())
+}
+
+/// Read a keystore backup from file.
+pub fn read_keystore_backup_from_file<P: AsRef<std::path::Path>>(
+    path: P,
+) -> anyhow::Result<KeystoreBackup> {
+    let contents = std::fs::read_to_string(path)?;
+    let backup: KeystoreBackup = serde_json::from_str(&contents)?;
+    if backup.version != KEYSTORE_BACKUP_VERSION {
+        return Err(anyhow!(
+            "Unsupported keystore backup version {}",
+            backup.version
+        ));
+    }
+    Ok(backup)
+}
@@ -14,6 +14,8 @@ use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zeroize::{Zeroize, Zeroizing};
 
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
@@ -21,14 +23,63 @@ use sui_types::crypto::{
     SignatureScheme, SuiKeyPair,
 };
 
+use crate::encryption::{
+    EncryptedKeystoreFile, EncryptionKey, KdfParams, ENCRYPTED_KEYSTORE_VERSION,
+};
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 
-#[derive(Serialize, Deserialize)]
+/// Environment variable holding the password of an encrypted keystore, to unlock it without
+/// starting a session, e.g. in scripts.
+pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "SUI_KEYSTORE_PASSWORD";
+
+/// Environment variable holding the token of a session started with
+/// [EncryptedFileKeystore::start_session], which unwraps the keystore key stored in the session
+/// file.
+pub const KEYSTORE_SESSION_ENV_VAR: &str = "SUI_KEYSTORE_SESSION";
+
+#[derive(Serialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
+    EncryptedFile(EncryptedFileKeystore),
     InMem(InMemKeystore),
 }
+
+impl Keystore {
+    /// Open the keystore file at `path`, which can either be a plain or an encrypted keystore.
+    pub fn open(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        Ok(if EncryptedFileKeystore::is_encrypted(path) {
+            Keystore::EncryptedFile(EncryptedFileKeystore::new(path)?)
+        } else {
+            Keystore::File(FileBasedKeystore::new(path)?)
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Keystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        // File keystores are opened according to the content of the file, so that the client
+        // config keeps working once its keystore is encrypted.
+        #[derive(Deserialize)]
+        enum KeystoreConfig {
+            File(PathBuf),
+            EncryptedFile(PathBuf),
+            InMem(InMemKeystore),
+        }
+
+        match KeystoreConfig::deserialize(deserializer)? {
+            KeystoreConfig::File(path) | KeystoreConfig::EncryptedFile(path) => {
+                Keystore::open(&path).map_err(D::Error::custom)
+            }
+            KeystoreConfig::InMem(keystore) => Ok(Keystore::InMem(keystore)),
+        }
+    }
+}
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error>;
@@ -87,6 +138,12 @@ impl Display for Keystore {
                 write!(writer, "Keystore Path : {:?}", file.path)?;
                 write!(f, "{}", writer)
             }
+            Keystore::EncryptedFile(file) => {
+                writeln!(writer, "Keystore Type : EncryptedFile")?;
+                writeln!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(writer, "Locked : {}", file.is_locked())?;
+                write!(f, "{}", writer)
+            }
             Keystore::InMem(_) => {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
@@ -199,6 +256,10 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
             let store = serde_json::to_string_pretty(
@@ -219,6 +280,390 @@ impl FileBasedKeystore {
     }
 }
 
+/// A file based keystore whose keypairs are encrypted with a key derived from a password, see
+/// [EncryptedKeystoreFile] for the file format. The keystore is locked when opened and only lists
+/// its public keys until it is unlocked, either with its password, with the password set in the
+/// [KEYSTORE_PASSWORD_ENV_VAR] environment variable, or by a session started with
+/// [EncryptedFileKeystore::start_session].
+pub struct EncryptedFileKeystore {
+    path: PathBuf,
+    file: EncryptedKeystoreFile,
+    public_keys: Vec<PublicKey>,
+    unlocked: Option<UnlockedKeys>,
+}
+
+struct UnlockedKeys {
+    key: EncryptionKey,
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+}
+
+/// An unlocked session of an encrypted keystore, stored next to the keystore file and only
+/// readable by its owner. The keystore key is wrapped by a random session token which is not
+/// stored, so the file alone does not unlock the keystore.
+#[derive(Serialize, Deserialize)]
+struct KeystoreSessionFile {
+    /// Base64 encoded nonce of the wrapped [KeystoreSession].
+    nonce: String,
+    /// Base64 encoded [KeystoreSession] encrypted with the session token.
+    ciphertext: String,
+    /// Expiration time of the session in seconds since the Unix epoch, to remove expired
+    /// sessions without the token.
+    expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreSession {
+    /// Base64 encoded key derived from the password.
+    key: String,
+    /// Expiration time of the session in seconds since the Unix epoch.
+    expires_at: u64,
+}
+
+impl Drop for KeystoreSession {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.path.to_str().unwrap_or(""))
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        EncryptedFileKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(Signature::new_hashed(
+            msg,
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+
+    fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let unlocked = match &mut self.unlocked {
+            Some(unlocked) => unlocked,
+            None => return Err(locked_error(&self.path)),
+        };
+        let address: SuiAddress = (&keypair.public()).into();
+        unlocked.keys.insert(address, keypair);
+        self.save()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        match &self.unlocked {
+            Some(unlocked) => unlocked.keys.values().map(|key| key.public()).collect(),
+            None => self.public_keys.clone(),
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        let unlocked = match &self.unlocked {
+            Some(unlocked) => unlocked,
+            None => return Err(locked_error(&self.path)),
+        };
+        match unlocked.keys.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+}
+
+impl EncryptedFileKeystore {
+    /// Open the encrypted keystore at `path`, the keystore is unlocked if the password or the
+    /// token of a session in progress is set in the environment.
+    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let file: EncryptedKeystoreFile = serde_json::from_reader(reader)
+            .map_err(|e| anyhow!("Invalid encrypted keystore file {:?}: {e}", path))?;
+        if file.version != ENCRYPTED_KEYSTORE_VERSION {
+            return Err(anyhow!(
+                "Unsupported encrypted keystore version {} in {:?}",
+                file.version,
+                path
+            ));
+        }
+        let public_keys = file
+            .public_keys
+            .iter()
+            .map(|pk| PublicKey::decode_base64(pk))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Invalid public key in keystore file {:?}: {:?}", path, e))?;
+
+        let mut keystore = Self {
+            path: path.to_path_buf(),
+            file,
+            public_keys,
+            unlocked: None,
+        };
+        if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV_VAR) {
+            keystore.unlock(&password)?;
+        } else if let Ok(token) = std::env::var(KEYSTORE_SESSION_ENV_VAR) {
+            // A stale session, e.g. started before a password change, leaves the keystore locked.
+            let _ = keystore.unlock_with_session(&Zeroizing::new(token));
+        }
+        Ok(keystore)
+    }
+
+    /// Create an unlocked encrypted keystore at `path` holding `keys`, replacing any existing file.
+    pub fn create(
+        path: &Path,
+        keys: Vec<SuiKeyPair>,
+        password: &str,
+        kdf: KdfParams,
+    ) -> Result<Self, anyhow::Error> {
+        let key = kdf.derive_key(password)?;
+        let keys = keys
+            .into_iter()
+            .map(|k| (Into::<SuiAddress>::into(&k.public()), k))
+            .collect();
+        let mut keystore = Self {
+            path: path.to_path_buf(),
+            file: EncryptedKeystoreFile {
+                version: ENCRYPTED_KEYSTORE_VERSION,
+                kdf,
+                nonce: String::new(),
+                ciphertext: String::new(),
+                public_keys: vec![],
+            },
+            public_keys: vec![],
+            unlocked: Some(UnlockedKeys { key, keys }),
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Encrypt a plain keystore in place, the plain keystore file is replaced by the encrypted one.
+    pub fn encrypt(
+        keystore: &FileBasedKeystore,
+        password: &str,
+        kdf: KdfParams,
+    ) -> Result<Self, anyhow::Error> {
+        let path = keystore
+            .path()
+            .ok_or_else(|| anyhow!("Cannot encrypt a keystore without a file path"))?;
+        let keys = keystore
+            .key_pairs()
+            .into_iter()
+            .map(|kp| SuiKeyPair::decode_base64(&kp.encode_base64()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("Invalid keypair in keystore {:?}: {:?}", path, e))?;
+        Self::create(path, keys, password, kdf)
+    }
+
+    /// Whether the file at `path` is an encrypted keystore rather than a plain one.
+    pub fn is_encrypted(path: &Path) -> bool {
+        fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice::<serde_json::Value>(&content).ok())
+            .map_or(false, |value| value.is_object())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.unlocked.is_none()
+    }
+
+    pub fn unlock(&mut self, password: &str) -> Result<(), anyhow::Error> {
+        let key = self.file.kdf.derive_key(password)?;
+        self.unlock_with_key(key)
+    }
+
+    /// Forget the keys and end the session in progress, if any.
+    pub fn lock(&mut self) -> Result<(), anyhow::Error> {
+        self.unlocked = None;
+        self.end_session()
+    }
+
+    /// Re-encrypt the keystore with a key derived from `new_password` and a fresh salt. Sessions
+    /// started with the current password are ended.
+    pub fn change_password(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.unlock(current_password)?;
+        let kdf = self.file.kdf.with_new_salt();
+        let key = kdf.derive_key(new_password)?;
+        self.file.kdf = kdf;
+        if let Some(unlocked) = &mut self.unlocked {
+            unlocked.key = key;
+        }
+        self.save()?;
+        self.end_session()
+    }
+
+    /// Keep the keystore unlocked for `duration` for the subsequent commands. The key derived
+    /// from the password is stored in a session file next to the keystore, wrapped with a random
+    /// token. The returned token unlocks the keystore when set in [KEYSTORE_SESSION_ENV_VAR].
+    pub fn start_session(&self, duration: Duration) -> Result<Zeroizing<String>, anyhow::Error> {
+        let key = match &self.unlocked {
+            Some(unlocked) => unlocked.key.encode_base64(),
+            None => return Err(locked_error(&self.path)),
+        };
+        let expires_at = (SystemTime::now() + duration)
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+        let session = Zeroizing::new(serde_json::to_vec(&KeystoreSession { key, expires_at })?);
+        let token = EncryptionKey::generate();
+        let (nonce, ciphertext) = token.encrypt(&session)?;
+        let file = KeystoreSessionFile {
+            nonce,
+            ciphertext,
+            expires_at,
+        };
+        write_private_file(&self.session_path(), &serde_json::to_vec(&file)?)?;
+        Ok(Zeroizing::new(token.encode_base64()))
+    }
+
+    pub fn end_session(&self) -> Result<(), anyhow::Error> {
+        let session_path = self.session_path();
+        if session_path.exists() {
+            fs::remove_file(session_path)?;
+        }
+        Ok(())
+    }
+
+    /// Unlock the keystore with the token of the session in progress, an expired session is
+    /// removed.
+    pub fn unlock_with_session(&mut self, token: &str) -> Result<(), anyhow::Error> {
+        let content = fs::read(self.session_path())
+            .map_err(|_| anyhow!("No session in progress for keystore {:?}", self.path))?;
+        let file: KeystoreSessionFile = serde_json::from_slice(&content)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if file.expires_at <= now {
+            self.end_session()?;
+            return Err(anyhow!("The session of keystore {:?} expired", self.path));
+        }
+        let session = Zeroizing::new(
+            EncryptionKey::decode_base64(token)?
+                .decrypt(&file.nonce, &file.ciphertext)
+                .map_err(|_| anyhow!("Invalid session token for keystore {:?}", self.path))?,
+        );
+        let session: KeystoreSession = serde_json::from_slice(&session)?;
+        // The expiration time in the clear is not authenticated, check the wrapped one.
+        if session.expires_at <= now {
+            self.end_session()?;
+            return Err(anyhow!("The session of keystore {:?} expired", self.path));
+        }
+        self.unlock_with_key(EncryptionKey::decode_base64(&session.key)?)
+    }
+
+    fn session_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".session");
+        path.into()
+    }
+
+    fn unlock_with_key(&mut self, key: EncryptionKey) -> Result<(), anyhow::Error> {
+        let plaintext = Zeroizing::new(key.decrypt(&self.file.nonce, &self.file.ciphertext)?);
+        let kp_strings: Zeroizing<Vec<String>> =
+            Zeroizing::new(serde_json::from_slice(&plaintext)?);
+        let keys = kp_strings
+            .iter()
+            .map(|kpstr| {
+                let kp = SuiKeyPair::decode_base64(kpstr);
+                kp.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map_err(|e| anyhow!("Invalid keypair in keystore {:?}: {:?}", self.path, e))?;
+        self.unlocked = Some(UnlockedKeys { key, keys });
+        Ok(())
+    }
+
+    fn save(&mut self) -> Result<(), anyhow::Error> {
+        let unlocked = match &self.unlocked {
+            Some(unlocked) => unlocked,
+            None => return Err(locked_error(&self.path)),
+        };
+        let plaintext = Zeroizing::new(serde_json::to_vec(
+            &unlocked
+                .keys
+                .values()
+                .map(EncodeDecodeBase64::encode_base64)
+                .collect::<Vec<_>>(),
+        )?);
+        let (nonce, ciphertext) = unlocked.key.encrypt(&plaintext)?;
+        self.public_keys = unlocked.keys.values().map(|key| key.public()).collect();
+        self.file.nonce = nonce;
+        self.file.ciphertext = ciphertext;
+        self.file.public_keys = self
+            .public_keys
+            .iter()
+            .map(EncodeDecodeBase64::encode_base64)
+            .collect();
+
+        // Write to a temporary file first, so that the keystore is never left half written.
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        write_private_file(
+            &tmp_path,
+            serde_json::to_string_pretty(&self.file)?.as_bytes(),
+        )?;
+        fs::rename(tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+fn locked_error(path: &Path) -> anyhow::Error {
+    anyhow!(
+        "Keystore {:?} is locked, unlock it with `sui keytool unlock` or set the password in {}",
+        path,
+        KEYSTORE_PASSWORD_ENV_VAR
+    )
+}
+
+/// Write a file only readable by its owner.
+pub(crate) fn write_private_file(path: &Path, content: &[u8]) -> Result<(), anyhow::Error> {
+    use std::io::Write as _;
+
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content)?;
+    Ok(())
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
@@ -1,12 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use std::str::FromStr;
+use std::time::Duration;
 
 use fastcrypto::hash::HashFunction;
+use shared_crypto::intent::Intent;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use sui_keys::encryption::KdfParams;
+use sui_keys::keypair_file::{
+    read_keystore_backup_from_file, write_keystore_backup_to_file, KeystoreBackup,
+};
+use sui_keys::keystore::{AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, Keystore};
+use sui_types::crypto::{DefaultHash, EncodeDecodeBase64, SignatureScheme, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -69,3 +75,166 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+/// Cheap KDF parameters to keep the tests fast.
+fn test_kdf_params() -> KdfParams {
+    KdfParams {
+        m_cost: 8,
+        t_cost: 1,
+        p_cost: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn encrypted_keystore_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    let mut encrypted = EncryptedFileKeystore::encrypt(&keystore, "password", test_kdf_params())?;
+    assert!(!encrypted.is_locked());
+    let (address2, _, _) = encrypted.generate_and_add_new_key(SignatureScheme::Secp256k1, None)?;
+
+    // The keypairs are not stored in plain text anymore.
+    let content = std::fs::read_to_string(&keystore_path)?;
+    assert!(!content.contains(&keystore.get_key(&address)?.encode_base64()));
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+
+    // The keystore is locked when opened, but still lists its addresses.
+    let mut keystore = Keystore::open(&keystore_path)?;
+    assert!(matches!(keystore, Keystore::EncryptedFile(_)));
+    assert_eq!(keystore.addresses(), encrypted.addresses());
+    assert!(keystore.get_key(&address).is_err());
+    assert!(keystore
+        .sign_secure(&address, b"hello", Intent::default())
+        .is_err());
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None)
+        .is_err());
+
+    let encrypted = match &mut keystore {
+        Keystore::EncryptedFile(encrypted) => encrypted,
+        _ => unreachable!(),
+    };
+    assert!(encrypted.unlock("wrong password").is_err());
+    assert!(encrypted.is_locked());
+    encrypted.unlock("password")?;
+    keystore.sign_secure(&address, b"hello", Intent::default())?;
+    keystore.sign_secure(&address2, b"hello", Intent::default())?;
+    Ok(())
+}
+
+#[test]
+fn encrypted_keystore_change_password_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore =
+        EncryptedFileKeystore::create(&keystore_path, vec![], "password", test_kdf_params())?;
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    assert!(keystore
+        .change_password("wrong password", "new password")
+        .is_err());
+    keystore.change_password("password", "new password")?;
+
+    let mut keystore = EncryptedFileKeystore::new(&keystore_path)?;
+    assert!(keystore.unlock("password").is_err());
+    keystore.unlock("new password")?;
+    assert!(keystore.get_key(&address).is_ok());
+    Ok(())
+}
+
+#[test]
+fn encrypted_keystore_session_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore =
+        EncryptedFileKeystore::create(&keystore_path, vec![], "password", test_kdf_params())?;
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    // A session keeps the keystore unlocked when it is opened again with its token.
+    let token = keystore.start_session(Duration::from_secs(60))?;
+    let mut reopened = EncryptedFileKeystore::new(&keystore_path)?;
+    assert!(reopened.is_locked());
+    reopened.unlock_with_session(&token)?;
+    assert!(reopened.get_key(&address).is_ok());
+
+    // The session file does not unlock the keystore without the token.
+    let session_path = temp_dir.path().join("sui.keystore.session");
+    let content = std::fs::read_to_string(&session_path)?;
+    assert!(!content.contains(token.as_str()));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&session_path)?.permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+    let other_token = keystore.start_session(Duration::from_secs(60))?;
+    assert!(EncryptedFileKeystore::new(&keystore_path)?
+        .unlock_with_session(&token)
+        .is_err());
+
+    // Changing the password ends the session.
+    keystore.change_password("password", "new password")?;
+    assert!(!session_path.exists());
+    assert!(EncryptedFileKeystore::new(&keystore_path)?
+        .unlock_with_session(&other_token)
+        .is_err());
+
+    // Locking ends the session.
+    let token = keystore.start_session(Duration::from_secs(60))?;
+    keystore.lock()?;
+    assert!(!session_path.exists());
+    assert!(EncryptedFileKeystore::new(&keystore_path)?
+        .unlock_with_session(&token)
+        .is_err());
+
+    // An expired session is removed.
+    keystore.unlock("new password")?;
+    let token = keystore.start_session(Duration::ZERO)?;
+    assert!(EncryptedFileKeystore::new(&keystore_path)?
+        .unlock_with_session(&token)
+        .is_err());
+    assert!(!session_path.exists());
+    Ok(())
+}
+
+#[test]
+fn keystore_backup_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    keystore.generate_and_add_new_key(SignatureScheme::Secp256r1, None)?;
+    let addresses = keystore.addresses();
+    let keypairs = addresses
+        .iter()
+        .map(|address| keystore.get_key(address))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Plain backup.
+    let backup_path = temp_dir.path().join("plain.backup");
+    let backup = KeystoreBackup::new(&keypairs, None, test_kdf_params())?;
+    write_keystore_backup_to_file(&backup, &backup_path)?;
+    let backup = read_keystore_backup_from_file(&backup_path)?;
+    assert!(!backup.is_encrypted());
+    let restored = backup.key_pairs(None)?;
+    assert_eq!(restored.iter().collect::<Vec<_>>(), keypairs);
+
+    // Encrypted backup, the keypairs are not stored in plain text.
+    let backup_path = temp_dir.path().join("encrypted.backup");
+    let backup = KeystoreBackup::new(&keypairs, Some("password"), test_kdf_params())?;
+    write_keystore_backup_to_file(&backup, &backup_path)?;
+    let content = std::fs::read_to_string(&backup_path)?;
+    assert!(!content.contains(&keypairs[0].encode_base64()));
+
+    let backup = read_keystore_backup_from_file(&backup_path)?;
+    assert!(backup.is_encrypted());
+    assert!(backup.key_pairs(None).is_err());
+    assert!(backup.key_pairs(Some("wrong password")).is_err());
+    let restored = backup.key_pairs(Some("password"))?;
+    assert_eq!(restored.iter().collect::<Vec<_>>(), keypairs);
+    Ok(())
+}
@@ -0,0 +1,182 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Faults injected into the outbound requests of a node on the p2p network, used by tests to
+//! reproduce slow or lossy links and partitions between specific peers.
+//!
+//! Only the anemo p2p network (state sync and discovery) goes through the `FaultInjectionLayer`:
+//! the Narwhal networks and the validator gRPC API are not affected by these faults.
+//!
+//! Faults can only be injected with the `fault-injection` feature. Without it, `NetworkFaults`
+//! is empty and `FaultInjectionLayer` leaves the requests of the node untouched.
+
+#[cfg(feature = "fault-injection")]
+pub use enabled::*;
+
+#[cfg(not(feature = "fault-injection"))]
+pub use disabled::*;
+
+#[cfg(feature = "fault-injection")]
+mod enabled {
+    use anemo::codegen::BoxError;
+    use anemo::{PeerId, Request, Response};
+    use bytes::Bytes;
+    use rand::Rng;
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, RwLock};
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+    use tower::{Layer, Service};
+
+    /// Fault injected into the requests sent to one peer.
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct LinkFault {
+        /// Delay added before sending every request.
+        pub latency: Duration,
+        /// Probability in [0, 1] to fail a request without sending it. 1 partitions the peers.
+        pub drop_rate: f64,
+    }
+
+    impl LinkFault {
+        pub fn latency(latency: Duration) -> Self {
+            Self {
+                latency,
+                drop_rate: 0.0,
+            }
+        }
+
+        pub fn drop_rate(drop_rate: f64) -> Self {
+            Self {
+                latency: Duration::ZERO,
+                drop_rate,
+            }
+        }
+
+        pub fn partition() -> Self {
+            Self::drop_rate(1.0)
+        }
+    }
+
+    /// The faults of the links from a node to its peers, shared between the `FaultInjectionLayer` of
+    /// the node's network and the test controlling them. No fault is injected by default.
+    #[derive(Clone, Debug, Default)]
+    pub struct NetworkFaults {
+        faults: Arc<RwLock<HashMap<PeerId, LinkFault>>>,
+    }
+
+    impl NetworkFaults {
+        /// Inject `fault` into the requests sent to `peer`, replacing the previous fault if any.
+        pub fn set(&self, peer: PeerId, fault: LinkFault) {
+            self.faults.write().unwrap().insert(peer, fault);
+        }
+
+        /// Stop injecting faults into the requests sent to `peer`.
+        pub fn clear(&self, peer: &PeerId) {
+            self.faults.write().unwrap().remove(peer);
+        }
+
+        /// Stop injecting faults into the requests sent to any peer.
+        pub fn clear_all(&self) {
+            self.faults.write().unwrap().clear();
+        }
+
+        pub fn get(&self, peer: &PeerId) -> Option<LinkFault> {
+            self.faults.read().unwrap().get(peer).copied()
+        }
+    }
+
+    /// Outbound request layer injecting the faults of `NetworkFaults`.
+    #[derive(Clone, Debug)]
+    pub struct FaultInjectionLayer {
+        faults: NetworkFaults,
+    }
+
+    impl FaultInjectionLayer {
+        pub fn new(faults: NetworkFaults) -> Self {
+            Self { faults }
+        }
+    }
+
+    impl<S> Layer<S> for FaultInjectionLayer {
+        type Service = FaultInjection<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            FaultInjection {
+                inner,
+                faults: self.faults.clone(),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct FaultInjection<S> {
+        inner: S,
+        faults: NetworkFaults,
+    }
+
+    impl<S> Service<Request<Bytes>> for FaultInjection<S>
+    where
+        S: Service<Request<Bytes>, Response = Response<Bytes>> + Clone + Send + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+    {
+        type Response = Response<Bytes>;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        #[inline]
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx).map_err(Into::into)
+        }
+
+        fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+            let fault = request.peer_id().and_then(|peer| self.faults.get(peer));
+            // The inner service is ready, take it and leave a clone in its place, see
+            // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+            let clone = self.inner.clone();
+            let mut inner = std::mem::replace(&mut self.inner, clone);
+            Box::pin(async move {
+                if let Some(fault) = fault {
+                    if rand::thread_rng().gen_bool(fault.drop_rate.clamp(0.0, 1.0)) {
+                        return Err(format!(
+                            "request {} dropped by fault injection",
+                            request.route()
+                        )
+                        .into());
+                    }
+                    tokio::time::sleep(fault.latency).await;
+                }
+                inner.call(request).await.map_err(Into::into)
+            })
+        }
+    }
+}
+
+#[cfg(not(feature = "fault-injection"))]
+mod disabled {
+    use tower::Layer;
+
+    /// No fault can be injected without the `fault-injection` feature.
+    #[derive(Clone, Debug, Default)]
+    pub struct NetworkFaults;
+
+    /// Outbound request layer leaving the requests untouched.
+    #[derive(Clone, Debug)]
+    pub struct FaultInjectionLayer;
+
+    impl FaultInjectionLayer {
+        pub fn new(_faults: NetworkFaults) -> Self {
+            Self
+        }
+    }
+
+    impl<S> Layer<S> for FaultInjectionLayer {
+        type Service = S;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            inner
+        }
+    }
+}
@@ -48,7 +48,7 @@ use sui_core::checkpoints::{
 use sui_core::consensus_adapter::{
     CheckConnection, ConnectionMonitorStatus, ConsensusAdapter, ConsensusAdapterMetrics,
 };
-use sui_core::consensus_handler::ConsensusHandler;
+use sui_core::consensus_handler::{ConsensusHandler, ConsensusPause};
 use sui_core::consensus_validator::{SuiTxValidator, SuiTxValidatorMetrics};
 use sui_core::db_checkpoint_handler::DBCheckpointHandler;
 use sui_core::epoch::committee_store::CommitteeStore;
@@ -75,6 +75,7 @@ use sui_macros::fail_point_async;
 use sui_network::api::ValidatorServer;
 use sui_network::discovery;
 use sui_network::discovery::TrustedPeerChangeEvent;
+use sui_network::fault_injection::{FaultInjectionLayer, NetworkFaults};
 use sui_network::{state_sync, DEFAULT_CONNECT_TIMEOUT_SEC, DEFAULT_HTTP2_KEEPALIVE_SEC};
 use sui_protocol_config::{ProtocolConfig, ProtocolVersion, SupportedProtocolVersions};
 use sui_storage::IndexStore;
@@ -128,6 +129,11 @@ pub struct SuiNode {
     /// Broadcast channel to notify state-sync for new validator peers.
     trusted_peer_change_tx: watch::Sender<TrustedPeerChangeEvent>,
 
+    /// Faults injected into the requests sent to peers on the p2p network, for tests.
+    network_faults: NetworkFaults,
+    /// Pauses the processing of consensus output, for tests.
+    consensus_pause: ConsensusPause,
+
     _db_checkpoint_handle: Option<Sender<()>>,
 
     #[cfg(msim)]
@@ -246,10 +252,12 @@ impl SuiNode {
         // TODO only configure validators as seed/preferred peers for validators and not for
         // fullnodes once we've had a chance to re-work fullnode configuration generation.
         let (trusted_peer_change_tx, trusted_peer_change_rx) = watch::channel(Default::default());
+        let network_faults = NetworkFaults::default();
         let (p2p_network, discovery_handle, state_sync_handle) = Self::create_p2p_network(
             &config,
             state_sync_store,
             trusted_peer_change_rx,
+            network_faults.clone(),
             &prometheus_registry,
         )?;
         // We must explicitly send this instead of relying on the initial value to trigger
@@ -334,6 +342,7 @@ impl SuiNode {
         let json_rpc_service = build_server(
             state.clone(),
             &transaction_orchestrator.clone(),
+            &state_sync_handle,
             &config,
             &prometheus_registry,
         )
@@ -364,6 +373,8 @@ impl SuiNode {
 
         let connection_monitor_status = Arc::new(connection_monitor_status);
 
+        let consensus_pause = ConsensusPause::default();
+
         let validator_components = if state.is_validator(&epoch_store) {
             let components = Self::construct_validator_components(
                 &config,
@@ -375,6 +386,7 @@ impl SuiNode {
                 accumulator.clone(),
                 connection_monitor_status.clone(),
                 &registry_service,
+                consensus_pause.clone(),
             )
             .await?;
             // This is only needed during cold start.
@@ -400,6 +412,8 @@ impl SuiNode {
             end_of_epoch_channel,
             connection_monitor_status,
             trusted_peer_change_tx,
+            network_faults,
+            consensus_pause,
 
             _db_checkpoint_handle: db_checkpoint_handle,
             #[cfg(msim)]
@@ -414,6 +428,28 @@ impl SuiNode {
         Ok(node)
     }
 
+    /// Faults injected into the requests this node sends to its peers on the p2p network (state
+    /// sync and discovery), for tests. Narwhal and gRPC requests are not affected, and no fault
+    /// can be injected without the `fault-injection` feature.
+    pub fn network_faults(&self) -> &NetworkFaults {
+        &self.network_faults
+    }
+
+    /// Stop processing the output of consensus until `resume_consensus` is called, for tests.
+    /// Transactions are still sequenced by Narwhal, but are neither executed nor included in
+    /// checkpoints. Consensus must be resumed for the node to be able to change epoch.
+    #[cfg(feature = "fault-injection")]
+    pub fn pause_consensus(&self) {
+        info!("Pausing consensus");
+        self.consensus_pause.pause();
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn resume_consensus(&self) {
+        info!("Resuming consensus");
+        self.consensus_pause.resume();
+    }
+
     pub fn subscribe_to_epoch_change(&self) -> broadcast::Receiver<(Committee, ProtocolVersion)> {
         self.end_of_epoch_channel.subscribe()
     }
@@ -473,6 +509,7 @@ impl SuiNode {
         config: &NodeConfig,
         state_sync_store: RocksDbStore,
         trusted_peer_change_rx: watch::Receiver<TrustedPeerChangeEvent>,
+        network_faults: NetworkFaults,
         prometheus_registry: &Registry,
     ) -> Result<(Network, discovery::Handle, state_sync::Handle)> {
         let (state_sync, state_sync_server) = state_sync::Builder::new()
@@ -517,6 +554,7 @@ impl SuiNode {
                     Arc::new(outbound_network_metrics),
                     config.p2p_config.excessive_message_size(),
                 )))
+                .layer(FaultInjectionLayer::new(network_faults))
                 .into_inner();
 
             let mut anemo_config = config.p2p_config.anemo_config.clone().unwrap_or_default();
@@ -551,6 +589,7 @@ impl SuiNode {
         accumulator: Arc<StateAccumulator>,
         connection_monitor_status: Arc<ConnectionMonitorStatus>,
         registry_service: &RegistryService,
+        consensus_pause: ConsensusPause,
     ) -> Result<ValidatorComponents> {
         let consensus_config = config
             .consensus_config()
@@ -597,6 +636,7 @@ impl SuiNode {
             validator_server_handle,
             checkpoint_metrics,
             sui_tx_validator_metrics,
+            consensus_pause,
         )
         .await
     }
@@ -615,6 +655,7 @@ impl SuiNode {
         validator_server_handle: JoinHandle<Result<()>>,
         checkpoint_metrics: Arc<CheckpointMetrics>,
         sui_tx_validator_metrics: Arc<SuiTxValidatorMetrics>,
+        consensus_pause: ConsensusPause,
     ) -> Result<ValidatorComponents> {
         let (checkpoint_service, checkpoint_service_exit) = Self::start_checkpoint_service(
             config,
@@ -642,6 +683,7 @@ impl SuiNode {
             low_scoring_authorities,
             committee,
             state.metrics.clone(),
+            consensus_pause,
         ));
 
         let new_epoch_start_state = epoch_store.epoch_start_state();
@@ -969,6 +1011,7 @@ impl SuiNode {
                             validator_server_handle,
                             checkpoint_metrics,
                             sui_tx_validator_metrics,
+                            self.consensus_pause.clone(),
                         )
                         .await?,
                     )
@@ -999,6 +1042,7 @@ impl SuiNode {
                             self.accumulator.clone(),
                             self.connection_monitor_status.clone(),
                             &self.registry_service,
+                            self.consensus_pause.clone(),
                         )
                         .await?,
                     )
@@ -1066,6 +1110,7 @@ fn send_trusted_peer_change(
 pub async fn build_server(
     state: Arc<AuthorityState>,
     transaction_orchestrator: &Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
+    state_sync_handle: &state_sync::Handle,
     config: &NodeConfig,
     prometheus_registry: &Registry,
 ) -> Result<Option<ServerHandle>> {
@@ -1076,7 +1121,7 @@ pub async fn build_server(
 
     let mut server = JsonRpcServerBuilder::new(env!("CARGO_PKG_VERSION"), prometheus_registry);
 
-    server.register_module(ReadApi::new(state.clone()))?;
+    server.register_module(ReadApi::new(state.clone(), state_sync_handle.clone()))?;
     server.register_module(CoinReadApi::new(state.clone()))?;
     server.register_module(TransactionBuilderApi::new(state.clone()))?;
     server.register_module(GovernanceReadApi::new(state.clone()))?;
@@ -13,7 +13,8 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc_types::{
-    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake, DryRunTransactionResponse,
+    Balance, Checkpoint, CheckpointId, CheckpointWithTransactions, Coin, CoinPage,
+    CoinSelectionStrategy, DelegatedStake, DevInspectResults, DryRunTransactionResponse,
     DynamicFieldPage, EventFilter, EventPage, ObjectsPage, SuiCoinMetadata, SuiCommittee, SuiEvent,
     SuiGetPastObjectRequest, SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse,
     SuiObjectResponseQuery, SuiPastObjectResponse, SuiTransactionEffectsAPI,
@@ -27,8 +28,11 @@ use sui_types::base_types::{
 use sui_types::committee::EpochId;
 use sui_types::error::TRANSACTION_NOT_FOUND_MSG_PREFIX;
 use sui_types::event::EventID;
-use sui_types::messages::{ExecuteTransactionRequestType, TransactionData, VerifiedTransaction};
+use sui_types::messages::{
+    ExecuteTransactionRequestType, TransactionData, TransactionKind, VerifiedTransaction,
+};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::query::TransactionFilter;
 
 use futures::StreamExt;
 use sui_json_rpc::api::{CoinReadApiClient, EventReadApiClient, ReadApiClient, WriteApiClient};
@@ -195,6 +199,24 @@ impl ReadApi {
             .await?)
     }
 
+    /// Subscribe to checkpoints as they are synced by the full node, in sequence number order,
+    /// optionally including the transactions of each checkpoint.
+    pub async fn subscribe_checkpoints(
+        &self,
+        options: Option<SuiTransactionResponseOptions>,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<CheckpointWithTransactions>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<CheckpointWithTransactions> =
+                    c.subscribe_checkpoint(options).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     pub fn get_transactions_stream(
         &self,
         query: SuiTransactionResponseQuery,
@@ -248,6 +270,28 @@ impl ReadApi {
             .dry_run_transaction(Base64::from_bytes(&bcs::to_bytes(&tx)?))
             .await?)
     }
+
+    /// Run the transaction of kind `tx` from `sender_address` without committing its effects,
+    /// returning the results of each of its commands. Unlike `dry_run_transaction`, the
+    /// transaction does not need gas and can call any Move function.
+    pub async fn dev_inspect_transaction(
+        &self,
+        sender_address: SuiAddress,
+        tx: TransactionKind,
+        gas_price: Option<u64>,
+        epoch: Option<EpochId>,
+    ) -> SuiRpcResult<DevInspectResults> {
+        Ok(self
+            .api
+            .http
+            .dev_inspect_transaction(
+                sender_address,
+                Base64::from_bytes(&bcs::to_bytes(&tx)?),
+                gas_price,
+                epoch,
+            )
+            .await?)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -340,16 +384,59 @@ impl CoinReadApi {
         Ok(coins)
     }
 
+    /// Select unlocked coins of `coin_type` owned by `address` covering `amount`, excluding the
+    /// coins in `exclude`. The selection is done by the full node, using the largest coins first
+    /// if no strategy is specified.
+    pub async fn select_coins_with_strategy(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        amount: u128,
+        exclude: Vec<ObjectID>,
+        strategy: Option<CoinSelectionStrategy>,
+    ) -> SuiRpcResult<Vec<Coin>> {
+        Ok(self
+            .api
+            .http
+            .select_coins(address, coin_type, amount, Some(exclude), strategy)
+            .await?)
+    }
+
     pub async fn get_balance(
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
     ) -> SuiRpcResult<Balance> {
-        Ok(self.api.http.get_balance(owner, coin_type).await?)
+        self.get_balance_at_checkpoint(owner, coin_type, None).await
+    }
+
+    /// Returns the balance of `coin_type` owned by `owner` at the end of `checkpoint`, or at the
+    /// latest state if it is `None`.
+    pub async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        checkpoint: Option<CheckpointId>,
+    ) -> SuiRpcResult<Balance> {
+        Ok(self
+            .api
+            .http
+            .get_balance(owner, coin_type, checkpoint)
+            .await?)
     }
 
     pub async fn get_all_balances(&self, owner: SuiAddress) -> SuiRpcResult<Vec<Balance>> {
-        Ok(self.api.http.get_all_balances(owner).await?)
+        self.get_all_balances_at_checkpoint(owner, None).await
+    }
+
+    /// Returns the balances of all coin types owned by `owner` at the end of `checkpoint`, or at
+    /// the latest state if it is `None`.
+    pub async fn get_all_balances_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        checkpoint: Option<CheckpointId>,
+    ) -> SuiRpcResult<Vec<Balance>> {
+        Ok(self.api.http.get_all_balances(owner, checkpoint).await?)
     }
 
     pub async fn get_coin_metadata(&self, coin_type: String) -> SuiRpcResult<SuiCoinMetadata> {
@@ -386,6 +473,22 @@ impl EventApi {
         }
     }
 
+    pub async fn subscribe_transaction(
+        &self,
+        filter: TransactionFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionResponse>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiTransactionResponse> =
+                    c.subscribe_transaction(filter).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     pub async fn get_events(&self, digest: TransactionDigest) -> SuiRpcResult<Vec<SuiEvent>> {
         Ok(self.api.http.get_events(digest).await?)
     }
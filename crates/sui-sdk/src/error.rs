@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use sui_types::base_types::{SuiAddress, TransactionDigest};
-use sui_types::error::UserInputError;
+use sui_types::error::{SuiError, UserInputError};
 use thiserror::Error;
 
 pub type SuiRpcResult<T = ()> = Result<T, Error>;
@@ -15,6 +15,8 @@ pub enum Error {
     PcsSerialisationError(#[from] bcs::Error),
     #[error(transparent)]
     UserInputError(#[from] UserInputError),
+    #[error(transparent)]
+    SuiError(#[from] SuiError),
     #[error("Subscription error : {0}")]
     Subscription(String),
     #[error("Encountered error when confirming tx status for {0:?}, err: {1:?}")]
@@ -23,6 +25,10 @@ pub enum Error {
     FailToConfirmTransactionStatus(TransactionDigest, u64),
     #[error("Data error: {0}")]
     DataError(String),
+    #[error("Signing error: {0}")]
+    SigningError(String),
+    #[error("Transaction dry run failed: {0}")]
+    DryRunFailed(String),
     #[error("Client/Server api version mismatch, client api version : {client_version}, server api version : {server_version}")]
     ServerVersionMismatch {
         client_version: String,
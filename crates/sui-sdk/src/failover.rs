@@ -0,0 +1,338 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use jsonrpsee::core::client::{BatchResponse, ClientT};
+use jsonrpsee::core::params::BatchRequestBuilder;
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::core::{Error as RpcError, JsonRawValue};
+use jsonrpsee::http_client::HttpClient;
+use jsonrpsee::rpc_params;
+use serde::de::DeserializeOwned;
+use tracing::{debug, warn};
+
+/// Method name prefixes, after the namespace, of the read methods which are safe to retry.
+const READ_METHOD_PREFIXES: [&str; 7] = [
+    "get",
+    "multiGet",
+    "tryGet",
+    "tryMultiGet",
+    "query",
+    "dryRun",
+    "devInspect",
+];
+
+/// Retry policy of idempotent read requests, the delay between two attempts starts at
+/// `initial_backoff` and doubles after each attempt up to `max_backoff`.
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+    pub max_retries: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Never retry failed requests.
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+}
+
+/// Request statistics of a fullnode endpoint.
+#[derive(Clone, Debug)]
+pub struct EndpointMetrics {
+    pub url: String,
+    /// Whether the endpoint answered its last request, unhealthy endpoints are only used again
+    /// once the health check interval has elapsed, or if no other endpoint is healthy.
+    pub healthy: bool,
+    pub requests: u64,
+    /// Number of requests which failed with a transport error or timed out.
+    pub failures: u64,
+    pub last_latency: Option<Duration>,
+    pub average_latency: Option<Duration>,
+    total_latency: Duration,
+    last_failure: Option<Instant>,
+}
+
+impl EndpointMetrics {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            healthy: true,
+            requests: 0,
+            failures: 0,
+            last_latency: None,
+            average_latency: None,
+            total_latency: Duration::ZERO,
+            last_failure: None,
+        }
+    }
+
+    fn record(&mut self, latency: Duration, success: bool) {
+        self.requests += 1;
+        self.last_latency = Some(latency);
+        self.total_latency += latency;
+        self.average_latency = Some(self.total_latency.div_f64(self.requests as f64));
+        self.healthy = success;
+        if !success {
+            self.failures += 1;
+            self.last_failure = Some(Instant::now());
+        }
+    }
+}
+
+struct Endpoint {
+    client: HttpClient,
+    metrics: Mutex<EndpointMetrics>,
+}
+
+/// A JSON-RPC HTTP client over a prioritized list of fullnode endpoints. Requests are sent to
+/// the first healthy endpoint and fail over once to each of the next ones on transport errors,
+/// writes only if they could not connect to the endpoint. Idempotent read requests are then
+/// retried according to the [RetryConfig].
+pub(crate) struct FailoverHttpClient {
+    endpoints: Vec<Endpoint>,
+    retry_config: RetryConfig,
+    health_check_interval: Duration,
+}
+
+impl FailoverHttpClient {
+    pub fn new(
+        clients: Vec<(String, HttpClient)>,
+        retry_config: RetryConfig,
+        health_check_interval: Duration,
+    ) -> Self {
+        assert!(!clients.is_empty(), "At least one endpoint is required");
+        Self {
+            endpoints: clients
+                .into_iter()
+                .map(|(url, client)| Endpoint {
+                    client,
+                    metrics: Mutex::new(EndpointMetrics::new(url)),
+                })
+                .collect(),
+            retry_config,
+            health_check_interval,
+        }
+    }
+
+    /// Check every endpoint and record whether it is reachable, fails if none is.
+    pub async fn check_health(&self) -> Result<(), RpcError> {
+        let mut last_error = None;
+        for endpoint in &self.endpoints {
+            let start = Instant::now();
+            let result: Result<u64, _> = endpoint
+                .client
+                .request("sui_getLatestCheckpointSequenceNumber", rpc_params![])
+                .await;
+            match result {
+                Err(e) if is_transport_error(&e) => {
+                    let url = {
+                        let mut metrics = endpoint.metrics();
+                        metrics.record(start.elapsed(), false);
+                        metrics.url.clone()
+                    };
+                    warn!(url = %url, "Fullnode endpoint health check failed: {e}");
+                    last_error = Some(e);
+                }
+                _ => endpoint.metrics().record(start.elapsed(), true),
+            }
+        }
+        let all_unhealthy = self
+            .endpoints
+            .iter()
+            .all(|endpoint| !endpoint.metrics().healthy);
+        match last_error {
+            Some(e) if all_unhealthy => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn metrics(&self) -> Vec<EndpointMetrics> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.metrics().clone())
+            .collect()
+    }
+
+    /// The first healthy endpoint in priority order. Unhealthy endpoints are considered healthy
+    /// again once the health check interval has elapsed since their last failure, and the
+    /// endpoint which failed the longest time ago is used if all of them are unhealthy.
+    fn select_endpoint(&self) -> usize {
+        self.endpoints
+            .iter()
+            .position(|endpoint| self.is_available(endpoint))
+            .or_else(|| {
+                (0..self.endpoints.len())
+                    .min_by_key(|index| self.endpoints[*index].metrics().last_failure)
+            })
+            .expect("At least one endpoint is required")
+    }
+
+    /// The first healthy endpoint in priority order which is not in `tried`, if any.
+    fn select_untried_endpoint(&self, tried: &[usize]) -> Option<usize> {
+        self.endpoints
+            .iter()
+            .enumerate()
+            .find(|(index, endpoint)| !tried.contains(index) && self.is_available(endpoint))
+            .map(|(index, _)| index)
+    }
+
+    fn is_available(&self, endpoint: &Endpoint) -> bool {
+        let metrics = endpoint.metrics();
+        metrics.healthy
+            || metrics.last_failure.map_or(true, |failure| {
+                failure.elapsed() >= self.health_check_interval
+            })
+    }
+}
+
+impl Endpoint {
+    fn metrics(&self) -> std::sync::MutexGuard<'_, EndpointMetrics> {
+        self.metrics.lock().unwrap()
+    }
+}
+
+impl Debug for FailoverHttpClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.endpoints.iter().map(|e| e.metrics().url.clone()))
+            .finish()
+    }
+}
+
+/// Already serialized request parameters, which can be sent multiple times.
+#[derive(Clone)]
+struct RawParams(Option<Box<JsonRawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<JsonRawValue>>, RpcError> {
+        Ok(self.0)
+    }
+}
+
+#[async_trait]
+impl ClientT for FailoverHttpClient {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), RpcError>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let endpoint = &self.endpoints[self.select_endpoint()];
+        let start = Instant::now();
+        let result = endpoint.client.notification(method, params).await;
+        let success = !matches!(&result, Err(e) if is_transport_error(e));
+        endpoint.metrics().record(start.elapsed(), success);
+        result
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, RpcError>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = RawParams(params.to_rpc_params()?);
+        let is_read = is_read_method(method);
+        let max_retries = if is_read {
+            self.retry_config.max_retries
+        } else {
+            0
+        };
+        let mut backoff = self.retry_config.initial_backoff;
+        let mut attempt = 0;
+        let mut tried = vec![];
+        let mut index = self.select_endpoint();
+        loop {
+            tried.push(index);
+            let endpoint = &self.endpoints[index];
+            let start = Instant::now();
+            let result = endpoint.client.request(method, params.clone()).await;
+            match result {
+                Err(e) if is_transport_error(&e) => {
+                    let url = {
+                        let mut metrics = endpoint.metrics();
+                        metrics.record(start.elapsed(), false);
+                        metrics.url.clone()
+                    };
+                    // Fail over to the next endpoint right away, whatever the retry policy. Writes
+                    // only fail over if they did not reach the endpoint, so they are never sent
+                    // twice.
+                    if is_read || is_connection_error(&e) {
+                        if let Some(next) = self.select_untried_endpoint(&tried) {
+                            debug!(url = %url, method, "Failing over request: {e}");
+                            index = next;
+                            continue;
+                        }
+                    }
+                    if attempt >= max_retries {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                    debug!(url = %url, method, attempt, "Retrying failed request: {e}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.retry_config.max_backoff);
+                    index = self.select_endpoint();
+                }
+                result => {
+                    endpoint.metrics().record(start.elapsed(), true);
+                    return result;
+                }
+            }
+        }
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, RpcError>
+    where
+        R: DeserializeOwned + Debug + 'a,
+    {
+        let endpoint = &self.endpoints[self.select_endpoint()];
+        let start = Instant::now();
+        let result = endpoint.client.batch_request(batch).await;
+        let success = !matches!(&result, Err(e) if is_transport_error(e));
+        endpoint.metrics().record(start.elapsed(), success);
+        result
+    }
+}
+
+fn is_transport_error(error: &RpcError) -> bool {
+    matches!(
+        error,
+        RpcError::Transport(_) | RpcError::RequestTimeout | RpcError::RestartNeeded(_)
+    )
+}
+
+/// Whether the request failed to connect to the endpoint, in which case it was not sent. hyper
+/// reports these failures as "error trying to connect", and jsonrpsee only keeps the message of
+/// the underlying error.
+fn is_connection_error(error: &RpcError) -> bool {
+    matches!(error, RpcError::Transport(e) if e.to_string().contains("error trying to connect"))
+}
+
+fn is_read_method(method: &str) -> bool {
+    if method == "rpc.discover" {
+        return true;
+    }
+    let name = method.split_once('_').map_or(method, |(_, name)| name);
+    READ_METHOD_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
@@ -8,28 +8,36 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use jsonrpsee::core::client::ClientT;
-use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
+use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClientBuilder};
 use jsonrpsee::rpc_params;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 
 use crate::error::{Error, SuiRpcResult};
+use crate::failover::{EndpointMetrics, FailoverHttpClient, RetryConfig};
+use crate::signer::Signer;
 use serde_json::Value;
 use sui_adapter::execution_mode::Normal;
 pub use sui_json as json;
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriver, ReadApi};
+use shared_crypto::intent::Intent;
 use sui_json_rpc::{
     CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER, CLIENT_TARGET_API_VERSION_HEADER,
 };
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    CheckpointId, ObjectsPage, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    CheckpointId, ObjectsPage, SuiExecutionStatus, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiTransactionEffectsAPI, SuiTransactionResponse,
+    SuiTransactionResponseOptions,
 };
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages::{Transaction, TransactionData, TransactionDataAPI};
 pub mod apis;
 pub mod error;
+pub mod failover;
+pub mod signer;
 pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
 const WAIT_FOR_TX_TIMEOUT_SEC: u64 = 60;
 
@@ -37,6 +45,8 @@ pub struct SuiClientBuilder {
     request_timeout: Duration,
     max_concurrent_requests: usize,
     ws_url: Option<String>,
+    retry_config: RetryConfig,
+    health_check_interval: Duration,
 }
 
 impl Default for SuiClientBuilder {
@@ -45,6 +55,8 @@ impl Default for SuiClientBuilder {
             request_timeout: Duration::from_secs(60),
             max_concurrent_requests: 256,
             ws_url: None,
+            retry_config: RetryConfig::default(),
+            health_check_interval: Duration::from_secs(30),
         }
     }
 }
@@ -65,7 +77,29 @@ impl SuiClientBuilder {
         self
     }
 
+    /// Retry policy of idempotent read requests failing with a transport error.
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// How long an endpoint which failed is avoided before requests are sent to it again.
+    pub fn health_check_interval(mut self, health_check_interval: Duration) -> Self {
+        self.health_check_interval = health_check_interval;
+        self
+    }
+
     pub async fn build(self, http: impl AsRef<str>) -> SuiRpcResult<SuiClient> {
+        self.build_with_endpoints([http]).await
+    }
+
+    /// Build a client over a list of fullnode endpoints in priority order, requests are sent to
+    /// the first healthy endpoint and fail over to the next ones on transport errors, writes only
+    /// if they could not connect to the endpoint.
+    pub async fn build_with_endpoints(
+        self,
+        endpoints: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> SuiRpcResult<SuiClient> {
         let client_version = env!("CARGO_PKG_VERSION");
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -93,12 +127,25 @@ impl SuiClientBuilder {
             None
         };
 
-        let http = HttpClientBuilder::default()
-            .max_request_body_size(2 << 30)
-            .max_concurrent_requests(self.max_concurrent_requests)
-            .set_headers(headers.clone())
-            .request_timeout(self.request_timeout)
-            .build(http)?;
+        let clients = endpoints
+            .into_iter()
+            .map(|url| {
+                let client = HttpClientBuilder::default()
+                    .max_request_body_size(2 << 30)
+                    .max_concurrent_requests(self.max_concurrent_requests)
+                    .set_headers(headers.clone())
+                    .request_timeout(self.request_timeout)
+                    .build(url.as_ref())?;
+                Ok((url.as_ref().to_string(), client))
+            })
+            .collect::<SuiRpcResult<Vec<_>>>()?;
+        if clients.is_empty() {
+            return Err(Error::DataError(
+                "At least one fullnode endpoint is required.".into(),
+            ));
+        }
+        let http = FailoverHttpClient::new(clients, self.retry_config, self.health_check_interval);
+        http.check_health().await?;
 
         let info = Self::get_server_info(&http, &ws).await?;
 
@@ -123,7 +170,7 @@ impl SuiClientBuilder {
     }
 
     async fn get_server_info(
-        http: &HttpClient,
+        http: &FailoverHttpClient,
         ws: &Option<WsClient>,
     ) -> Result<ServerInfo, Error> {
         let rpc_spec: Value = http.request("rpc.discover", rpc_params![]).await?;
@@ -179,7 +226,7 @@ pub struct SuiClient {
 }
 
 pub(crate) struct RpcClient {
-    http: HttpClient,
+    http: FailoverHttpClient,
     ws: Option<WsClient>,
     info: ServerInfo,
 }
@@ -213,6 +260,11 @@ impl SuiClient {
         &self.api.info.version
     }
 
+    /// Request statistics of each fullnode endpoint, in priority order.
+    pub fn endpoint_metrics(&self) -> Vec<EndpointMetrics> {
+        self.api.http.metrics()
+    }
+
     pub fn check_api_version(&self) -> SuiRpcResult<()> {
         let server_version = self.api_version();
         let client_version = env!("CARGO_PKG_VERSION");
@@ -245,6 +297,34 @@ impl SuiClient {
     pub fn governance_api(&self) -> &GovernanceApi {
         &self.governance_api
     }
+
+    /// Dry run `tx_data`, then sign it with `signer` and execute it if the dry run succeeded.
+    pub async fn sign_and_execute(
+        &self,
+        tx_data: TransactionData,
+        signer: &dyn Signer,
+        options: SuiTransactionResponseOptions,
+    ) -> SuiRpcResult<SuiTransactionResponse> {
+        if signer.address() != tx_data.sender() {
+            return Err(Error::SigningError(format!(
+                "Signer address [{}] does not match the transaction sender [{}]",
+                signer.address(),
+                tx_data.sender()
+            )));
+        }
+
+        let dry_run = self.read_api.dry_run_transaction(tx_data.clone()).await?;
+        if let SuiExecutionStatus::Failure { error } = dry_run.effects.into_status() {
+            return Err(Error::DryRunFailed(error));
+        }
+
+        let signature = signer.sign_transaction(&tx_data, Intent::default())?;
+        let tx = Transaction::from_generic_sig_data(tx_data, Intent::default(), vec![signature])
+            .verify()?;
+        self.quorum_driver
+            .execute_transaction(tx, options, None)
+            .await
+    }
 }
 
 #[async_trait]
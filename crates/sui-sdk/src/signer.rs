@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{Signature, SuiKeyPair};
+use sui_types::messages::TransactionData;
+use sui_types::multisig::{MultiSig, MultiSigPublicKey};
+use sui_types::signature::GenericSignature;
+
+use crate::error::{Error, SuiRpcResult};
+
+/// Signs transactions on behalf of a single address.
+pub trait Signer: Send + Sync {
+    /// The address of the signer, which must be the sender of the signed transactions.
+    fn address(&self) -> SuiAddress;
+
+    fn sign_transaction(
+        &self,
+        data: &TransactionData,
+        intent: Intent,
+    ) -> SuiRpcResult<GenericSignature>;
+}
+
+impl Signer for SuiKeyPair {
+    fn address(&self) -> SuiAddress {
+        (&self.public()).into()
+    }
+
+    fn sign_transaction(
+        &self,
+        data: &TransactionData,
+        intent: Intent,
+    ) -> SuiRpcResult<GenericSignature> {
+        Ok(Signature::new_secure(&IntentMessage::new(intent, data), self).into())
+    }
+}
+
+/// Signs with the key of `address` stored in a [Keystore].
+pub struct KeystoreSigner<'a> {
+    keystore: &'a Keystore,
+    address: SuiAddress,
+}
+
+impl<'a> KeystoreSigner<'a> {
+    pub fn new(keystore: &'a Keystore, address: SuiAddress) -> SuiRpcResult<Self> {
+        if !keystore.addresses().contains(&address) {
+            return Err(Error::SigningError(format!(
+                "Cannot find key for address: [{address}]"
+            )));
+        }
+        Ok(Self { keystore, address })
+    }
+}
+
+impl Signer for KeystoreSigner<'_> {
+    fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    fn sign_transaction(
+        &self,
+        data: &TransactionData,
+        intent: Intent,
+    ) -> SuiRpcResult<GenericSignature> {
+        Ok(self
+            .keystore
+            .sign_secure(&self.address, data, intent)
+            .map_err(|e| Error::SigningError(e.to_string()))?
+            .into())
+    }
+}
+
+/// Signs for a multisig address, by collecting the signatures of the member keys held locally
+/// and combining them into a [MultiSig]. The weights of the local members must reach the
+/// threshold of the multisig public key, otherwise partial signatures collected from other
+/// members can be combined with [MultiSigSigner::combine].
+pub struct MultiSigSigner<'a> {
+    multisig_pk: MultiSigPublicKey,
+    signers: Vec<&'a dyn Signer>,
+}
+
+impl<'a> MultiSigSigner<'a> {
+    pub fn new(multisig_pk: MultiSigPublicKey) -> Self {
+        Self {
+            multisig_pk,
+            signers: vec![],
+        }
+    }
+
+    pub fn with_signer(mut self, signer: &'a dyn Signer) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// Sign `data` with the local members only, returning their partial signatures.
+    pub fn partial_signatures(
+        &self,
+        data: &TransactionData,
+        intent: Intent,
+    ) -> SuiRpcResult<Vec<Signature>> {
+        self.signers
+            .iter()
+            .map(
+                |signer| match signer.sign_transaction(data, intent.clone())? {
+                    GenericSignature::Signature(signature) => Ok(signature),
+                    GenericSignature::MultiSig(_) => Err(Error::SigningError(
+                        "A multisig member cannot be a multisig".to_string(),
+                    )),
+                },
+            )
+            .collect()
+    }
+
+    /// Combine partial signatures of the members into a multisig signature.
+    pub fn combine(&self, partial_signatures: Vec<Signature>) -> SuiRpcResult<GenericSignature> {
+        let multisig = MultiSig::combine(partial_signatures, self.multisig_pk.clone())
+            .map_err(|e| Error::SigningError(e.to_string()))?;
+        Ok(GenericSignature::MultiSig(multisig))
+    }
+}
+
+impl Signer for MultiSigSigner<'_> {
+    fn address(&self) -> SuiAddress {
+        self.multisig_pk.clone().into()
+    }
+
+    fn sign_transaction(
+        &self,
+        data: &TransactionData,
+        intent: Intent,
+    ) -> SuiRpcResult<GenericSignature> {
+        self.combine(self.partial_signatures(data, intent)?)
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+use sui_json_rpc_types::{SuiTransactionEffectsAPI, SuiTransactionResponseOptions};
+use sui_sdk::failover::RetryConfig;
+use sui_sdk::SuiClientBuilder;
+use sui_types::messages::ExecuteTransactionRequestType;
+use test_utils::messages::make_transactions_with_wallet_context;
+use test_utils::network::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_failover_to_healthy_endpoint() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    // Nothing listens on this port, requests to it fail with a transport error.
+    let unreachable_url = "http://127.0.0.1:1";
+
+    let client = SuiClientBuilder::default()
+        .retry_config(RetryConfig {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+        })
+        .build_with_endpoints([unreachable_url, test_cluster.rpc_url()])
+        .await?;
+    let metrics = client.endpoint_metrics();
+    assert!(!metrics[0].healthy);
+    assert!(metrics[1].healthy);
+
+    client.read_api().get_total_transaction_number().await?;
+    let metrics = client.endpoint_metrics();
+    assert_eq!(unreachable_url, metrics[0].url);
+    assert_eq!(1, metrics[0].failures);
+    assert_eq!(0, metrics[1].failures);
+    assert!(metrics[1].requests >= 2);
+    assert!(metrics[1].average_latency.is_some());
+
+    // Building a client fails if no endpoint is reachable.
+    assert!(SuiClientBuilder::default()
+        .build_with_endpoints([unreachable_url])
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failover_without_retries() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let unreachable_url = "http://127.0.0.1:1";
+
+    let client = SuiClientBuilder::default()
+        .retry_config(RetryConfig::disabled())
+        // The unreachable endpoint is never avoided, every request is sent to it first.
+        .health_check_interval(Duration::ZERO)
+        .build_with_endpoints([unreachable_url, test_cluster.rpc_url()])
+        .await?;
+    let failures = client.endpoint_metrics()[0].failures;
+
+    client.read_api().get_total_transaction_number().await?;
+    assert_eq!(failures + 1, client.endpoint_metrics()[0].failures);
+
+    // Writes fail over too, since they could not connect to the unreachable endpoint.
+    let tx = make_transactions_with_wallet_context(&mut test_cluster.wallet, 1)
+        .await
+        .pop()
+        .unwrap();
+    let response = client
+        .quorum_driver()
+        .execute_transaction(
+            tx,
+            SuiTransactionResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+        )
+        .await?;
+    assert!(response.effects.unwrap().status().is_ok());
+    assert_eq!(failures + 2, client.endpoint_metrics()[0].failures);
+    Ok(())
+}
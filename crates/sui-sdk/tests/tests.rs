@@ -1,10 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use shared_crypto::intent::Intent;
 use tempfile::TempDir;
 
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::signer::{KeystoreSigner, MultiSigSigner, Signer};
+use sui_types::base_types::{random_object_ref, SuiAddress};
 use sui_types::crypto::Ed25519SuiSignature;
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_types::crypto::{get_key_pair, SignatureScheme, SuiKeyPair, SuiSignatureInner};
+use sui_types::messages::{Transaction, TransactionData};
+use sui_types::multisig::MultiSigPublicKey;
 #[test]
 fn mnemonic_test() {
     let temp_dir = TempDir::new().unwrap();
@@ -32,3 +37,38 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn signer_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    let keystore_signer = KeystoreSigner::new(&keystore, address)?;
+    assert!(KeystoreSigner::new(&keystore, SuiAddress::random_for_testing_only()).is_err());
+
+    let keypair = SuiKeyPair::Secp256k1(get_key_pair().1);
+    let multisig_pk = MultiSigPublicKey::new(
+        vec![keystore.get_key(&address)?.public(), keypair.public()],
+        vec![1, 1],
+        2,
+    )?;
+    let multisig_signer = MultiSigSigner::new(multisig_pk)
+        .with_signer(&keystore_signer)
+        .with_signer(&keypair);
+
+    let signers: [&dyn Signer; 3] = [&keystore_signer, &keypair, &multisig_signer];
+    for signer in signers {
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::random_for_testing_only(),
+            signer.address(),
+            None,
+            random_object_ref(),
+            10000,
+            1,
+        );
+        let signature = signer.sign_transaction(&data, Intent::default())?;
+        Transaction::from_generic_sig_data(data, Intent::default(), vec![signature]).verify()?;
+    }
+    Ok(())
+}
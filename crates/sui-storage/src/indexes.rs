@@ -5,25 +5,26 @@
 //! The main user of this data is the explorer.
 
 use std::cmp::{max, min};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use anyhow::anyhow;
+use itertools::Itertools;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, StructTag};
 use serde::{de::DeserializeOwned, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use sui_json_rpc_types::SuiObjectDataFilter;
-use sui_types::base_types::{
-    ObjectID, ObjectType, SuiAddress, TransactionDigest, TxSequenceNumber,
-};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest, TxSequenceNumber};
 use sui_types::base_types::{ObjectInfo, ObjectRef};
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::fp_ensure;
 use sui_types::messages::TransactionEvents;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::query::TransactionFilter;
 use typed_store::rocks::DBOptions;
@@ -36,9 +37,40 @@ type OwnerIndexKey = (SuiAddress, ObjectID);
 type DynamicFieldKey = (ObjectID, ObjectID);
 type EventId = (TxSequenceNumber, usize);
 type EventIndex = (TransactionEventsDigest, TransactionDigest, u64);
+type TransactionIter<'a> = Box<dyn Iterator<Item = (TxSequenceNumber, TransactionDigest)> + 'a>;
 
 pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
+/// The maximum difference between the timestamps of transactions indexed concurrently, whose
+/// sequence numbers may be in the opposite order.
+const MAX_TIMESTAMP_SKEW_MS: u64 = 1_000;
+
+/// Checkpoints of transactions, which are not tracked by the IndexStore itself.
+pub trait CheckpointIndex {
+    /// The checkpoint a transaction was finalized in.
+    fn checkpoint_of(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>>;
+
+    /// The transactions of a checkpoint, or `None` if it is not executed yet.
+    fn checkpoint_transactions(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<Vec<TransactionDigest>>>;
+}
+
+/// A filter checked for each transaction of another filter's results, with point lookups by
+/// sequence number.
+enum TransactionPredicate {
+    Filter(TransactionFilter),
+    /// The transactions of a partially specified move function, which are not keyed by sequence
+    /// number and are collected once with a scan of the function index.
+    Transactions(HashSet<TxSequenceNumber>),
+    And(Box<TransactionPredicate>, Box<TransactionPredicate>),
+    Or(Box<TransactionPredicate>, Box<TransactionPredicate>),
+}
+
 pub const MAX_GET_OWNED_OBJECT_SIZE: usize = 256;
 
 pub struct ObjectIndexChanges {
@@ -324,6 +356,7 @@ impl IndexStore {
         cursor: Option<TransactionDigest>,
         limit: Option<usize>,
         reverse: bool,
+        checkpoints: &dyn CheckpointIndex,
     ) -> Result<Vec<TransactionDigest>, anyhow::Error> {
         // Lookup TransactionDigest sequence number,
         let cursor = if let Some(cursor) = cursor {
@@ -354,6 +387,23 @@ impl IndexStore {
             Some(TransactionFilter::ToAddress(address)) => {
                 self.get_transactions_to_addr(address, cursor, limit, reverse)?
             }
+            Some(
+                filter @ (TransactionFilter::CheckpointRange { .. }
+                | TransactionFilter::TimeRange { .. }
+                | TransactionFilter::And(..)
+                | TransactionFilter::Or(..)),
+            ) => {
+                let iter = self
+                    .iter_transactions(&filter, cursor, reverse, checkpoints)?
+                    // skip the cursor itself, all iterators start at the cursor if it is `Some`
+                    .skip_while(|(seq, _)| Some(*seq) == cursor)
+                    .map(|(_, digest)| digest);
+                if let Some(limit) = limit {
+                    iter.take(limit).collect()
+                } else {
+                    iter.collect()
+                }
+            }
             None => {
                 let iter = self.tables.transaction_order.iter();
 
@@ -615,6 +665,380 @@ impl IndexStore {
         )
     }
 
+    /// Returns the sequence numbers and digests of transactions matching `filter` in sequence
+    /// number order, starting at `cursor` (inclusive) if it is `Some`.
+    fn iter_transactions<'a>(
+        &'a self,
+        filter: &TransactionFilter,
+        cursor: Option<TxSequenceNumber>,
+        reverse: bool,
+        checkpoints: &'a dyn CheckpointIndex,
+    ) -> SuiResult<TransactionIter<'a>> {
+        Ok(match filter {
+            TransactionFilter::MoveFunction {
+                package,
+                module: Some(module),
+                function: Some(function),
+            } => {
+                let (package, module, function) = (*package, module.clone(), function.clone());
+                let iter = self.tables.transactions_by_move_function.iter();
+                let iter: Box<dyn Iterator<Item = _> + '_> = if reverse {
+                    let key = (
+                        package,
+                        module.clone(),
+                        function.clone(),
+                        cursor.unwrap_or(TxSequenceNumber::MAX),
+                    );
+                    Box::new(iter.skip_prior_to(&key)?.reverse())
+                } else {
+                    let key = (
+                        package,
+                        module.clone(),
+                        function.clone(),
+                        cursor.unwrap_or(TxSequenceNumber::MIN),
+                    );
+                    Box::new(iter.skip_to(&key)?)
+                };
+                Box::new(
+                    iter.take_while(move |((id, m, f, _), _)| {
+                        *id == package && *m == module && *f == function
+                    })
+                    .map(|((_, _, _, seq), digest)| (seq, digest)),
+                )
+            }
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } => {
+                // Entries of a partially specified move function are not ordered by sequence
+                // number, collect and sort them.
+                let transactions = self
+                    .iter_move_function_transactions(*package, module, function)?
+                    .collect();
+                Box::new(Self::sorted_from_cursor(transactions, cursor, reverse))
+            }
+            TransactionFilter::InputObject(object_id) => Self::iter_transactions_from_index(
+                &self.tables.transactions_by_input_object_id,
+                *object_id,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::ChangedObject(object_id) => Self::iter_transactions_from_index(
+                &self.tables.transactions_by_mutated_object_id,
+                *object_id,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::FromAddress(address) => Self::iter_transactions_from_index(
+                &self.tables.transactions_from_addr,
+                *address,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::ToAddress(address) => Self::iter_transactions_from_index(
+                &self.tables.transactions_to_addr,
+                *address,
+                cursor,
+                reverse,
+            )?,
+            TransactionFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            } => {
+                // Read the transactions of the checkpoints in the range, up to the last executed
+                // one, rather than scanning all transactions.
+                let mut transactions = vec![];
+                for checkpoint in *start_checkpoint..*end_checkpoint {
+                    let Some(digests) = checkpoints.checkpoint_transactions(checkpoint)? else {
+                        break;
+                    };
+                    let seqs = self.tables.transactions_seq.multi_get(&digests)?;
+                    transactions.extend(
+                        seqs.into_iter()
+                            .zip(digests)
+                            .filter_map(|(seq, digest)| Some((seq?, digest))),
+                    );
+                }
+                Box::new(Self::sorted_from_cursor(transactions, cursor, reverse))
+            }
+            TransactionFilter::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                // Transactions are timestamped when they are indexed, so timestamps follow the
+                // sequence numbers up to the skew of concurrent indexing. Seek to the start of
+                // the range and stop past its end, with a margin for the skew.
+                let (start_time, end_time) = (*start_time, *end_time);
+                let iter = self.tables.transaction_order.iter();
+                let iter: TransactionIter = if reverse {
+                    let end =
+                        self.first_transaction_at(end_time.saturating_add(MAX_TIMESTAMP_SKEW_MS))?;
+                    let start = min(end, cursor.unwrap_or(TxSequenceNumber::MAX));
+                    Box::new(iter.skip_prior_to(&start)?.reverse().take_while(
+                        move |(_, digest)| {
+                            self.timestamp_or_warn(digest).map_or(false, |timestamp| {
+                                timestamp.saturating_add(MAX_TIMESTAMP_SKEW_MS) >= start_time
+                            })
+                        },
+                    ))
+                } else {
+                    let start = self
+                        .first_transaction_at(start_time.saturating_sub(MAX_TIMESTAMP_SKEW_MS))?;
+                    let start = max(start, cursor.unwrap_or(TxSequenceNumber::MIN));
+                    Box::new(iter.skip_to(&start)?.take_while(move |(_, digest)| {
+                        self.timestamp_or_warn(digest).map_or(false, |timestamp| {
+                            timestamp < end_time.saturating_add(MAX_TIMESTAMP_SKEW_MS)
+                        })
+                    }))
+                };
+                Box::new(iter.filter(move |(_, digest)| {
+                    matches!(
+                        self.timestamp_or_warn(digest),
+                        Some(timestamp) if start_time <= timestamp && timestamp < end_time
+                    )
+                }))
+            }
+            TransactionFilter::And(f1, f2) => {
+                // Drive the query with an indexed filter where possible and check the other
+                // filter for each transaction.
+                let (driver, predicate) = if f1.is_range() { (f2, f1) } else { (f1, f2) };
+                let predicate = self.predicate(predicate)?;
+                Box::new(
+                    self.iter_transactions(driver, cursor, reverse, checkpoints)?
+                        .filter(move |(seq, digest)| {
+                            self.transaction_matches_or_warn(&predicate, *seq, digest, checkpoints)
+                        }),
+                )
+            }
+            TransactionFilter::Or(f1, f2) => Box::new(
+                self.iter_transactions(f1, cursor, reverse, checkpoints)?
+                    .merge_by(
+                        self.iter_transactions(f2, cursor, reverse, checkpoints)?,
+                        move |(seq1, _), (seq2, _)| {
+                            if reverse {
+                                seq1 >= seq2
+                            } else {
+                                seq1 <= seq2
+                            }
+                        },
+                    )
+                    .dedup_by(|(seq1, _), (seq2, _)| seq1 == seq2),
+            ),
+        })
+    }
+
+    /// Orders `transactions` by sequence number, starting at `cursor` (inclusive) if it is `Some`.
+    fn sorted_from_cursor(
+        mut transactions: Vec<(TxSequenceNumber, TransactionDigest)>,
+        cursor: Option<TxSequenceNumber>,
+        reverse: bool,
+    ) -> impl Iterator<Item = (TxSequenceNumber, TransactionDigest)> {
+        transactions.retain(|(seq, _)| match cursor {
+            Some(cursor) if reverse => *seq <= cursor,
+            Some(cursor) => *seq >= cursor,
+            None => true,
+        });
+        transactions.sort_by_key(|(seq, _)| *seq);
+        if reverse {
+            transactions.reverse();
+        }
+        transactions.into_iter()
+    }
+
+    fn iter_transactions_from_index<'a, KeyT>(
+        index: &'a DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
+        key: KeyT,
+        cursor: Option<TxSequenceNumber>,
+        reverse: bool,
+    ) -> SuiResult<TransactionIter<'a>>
+    where
+        KeyT: Clone + Serialize + DeserializeOwned + PartialEq + 'a,
+    {
+        Ok(if reverse {
+            let iter = index
+                .iter()
+                .skip_prior_to(&(key.clone(), cursor.unwrap_or(TxSequenceNumber::MAX)))?
+                .reverse()
+                .take_while(move |((id, _), _)| *id == key)
+                .map(|((_, seq), digest)| (seq, digest));
+            Box::new(iter)
+        } else {
+            let iter = index
+                .iter()
+                .skip_to(&(key.clone(), cursor.unwrap_or(TxSequenceNumber::MIN)))?
+                .take_while(move |((id, _), _)| *id == key)
+                .map(|((_, seq), digest)| (seq, digest));
+            Box::new(iter)
+        })
+    }
+
+    /// Returns all transactions calling into `package`, optionally restricted to a module and
+    /// function, ordered by module and function first. Only the entries of the package, or of the
+    /// module if it is given, are scanned.
+    fn iter_move_function_transactions<'a>(
+        &'a self,
+        package: ObjectID,
+        module: &Option<String>,
+        function: &Option<String>,
+    ) -> SuiResult<impl Iterator<Item = (TxSequenceNumber, TransactionDigest)> + 'a> {
+        let (module, function) = (module.clone(), function.clone());
+        let key = (
+            package,
+            module.clone().unwrap_or_default(),
+            function
+                .clone()
+                .filter(|_| module.is_some())
+                .unwrap_or_default(),
+            TxSequenceNumber::MIN,
+        );
+        Ok(self
+            .tables
+            .transactions_by_move_function
+            .iter()
+            .skip_to(&key)?
+            .take_while(move |((id, m, _, _), _)| {
+                *id == package && module.as_ref().map(|x| x == m).unwrap_or(true)
+            })
+            .filter(move |((_, _, f, _), _)| function.as_ref().map(|x| x == f).unwrap_or(true))
+            .map(|((_, _, _, seq), digest)| (seq, digest)))
+    }
+
+    /// Returns the sequence number of the first transaction timestamped at or after
+    /// `timestamp_ms`, by binary search over the transactions in sequence number order.
+    fn first_transaction_at(&self, timestamp_ms: u64) -> SuiResult<TxSequenceNumber> {
+        let (mut low, mut high) = (TxSequenceNumber::MIN, self.next_sequence_number());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.tables.transaction_order.iter().skip_to(&mid)?.next() {
+                Some((seq, digest))
+                    if seq < high && self.indexed_timestamp(&digest)? < timestamp_ms =>
+                {
+                    low = seq + 1
+                }
+                _ => high = mid,
+            }
+        }
+        Ok(low)
+    }
+
+    /// The time a transaction was indexed at, 0 for transactions indexed before timestamps were.
+    fn indexed_timestamp(&self, digest: &TransactionDigest) -> SuiResult<u64> {
+        Ok(self.tables.timestamps.get(digest)?.unwrap_or_default())
+    }
+
+    fn timestamp_or_warn(&self, digest: &TransactionDigest) -> Option<u64> {
+        self.indexed_timestamp(digest)
+            .map_err(|e| warn!(?digest, "Failed to read transaction timestamp: {e}"))
+            .ok()
+    }
+
+    /// Prepares `filter` to be checked for each transaction of another filter's results.
+    fn predicate(&self, filter: &TransactionFilter) -> SuiResult<TransactionPredicate> {
+        Ok(match filter {
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } if module.is_none() || function.is_none() => TransactionPredicate::Transactions(
+                self.iter_move_function_transactions(*package, module, function)?
+                    .map(|(seq, _)| seq)
+                    .collect(),
+            ),
+            TransactionFilter::And(f1, f2) => TransactionPredicate::And(
+                Box::new(self.predicate(f1)?),
+                Box::new(self.predicate(f2)?),
+            ),
+            TransactionFilter::Or(f1, f2) => TransactionPredicate::Or(
+                Box::new(self.predicate(f1)?),
+                Box::new(self.predicate(f2)?),
+            ),
+            filter => TransactionPredicate::Filter(filter.clone()),
+        })
+    }
+
+    fn transaction_matches(
+        &self,
+        predicate: &TransactionPredicate,
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+        checkpoints: &dyn CheckpointIndex,
+    ) -> SuiResult<bool> {
+        let filter = match predicate {
+            TransactionPredicate::Transactions(transactions) => {
+                return Ok(transactions.contains(&seq))
+            }
+            TransactionPredicate::And(p1, p2) => {
+                return Ok(self.transaction_matches(p1, seq, digest, checkpoints)?
+                    && self.transaction_matches(p2, seq, digest, checkpoints)?)
+            }
+            TransactionPredicate::Or(p1, p2) => {
+                return Ok(self.transaction_matches(p1, seq, digest, checkpoints)?
+                    || self.transaction_matches(p2, seq, digest, checkpoints)?)
+            }
+            TransactionPredicate::Filter(filter) => filter,
+        };
+        Ok(match filter {
+            TransactionFilter::MoveFunction {
+                package,
+                module: Some(module),
+                function: Some(function),
+            } => self.tables.transactions_by_move_function.contains_key(&(
+                *package,
+                module.clone(),
+                function.clone(),
+                seq,
+            ))?,
+            TransactionFilter::InputObject(object_id) => self
+                .tables
+                .transactions_by_input_object_id
+                .contains_key(&(*object_id, seq))?,
+            TransactionFilter::ChangedObject(object_id) => self
+                .tables
+                .transactions_by_mutated_object_id
+                .contains_key(&(*object_id, seq))?,
+            TransactionFilter::FromAddress(address) => self
+                .tables
+                .transactions_from_addr
+                .contains_key(&(*address, seq))?,
+            TransactionFilter::ToAddress(address) => self
+                .tables
+                .transactions_to_addr
+                .contains_key(&(*address, seq))?,
+            TransactionFilter::CheckpointRange {
+                start_checkpoint,
+                end_checkpoint,
+            } => matches!(
+                checkpoints.checkpoint_of(digest)?,
+                Some(checkpoint) if *start_checkpoint <= checkpoint && checkpoint < *end_checkpoint
+            ),
+            TransactionFilter::TimeRange {
+                start_time,
+                end_time,
+            } => matches!(
+                self.tables.timestamps.get(digest)?,
+                Some(timestamp) if *start_time <= timestamp && timestamp < *end_time
+            ),
+            TransactionFilter::MoveFunction { .. }
+            | TransactionFilter::And(..)
+            | TransactionFilter::Or(..) => unreachable!("prepared by IndexStore::predicate"),
+        })
+    }
+
+    fn transaction_matches_or_warn(
+        &self,
+        predicate: &TransactionPredicate,
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+        checkpoints: &dyn CheckpointIndex,
+    ) -> bool {
+        self.transaction_matches(predicate, seq, digest, checkpoints)
+            .unwrap_or_else(|e| {
+                warn!(?digest, "Failed to evaluate transaction filter: {e}");
+                false
+            })
+    }
+
     pub fn get_transaction_seq(
         &self,
         digest: &TransactionDigest,
@@ -884,45 +1308,8 @@ impl IndexStore {
             .iter()
             // The object id 0 is the smallest possible
             .skip_to(&(owner, starting_object_id))?
-            .filter(move |((object_owner, _), obj_info)| {
-                let to_include: bool = match &filter {
-                    Some(SuiObjectDataFilter::StructType(struct_tag)) => {
-                        let obj_tag: StructTag = match obj_info.type_.clone().try_into() {
-                            Ok(tag) => tag,
-                            Err(_) => {
-                                return false;
-                            }
-                        };
-                        // If people do not provide type_params, we will match all type_params
-                        // e.g. `0x2::coin::Coin` can match `0x2::coin::Coin<0x2::sui::SUI>`
-                        if !struct_tag.type_params.is_empty()
-                            && struct_tag.type_params != obj_tag.type_params
-                        {
-                            return false;
-                        }
-                        return obj_tag.address == struct_tag.address
-                            && obj_tag.module == struct_tag.module
-                            && obj_tag.name == struct_tag.name;
-                    }
-                    Some(SuiObjectDataFilter::MoveModule { package, module }) => {
-                        if let ObjectType::Struct(o) = obj_info.clone().type_ {
-                            o.address().to_string() == package.to_string()
-                                && o.module().to_string() == module.to_string()
-                        } else {
-                            false
-                        }
-                    }
-                    Some(SuiObjectDataFilter::Package(package_id)) => {
-                        if let ObjectType::Struct(o) = obj_info.clone().type_ {
-                            o.address().to_string() == package_id.to_string()
-                        } else {
-                            false
-                        }
-                    }
-                    None => true,
-                };
-                object_owner == &owner && to_include
-            })
+            .take_while(move |((object_owner, _), _)| object_owner == &owner)
+            .filter(move |(_, obj_info)| filter.as_ref().map_or(true, |f| f.matches(obj_info)))
             .take(count)
             .map(|(_, object_info)| object_info))
     }
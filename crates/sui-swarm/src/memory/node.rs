@@ -1,10 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anemo::PeerId;
 use anyhow::anyhow;
 use anyhow::Result;
+#[cfg(feature = "fault-injection")]
+use std::collections::HashMap;
+#[cfg(feature = "fault-injection")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use sui_config::NodeConfig;
+#[cfg(feature = "fault-injection")]
+use sui_network::fault_injection::LinkFault;
 use sui_node::SuiNodeHandle;
 use sui_types::base_types::AuthorityName;
 use tap::TapFallible;
@@ -23,6 +30,12 @@ pub struct Node {
     container: Mutex<Option<Container>>,
     pub config: NodeConfig,
     runtime_type: RuntimeType,
+    /// Faults injected into the requests sent to each peer, applied again when the node restarts.
+    #[cfg(feature = "fault-injection")]
+    link_faults: Mutex<HashMap<PeerId, LinkFault>>,
+    /// Whether consensus is paused, applied again when the node restarts.
+    #[cfg(feature = "fault-injection")]
+    consensus_paused: AtomicBool,
 }
 
 impl Node {
@@ -37,6 +50,10 @@ impl Node {
             container: Default::default(),
             config,
             runtime_type: RuntimeType::SingleThreaded,
+            #[cfg(feature = "fault-injection")]
+            link_faults: Default::default(),
+            #[cfg(feature = "fault-injection")]
+            consensus_paused: AtomicBool::new(false),
         }
     }
 
@@ -45,6 +62,11 @@ impl Node {
         self.config.protocol_public_key()
     }
 
+    /// Return the `PeerId` of this Node on the p2p network
+    pub fn peer_id(&self) -> PeerId {
+        PeerId(self.config.network_key_pair().public().0.to_bytes())
+    }
+
     pub fn json_rpc_address(&self) -> std::net::SocketAddr {
         self.config.json_rpc_address
     }
@@ -54,6 +76,13 @@ impl Node {
         info!(name =% self.name().concise(), "starting in-memory node");
         *self.container.lock().unwrap() =
             Some(Container::spawn(self.config.clone(), self.runtime_type).await);
+        #[cfg(feature = "fault-injection")]
+        {
+            self.apply_link_faults();
+            if self.consensus_paused.load(Ordering::SeqCst) {
+                self.apply_consensus_pause();
+            }
+        }
         Ok(())
     }
 
@@ -77,6 +106,74 @@ impl Node {
             .map_or(false, |c| c.is_alive())
     }
 
+    /// Stop this Node and start it again from its on-disk data, as after a crash
+    pub async fn restart(&self) -> Result<()> {
+        self.stop();
+        self.start().await
+    }
+
+    /// Inject `fault` into the requests this Node sends to `peer` on the p2p network (state sync
+    /// and discovery), until it is cleared. Narwhal and gRPC requests are not affected. Faults
+    /// are kept when the Node restarts.
+    #[cfg(feature = "fault-injection")]
+    pub fn set_link_fault(&self, peer: PeerId, fault: LinkFault) {
+        self.link_faults.lock().unwrap().insert(peer, fault);
+        self.apply_link_faults();
+    }
+
+    /// Stop injecting faults into the requests this Node sends to `peer`
+    #[cfg(feature = "fault-injection")]
+    pub fn clear_link_fault(&self, peer: &PeerId) {
+        self.link_faults.lock().unwrap().remove(peer);
+        self.apply_link_faults();
+    }
+
+    /// Stop injecting faults into the requests this Node sends to any peer
+    #[cfg(feature = "fault-injection")]
+    pub fn clear_link_faults(&self) {
+        self.link_faults.lock().unwrap().clear();
+        self.apply_link_faults();
+    }
+
+    /// Stop processing consensus output on this Node, which must be a validator, until
+    /// [`Node::resume_consensus`] is called. Consensus stays paused when the Node restarts.
+    #[cfg(feature = "fault-injection")]
+    pub fn pause_consensus(&self) {
+        self.consensus_paused.store(true, Ordering::SeqCst);
+        self.apply_consensus_pause();
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn resume_consensus(&self) {
+        self.consensus_paused.store(false, Ordering::SeqCst);
+        self.apply_consensus_pause();
+    }
+
+    #[cfg(feature = "fault-injection")]
+    fn apply_link_faults(&self) {
+        if let Some(handle) = self.get_node_handle() {
+            let link_faults = self.link_faults.lock().unwrap().clone();
+            handle.with(|node| {
+                let network_faults = node.network_faults();
+                network_faults.clear_all();
+                for (peer, fault) in link_faults {
+                    network_faults.set(peer, fault);
+                }
+            });
+        }
+    }
+
+    #[cfg(feature = "fault-injection")]
+    fn apply_consensus_pause(&self) {
+        if let Some(handle) = self.get_node_handle() {
+            if self.consensus_paused.load(Ordering::SeqCst) {
+                handle.with(|node| node.pause_consensus());
+            } else {
+                handle.with(|node| node.resume_consensus());
+            }
+        }
+    }
+
     pub fn get_node_handle(&self) -> Option<SuiNodeHandle> {
         self.container
             .lock()
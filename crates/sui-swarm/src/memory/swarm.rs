@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::Node;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use futures::future::try_join_all;
 use rand::rngs::OsRng;
 use std::collections::HashMap;
@@ -18,6 +18,8 @@ use sui_config::builder::{
 use sui_config::genesis_config::{GenesisConfig, ValidatorConfigInfo};
 use sui_config::node::DBCheckpointConfig;
 use sui_config::NetworkConfig;
+#[cfg(feature = "fault-injection")]
+use sui_network::fault_injection::LinkFault;
 use sui_protocol_config::{ProtocolVersion, SupportedProtocolVersions};
 use sui_types::base_types::AuthorityName;
 use sui_types::object::Object;
@@ -326,6 +328,93 @@ impl Swarm {
     pub fn fullnodes(&self) -> impl Iterator<Item = &Node> {
         self.fullnodes.values()
     }
+
+    /// Attempt to lookup and return a shared reference to the Validator or Fullnode with the
+    /// provided `name`.
+    pub fn node(&self, name: AuthorityName) -> Option<&Node> {
+        self.validator(name).or_else(|| self.fullnode(name))
+    }
+
+    fn node_or_err(&self, name: AuthorityName) -> Result<&Node> {
+        self.node(name)
+            .ok_or_else(|| anyhow!("no node named {} in the swarm", name.concise()))
+    }
+
+    /// Stop the Validator or Fullnode with the provided `name`, keeping its on-disk data.
+    pub fn stop_node(&self, name: AuthorityName) -> Result<()> {
+        self.node_or_err(name)?.stop();
+        Ok(())
+    }
+
+    /// Start again the Validator or Fullnode with the provided `name` from its on-disk data.
+    pub async fn start_node(&self, name: AuthorityName) -> Result<()> {
+        self.node_or_err(name)?.start().await
+    }
+
+    /// Stop and start again the Validator or Fullnode with the provided `name`.
+    pub async fn restart_node(&self, name: AuthorityName) -> Result<()> {
+        self.node_or_err(name)?.restart().await
+    }
+
+    /// Inject `fault` into the p2p requests sent from the node `from` to the node `to`. The
+    /// requests sent from `to` to `from` are left unchanged.
+    #[cfg(feature = "fault-injection")]
+    pub fn set_link_fault(
+        &self,
+        from: AuthorityName,
+        to: AuthorityName,
+        fault: LinkFault,
+    ) -> Result<()> {
+        let to = self.node_or_err(to)?.peer_id();
+        self.node_or_err(from)?.set_link_fault(to, fault);
+        Ok(())
+    }
+
+    /// Stop injecting faults into the p2p requests sent from the node `from` to the node `to`.
+    #[cfg(feature = "fault-injection")]
+    pub fn clear_link_fault(&self, from: AuthorityName, to: AuthorityName) -> Result<()> {
+        let to = self.node_or_err(to)?.peer_id();
+        self.node_or_err(from)?.clear_link_fault(&to);
+        Ok(())
+    }
+
+    /// Drop all p2p requests between the nodes of `side_a` and the nodes of `side_b`, in both
+    /// directions, until [`Swarm::heal_partitions`] is called.
+    ///
+    /// Only the anemo p2p network is partitioned, so the nodes stop exchanging checkpoints through
+    /// state sync and peers through discovery. Narwhal and the validator gRPC API are not
+    /// affected: partitioned validators keep reaching consensus and making checkpoints, use
+    /// [`Swarm::pause_consensus`] to stall them.
+    #[cfg(feature = "fault-injection")]
+    pub fn partition(&self, side_a: &[AuthorityName], side_b: &[AuthorityName]) -> Result<()> {
+        for a in side_a {
+            for b in side_b {
+                self.set_link_fault(*a, *b, LinkFault::partition())?;
+                self.set_link_fault(*b, *a, LinkFault::partition())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop injecting faults into the p2p requests sent between any nodes of this Swarm.
+    #[cfg(feature = "fault-injection")]
+    pub fn heal_partitions(&self) {
+        self.validators()
+            .chain(self.fullnodes())
+            .for_each(|node| node.clear_link_faults());
+    }
+
+    /// Stop processing consensus output on all Validators, so that no transaction with shared
+    /// objects and no checkpoint makes progress until [`Swarm::resume_consensus`] is called.
+    #[cfg(feature = "fault-injection")]
+    pub fn pause_consensus(&self) {
+        self.validators().for_each(|node| node.pause_consensus());
+    }
+
+    #[cfg(feature = "fault-injection")]
+    pub fn resume_consensus(&self) {
+        self.validators().for_each(|node| node.resume_consensus());
+    }
 }
 
 #[derive(Debug)]
@@ -398,3 +487,97 @@ mod test {
         }
     }
 }
+
+/// Run with `cargo test -p sui-swarm --features fault-injection`.
+#[cfg(all(test, feature = "fault-injection"))]
+mod fault_injection_test {
+    use super::{Node, Swarm};
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+    use sui_network::fault_injection::LinkFault;
+
+    /// Highest checkpoint executed by `node`
+    fn highest_checkpoint(node: &Node) -> u64 {
+        node.get_node_handle().unwrap().with(|node| {
+            node.state()
+                .get_latest_checkpoint_sequence_number()
+                .unwrap_or(0)
+        })
+    }
+
+    async fn wait_for_checkpoint(node: &Node, checkpoint: u64) {
+        tokio::time::timeout(Duration::from_secs(60), async {
+            while highest_checkpoint(node) < checkpoint {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("node did not execute checkpoint {}", checkpoint));
+    }
+
+    /// Assert that `node` does not execute any checkpoint for a while, once the checkpoints in
+    /// flight when the fault was injected are executed.
+    async fn assert_checkpoints_stalled(node: &Node) {
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        let stalled = highest_checkpoint(node);
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(highest_checkpoint(node), stalled);
+    }
+
+    #[tokio::test]
+    async fn inject_faults() {
+        telemetry_subscribers::init_for_testing();
+        let mut swarm = Swarm::builder()
+            .committee_size(NonZeroUsize::new(4).unwrap())
+            .with_fullnode_count(1)
+            .build();
+
+        swarm.launch().await.unwrap();
+
+        let validators: Vec<_> = swarm.validators().map(|v| v.name()).collect();
+        let fullnode = swarm.fullnodes().next().unwrap().name();
+        let validator_node = swarm.validator(validators[0]).unwrap();
+        let fullnode_node = swarm.fullnode(fullnode).unwrap();
+        wait_for_checkpoint(fullnode_node, 1).await;
+
+        swarm.stop_node(validators[0]).unwrap();
+        assert!(!validator_node.is_running());
+        swarm.start_node(validators[0]).await.unwrap();
+        swarm.restart_node(fullnode).await.unwrap();
+
+        // the fullnode only gets checkpoints from state sync, which stalls when it is partitioned
+        // from the validators, while the validators keep making checkpoints through consensus
+        swarm.partition(&validators, &[fullnode]).unwrap();
+        assert_checkpoints_stalled(fullnode_node).await;
+        let checkpoint = highest_checkpoint(validator_node);
+        wait_for_checkpoint(validator_node, checkpoint + 1).await;
+        swarm.heal_partitions();
+        wait_for_checkpoint(fullnode_node, checkpoint + 1).await;
+
+        // no checkpoint is made while consensus is paused, even across restarts
+        swarm
+            .set_link_fault(
+                validators[1],
+                fullnode,
+                LinkFault::latency(Duration::from_millis(100)),
+            )
+            .unwrap();
+        swarm.partition(&validators[..2], &validators[2..]).unwrap();
+        swarm.pause_consensus();
+        swarm.restart_node(validators[2]).await.unwrap();
+        assert_checkpoints_stalled(validator_node).await;
+        let checkpoint = highest_checkpoint(validator_node);
+        swarm.resume_consensus();
+        swarm.heal_partitions();
+        wait_for_checkpoint(validator_node, checkpoint + 1).await;
+        wait_for_checkpoint(fullnode_node, checkpoint + 1).await;
+
+        for validator in swarm.validators() {
+            validator.health_check(true).await.unwrap();
+        }
+
+        for fullnode in swarm.fullnodes() {
+            fullnode.health_check(false).await.unwrap();
+        }
+    }
+}
@@ -1,15 +1,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::replay::{
+    replay_checkpoints, replay_transaction, DbReplaySource, ReplayOverrides, ReplayResult,
+    ReplaySource, RpcReplaySource,
+};
 use crate::{
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
     get_object, get_transaction, make_clients, restore_from_db_checkpoint, ConciseObjectOutput,
     GroupedObjectOutput, VerboseObjectOutput,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use std::path::PathBuf;
+use std::sync::Arc;
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
+use sui_core::state_snapshot::{export_state_snapshot, restore_state_snapshot};
+use sui_protocol_config::ProtocolVersion;
+use sui_storage::object_store::{ObjectStoreConfig, ObjectStoreType};
+use sui_types::committee::EpochId;
 
 use sui_types::{base_types::*, object::Owner};
 
@@ -91,7 +100,8 @@ pub enum ToolCommand {
     /// Tool to read validator & node db.
     #[clap(name = "db-tool")]
     DbTool {
-        /// Path of the DB to read
+        /// Path of the DB to read, the `store` directory of a node for the validator tables and the
+        /// `checkpoints` directory for the checkpoint tables
         #[clap(long = "db-path")]
         db_path: String,
         #[clap(subcommand)]
@@ -139,6 +149,136 @@ pub enum ToolCommand {
         #[clap(long = "db-checkpoint-path")]
         db_checkpoint_path: PathBuf,
     },
+
+    /// Export and restore state snapshots, the live object set at the end of an epoch.
+    #[clap(name = "snapshot")]
+    Snapshot {
+        #[clap(subcommand)]
+        cmd: SnapshotCommand,
+    },
+
+    /// Re-execute transactions with the versions of their input objects at the time they were
+    /// executed and compare the produced effects with the recorded ones.
+    #[clap(name = "replay")]
+    #[clap(group(ArgGroup::new("source").required(true).args(&["db_path", "rpc_url"])))]
+    #[clap(group(ArgGroup::new("target").required(true).args(&["tx_digest", "start_checkpoint"])))]
+    Replay {
+        /// Path of the node database, the `live` directory or a database checkpoint
+        #[clap(long = "db-path")]
+        db_path: Option<PathBuf>,
+        /// URL of a fullnode JSON-RPC endpoint, which can only replay transactions whose inputs
+        /// have not been modified since
+        #[clap(long = "rpc-url")]
+        rpc_url: Option<String>,
+        #[clap(long = "tx-digest")]
+        tx_digest: Option<TransactionDigest>,
+        /// First checkpoint of the transactions to replay
+        #[clap(long = "start-checkpoint")]
+        start_checkpoint: Option<CheckpointSequenceNumber>,
+        /// Last checkpoint of the transactions to replay, the start checkpoint if not set
+        #[clap(long = "end-checkpoint", requires = "start_checkpoint")]
+        end_checkpoint: Option<CheckpointSequenceNumber>,
+        /// Protocol version to execute with instead of the one of the transaction epoch
+        #[clap(long = "protocol-version")]
+        protocol_version: Option<u64>,
+        /// Start timestamp of the transaction epoch, required when it is not recorded by the
+        /// source, e.g. for the first epoch
+        #[clap(long = "epoch-start-timestamp-ms")]
+        epoch_start_timestamp_ms: Option<u64>,
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SnapshotCommand {
+    /// Export a state snapshot of the last epoch accumulated in a node database. The database
+    /// must be at the end of the epoch, e.g. a database checkpoint taken at the end of the epoch.
+    Export {
+        /// Path of the node database, the `live` directory or a database checkpoint
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(flatten)]
+        store: SnapshotStoreArgs,
+    },
+    /// Restore the state snapshot of an epoch into the empty database of a node, which then syncs
+    /// from the first checkpoint of the next epoch.
+    Restore {
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        #[clap(long)]
+        epoch: EpochId,
+        #[clap(flatten)]
+        store: SnapshotStoreArgs,
+    },
+}
+
+#[derive(Args)]
+#[clap(group(ArgGroup::new("snapshot-store").required(true).args(&["local_dir", "store_config"])))]
+pub struct SnapshotStoreArgs {
+    /// Local directory of the snapshots
+    #[clap(long = "local-dir")]
+    local_dir: Option<PathBuf>,
+    /// YAML file of the object store config of the snapshots, e.g. for S3 or GCS
+    #[clap(long = "store-config")]
+    store_config: Option<PathBuf>,
+}
+
+impl SnapshotStoreArgs {
+    fn object_store_config(&self) -> Result<ObjectStoreConfig> {
+        match (&self.local_dir, &self.store_config) {
+            (Some(local_dir), _) => Ok(ObjectStoreConfig {
+                object_store: Some(ObjectStoreType::File),
+                directory: Some(local_dir.clone()),
+                ..Default::default()
+            }),
+            (None, Some(store_config)) => {
+                let config = std::fs::read_to_string(store_config).map_err(|e| {
+                    anyhow!("Cannot read store config {}: {e}", store_config.display())
+                })?;
+                Ok(serde_yaml::from_str(&config)?)
+            }
+            (None, None) => Err(anyhow!("Missing snapshot store")),
+        }
+    }
+}
+
+fn print_replay_results(results: &[ReplayResult], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(results)?);
+    } else {
+        for result in results {
+            if result.differences.is_empty() {
+                println!("{}: effects match", result.digest);
+                continue;
+            }
+            println!(
+                "{}: effects differ with protocol version {}",
+                result.digest, result.protocol_version
+            );
+            if let Some(error) = &result.execution_error {
+                println!("  execution error: {error}");
+            }
+            for difference in &result.differences {
+                println!(
+                    "  {}: expected {}, actual {}",
+                    difference.path, difference.expected, difference.actual
+                );
+            }
+        }
+    }
+    let diverged = results
+        .iter()
+        .filter(|result| !result.differences.is_empty())
+        .count();
+    if diverged > 0 {
+        return Err(anyhow!(
+            "{diverged} of {} replayed transactions diverged",
+            results.len()
+        ));
+    }
+    Ok(())
 }
 
 trait OptionDebug<T> {
@@ -290,6 +430,68 @@ impl ToolCommand {
                 let config = sui_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::Snapshot { cmd } => match cmd {
+                SnapshotCommand::Export { db_path, store } => {
+                    let manifest =
+                        export_state_snapshot(&db_path, &store.object_store_config()?).await?;
+                    println!(
+                        "Exported {} objects of epoch {}, last checkpoint {}",
+                        manifest.num_objects,
+                        manifest.epoch,
+                        manifest.last_checkpoint.sequence_number()
+                    );
+                }
+                SnapshotCommand::Restore {
+                    config_path,
+                    epoch,
+                    store,
+                } => {
+                    let config = sui_config::NodeConfig::load(config_path)?;
+                    let manifest = restore_state_snapshot(
+                        &store.object_store_config()?,
+                        epoch,
+                        &config.db_path(),
+                        config.genesis()?,
+                        config.indirect_objects_threshold,
+                    )
+                    .await?;
+                    println!(
+                        "Restored {} objects of epoch {}, the node will sync from checkpoint {}",
+                        manifest.num_objects,
+                        manifest.epoch,
+                        manifest.last_checkpoint.sequence_number() + 1
+                    );
+                }
+            },
+            ToolCommand::Replay {
+                db_path,
+                rpc_url,
+                tx_digest,
+                start_checkpoint,
+                end_checkpoint,
+                protocol_version,
+                epoch_start_timestamp_ms,
+                json,
+            } => {
+                let source: Arc<dyn ReplaySource> = match (db_path, rpc_url) {
+                    (Some(db_path), _) => Arc::new(DbReplaySource::new(&db_path)),
+                    (None, Some(rpc_url)) => Arc::new(RpcReplaySource::new(&rpc_url).await?),
+                    (None, None) => return Err(anyhow!("Missing replay source")),
+                };
+                let overrides = ReplayOverrides {
+                    protocol_version: protocol_version.map(ProtocolVersion::new),
+                    epoch_start_timestamp_ms,
+                };
+                let results = match (tx_digest, start_checkpoint) {
+                    (Some(digest), _) => vec![replay_transaction(source, digest, overrides).await?],
+                    (None, Some(start)) => {
+                        let end = end_checkpoint.unwrap_or(start);
+                        replay_checkpoints(source, start..=end, overrides).await?
+                    }
+                    (None, None) => return Err(anyhow!("Missing transaction or checkpoint")),
+                };
+                print_replay_results(&results, json)?;
+            }
         };
         Ok(())
     }
@@ -11,6 +11,7 @@ use strum_macros::EnumString;
 use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority::authority_store_types::StoreData;
+use sui_core::checkpoints::CheckpointStore;
 use sui_core::epoch::committee_store::CommitteeStoreTables;
 use sui_storage::write_ahead_log::DBWriteAheadLogTables;
 use sui_storage::IndexStoreTables;
@@ -18,7 +19,7 @@ use sui_types::base_types::{EpochId, ObjectID};
 use sui_types::messages::{SignedTransactionEffects, TrustedCertificate};
 use sui_types::temporary_store::InnerTemporaryStore;
 use typed_store::rocks::{default_db_options, MetricConf};
-use typed_store::traits::{Map, TableSummary};
+use typed_store::traits::{DumpEntry, Map, TableSummary};
 
 #[derive(EnumString, Clone, Parser, Debug, ValueEnum)]
 pub enum StoreName {
@@ -26,7 +27,7 @@ pub enum StoreName {
     Index,
     Wal,
     Epoch,
-    // TODO: Add the new checkpoint v2 tables.
+    Checkpoint,
 }
 impl std::fmt::Display for StoreName {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -82,6 +83,7 @@ pub fn table_summary(
             CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
                 .table_summary(table_name)
         }
+        StoreName::Checkpoint => CheckpointStore::open_readonly(&db_path).table_summary(table_name),
     }
     .map_err(|err| anyhow!(err.to_string()))
 }
@@ -158,6 +160,55 @@ pub fn dump_table(
             CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
                 .dump(table_name, page_size, page_number)
         }
+        StoreName::Checkpoint => {
+            CheckpointStore::open_readonly(&db_path).dump(table_name, page_size, page_number)
+        }
+    }
+    .map_err(|err| anyhow!(err.to_string()))
+}
+
+/// Like `dump_table`, but with the keys and values serialized as JSON rather than formatted with
+/// their Debug representation.
+pub fn dump_table_json(
+    store_name: StoreName,
+    epoch: Option<EpochId>,
+    db_path: PathBuf,
+    table_name: &str,
+    page_size: u16,
+    page_number: usize,
+) -> anyhow::Result<Vec<DumpEntry>> {
+    match store_name {
+        StoreName::Validator => {
+            let epoch_tables = AuthorityEpochTables::describe_tables();
+            if epoch_tables.contains_key(table_name) {
+                let epoch = epoch.ok_or_else(|| anyhow!("--epoch is required"))?;
+                AuthorityEpochTables::open_readonly(epoch, &db_path).dump_json(
+                    table_name,
+                    page_size,
+                    page_number,
+                )
+            } else {
+                AuthorityPerpetualTables::open_readonly(&db_path).dump_json(
+                    table_name,
+                    page_size,
+                    page_number,
+                )
+            }
+        }
+        StoreName::Index => {
+            IndexStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .dump_json(table_name, page_size, page_number)
+        }
+        StoreName::Wal => Err(eyre!(
+            "Dumping WAL not yet supported. It requires kmowing the value type"
+        )),
+        StoreName::Epoch => {
+            CommitteeStoreTables::get_read_only_handle(db_path, None, None, MetricConf::default())
+                .dump_json(table_name, page_size, page_number)
+        }
+        StoreName::Checkpoint => {
+            CheckpointStore::open_readonly(&db_path).dump_json(table_name, page_size, page_number)
+        }
     }
     .map_err(|err| anyhow!(err.to_string()))
 }
@@ -166,8 +217,10 @@ pub fn dump_table(
 mod test {
     use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
     use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+    use sui_core::checkpoints::CheckpointStore;
+    use sui_types::base_types::ObjectRef;
 
-    use crate::db_tool::db_dump::{dump_table, list_tables, StoreName};
+    use crate::db_tool::db_dump::{dump_table, dump_table_json, list_tables, StoreName};
 
     #[tokio::test]
     async fn db_dump_population() -> Result<(), anyhow::Error> {
@@ -203,6 +256,24 @@ mod test {
                 missing_tables.push(t);
             }
         }
+
+        let checkpoint_path = primary_path.join("checkpoints");
+        let _ = CheckpointStore::new(&checkpoint_path);
+        for t in list_tables(checkpoint_path.clone()).unwrap() {
+            if dump_table(
+                StoreName::Checkpoint,
+                None,
+                checkpoint_path.clone(),
+                &t,
+                0,
+                0,
+            )
+            .is_err()
+            {
+                missing_tables.push(t);
+            }
+        }
+
         if missing_tables.is_empty() {
             return Ok(());
         }
@@ -215,4 +286,40 @@ mod test {
             )
         );
     }
+
+    #[tokio::test]
+    async fn db_dump_json() -> Result<(), anyhow::Error> {
+        let primary_path = tempfile::tempdir()?.into_path();
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let objects = network_config.genesis.objects();
+
+        let perpetual_tables = AuthorityPerpetualTables::open(&primary_path, None);
+        perpetual_tables.insert_live_objects(objects, 0)?;
+        drop(perpetual_tables);
+
+        let entries = dump_table_json(
+            StoreName::Validator,
+            None,
+            primary_path,
+            "parent_sync",
+            u16::MAX,
+            0,
+        )?;
+        assert_eq!(entries.len(), objects.len());
+        for entry in entries {
+            // The entries are the serde representation of the table types, so they can be read
+            // back as such.
+            let object_ref: ObjectRef = serde_json::from_value(entry.key)?;
+            let object = objects
+                .iter()
+                .find(|object| object.id() == object_ref.0)
+                .unwrap();
+            assert_eq!(object_ref, object.compute_object_reference());
+            assert_eq!(
+                entry.value,
+                serde_json::to_value(object.previous_transaction)?
+            );
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,249 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use serde::Serialize;
+use std::path::PathBuf;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::checkpoints::CheckpointStore;
+use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::event::Event;
+use sui_types::messages::{Transaction, TransactionEffects, TransactionEffectsAPI};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+};
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use typed_store::traits::Map;
+
+#[derive(Debug, Serialize)]
+pub struct TransactionOutput {
+    pub transaction: Option<Transaction>,
+    /// The effects of the transaction, if it was executed by the node.
+    pub effects: Option<TransactionEffects>,
+    pub events: Vec<Event>,
+    /// The epoch and sequence number of the checkpoint which executed the transaction.
+    pub checkpoint: Option<(EpochId, CheckpointSequenceNumber)>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckpointOutput {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: Option<CheckpointContents>,
+}
+
+/// Get an object from the perpetual tables at `db_path`, at `version` or at its latest version.
+pub fn get_object(
+    db_path: PathBuf,
+    object_id: ObjectID,
+    version: Option<SequenceNumber>,
+) -> anyhow::Result<Object> {
+    let perpetual_tables = AuthorityPerpetualTables::open_readonly(&db_path);
+    let store_object = match version {
+        Some(version) => perpetual_tables
+            .objects
+            .get(&ObjectKey(object_id, version))?
+            .ok_or_else(|| anyhow!("Object {object_id} not found at version {version}"))?,
+        None => perpetual_tables
+            .objects
+            .iter()
+            .skip_prior_to(&ObjectKey::max_for_id(&object_id))?
+            .next()
+            .filter(|(key, _)| key.0 == object_id)
+            .map(|(_, store_object)| store_object)
+            .ok_or_else(|| anyhow!("Object {object_id} not found"))?,
+    };
+    Ok(perpetual_tables.object(store_object)?)
+}
+
+/// Get a transaction, its effects and events from the perpetual tables at `db_path`.
+pub fn get_transaction(
+    db_path: PathBuf,
+    digest: TransactionDigest,
+) -> anyhow::Result<TransactionOutput> {
+    let perpetual_tables = AuthorityPerpetualTables::open_readonly(&db_path);
+    let transaction = perpetual_tables
+        .transactions
+        .get(&digest)?
+        .map(|transaction| transaction.into_inner());
+    let effects = match perpetual_tables.executed_effects.get(&digest)? {
+        Some(effects_digest) => perpetual_tables.effects.get(&effects_digest)?,
+        None => None,
+    };
+    if transaction.is_none() && effects.is_none() {
+        return Err(anyhow!("Transaction {digest} not found"));
+    }
+    let events = match effects.as_ref().and_then(|effects| effects.events_digest()) {
+        Some(events_digest) => perpetual_tables
+            .events
+            .iter()
+            .skip_to(&(*events_digest, 0))?
+            .take_while(|((digest, _), _)| digest == events_digest)
+            .map(|(_, event)| event)
+            .collect(),
+        None => vec![],
+    };
+    let checkpoint = perpetual_tables
+        .executed_transactions_to_checkpoint
+        .get(&digest)?;
+    Ok(TransactionOutput {
+        transaction,
+        effects,
+        events,
+        checkpoint,
+    })
+}
+
+/// Get a checkpoint and its contents from the checkpoint store at `db_path`, the latest certified
+/// checkpoint if `sequence_number` is not set.
+pub fn get_checkpoint(
+    db_path: PathBuf,
+    sequence_number: Option<CheckpointSequenceNumber>,
+) -> anyhow::Result<CheckpointOutput> {
+    let checkpoint_store = CheckpointStore::open_readonly(&db_path);
+    let checkpoint = match sequence_number {
+        Some(sequence_number) => checkpoint_store
+            .certified_checkpoints
+            .get(&sequence_number)?
+            .ok_or_else(|| anyhow!("Checkpoint {sequence_number} not found"))?,
+        None => checkpoint_store
+            .certified_checkpoints
+            .iter()
+            .skip_to_last()
+            .next()
+            .map(|(_, checkpoint)| checkpoint)
+            .ok_or_else(|| anyhow!("No checkpoint found"))?,
+    }
+    .into_inner();
+    let contents = checkpoint_store
+        .checkpoint_content
+        .get(&checkpoint.content_digest)?;
+    Ok(CheckpointOutput {
+        checkpoint,
+        contents,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use sui_config::genesis::Genesis;
+    use sui_core::authority::AuthorityStore;
+    use sui_core::checkpoints::CheckpointStore;
+    use sui_types::base_types::SequenceNumber;
+    use sui_types::messages::{TransactionEffectsAPI, VerifiedTransaction};
+    use sui_types::object::Object;
+    use sui_types::temporary_store::InnerTemporaryStore;
+
+    use crate::db_tool::db_lookup::{get_checkpoint, get_object, get_transaction};
+
+    /// A validator database in which the genesis transaction has been executed, writing `objects`
+    /// as its outputs.
+    pub(crate) async fn executed_genesis_database(
+        genesis: &Genesis,
+        objects: &[Object],
+    ) -> anyhow::Result<PathBuf> {
+        let path = tempfile::tempdir()?.into_path();
+        let store = AuthorityStore::open_with_committee_for_testing(
+            &path,
+            None,
+            &genesis.committee()?,
+            genesis,
+            0,
+        )
+        .await?;
+        let effects = genesis.effects();
+        let written = effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|(object_ref, _, kind)| {
+                let object = objects
+                    .iter()
+                    .find(|object| object.id() == object_ref.0)
+                    .unwrap();
+                (object_ref.0, (*object_ref, object.clone(), kind))
+            })
+            .collect();
+        let deleted = effects
+            .all_deleted()
+            .into_iter()
+            .map(|(object_ref, kind)| (object_ref.0, (object_ref.1, kind)))
+            .collect();
+        let inner_temporary_store = InnerTemporaryStore {
+            objects: BTreeMap::new(),
+            mutable_inputs: vec![],
+            written,
+            deleted,
+            events: genesis.events().clone(),
+        };
+        let transaction = VerifiedTransaction::new_unchecked(genesis.transaction().clone());
+        store
+            .update_state(inner_temporary_store, &transaction, effects)
+            .await?;
+        Ok(path)
+    }
+
+    #[tokio::test]
+    async fn db_lookup_genesis() -> Result<(), anyhow::Error> {
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let genesis = &network_config.genesis;
+        let path = executed_genesis_database(genesis, genesis.objects()).await?;
+
+        let digest = *genesis.transaction().digest();
+        let output = get_transaction(path.clone(), digest)?;
+        assert_eq!(output.transaction.as_ref(), Some(genesis.transaction()));
+        assert_eq!(output.effects.as_ref(), Some(genesis.effects()));
+        assert_eq!(output.events, genesis.events().data);
+        // The output is serialized with the serde representation of the stored types.
+        let json = serde_json::to_value(&output)?;
+        assert_eq!(json["effects"], serde_json::to_value(genesis.effects())?);
+        assert_eq!(
+            json["events"],
+            serde_json::to_value(&genesis.events().data)?
+        );
+
+        for object in genesis.objects() {
+            assert_eq!(&get_object(path.clone(), object.id(), None)?, object);
+            assert_eq!(
+                &get_object(path.clone(), object.id(), Some(object.version()))?,
+                object
+            );
+        }
+        let object_id = genesis.objects()[0].id();
+        assert!(get_object(path, object_id, Some(SequenceNumber::MAX)).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn db_lookup_checkpoint() -> Result<(), anyhow::Error> {
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let genesis = &network_config.genesis;
+        let path = tempfile::tempdir()?.into_path();
+
+        let checkpoint_store = CheckpointStore::new(&path);
+        checkpoint_store.insert_verified_checkpoint(genesis.checkpoint())?;
+        checkpoint_store.insert_checkpoint_contents(genesis.checkpoint_contents().clone())?;
+        drop(checkpoint_store);
+
+        let output = get_checkpoint(path.clone(), None)?;
+        assert_eq!(output.checkpoint, genesis.checkpoint().into_inner());
+        assert_eq!(
+            output.contents.as_ref(),
+            Some(genesis.checkpoint_contents())
+        );
+        let json = serde_json::to_value(&output)?;
+        assert_eq!(
+            json["contents"],
+            serde_json::to_value(genesis.checkpoint_contents())?
+        );
+
+        assert_eq!(
+            get_checkpoint(path.clone(), Some(0))?.checkpoint,
+            output.checkpoint
+        );
+        assert!(get_checkpoint(path, Some(1)).is_err());
+        Ok(())
+    }
+}
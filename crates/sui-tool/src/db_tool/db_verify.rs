@@ -0,0 +1,259 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use std::path::PathBuf;
+use sui_core::authority::authority_store_tables::{
+    AuthorityPerpetualTables, AuthorityPerpetualTablesReadOnly,
+};
+use sui_types::base_types::{
+    ObjectDigest, ObjectID, ObjectRef, SequenceNumber, TransactionDigest, TransactionEffectsDigest,
+};
+use sui_types::messages::TransactionEffectsAPI;
+use sui_types::object::Object;
+use sui_types::storage::{DeleteKind, ObjectKey};
+use typed_store::traits::Map;
+
+/// An inconsistency between the effects of an executed transaction and the other tables.
+#[derive(Debug, Serialize)]
+pub enum Inconsistency {
+    MissingEffects {
+        transaction: TransactionDigest,
+        effects: TransactionEffectsDigest,
+    },
+    MissingTransaction {
+        transaction: TransactionDigest,
+    },
+    MissingEvents {
+        transaction: TransactionDigest,
+    },
+    /// A written object is neither in the objects table nor pruned.
+    MissingObject {
+        transaction: TransactionDigest,
+        object: ObjectRef,
+    },
+    ObjectDigestMismatch {
+        transaction: TransactionDigest,
+        object: ObjectRef,
+        digest: ObjectDigest,
+    },
+    UnreadableObject {
+        transaction: TransactionDigest,
+        object: ObjectRef,
+        error: String,
+    },
+    /// A written or deleted object is not in the parent_sync table.
+    MissingParent {
+        transaction: TransactionDigest,
+        object: ObjectRef,
+    },
+    /// The parent_sync table links an object to another transaction.
+    ParentMismatch {
+        transaction: TransactionDigest,
+        object: ObjectRef,
+        parent: TransactionDigest,
+    },
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VerifyReport {
+    pub transactions: u64,
+    pub objects: u64,
+    /// Object versions which are not in the objects table because they have been pruned.
+    pub pruned_objects: u64,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+/// Check the effects of every executed transaction against the transaction, events, objects and
+/// parent_sync tables of the perpetual tables at `db_path`. The database is opened read-only, so
+/// this can run against the database of a live node.
+pub fn verify(db_path: PathBuf) -> anyhow::Result<VerifyReport> {
+    let tables = AuthorityPerpetualTables::open_readonly(&db_path);
+    let mut report = VerifyReport::default();
+
+    for (transaction, effects_digest) in tables.executed_effects.iter() {
+        report.transactions += 1;
+        let effects = match tables.effects.get(&effects_digest)? {
+            Some(effects) => effects,
+            None => {
+                report.inconsistencies.push(Inconsistency::MissingEffects {
+                    transaction,
+                    effects: effects_digest,
+                });
+                continue;
+            }
+        };
+        if !tables.transactions.contains_key(&transaction)? {
+            report
+                .inconsistencies
+                .push(Inconsistency::MissingTransaction { transaction });
+        }
+        if let Some(events_digest) = effects.events_digest() {
+            let has_events = tables
+                .events
+                .iter()
+                .skip_to(&(*events_digest, 0))?
+                .next()
+                .map_or(false, |((digest, _), _)| digest == *events_digest);
+            if !has_events {
+                report
+                    .inconsistencies
+                    .push(Inconsistency::MissingEvents { transaction });
+            }
+        }
+
+        for (object_ref, _, _) in effects.all_changed_objects() {
+            report.objects += 1;
+            check_parent(&tables, &mut report, transaction, *object_ref)?;
+            match tables.objects.get(&ObjectKey::from(object_ref))? {
+                Some(store_object) => match tables.object(store_object) {
+                    Ok(object) => {
+                        check_object_digest(&mut report, transaction, object_ref, &object)
+                    }
+                    Err(e) => report
+                        .inconsistencies
+                        .push(Inconsistency::UnreadableObject {
+                            transaction,
+                            object: *object_ref,
+                            error: e.to_string(),
+                        }),
+                },
+                None if is_pruned(&tables, object_ref.0, object_ref.1)? => {
+                    report.pruned_objects += 1
+                }
+                None => report.inconsistencies.push(Inconsistency::MissingObject {
+                    transaction,
+                    object: *object_ref,
+                }),
+            }
+        }
+        for (object_ref, kind) in effects.all_deleted() {
+            let digest = if kind == DeleteKind::Wrap {
+                ObjectDigest::OBJECT_DIGEST_WRAPPED
+            } else {
+                ObjectDigest::OBJECT_DIGEST_DELETED
+            };
+            check_parent(
+                &tables,
+                &mut report,
+                transaction,
+                (object_ref.0, object_ref.1, digest),
+            )?;
+        }
+    }
+    Ok(report)
+}
+
+fn check_object_digest(
+    report: &mut VerifyReport,
+    transaction: TransactionDigest,
+    object_ref: &ObjectRef,
+    object: &Object,
+) {
+    let digest = object.digest();
+    if digest != object_ref.2 {
+        report
+            .inconsistencies
+            .push(Inconsistency::ObjectDigestMismatch {
+                transaction,
+                object: *object_ref,
+                digest,
+            });
+    }
+}
+
+fn check_parent(
+    tables: &AuthorityPerpetualTablesReadOnly,
+    report: &mut VerifyReport,
+    transaction: TransactionDigest,
+    object_ref: ObjectRef,
+) -> anyhow::Result<()> {
+    match tables.parent_sync.get(&object_ref)? {
+        Some(parent) if parent == transaction => {}
+        Some(parent) => report.inconsistencies.push(Inconsistency::ParentMismatch {
+            transaction,
+            object: object_ref,
+            parent,
+        }),
+        None => report.inconsistencies.push(Inconsistency::MissingParent {
+            transaction,
+            object: object_ref,
+        }),
+    }
+    Ok(())
+}
+
+/// Only object versions which have been overwritten by a later version can be pruned, and the
+/// parent_sync table, which is never pruned, has an entry for every version.
+fn is_pruned(
+    tables: &AuthorityPerpetualTablesReadOnly,
+    object_id: ObjectID,
+    version: SequenceNumber,
+) -> anyhow::Result<bool> {
+    let latest = tables
+        .parent_sync
+        .iter()
+        .skip_prior_to(&(object_id, SequenceNumber::MAX, ObjectDigest::MAX))?
+        .next()
+        .filter(|((id, _, _), _)| *id == object_id);
+    Ok(matches!(latest, Some(((_, latest_version, _), _)) if latest_version > version))
+}
+
+#[cfg(test)]
+mod test {
+    use sui_types::base_types::TransactionDigest;
+    use sui_types::messages::TransactionEffectsAPI;
+
+    use crate::db_tool::db_lookup::test::executed_genesis_database;
+    use crate::db_tool::db_verify::{verify, Inconsistency};
+
+    #[tokio::test]
+    async fn db_verify_genesis() -> Result<(), anyhow::Error> {
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let genesis = &network_config.genesis;
+        let path = executed_genesis_database(genesis, genesis.objects()).await?;
+
+        let report = verify(path)?;
+        assert_eq!(report.transactions, 1);
+        assert_eq!(
+            report.objects,
+            genesis.effects().all_changed_objects().len() as u64
+        );
+        assert_eq!(report.pruned_objects, 0);
+        assert!(report.inconsistencies.is_empty());
+        let json = serde_json::to_value(&report)?;
+        assert_eq!(json["inconsistencies"], serde_json::json!([]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn db_verify_object_digest_mismatch() -> Result<(), anyhow::Error> {
+        let network_config = sui_config::builder::ConfigBuilder::new_with_temp_dir().build();
+        let genesis = &network_config.genesis;
+        let object_ref = *genesis.effects().all_changed_objects()[0].0;
+        let mut objects = genesis.objects().to_vec();
+        let object = objects
+            .iter_mut()
+            .find(|object| object.id() == object_ref.0)
+            .unwrap();
+        object.previous_transaction = TransactionDigest::random();
+        let digest = object.digest();
+        let path = executed_genesis_database(genesis, &objects).await?;
+
+        let report = verify(path)?;
+        assert_eq!(report.inconsistencies.len(), 1);
+        assert!(matches!(
+            &report.inconsistencies[0],
+            Inconsistency::ObjectDigestMismatch { transaction, object, digest: found }
+                if transaction == genesis.transaction().digest()
+                    && *object == object_ref
+                    && *found == digest
+        ));
+        let json = serde_json::to_value(&report)?;
+        assert_eq!(
+            json["inconsistencies"][0]["ObjectDigestMismatch"]["digest"],
+            serde_json::to_value(digest)?
+        );
+        Ok(())
+    }
+}
@@ -1,12 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
+use self::db_dump::{
+    dump_table, dump_table_json, duplicate_objects_summary, list_tables, table_summary, StoreName,
+};
+use self::db_lookup::{get_checkpoint, get_object, get_transaction};
+use self::db_verify::verify;
 use clap::Parser;
+use serde::Serialize;
+use std::fmt::Debug;
 use std::path::PathBuf;
-use sui_types::base_types::EpochId;
+use sui_types::base_types::{EpochId, ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 pub mod db_dump;
+pub mod db_lookup;
+pub mod db_verify;
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
@@ -15,6 +24,15 @@ pub enum DbToolCommand {
     Dump(Dump),
     TableSummary(Dump),
     DuplicatesSummary,
+    /// Print an object of the validator store, by default at its latest version
+    GetObject(GetObject),
+    /// Print a transaction of the validator store, along with its effects and events
+    GetTransaction(GetTransaction),
+    /// Print a checkpoint of the checkpoint store and its contents, by default the latest one
+    GetCheckpoint(GetCheckpoint),
+    /// Cross-check the effects of the executed transactions of the validator store against its
+    /// transactions, events and objects, and report the inconsistencies
+    Verify(Verify),
 }
 
 #[derive(Parser)]
@@ -39,6 +57,49 @@ pub struct Dump {
     /// The epoch to use when loading AuthorityEpochTables.
     #[clap(long = "epoch")]
     epoch: Option<EpochId>,
+    /// Print the entries as JSON
+    #[clap(long = "json")]
+    json: bool,
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct GetObject {
+    #[clap(long = "id")]
+    id: ObjectID,
+    #[clap(long = "version")]
+    version: Option<u64>,
+    /// Print the object as JSON
+    #[clap(long = "json")]
+    json: bool,
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct GetTransaction {
+    #[clap(long = "digest")]
+    digest: TransactionDigest,
+    /// Print the transaction as JSON
+    #[clap(long = "json")]
+    json: bool,
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct GetCheckpoint {
+    #[clap(long = "sequence-number")]
+    sequence_number: Option<CheckpointSequenceNumber>,
+    /// Print the checkpoint as JSON
+    #[clap(long = "json")]
+    json: bool,
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct Verify {
+    /// Print the report as JSON
+    #[clap(long = "json")]
+    json: bool,
 }
 
 pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
@@ -51,12 +112,33 @@ pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::
             &d.table_name,
             d.page_size,
             d.page_number,
+            d.json,
         ),
         DbToolCommand::TableSummary(d) => {
             print_db_table_summary(d.store_name, d.epoch, db_path, &d.table_name)
         }
         DbToolCommand::DuplicatesSummary => print_db_duplicates_summary(db_path),
+        DbToolCommand::GetObject(g) => print_output(
+            &get_object(db_path, g.id, g.version.map(SequenceNumber::from))?,
+            g.json,
+        ),
+        DbToolCommand::GetTransaction(g) => {
+            print_output(&get_transaction(db_path, g.digest)?, g.json)
+        }
+        DbToolCommand::GetCheckpoint(g) => {
+            print_output(&get_checkpoint(db_path, g.sequence_number)?, g.json)
+        }
+        DbToolCommand::Verify(v) => print_db_verify_report(db_path, v.json),
+    }
+}
+
+fn print_output<T: Serialize + Debug>(output: &T, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(output)?);
+    } else {
+        println!("{:#?}", output);
     }
+    Ok(())
 }
 
 pub fn print_db_all_tables(db_path: PathBuf) -> anyhow::Result<()> {
@@ -112,9 +194,38 @@ pub fn print_all_entries(
     table_name: &str,
     page_size: u16,
     page_number: usize,
+    json: bool,
 ) -> anyhow::Result<()> {
-    for (k, v) in dump_table(store, epoch, path, table_name, page_size, page_number)? {
+    if json {
+        let entries = dump_table_json(store, epoch, path, table_name, page_size, page_number)?;
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    let entries = dump_table(store, epoch, path, table_name, page_size, page_number)?;
+    for (k, v) in entries {
         println!("{:>100?}: {:?}", k, v);
     }
     Ok(())
 }
+
+pub fn print_db_verify_report(path: PathBuf, json: bool) -> anyhow::Result<()> {
+    let report = verify(path)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "Checked {} transactions and {} objects, {} objects were pruned",
+            report.transactions, report.objects, report.pruned_objects
+        );
+        for inconsistency in &report.inconsistencies {
+            println!("{:?}", inconsistency);
+        }
+    }
+    if !report.inconsistencies.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Found {} inconsistencies",
+            report.inconsistencies.len()
+        ));
+    }
+    Ok(())
+}
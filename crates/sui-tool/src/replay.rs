@@ -0,0 +1,788 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-execution of historical transactions against the versions of their input objects at the
+//! time they were executed, to compare the produced effects with the recorded ones.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use move_binary_format::CompiledModule;
+use move_bytecode_utils::module_cache::GetModule;
+use move_core_types::language_storage::ModuleId;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use sui_adapter::{adapter, execution_engine, execution_mode};
+use sui_core::authority::authority_store_tables::{
+    AuthorityPerpetualTables, AuthorityPerpetualTablesReadOnly,
+};
+use sui_core::authority::epoch_start_configuration::EpochStartConfigTrait;
+use sui_core::checkpoints::{CheckpointStore, CheckpointStoreReadOnly};
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_sdk::rpc_types::{
+    CheckpointId, SuiObjectDataOptions, SuiPastObjectResponse, SuiTransactionEffects,
+    SuiTransactionEffectsAPI, SuiTransactionResponseOptions,
+};
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::base_types::{
+    EpochId, ObjectDigest, ObjectID, ObjectRef, SequenceNumber, TransactionDigest,
+};
+use sui_types::digests::CheckpointDigest;
+use sui_types::epoch_data::EpochData;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::gas::{self, SuiCostTable, SuiGasStatus};
+use sui_types::messages::{
+    InputObjectKind, InputObjects, SenderSignedData, TransactionDataAPI, TransactionEffectsAPI,
+    TransactionKind,
+};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::{Object, Owner};
+use sui_types::storage::{
+    get_module_by_id, BackingPackageStore, ChildObjectResolver, ObjectKey, ObjectStore, ParentSync,
+};
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+use sui_types::temporary_store::TemporaryStore;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+use tokio::runtime::Handle;
+use typed_store::traits::Map;
+
+/// A transaction along with the effects it produced when it was executed.
+pub struct ExecutedTransaction {
+    pub data: SenderSignedData,
+    pub effects: SuiTransactionEffects,
+    /// Versions of the objects modified by the transaction, when recorded by the source. The
+    /// versions of the mutated child objects cannot be known otherwise.
+    pub modified_at_versions: Vec<(ObjectID, SequenceNumber)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EpochInfo {
+    pub protocol_version: ProtocolVersion,
+    pub epoch_start_timestamp_ms: u64,
+}
+
+/// A source of historical transactions and object versions.
+#[async_trait]
+pub trait ReplaySource: Send + Sync {
+    async fn get_transaction(&self, digest: TransactionDigest) -> Result<ExecutedTransaction>;
+
+    async fn get_object(&self, id: ObjectID, version: SequenceNumber) -> Result<Option<Object>>;
+
+    /// The latest version of an object lower than `version`. This is used for the packages and
+    /// the child objects loaded during execution, whose versions are not recorded.
+    async fn get_object_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>>;
+
+    /// The latest reference of an object lower than `version`, including deleted and wrapped
+    /// objects.
+    async fn get_parent_entry_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<ObjectRef>>;
+
+    async fn get_epoch_info(&self, epoch: EpochId) -> Result<EpochInfo>;
+
+    async fn get_checkpoint_transactions(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<TransactionDigest>>;
+}
+
+/// Replays from the database of a node, opened read-only.
+pub struct DbReplaySource {
+    perpetual_tables: AuthorityPerpetualTablesReadOnly,
+    checkpoint_store: CheckpointStoreReadOnly,
+}
+
+impl DbReplaySource {
+    /// `db_path` is the database directory of the node, which contains the `store` and
+    /// `checkpoints` directories.
+    pub fn new(db_path: &Path) -> Self {
+        Self {
+            perpetual_tables: AuthorityPerpetualTables::open_readonly(&db_path.join("store")),
+            checkpoint_store: CheckpointStore::open_readonly(&db_path.join("checkpoints")),
+        }
+    }
+
+    fn get_checkpoint_digests(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<TransactionDigest>> {
+        let checkpoint = self
+            .checkpoint_store
+            .certified_checkpoints
+            .get(&sequence_number)?
+            .ok_or_else(|| anyhow!("Checkpoint {sequence_number} not found"))?;
+        let contents = self
+            .checkpoint_store
+            .checkpoint_content
+            .get(&checkpoint.inner().content_digest)?
+            .ok_or_else(|| anyhow!("Contents of checkpoint {sequence_number} not found"))?;
+        Ok(contents.iter().map(|digests| digests.transaction).collect())
+    }
+}
+
+#[async_trait]
+impl ReplaySource for DbReplaySource {
+    async fn get_transaction(&self, digest: TransactionDigest) -> Result<ExecutedTransaction> {
+        let transaction = self
+            .perpetual_tables
+            .transactions
+            .get(&digest)?
+            .ok_or_else(|| anyhow!("Transaction {digest} not found"))?;
+        let effects_digest = self
+            .perpetual_tables
+            .executed_effects
+            .get(&digest)?
+            .ok_or_else(|| anyhow!("Transaction {digest} was not executed"))?;
+        let effects = self
+            .perpetual_tables
+            .effects
+            .get(&effects_digest)?
+            .ok_or_else(|| anyhow!("Effects {effects_digest} not found"))?;
+        Ok(ExecutedTransaction {
+            data: transaction.into_inner().into_data(),
+            modified_at_versions: effects.modified_at_versions().to_vec(),
+            effects: effects.try_into()?,
+        })
+    }
+
+    async fn get_object(&self, id: ObjectID, version: SequenceNumber) -> Result<Option<Object>> {
+        match self.perpetual_tables.objects.get(&ObjectKey(id, version))? {
+            Some(store_object) => Ok(Some(self.perpetual_tables.object(store_object)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_object_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>> {
+        let version = match previous_version(version) {
+            Some(version) => version,
+            None => return Ok(None),
+        };
+        let store_object = self
+            .perpetual_tables
+            .objects
+            .iter()
+            .skip_prior_to(&ObjectKey(id, version))?
+            .next()
+            .filter(|(key, _)| key.0 == id);
+        match store_object {
+            Some((_, store_object)) => Ok(Some(self.perpetual_tables.object(store_object)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_parent_entry_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<ObjectRef>> {
+        let version = match previous_version(version) {
+            Some(version) => version,
+            None => return Ok(None),
+        };
+        Ok(self
+            .perpetual_tables
+            .parent_sync
+            .iter()
+            .skip_prior_to(&(id, version, ObjectDigest::MAX))?
+            .next()
+            .map(|(object_ref, _)| object_ref)
+            .filter(|object_ref| object_ref.0 == id))
+    }
+
+    async fn get_epoch_info(&self, epoch: EpochId) -> Result<EpochInfo> {
+        if let Some(config) = self.perpetual_tables.epoch_start_configuration.get(&())? {
+            let state = config.epoch_start_state();
+            if state.epoch() == epoch {
+                return Ok(EpochInfo {
+                    protocol_version: state.protocol_version(),
+                    epoch_start_timestamp_ms: state.epoch_start_timestamp_ms(),
+                });
+            }
+        }
+        // Otherwise the epoch was started by the last transaction of the previous epoch.
+        let previous_epoch = epoch
+            .checked_sub(1)
+            .ok_or_else(|| anyhow!("The protocol version of epoch 0 is not recorded"))?;
+        let last_checkpoint = self
+            .checkpoint_store
+            .epoch_last_checkpoint_map
+            .get(&previous_epoch)?
+            .ok_or_else(|| anyhow!("Last checkpoint of epoch {previous_epoch} not found"))?;
+        let digest = self
+            .get_checkpoint_digests(last_checkpoint)?
+            .pop()
+            .ok_or_else(|| anyhow!("Checkpoint {last_checkpoint} is empty"))?;
+        let transaction = self.get_transaction(digest).await?;
+        match transaction.data.transaction_data().kind() {
+            TransactionKind::ChangeEpoch(change_epoch) => Ok(EpochInfo {
+                protocol_version: change_epoch.protocol_version,
+                epoch_start_timestamp_ms: change_epoch.epoch_start_timestamp_ms,
+            }),
+            _ => Err(anyhow!(
+                "Last transaction of epoch {previous_epoch} is not a change epoch transaction"
+            )),
+        }
+    }
+
+    async fn get_checkpoint_transactions(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<TransactionDigest>> {
+        self.get_checkpoint_digests(sequence_number)
+    }
+}
+
+/// Replays from a fullnode through JSON-RPC. The versions of the objects modified by a
+/// transaction are not served, so a transaction mutating child objects, or reading objects which
+/// have been modified since, cannot be replayed from a fullnode.
+pub struct RpcReplaySource {
+    client: SuiClient,
+}
+
+impl RpcReplaySource {
+    pub async fn new(rpc_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: SuiClientBuilder::default().build(rpc_url).await?,
+        })
+    }
+
+    async fn get_latest_object_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>> {
+        let response = self
+            .client
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::bcs_lossless())
+            .await?;
+        let object = match response.into_object() {
+            Ok(object) => object,
+            Err(_) => return Ok(None),
+        };
+        if object.version >= version {
+            return Err(anyhow!(
+                "Object {id} was modified after version {version}, its previous version is \
+                 unknown to the fullnode, replay from a database instead"
+            ));
+        }
+        Ok(Some(object.try_into()?))
+    }
+}
+
+#[async_trait]
+impl ReplaySource for RpcReplaySource {
+    async fn get_transaction(&self, digest: TransactionDigest) -> Result<ExecutedTransaction> {
+        let response = self
+            .client
+            .read_api()
+            .get_transaction_with_options(
+                digest,
+                SuiTransactionResponseOptions::new()
+                    .with_raw_input()
+                    .with_effects(),
+            )
+            .await?;
+        Ok(ExecutedTransaction {
+            data: bcs::from_bytes(&response.raw_transaction)?,
+            effects: response
+                .effects
+                .ok_or_else(|| anyhow!("Effects of transaction {digest} not found"))?,
+            modified_at_versions: vec![],
+        })
+    }
+
+    async fn get_object(&self, id: ObjectID, version: SequenceNumber) -> Result<Option<Object>> {
+        let response = self
+            .client
+            .read_api()
+            .try_get_parsed_past_object(id, version, SuiObjectDataOptions::bcs_lossless())
+            .await?;
+        match response {
+            SuiPastObjectResponse::VersionFound(object) => Ok(Some(object.try_into()?)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn get_object_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>> {
+        self.get_latest_object_before(id, version).await
+    }
+
+    async fn get_parent_entry_before(
+        &self,
+        id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<ObjectRef>> {
+        Ok(self
+            .get_latest_object_before(id, version)
+            .await?
+            .map(|object| object.compute_object_reference()))
+    }
+
+    async fn get_epoch_info(&self, epoch: EpochId) -> Result<EpochInfo> {
+        let system_state = self
+            .client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?;
+        if system_state.epoch != epoch {
+            return Err(anyhow!(
+                "The protocol version of past epoch {epoch} is unknown to the fullnode"
+            ));
+        }
+        Ok(EpochInfo {
+            protocol_version: ProtocolVersion::new(system_state.protocol_version),
+            epoch_start_timestamp_ms: system_state.epoch_start_timestamp_ms,
+        })
+    }
+
+    async fn get_checkpoint_transactions(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Vec<TransactionDigest>> {
+        Ok(self
+            .client
+            .read_api()
+            .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+            .await?
+            .transactions)
+    }
+}
+
+/// Values used instead of the ones of the source, e.g. to replay with another protocol version.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReplayOverrides {
+    pub protocol_version: Option<ProtocolVersion>,
+    pub epoch_start_timestamp_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EffectsDifference {
+    /// Path of the field in the JSON representation of the effects.
+    pub path: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayResult {
+    pub digest: TransactionDigest,
+    pub protocol_version: u64,
+    pub execution_error: Option<String>,
+    pub differences: Vec<EffectsDifference>,
+}
+
+/// Replay the transactions of a range of checkpoints. The genesis checkpoint is skipped, as the
+/// genesis transaction is not executed by the execution engine.
+pub async fn replay_checkpoints(
+    source: Arc<dyn ReplaySource>,
+    checkpoints: RangeInclusive<CheckpointSequenceNumber>,
+    overrides: ReplayOverrides,
+) -> Result<Vec<ReplayResult>> {
+    let mut results = vec![];
+    for sequence_number in (*checkpoints.start()).max(1)..=*checkpoints.end() {
+        for digest in source.get_checkpoint_transactions(sequence_number).await? {
+            results.push(replay_transaction(source.clone(), digest, overrides).await?);
+        }
+    }
+    Ok(results)
+}
+
+pub async fn replay_transaction(
+    source: Arc<dyn ReplaySource>,
+    digest: TransactionDigest,
+    overrides: ReplayOverrides,
+) -> Result<ReplayResult> {
+    let ExecutedTransaction {
+        data,
+        effects: expected_effects,
+        modified_at_versions,
+    } = source.get_transaction(digest).await?;
+    let transaction_data = data.transaction_data().clone();
+    if matches!(transaction_data.kind(), TransactionKind::Genesis(_)) {
+        return Err(anyhow!("The genesis transaction cannot be replayed"));
+    }
+
+    let epoch = expected_effects.executed_epoch();
+    let epoch_info = match overrides {
+        ReplayOverrides {
+            protocol_version: Some(protocol_version),
+            epoch_start_timestamp_ms: Some(epoch_start_timestamp_ms),
+        } => EpochInfo {
+            protocol_version,
+            epoch_start_timestamp_ms,
+        },
+        _ => {
+            let epoch_info = source.get_epoch_info(epoch).await?;
+            EpochInfo {
+                protocol_version: overrides
+                    .protocol_version
+                    .unwrap_or(epoch_info.protocol_version),
+                epoch_start_timestamp_ms: overrides
+                    .epoch_start_timestamp_ms
+                    .unwrap_or(epoch_info.epoch_start_timestamp_ms),
+            }
+        }
+    };
+    let protocol_config = ProtocolConfig::get_for_version(epoch_info.protocol_version);
+
+    // All the objects written or deleted by a transaction get its lamport version.
+    let lamport_version = expected_effects
+        .created()
+        .iter()
+        .chain(expected_effects.mutated())
+        .chain(expected_effects.unwrapped())
+        .map(|object| object.reference.version)
+        .chain(
+            expected_effects
+                .deleted()
+                .iter()
+                .chain(expected_effects.wrapped())
+                .map(|object_ref| object_ref.version),
+        )
+        .next()
+        .ok_or_else(|| anyhow!("Transaction {digest} did not modify any object"))?;
+    let shared_object_versions: BTreeMap<_, _> = expected_effects
+        .shared_objects()
+        .iter()
+        .map(|object_ref| (object_ref.object_id, object_ref.version))
+        .collect();
+
+    let mut input_objects = vec![];
+    for kind in transaction_data.input_objects()? {
+        let object = match &kind {
+            InputObjectKind::MovePackage(id) => {
+                source.get_object_before(*id, lamport_version).await?
+            }
+            InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                source.get_object(*id, *version).await?
+            }
+            InputObjectKind::SharedMoveObject { id, .. } => {
+                let version = shared_object_versions.get(id).ok_or_else(|| {
+                    anyhow!("Version of shared object {id} not found in the effects")
+                })?;
+                source.get_object(*id, *version).await?
+            }
+        };
+        let object = object.ok_or_else(|| anyhow!("Input object {:?} not found", kind))?;
+        input_objects.push((kind, object));
+    }
+
+    let gas_status = if transaction_data.kind().is_system_tx() {
+        SuiGasStatus::new_unmetered()
+    } else {
+        gas::start_gas_metering(
+            transaction_data.gas_budget(),
+            transaction_data.gas_price(),
+            protocol_config.storage_gas_price(),
+            SuiCostTable::new(&protocol_config),
+        )?
+    };
+    let store = ReplayStore {
+        source,
+        runtime: Handle::current(),
+        lamport_version,
+        modified_at_versions: modified_at_versions.into_iter().collect(),
+        objects: Mutex::new(BTreeMap::new()),
+    };
+    let epoch_data = EpochData::new(
+        epoch,
+        epoch_info.epoch_start_timestamp_ms,
+        CheckpointDigest::default(),
+    );
+
+    // The store blocks on the source to load the objects read during execution.
+    let (effects, execution_error) = tokio::task::spawn_blocking(move || {
+        let native_functions =
+            sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+        let move_vm = Arc::new(
+            adapter::new_move_vm(native_functions, &protocol_config)
+                .expect("We defined natives to not fail here"),
+        );
+        let input_objects = InputObjects::new(input_objects);
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store = TemporaryStore::new(store, input_objects, digest, &protocol_config);
+        let (kind, signer, gas) = transaction_data.execution_parts();
+        let (_, effects, result) =
+            execution_engine::execute_transaction_to_effects::<execution_mode::Normal, _>(
+                shared_object_refs,
+                temporary_store,
+                kind,
+                signer,
+                &gas,
+                digest,
+                transaction_dependencies,
+                &move_vm,
+                gas_status,
+                &epoch_data,
+                &protocol_config,
+            );
+        (effects, result.err().map(|e| e.to_string()))
+    })
+    .await?;
+
+    let mut differences = vec![];
+    diff_json(
+        String::new(),
+        &serde_json::to_value(&expected_effects)?,
+        &serde_json::to_value(SuiTransactionEffects::try_from(effects)?)?,
+        &mut differences,
+    );
+    Ok(ReplayResult {
+        digest,
+        protocol_version: epoch_info.protocol_version.as_u64(),
+        execution_error,
+        differences,
+    })
+}
+
+fn diff_json(
+    path: String,
+    expected: &Value,
+    actual: &Value,
+    differences: &mut Vec<EffectsDifference>,
+) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let keys: BTreeSet<_> = expected.keys().chain(actual.keys()).collect();
+            for key in keys {
+                diff_json(
+                    format!("{path}.{key}"),
+                    expected.get(key).unwrap_or(&Value::Null),
+                    actual.get(key).unwrap_or(&Value::Null),
+                    differences,
+                );
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                diff_json(format!("{path}[{i}]"), expected, actual, differences);
+            }
+        }
+        (expected, actual) if expected != actual => differences.push(EffectsDifference {
+            path,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn previous_version(version: SequenceNumber) -> Option<SequenceNumber> {
+    version.value().checked_sub(1).map(SequenceNumber::from)
+}
+
+/// The state read during the execution of a replayed transaction, other than its input objects.
+struct ReplayStore {
+    source: Arc<dyn ReplaySource>,
+    runtime: Handle,
+    lamport_version: SequenceNumber,
+    modified_at_versions: BTreeMap<ObjectID, SequenceNumber>,
+    objects: Mutex<BTreeMap<ObjectID, Option<Object>>>,
+}
+
+impl ReplayStore {
+    fn object(&self, id: &ObjectID) -> SuiResult<Option<Object>> {
+        if let Some(object) = self.objects.lock().unwrap().get(id) {
+            return Ok(object.clone());
+        }
+        let object = self
+            .runtime
+            .block_on(async {
+                match self.modified_at_versions.get(id) {
+                    Some(version) => self.source.get_object(*id, *version).await,
+                    None => {
+                        self.source
+                            .get_object_before(*id, self.lamport_version)
+                            .await
+                    }
+                }
+            })
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+        self.objects.lock().unwrap().insert(*id, object.clone());
+        Ok(object)
+    }
+}
+
+impl BackingPackageStore for ReplayStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.object(package_id)
+    }
+}
+
+impl ChildObjectResolver for ReplayStore {
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        let child_object = match self.object(child)? {
+            None => return Ok(None),
+            Some(object) => object,
+        };
+        let parent = *parent;
+        if child_object.owner != Owner::ObjectOwner(parent.into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: parent,
+                actual_owner: child_object.owner,
+            });
+        }
+        Ok(Some(child_object))
+    }
+}
+
+impl ParentSync for ReplayStore {
+    fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
+        self.runtime
+            .block_on(
+                self.source
+                    .get_parent_entry_before(object_id, self.lamport_version),
+            )
+            .map_err(|e| SuiError::GenericStorageError(e.to_string()))
+    }
+}
+
+impl ObjectStore for ReplayStore {
+    fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
+        self.object(object_id)
+    }
+}
+
+impl GetModule for ReplayStore {
+    type Error = SuiError;
+    type Item = CompiledModule;
+
+    fn get_module_by_id(&self, id: &ModuleId) -> anyhow::Result<Option<Self::Item>, Self::Error> {
+        get_module_by_id(self, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        diff_json, replay_transaction, CheckpointSequenceNumber, EpochId, EpochInfo,
+        ExecutedTransaction, Object, ObjectID, ObjectRef, ReplayOverrides, ReplaySource,
+        RpcReplaySource, SequenceNumber, TransactionDigest,
+    };
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Arc;
+    use sui_sdk::rpc_types::{SuiTransactionEffects, SuiTransactionEffectsAPI};
+    use test_utils::network::TestClusterBuilder;
+    use test_utils::transaction::transfer_sui;
+
+    /// A source recording a higher computation cost than the one charged by the network.
+    struct OverchargedSource(RpcReplaySource);
+
+    #[async_trait]
+    impl ReplaySource for OverchargedSource {
+        async fn get_transaction(&self, digest: TransactionDigest) -> Result<ExecutedTransaction> {
+            let mut transaction = self.0.get_transaction(digest).await?;
+            let SuiTransactionEffects::V1(effects) = &mut transaction.effects;
+            effects.gas_used.computation_cost += 1;
+            Ok(transaction)
+        }
+
+        async fn get_object(
+            &self,
+            id: ObjectID,
+            version: SequenceNumber,
+        ) -> Result<Option<Object>> {
+            self.0.get_object(id, version).await
+        }
+
+        async fn get_object_before(
+            &self,
+            id: ObjectID,
+            version: SequenceNumber,
+        ) -> Result<Option<Object>> {
+            self.0.get_object_before(id, version).await
+        }
+
+        async fn get_parent_entry_before(
+            &self,
+            id: ObjectID,
+            version: SequenceNumber,
+        ) -> Result<Option<ObjectRef>> {
+            self.0.get_parent_entry_before(id, version).await
+        }
+
+        async fn get_epoch_info(&self, epoch: EpochId) -> Result<EpochInfo> {
+            self.0.get_epoch_info(epoch).await
+        }
+
+        async fn get_checkpoint_transactions(
+            &self,
+            sequence_number: CheckpointSequenceNumber,
+        ) -> Result<Vec<TransactionDigest>> {
+            self.0.get_checkpoint_transactions(sequence_number).await
+        }
+    }
+
+    // The replay store blocks on the source from a blocking thread, which needs the IO driver to
+    // be driven by another worker thread.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_replay_transaction() -> Result<()> {
+        let mut test_cluster = TestClusterBuilder::new().build().await?;
+        let (_, _, _, digest) = transfer_sui(&mut test_cluster.wallet, None, None).await?;
+        let source = RpcReplaySource::new(test_cluster.rpc_url()).await?;
+        let expected_effects = source.get_transaction(digest).await?.effects;
+
+        let result =
+            replay_transaction(Arc::new(source), digest, ReplayOverrides::default()).await?;
+        assert_eq!(result.digest, digest);
+        assert_eq!(result.execution_error, None);
+        assert!(result.differences.is_empty(), "{:?}", result.differences);
+
+        // The effects recorded by the source differ from the replayed ones.
+        let source = OverchargedSource(RpcReplaySource::new(test_cluster.rpc_url()).await?);
+        let result =
+            replay_transaction(Arc::new(source), digest, ReplayOverrides::default()).await?;
+        assert_eq!(result.execution_error, None);
+        assert_eq!(result.differences.len(), 1);
+        let difference = &result.differences[0];
+        assert_eq!(difference.path, ".gasUsed.computationCost");
+        let computation_cost = expected_effects.gas_used().computation_cost;
+        assert_eq!(difference.expected, json!(computation_cost + 1));
+        assert_eq!(difference.actual, json!(computation_cost));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let expected = json!({
+            "status": { "status": "success" },
+            "gasUsed": { "computationCost": 100, "storageCost": 10 },
+            "created": [{ "version": 2 }],
+        });
+        let actual = json!({
+            "status": { "status": "success" },
+            "gasUsed": { "computationCost": 120, "storageCost": 10 },
+            "created": [{ "version": 2 }, { "version": 2 }],
+            "eventsDigest": "digest",
+        });
+        let mut differences = vec![];
+        diff_json(String::new(), &expected, &actual, &mut differences);
+        let paths: Vec<_> = differences.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![".created", ".eventsDigest", ".gasUsed.computationCost"]
+        );
+    }
+}
@@ -11,6 +11,7 @@ use futures::future::join_all;
 
 use anyhow::{anyhow, bail, ensure, Ok};
 use move_binary_format::file_format::SignatureToken;
+use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::TypeTag;
 use std::result::Result;
@@ -27,9 +28,10 @@ use sui_types::error::UserInputError;
 use sui_types::gas_coin::GasCoin;
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, GasData, InputObjectKind, ObjectArg, TransactionData,
+    TransactionDataAPI, TransactionKind,
 };
-use sui_types::move_package::MovePackage;
+use sui_types::move_package::{MovePackage, PACKAGE_MODULE_NAME};
 use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
@@ -58,15 +60,40 @@ pub trait DataReader {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
 }
 
+/// Builds the transaction data of the JSON-RPC transaction builder API. A builder returned by
+/// `with_sponsor` pays for the transactions it builds with the gas coins of the sponsor instead
+/// of the coins of the signer.
 #[derive(Clone)]
 pub struct TransactionBuilder<Mode: ExecutionMode>(
     Arc<dyn DataReader + Sync + Send>,
+    Option<SuiAddress>,
     PhantomData<Mode>,
 );
 
 impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
     pub fn new(data_reader: Arc<dyn DataReader + Sync + Send>) -> Self {
-        Self(data_reader, PhantomData)
+        Self(data_reader, None, PhantomData)
+    }
+
+    /// A builder selecting the gas of its transactions from the coins of `sponsor`, if any, and
+    /// making `sponsor` their gas owner. The transactions must then be signed by both the signer
+    /// and the sponsor. `transfer_sui`, `pay_sui` and `pay_all_sui` pay for gas with their input
+    /// coins and are never sponsored.
+    pub fn with_sponsor(&self, sponsor: Option<SuiAddress>) -> Self {
+        Self(self.0.clone(), sponsor, PhantomData)
+    }
+
+    /// Make the sponsor of this builder, if any, the gas owner of `data`.
+    fn sponsor(&self, data: TransactionData) -> TransactionData {
+        let Some(sponsor) = self.1 else {
+            return data;
+        };
+        let sender = data.sender();
+        let gas_data = GasData {
+            owner: sponsor,
+            ..data.gas_data().clone()
+        };
+        TransactionData::new_with_gas_data(data.into_kind(), sender, gas_data)
     }
 
     async fn select_gas(
@@ -80,10 +107,11 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         if let Some(gas) = input_gas {
             self.get_object_ref(gas).await
         } else {
+            let owner = self.1.unwrap_or(signer);
             let objs = self
                 .0
                 .get_owned_objects(
-                    signer,
+                    owner,
                     Some(SuiObjectResponseQuery::new_with_options(
                         SuiObjectDataOptions::full_content(),
                     )),
@@ -124,7 +152,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
                     return Ok(obj.object_ref());
                 }
             }
-            Err(anyhow!("Cannot find gas coin for signer address [{signer}] with amount sufficient for the required gas amount [{required_gas_amount}]."))
+            Err(anyhow!("Cannot find gas coin for address [{owner}] with amount sufficient for the required gas amount [{required_gas_amount}]."))
         }
     }
 
@@ -144,13 +172,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .select_gas(signer, gas, gas_budget, vec![object_id], gas_price)
             .await?;
 
-        Ok(TransactionData::new(
+        Ok(self.sponsor(TransactionData::new(
             TransactionKind::programmable(builder.finish()),
             signer,
             gas,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     async fn single_transfer_object(
@@ -209,6 +237,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         TransactionData::new_pay(
             signer, coin_refs, recipients, amounts, gas, gas_budget, gas_price,
         )
+        .map(|data| self.sponsor(data))
     }
 
     pub async fn pay_sui(
@@ -315,13 +344,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .select_gas(signer, gas, gas_budget, input_objects, gas_price)
             .await?;
 
-        Ok(TransactionData::new(
+        Ok(self.sponsor(TransactionData::new(
             TransactionKind::programmable(pt),
             signer,
             gas,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn single_move_call(
@@ -470,14 +499,68 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let gas = self
             .select_gas(sender, gas, gas_budget, vec![], gas_price)
             .await?;
-        Ok(TransactionData::new_module(
+        Ok(self.sponsor(TransactionData::new_module(
             sender,
             gas,
             compiled_modules,
             dep_ids,
             gas_budget,
             gas_price,
-        ))
+        )))
+    }
+
+    /// Upgrade the package at `package_id` to `compiled_modules` in a single programmable
+    /// transaction that authorizes the upgrade with `upgrade_capability`, upgrades the package,
+    /// and commits the upgrade back to the capability. `digest` is the digest of the modules and
+    /// dependencies of the new package.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upgrade(
+        &self,
+        sender: SuiAddress,
+        package_id: ObjectID,
+        compiled_modules: Vec<Vec<u8>>,
+        dep_ids: Vec<ObjectID>,
+        upgrade_capability: ObjectID,
+        upgrade_policy: u8,
+        digest: Vec<u8>,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let upgrade_cap = self.get_object_ref(upgrade_capability).await?;
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sender, gas, gas_budget, vec![upgrade_capability], gas_price)
+            .await?;
+
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            let cap = builder.obj(ObjectArg::ImmOrOwnedObject(upgrade_cap))?;
+            let policy = builder.pure(upgrade_policy)?;
+            let digest = builder.pure(digest)?;
+            let ticket = builder.programmable_move_call(
+                SUI_FRAMEWORK_OBJECT_ID,
+                PACKAGE_MODULE_NAME.to_owned(),
+                ident_str!("authorize_upgrade").to_owned(),
+                vec![],
+                vec![cap, policy, digest],
+            );
+            let receipt = builder.upgrade(package_id, ticket, dep_ids, compiled_modules);
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_OBJECT_ID,
+                PACKAGE_MODULE_NAME.to_owned(),
+                ident_str!("commit_upgrade").to_owned(),
+                vec![],
+                vec![cap, receipt],
+            );
+            builder.finish()
+        };
+        Ok(self.sponsor(TransactionData::new_programmable(
+            sender,
+            vec![gas],
+            pt,
+            gas_budget,
+            gas_price,
+        )))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -516,6 +599,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.sponsor(data))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -554,6 +638,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.sponsor(data))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -599,6 +684,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.sponsor(data))
     }
 
     pub async fn batch_transaction(
@@ -649,13 +735,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .select_gas(signer, gas, gas_budget, inputs, gas_price)
             .await?;
 
-        Ok(TransactionData::new(
+        Ok(self.sponsor(TransactionData::new(
             TransactionKind::programmable(pt),
             signer,
             gas,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn request_add_stake(
@@ -723,13 +809,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ));
             builder.finish()
         };
-        Ok(TransactionData::new_programmable(
+        Ok(self.sponsor(TransactionData::new_programmable(
             signer,
             vec![gas],
             pt,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn request_withdraw_stake(
@@ -762,6 +848,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.sponsor(data))
     }
 
     // TODO: we should add retrial to reduce the transaction building error rate
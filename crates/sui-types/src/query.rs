@@ -6,6 +6,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::base_types::SuiAddress;
+use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::ObjectID;
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
@@ -24,4 +25,52 @@ pub enum TransactionFilter {
     FromAddress(SuiAddress),
     /// Query by recipient address.
     ToAddress(SuiAddress),
+    /// Query by checkpoint sequence number in [start_checkpoint, end_checkpoint) interval.
+    /// Only supported by queries, transactions are streamed to subscribers before they are
+    /// included in a checkpoint.
+    #[serde(rename_all = "camelCase")]
+    CheckpointRange {
+        /// left endpoint of checkpoint interval, inclusive
+        start_checkpoint: CheckpointSequenceNumber,
+        /// right endpoint of checkpoint interval, exclusive
+        end_checkpoint: CheckpointSequenceNumber,
+    },
+    /// Query by transaction timestamp in [start_time, end_time) interval.
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        /// left endpoint of time interval, milliseconds since epoch, inclusive
+        start_time: u64,
+        /// right endpoint of time interval, milliseconds since epoch, exclusive
+        end_time: u64,
+    },
+    /// Return transactions matching both filters.
+    And(Box<TransactionFilter>, Box<TransactionFilter>),
+    /// Return transactions matching either filter.
+    Or(Box<TransactionFilter>, Box<TransactionFilter>),
+}
+
+impl TransactionFilter {
+    pub fn and(self, other_filter: TransactionFilter) -> Self {
+        Self::And(Box::new(self), Box::new(other_filter))
+    }
+
+    pub fn or(self, other_filter: TransactionFilter) -> Self {
+        Self::Or(Box::new(self), Box::new(other_filter))
+    }
+
+    /// Whether this filter can only be evaluated by scanning transactions in order, rather than
+    /// by looking up an index.
+    pub fn is_range(&self) -> bool {
+        matches!(self, Self::CheckpointRange { .. } | Self::TimeRange { .. })
+    }
+
+    /// Whether this filter, or one of the filters it combines, selects transactions by
+    /// checkpoint, which cannot be matched against transactions that are not checkpointed yet.
+    pub fn uses_checkpoint(&self) -> bool {
+        match self {
+            Self::CheckpointRange { .. } => true,
+            Self::And(f1, f2) | Self::Or(f1, f2) => f1.uses_checkpoint() || f2.uses_checkpoint(),
+            _ => false,
+        }
+    }
 }
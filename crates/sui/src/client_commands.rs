@@ -6,12 +6,15 @@ use std::sync::Arc;
 use std::{
     collections::BTreeSet,
     fmt::{Debug, Display, Formatter, Write},
+    future::Future,
     path::{Path, PathBuf},
     time::Instant,
 };
 
 use crate::config::{Config, PersistedConfig, SuiClientConfig, SuiEnv};
-use anyhow::{anyhow, ensure};
+use crate::ptb::{build_programmable_transaction, input_objects, parse_commands_file, PtbCommand};
+use crate::sui_commands::CliError;
+use anyhow::{anyhow, bail, ensure};
 use bip32::DerivationPath;
 use clap::*;
 use colored::Colorize;
@@ -19,6 +22,9 @@ use fastcrypto::{
     encoding::{Base64, Encoding},
     traits::ToFromBytes,
 };
+use move_binary_format::compatibility::Compatibility;
+use move_binary_format::normalized;
+use move_binary_format::CompiledModule;
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig as MoveBuildConfig;
 use prettytable::Table;
@@ -31,25 +37,35 @@ use sui_source_validation::{BytecodeSourceVerifier, SourceMode};
 use sui_types::error::SuiError;
 
 use shared_crypto::intent::Intent;
+use sui_adapter::adapter::substitute_package_id;
 use sui_framework_build::compiled_package::{
     build_from_resolution_graph, check_invalid_dependencies, check_unpublished_dependencies,
-    gather_dependencies, BuildConfig,
+    gather_dependencies, BuildConfig, CompiledPackage,
 };
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, SuiObjectData, SuiObjectResponse, SuiObjectResponseQuery, SuiRawData,
-    SuiTransactionEffectsAPI, SuiTransactionResponse, SuiTransactionResponseOptions,
+    DevInspectResults, DryRunTransactionResponse, DynamicFieldPage, SuiObjectData,
+    SuiObjectDataFilter, SuiObjectResponse, SuiObjectResponseQuery, SuiRawData,
+    SuiTransactionEffectsAPI, SuiTransactionResponse, SuiTransactionResponseOptions, SuiTypeTag,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
 use sui_keys::keystore::AccountKeystore;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_sdk::SuiClient;
-use sui_types::crypto::SignatureScheme;
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme};
 use sui_types::dynamic_field::DynamicFieldType;
 use sui_types::signature::GenericSignature;
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
     gas_coin::GasCoin,
-    messages::{Transaction, VerifiedTransaction},
+    messages::{
+        GasData, Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+        VerifiedTransaction,
+    },
+    move_package::{
+        is_valid_package_upgrade_policy, normalize_deserialized_modules, MovePackage, UpgradeCap,
+        UPGRADE_POLICY_ADDITIVE, UPGRADE_POLICY_DEP_ONLY,
+    },
     object::Owner,
     parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
@@ -61,6 +77,10 @@ pub const EXAMPLE_NFT_DESCRIPTION: &str = "An NFT created by the Sui Command Lin
 pub const EXAMPLE_NFT_URL: &str =
     "ipfs://bafkreibngqhl3gaa7daob4i2vccziay2jjlp435cf66vhono7nrvww53ty";
 
+/// Margin added to the gas used by the dry run of a transaction when estimating its budget, to
+/// absorb the differences between the dry run and the actual execution.
+const GAS_BUDGET_ESTIMATE_MARGIN_PERCENT: u64 = 20;
+
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum SuiClientCommands {
@@ -130,9 +150,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for running module initializers
+        /// Gas budget for running module initializers. If not provided, it is estimated by
+        /// dry-running the transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Publish the package without checking whether compiling dependencies from source results
         /// in bytecode matching the dependencies found on-chain.
@@ -144,6 +165,52 @@ pub enum SuiClientCommands {
         with_unpublished_dependencies: bool,
     },
 
+    /// Upgrade Move modules
+    #[clap(name = "upgrade")]
+    Upgrade {
+        /// Path to directory containing a Move package
+        #[clap(
+            name = "package_path",
+            global = true,
+            parse(from_os_str),
+            default_value = "."
+        )]
+        package_path: PathBuf,
+
+        /// Package build options
+        #[clap(flatten)]
+        build_config: MoveBuildConfig,
+
+        /// ID of the on-chain package to upgrade. If not provided, it is read from the upgrade
+        /// capability.
+        #[clap(long, required_unless_present = "upgrade_capability")]
+        package_id: Option<ObjectID>,
+
+        /// ID of the `UpgradeCap` of the package. If not provided, the `UpgradeCap` of the
+        /// package is looked up among the objects owned by the sender.
+        #[clap(long)]
+        upgrade_capability: Option<ObjectID>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for running module initializers. If not provided, it is estimated by
+        /// dry-running the transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+
+        /// Upgrade the package without checking whether compiling dependencies from source results
+        /// in bytecode matching the dependencies found on-chain.
+        #[clap(long)]
+        skip_dependency_verification: bool,
+
+        /// Also publish transitive dependencies that have not already been published.
+        #[clap(long)]
+        with_unpublished_dependencies: bool,
+    },
+
     /// Verify local Move packages against on-chain packages, and optionally their dependencies.
     #[clap(name = "verify-source")]
     VerifySource {
@@ -203,9 +270,10 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Transfer object
@@ -224,9 +292,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
     /// Transfer SUI, and pay gas with the same SUI coin object.
     /// If amount is specified, only the amount is transferred; otherwise the entire object
@@ -241,9 +310,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         sui_coin_object_id: ObjectID,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
@@ -270,9 +340,10 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Pay SUI coins to recipients following following specified amounts, with input coins.
@@ -292,9 +363,10 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         amounts: Vec<u64>,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Pay all residual SUI coins to the recipient with input coins, after deducting the gas cost.
@@ -309,9 +381,10 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false)]
         recipient: SuiAddress,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Obtain the Addresses managed by the client.
@@ -373,9 +446,10 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Merge two coin objects into one coin
@@ -390,9 +464,10 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Create an example NFT
@@ -415,7 +490,8 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer. If not provided, it is estimated by dry-running the
+        /// transaction.
         #[clap(long)]
         gas_budget: Option<u64>,
     },
@@ -440,6 +516,56 @@ pub enum SuiClientCommands {
         amount: Option<u64>,
     },
 
+    /// Compose and run a programmable transaction from a sequence of commands, where later
+    /// commands can use the results of earlier ones.
+    #[clap(name = "ptb")]
+    Ptb {
+        /// A command of the transaction, repeated for each command in order, e.g.
+        /// `--command "split-coins gas 1000" --command "transfer-objects 0x2 %0"`.
+        /// Commands are move-call, transfer-objects, split-coins, merge-coins and make-move-vec.
+        #[clap(long = "command", required_unless_present = "file")]
+        commands: Vec<String>,
+
+        /// Read the commands from a file, one per line, instead of `--command`.
+        #[clap(long, conflicts_with = "commands", parse(from_os_str))]
+        file: Option<PathBuf>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction. If not provided, it is estimated by dry-running the
+        /// transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+
+        /// Address of the sponsor paying for the gas of the transaction, sent by the active
+        /// address. The gas object is selected from the coins of the sponsor, and the
+        /// transaction is signed with the keys of both the sender and the sponsor. Use
+        /// `--serialize-unsigned` when the key of the sponsor is not in the keystore.
+        #[clap(long)]
+        sponsor: Option<SuiAddress>,
+
+        /// Print the Base64 encoded unsigned transaction data instead of executing it, to be
+        /// signed elsewhere and executed with `execute-signed-tx`.
+        #[clap(long)]
+        serialize_unsigned: bool,
+    },
+
+    /// Sign a transaction with the key of one of its signers, i.e. its sender or its gas owner
+    /// for a sponsored transaction. The signature can be passed to `execute-signed-tx`.
+    #[clap(name = "sign-tx")]
+    SignTx {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+
+        /// Address of the key to sign with. Defaults to the active address.
+        #[clap(long)]
+        address: Option<SuiAddress>,
+    },
+
     /// Execute a Signed Transaction. This is useful when the user prefers to sign elsewhere and use this command to execute.
     ExecuteSignedTx {
         /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
@@ -452,10 +578,32 @@ pub enum SuiClientCommands {
     },
 }
 
+/// What the client does with the transaction built by a command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionMode {
+    /// Sign the transaction with the key of its sender and execute it.
+    Execute,
+    /// Run the transaction against the current state of the network without committing it.
+    DryRun,
+    /// Run the transaction in dev-inspect mode, which neither checks nor charges gas, and report
+    /// the results of each of its commands.
+    DevInspect,
+}
+
 impl SuiClientCommands {
     pub async fn execute(
         self,
         context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, anyhow::Error> {
+        self.execute_with_mode(context, TransactionMode::Execute)
+            .await
+    }
+
+    /// Run the command, handling the transaction it builds, if any, according to `mode`.
+    pub async fn execute_with_mode(
+        self,
+        context: &mut WalletContext,
+        mode: TransactionMode,
     ) -> Result<SuiClientCommandResult, anyhow::Error> {
         let ret = Ok(match self {
             SuiClientCommands::Publish {
@@ -469,73 +617,118 @@ impl SuiClientCommands {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
-                let config = resolve_lock_file_path(build_config, Some(package_path.clone()))?;
-                let run_bytecode_verifier = true;
-                let print_diags_to_stderr = true;
-
-                let config = BuildConfig {
-                    config,
-                    run_bytecode_verifier,
-                    print_diags_to_stderr,
-                };
+                let client = context.get_client().await?;
+                let compiled_package = compile_package(
+                    &client,
+                    build_config,
+                    package_path,
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                )
+                .await?;
+                let compiled_modules =
+                    compiled_package.get_package_bytes(with_unpublished_dependencies);
+                let dependencies: Vec<_> = compiled_package
+                    .dependency_ids
+                    .published
+                    .into_values()
+                    .collect();
 
-                let resolution_graph = config.resolution_graph(&package_path)?;
-                let dependencies = gather_dependencies(&resolution_graph);
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client.transaction_builder().publish(
+                        sender,
+                        compiled_modules.clone(),
+                        dependencies.clone(),
+                        gas,
+                        gas_budget,
+                    )
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
+                let signature =
+                    context
+                        .config
+                        .keystore
+                        .sign_secure(&sender, &data, Intent::default())?;
+                let response = context
+                    .execute_transaction(
+                        Transaction::from_data(data, Intent::default(), vec![signature])
+                            .verify()?,
+                    )
+                    .await?;
 
-                check_invalid_dependencies(&dependencies.invalid)?;
+                SuiClientCommandResult::Publish(response)
+            }
 
-                if !with_unpublished_dependencies {
-                    check_unpublished_dependencies(&dependencies.unpublished)?;
-                };
+            SuiClientCommands::Upgrade {
+                package_path,
+                build_config,
+                package_id,
+                upgrade_capability,
+                gas,
+                gas_budget,
+                skip_dependency_verification,
+                with_unpublished_dependencies,
+            } => {
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
 
-                let compiled_package = build_from_resolution_graph(
+                let client = context.get_client().await?;
+                let (upgrade_capability, cap) =
+                    find_upgrade_cap(&client, sender, package_id, upgrade_capability).await?;
+                let package_id = cap.package.bytes;
+                let compiled_package = compile_package(
+                    &client,
+                    build_config,
                     package_path,
-                    resolution_graph,
-                    run_bytecode_verifier,
-                    print_diags_to_stderr,
-                )?;
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                )
+                .await?;
 
-                if !compiled_package.is_framework() {
-                    if let Some(already_published) = compiled_package.published_root_module() {
-                        return Err(SuiError::ModulePublishFailure {
-                            error: format!(
-                                "Modules must all have 0x0 as their addresses. \
-                                 Violated by module {:?}",
-                                already_published.self_id(),
-                            ),
-                        }
-                        .into());
-                    }
-                }
+                check_upgrade_compatibility(
+                    &client,
+                    package_id,
+                    cap.policy,
+                    compiled_package.get_dependency_sorted_modules(with_unpublished_dependencies),
+                )
+                .await?;
+                eprintln!(
+                    "{}",
+                    "Successfully checked the upgrade is compatible with the on-chain package."
+                        .bold()
+                        .green(),
+                );
 
-                let client = context.get_client().await?;
                 let compiled_modules =
                     compiled_package.get_package_bytes(with_unpublished_dependencies);
-
-                if !skip_dependency_verification {
-                    BytecodeSourceVerifier::new(client.read_api(), false)
-                        .verify_package_deps(&compiled_package.package)
-                        .await?;
-                    eprintln!(
-                        "{}",
-                        "Successfully verified dependencies on-chain against source."
-                            .bold()
-                            .green(),
-                    );
-                } else {
-                    eprintln!("{}", "Skipping dependency verification".bold().yellow());
-                }
-
-                let data = client
-                    .transaction_builder()
-                    .publish(
+                let digest = compiled_package
+                    .get_package_digest(with_unpublished_dependencies)
+                    .to_vec();
+                let dependencies: Vec<_> = compiled_package
+                    .dependency_ids
+                    .published
+                    .into_values()
+                    .collect();
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client.transaction_builder().upgrade(
                         sender,
-                        compiled_modules,
-                        dependencies.published.into_values().collect(),
+                        package_id,
+                        compiled_modules.clone(),
+                        dependencies.clone(),
+                        upgrade_capability,
+                        cap.policy,
+                        digest.clone(),
                         gas,
                         gas_budget,
                     )
-                    .await?;
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -548,7 +741,7 @@ impl SuiClientCommands {
                     )
                     .await?;
 
-                SuiClientCommandResult::Publish(response)
+                SuiClientCommandResult::Upgrade(response)
             }
 
             SuiClientCommands::Object { id, bcs } => {
@@ -587,11 +780,10 @@ impl SuiClientCommands {
                 gas_budget,
                 args,
             } => {
-                let response = call_move(
-                    package, &module, &function, type_args, gas, gas_budget, args, context,
+                call_move(
+                    package, &module, &function, type_args, gas, gas_budget, args, context, mode,
                 )
-                .await?;
-                SuiClientCommandResult::Call(response)
+                .await?
             }
 
             SuiClientCommands::Transfer {
@@ -604,10 +796,15 @@ impl SuiClientCommands {
                 let time_start = Instant::now();
 
                 let client = context.get_client().await?;
-                let data = client
-                    .transaction_builder()
-                    .transfer_object(from, object_id, gas, gas_budget, to)
-                    .await?;
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client
+                        .transaction_builder()
+                        .transfer_object(from, object_id, gas, gas_budget, to)
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -624,10 +821,10 @@ impl SuiClientCommands {
                 })?;
                 let time_total = time_start.elapsed().as_micros();
                 if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-                    return Err(anyhow!(
+                    bail!(CliError::TransactionFailed(format!(
                         "Error transferring object: {:#?}",
                         effects.status()
-                    ));
+                    )));
                 }
                 SuiClientCommandResult::Transfer(time_total, response)
             }
@@ -641,10 +838,15 @@ impl SuiClientCommands {
                 let from = context.get_object_owner(&object_id).await?;
 
                 let client = context.get_client().await?;
-                let data = client
-                    .transaction_builder()
-                    .transfer_sui(from, object_id, gas_budget, to, amount)
-                    .await?;
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client
+                        .transaction_builder()
+                        .transfer_sui(from, object_id, gas_budget, to, amount)
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -660,7 +862,10 @@ impl SuiClientCommands {
                     anyhow!("Effects from SuiTransactionResult should not be empty")
                 })?;
                 if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-                    return Err(anyhow!("Error transferring SUI: {:#?}", effects.status()));
+                    bail!(CliError::TransactionFailed(format!(
+                        "Error transferring SUI: {:#?}",
+                        effects.status()
+                    )));
                 }
                 SuiClientCommandResult::TransferSui(response)
             }
@@ -690,10 +895,20 @@ impl SuiClientCommands {
                 );
                 let from = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
-                let data = client
-                    .transaction_builder()
-                    .pay(from, input_coins, recipients, amounts, gas, gas_budget)
-                    .await?;
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client.transaction_builder().pay(
+                        from,
+                        input_coins.clone(),
+                        recipients.clone(),
+                        amounts.clone(),
+                        gas,
+                        gas_budget,
+                    )
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -709,10 +924,10 @@ impl SuiClientCommands {
                     anyhow!("Effects from SuiTransactionResult should not be empty")
                 })?;
                 if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-                    return Err(anyhow!(
+                    bail!(CliError::TransactionFailed(format!(
                         "Error executing Pay transaction: {:#?}",
                         effects.status()
-                    ));
+                    )));
                 }
                 SuiClientCommandResult::Pay(response)
             }
@@ -741,10 +956,19 @@ impl SuiClientCommands {
                 );
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
-                let data = client
-                    .transaction_builder()
-                    .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
-                    .await?;
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client.transaction_builder().pay_sui(
+                        signer,
+                        input_coins.clone(),
+                        recipients.clone(),
+                        amounts.clone(),
+                        gas_budget,
+                    )
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -760,10 +984,10 @@ impl SuiClientCommands {
                     anyhow!("Effects from SuiTransactionResult should not be empty")
                 })?;
                 if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-                    return Err(anyhow!(
+                    bail!(CliError::TransactionFailed(format!(
                         "Error executing PaySui transaction: {:#?}",
                         effects.status()
-                    ));
+                    )));
                 }
                 SuiClientCommandResult::PaySui(response)
             }
@@ -779,11 +1003,18 @@ impl SuiClientCommands {
                 );
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
-                let data = client
-                    .transaction_builder()
-                    .pay_all_sui(signer, input_coins, recipient, gas_budget)
-                    .await?;
-
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client.transaction_builder().pay_all_sui(
+                        signer,
+                        input_coins.clone(),
+                        recipient,
+                        gas_budget,
+                    )
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -799,18 +1030,18 @@ impl SuiClientCommands {
                     anyhow!("Effects from SuiTransactionResult should not be empty")
                 })?;
                 if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-                    return Err(anyhow!(
+                    bail!(CliError::TransactionFailed(format!(
                         "Error executing PayAllSui transaction: {:#?}",
                         effects.status()
-                    ));
+                    )));
                 }
                 SuiClientCommandResult::PayAllSui(response)
             }
 
-            SuiClientCommands::Addresses => SuiClientCommandResult::Addresses(
-                context.config.keystore.addresses(),
-                context.active_address().ok(),
-            ),
+            SuiClientCommands::Addresses => SuiClientCommandResult::Addresses {
+                addresses: context.config.keystore.addresses(),
+                active_address: context.active_address().ok(),
+            },
 
             SuiClientCommands::Objects { address } => {
                 let address = address.unwrap_or(context.active_address()?);
@@ -834,11 +1065,15 @@ impl SuiClientCommands {
                 key_scheme,
                 derivation_path,
             } => {
-                let (address, phrase, scheme) = context
+                let (address, recovery_phrase, key_scheme) = context
                     .config
                     .keystore
                     .generate_and_add_new_key(key_scheme, derivation_path)?;
-                SuiClientCommandResult::NewAddress((address, phrase, scheme))
+                SuiClientCommandResult::NewAddress {
+                    address,
+                    recovery_phrase,
+                    key_scheme,
+                }
             }
             SuiClientCommands::Gas { address } => {
                 let address = address.unwrap_or(context.active_address()?);
@@ -862,24 +1097,37 @@ impl SuiClientCommands {
                 let client = context.get_client().await?;
                 let data = match (amounts, count) {
                     (Some(amounts), None) => {
-                        client
-                            .transaction_builder()
-                            .split_coin(signer, coin_id, amounts, gas, gas_budget)
-                            .await?
+                        build_transaction(&client, gas_budget, mode, |gas_budget| {
+                            client.transaction_builder().split_coin(
+                                signer,
+                                coin_id,
+                                amounts.clone(),
+                                gas,
+                                gas_budget,
+                            )
+                        })
+                        .await?
                     }
                     (None, Some(count)) => {
                         if count == 0 {
-                            return Err(anyhow!("Coin split count must be greater than 0"));
+                            bail!(CliError::InvalidInput(
+                                "Coin split count must be greater than 0".to_string()
+                            ));
                         }
-                        client
-                            .transaction_builder()
-                            .split_coin_equal(signer, coin_id, count, gas, gas_budget)
-                            .await?
+                        build_transaction(&client, gas_budget, mode, |gas_budget| {
+                            client
+                                .transaction_builder()
+                                .split_coin_equal(signer, coin_id, count, gas, gas_budget)
+                        })
+                        .await?
                     }
                     _ => {
-                        return Err(anyhow!("Exactly one of `count` and `amounts` must be present for split-coin command."));
+                        bail!(CliError::InvalidInput("Exactly one of `count` and `amounts` must be present for split-coin command.".to_string()));
                     }
                 };
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -901,10 +1149,19 @@ impl SuiClientCommands {
             } => {
                 let client = context.get_client().await?;
                 let signer = context.get_object_owner(&primary_coin).await?;
-                let data = client
-                    .transaction_builder()
-                    .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
-                    .await?;
+                let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+                    client.transaction_builder().merge_coins(
+                        signer,
+                        primary_coin,
+                        coin_to_merge,
+                        gas,
+                        gas_budget,
+                    )
+                })
+                .await?;
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
                 let signature =
                     context
                         .config
@@ -926,11 +1183,15 @@ impl SuiClientCommands {
                     }
                     (Some(addr), None) => {
                         if !context.config.keystore.addresses().contains(&addr) {
-                            return Err(anyhow!("Address {} not managed by wallet", addr));
+                            bail!(CliError::InvalidInput(format!(
+                                "Address {addr} not managed by wallet"
+                            )));
                         }
                         context.config.active_address = Some(addr);
                     }
-                    _ => return Err(anyhow!("No address or env specified. Please Specify one.")),
+                    _ => bail!(CliError::InvalidInput(
+                        "No address or env specified. Please Specify one.".to_string()
+                    )),
                 }
                 context.config.save()?;
                 SuiClientCommandResult::Switch(SwitchResponse { address, env })
@@ -954,17 +1215,22 @@ impl SuiClientCommands {
                 for a in args_json.as_array().unwrap() {
                     args.push(SuiJsonValue::new(a.clone()).unwrap());
                 }
-                let response = call_move(
+                let response = match call_move(
                     ObjectID::from(SUI_FRAMEWORK_ADDRESS),
                     "devnet_nft",
                     "mint",
                     vec![],
                     gas,
-                    gas_budget.unwrap_or(100_000),
+                    gas_budget,
                     args,
                     context,
+                    mode,
                 )
-                .await?;
+                .await?
+                {
+                    SuiClientCommandResult::Call(response) => response,
+                    result => return Ok(result),
+                };
                 let nft_id = response
                     .effects
                     .ok_or_else(|| anyhow!("Failed to fetch transaction effects"))?
@@ -998,6 +1264,123 @@ impl SuiClientCommands {
                 ))
             }
 
+            SuiClientCommands::Ptb {
+                commands,
+                file,
+                gas,
+                gas_budget,
+                sponsor,
+                serialize_unsigned,
+            } => {
+                let commands = match file {
+                    Some(file) => parse_commands_file(&std::fs::read_to_string(&file)?)
+                        .map_err(|e| CliError::InvalidInput(format!("{e:#}")))?,
+                    None => commands
+                        .iter()
+                        .enumerate()
+                        .map(|(i, command)| {
+                            command.parse::<PtbCommand>().map_err(|e| {
+                                CliError::InvalidInput(format!(
+                                    "Invalid command {i} {command:?}: {e:#}"
+                                ))
+                            })
+                        })
+                        .collect::<Result<_, _>>()?,
+                };
+                // The gas object of a sponsored transaction belongs to the sponsor, not the
+                // sender.
+                let (sender, gas_owner) = match sponsor {
+                    Some(sponsor) => (context.active_address()?, sponsor),
+                    None => {
+                        let sender = context.try_get_object_owner(&gas).await?;
+                        let sender = sender.unwrap_or(context.active_address()?);
+                        (sender, sender)
+                    }
+                };
+                let forbidden_gas_objects = input_objects(&commands);
+
+                let client = context.get_client().await?;
+                let pt = build_programmable_transaction(&client, commands).await?;
+
+                let gas_price = client.read_api().get_reference_gas_price().await?;
+                let wallet: &WalletContext = context;
+                let data = build_transaction(&client, gas_budget, mode, move |gas_budget| {
+                    let pt = pt.clone();
+                    let forbidden_gas_objects = forbidden_gas_objects.clone();
+                    async move {
+                        let gas = match gas {
+                            Some(gas) => wallet.get_object_ref(gas).await?,
+                            None => wallet
+                                .gas_for_owner_budget(gas_owner, gas_budget, forbidden_gas_objects)
+                                .await?
+                                .1
+                                .object_ref(),
+                        };
+                        Ok(TransactionData::new_with_gas_data(
+                            TransactionKind::programmable(pt),
+                            sender,
+                            GasData {
+                                payment: vec![gas],
+                                owner: gas_owner,
+                                price: gas_price,
+                                budget: gas_budget,
+                            },
+                        ))
+                    }
+                })
+                .await?;
+
+                if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+                    return Ok(result);
+                }
+                if serialize_unsigned {
+                    SuiClientCommandResult::SerializeUnsignedTransaction(Base64::encode(
+                        bcs::to_bytes(&data)?,
+                    ))
+                } else {
+                    let signatures = data
+                        .signers()
+                        .iter()
+                        .map(|signer| {
+                            context
+                                .config
+                                .keystore
+                                .sign_secure(signer, &data, Intent::default())
+                        })
+                        .collect::<Result<_, _>>()?;
+                    let response = context
+                        .execute_transaction(
+                            Transaction::from_data(data, Intent::default(), signatures).verify()?,
+                        )
+                        .await?;
+                    SuiClientCommandResult::Ptb(response)
+                }
+            }
+
+            SuiClientCommands::SignTx { tx_bytes, address } => {
+                let data: TransactionData = bcs::from_bytes(
+                    &Base64::try_from(tx_bytes)
+                        .map_err(|e| anyhow!(e))?
+                        .to_vec()
+                        .map_err(|e| anyhow!(e))?,
+                )?;
+                let address = match address {
+                    Some(address) => address,
+                    None => context.active_address()?,
+                };
+                if !data.signers().contains(&address) {
+                    bail!(CliError::InvalidInput(format!(
+                        "Address {address} is neither the sender nor the gas owner of the transaction."
+                    )));
+                }
+                let signature =
+                    context
+                        .config
+                        .keystore
+                        .sign_secure(&address, &data, Intent::default())?;
+                SuiClientCommandResult::SignTx(signature.encode_base64())
+            }
+
             SuiClientCommands::ExecuteSignedTx {
                 tx_bytes,
                 signatures,
@@ -1024,6 +1407,12 @@ impl SuiClientCommands {
                 let verified =
                     Transaction::from_generic_sig_data(data, Intent::default(), sigs).verify()?;
 
+                let client = context.get_client().await?;
+                let data = verified.data().transaction_data();
+                if let Some(result) = simulate_transaction(&client, data, mode).await? {
+                    return Ok(result);
+                }
+
                 let response = context.execute_transaction(verified).await?;
                 SuiClientCommandResult::ExecuteSignedTx(response)
             }
@@ -1044,10 +1433,10 @@ impl SuiClientCommands {
             SuiClientCommands::ActiveEnv => {
                 SuiClientCommandResult::ActiveEnv(context.config.active_env.clone())
             }
-            SuiClientCommands::Envs => SuiClientCommandResult::Envs(
-                context.config.envs.clone(),
-                context.config.active_env.clone(),
-            ),
+            SuiClientCommands::Envs => SuiClientCommandResult::Envs {
+                envs: context.config.envs.clone(),
+                active_env: context.config.active_env.clone(),
+            },
             SuiClientCommands::VerifySource {
                 package_path,
                 build_config,
@@ -1056,8 +1445,8 @@ impl SuiClientCommands {
                 address_override,
             } => {
                 if skip_source && !verify_deps {
-                    return Err(anyhow!(
-                        "Source skipped and not verifying deps: Nothing to verify."
+                    bail!(CliError::InvalidInput(
+                        "Source skipped and not verifying deps: Nothing to verify.".to_string()
                     ));
                 }
 
@@ -1292,7 +1681,8 @@ impl Display for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
         match self {
-            SuiClientCommandResult::Publish(response) => {
+            SuiClientCommandResult::Publish(response)
+            | SuiClientCommandResult::Upgrade(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
             SuiClientCommandResult::Object(object_read) => {
@@ -1339,7 +1729,10 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::PayAllSui(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
-            SuiClientCommandResult::Addresses(addresses, active_address) => {
+            SuiClientCommandResult::Addresses {
+                addresses,
+                active_address,
+            } => {
                 writeln!(writer, "Showing {} results.", addresses.len())?;
                 for address in addresses {
                     if *active_address == Some(*address) {
@@ -1413,10 +1806,14 @@ impl Display for SuiClientCommandResult {
             }
             // Do not use writer for new address output, which may get sent to logs.
             #[allow(clippy::print_in_format_impl)]
-            SuiClientCommandResult::NewAddress((address, recovery_phrase, scheme)) => {
+            SuiClientCommandResult::NewAddress {
+                address,
+                recovery_phrase,
+                key_scheme,
+            } => {
                 println!(
                     "Created new keypair for address with scheme {:?}: [{address}]",
-                    scheme
+                    key_scheme
                 );
                 println!("Secret Recovery Phrase : [{recovery_phrase}]");
             }
@@ -1455,19 +1852,53 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::ExecuteSignedTx(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
-            SuiClientCommandResult::SerializeTransferSui(data) => {
+            SuiClientCommandResult::SerializeTransferSui(data)
+            | SuiClientCommandResult::SerializeUnsignedTransaction(data) => {
                 writeln!(writer, "Raw tx_bytes to execute: {}", data)?;
             }
+            SuiClientCommandResult::Ptb(response) => {
+                write!(writer, "{}", write_transaction_response(response)?)?;
+            }
+            SuiClientCommandResult::SignTx(signature) => {
+                writeln!(writer, "Signature to execute with: {}", signature)?;
+            }
+            SuiClientCommandResult::DryRun(response) => {
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                writeln!(writer, "{}", response.effects)?;
+                writeln!(writer, "{}", "----- Gas Summary ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.effects.gas_used()))?;
+                writeln!(writer, "{}", "----- Events ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.events))?;
+                writeln!(writer, "{}", "----- Object changes ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.object_changes))?;
+                writeln!(writer, "{}", "----- Balance changes ----".bold())?;
+                writeln!(writer, "{:#?}", json!(response.balance_changes))?;
+            }
+            SuiClientCommandResult::DevInspect(results) => {
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                writeln!(writer, "{}", results.effects)?;
+                writeln!(writer, "{}", "----- Gas Summary ----".bold())?;
+                writeln!(writer, "{:#?}", json!(results.effects.gas_used()))?;
+                writeln!(writer, "{}", "----- Events ----".bold())?;
+                writeln!(writer, "{:#?}", json!(results.events))?;
+                writeln!(writer, "{}", "----- Results ----".bold())?;
+                if let Some(results) = &results.results {
+                    writeln!(writer, "{:#?}", json!(results))?;
+                }
+                if let Some(error) = &results.error {
+                    writeln!(writer, "{}", format!("Execution error: {error}").red())?;
+                }
+            }
             SuiClientCommandResult::ActiveEnv(env) => {
                 write!(writer, "{}", env.as_deref().unwrap_or("None"))?;
             }
             SuiClientCommandResult::NewEnv(env) => {
                 writeln!(writer, "Added new Sui env [{}] to config.", env.alias)?;
             }
-            SuiClientCommandResult::Envs(envs, active) => {
+            SuiClientCommandResult::Envs { envs, active_env } => {
                 for env in envs {
                     write!(writer, "{} => {}", env.alias, env.rpc)?;
-                    if Some(env.alias.as_str()) == active.as_deref() {
+                    if Some(env.alias.as_str()) == active_env.as_deref() {
                         write!(writer, " (active)")?;
                     }
                     writeln!(writer)?;
@@ -1481,42 +1912,311 @@ impl Display for SuiClientCommandResult {
     }
 }
 
+/// Build the Move package at `package_path` for publishing or upgrading, checking its
+/// dependencies and, unless `skip_dependency_verification` is set, verifying them against their
+/// on-chain bytecode.
+async fn compile_package(
+    client: &SuiClient,
+    build_config: MoveBuildConfig,
+    package_path: PathBuf,
+    with_unpublished_dependencies: bool,
+    skip_dependency_verification: bool,
+) -> Result<CompiledPackage, anyhow::Error> {
+    let config = resolve_lock_file_path(build_config, Some(package_path.clone()))?;
+    let run_bytecode_verifier = true;
+    let print_diags_to_stderr = true;
+
+    let config = BuildConfig {
+        config,
+        run_bytecode_verifier,
+        print_diags_to_stderr,
+    };
+
+    let resolution_graph = config.resolution_graph(&package_path)?;
+    let dependencies = gather_dependencies(&resolution_graph);
+
+    check_invalid_dependencies(&dependencies.invalid)?;
+
+    if !with_unpublished_dependencies {
+        check_unpublished_dependencies(&dependencies.unpublished)?;
+    };
+
+    let compiled_package = build_from_resolution_graph(
+        package_path,
+        resolution_graph,
+        run_bytecode_verifier,
+        print_diags_to_stderr,
+    )?;
+
+    if !compiled_package.is_framework() {
+        if let Some(already_published) = compiled_package.published_root_module() {
+            return Err(SuiError::ModulePublishFailure {
+                error: format!(
+                    "Modules must all have 0x0 as their addresses. \
+                     Violated by module {:?}",
+                    already_published.self_id(),
+                ),
+            }
+            .into());
+        }
+    }
+
+    if !skip_dependency_verification {
+        BytecodeSourceVerifier::new(client.read_api(), false)
+            .verify_package_deps(&compiled_package.package)
+            .await?;
+        eprintln!(
+            "{}",
+            "Successfully verified dependencies on-chain against source."
+                .bold()
+                .green(),
+        );
+    } else {
+        eprintln!("{}", "Skipping dependency verification".bold().yellow());
+    }
+
+    Ok(compiled_package)
+}
+
+/// Return the ID and the contents of the `UpgradeCap` of the package to upgrade, looking up
+/// whichever of the two IDs is not provided: the package from the `UpgradeCap`, or the
+/// `UpgradeCap` of the package among the objects owned by `sender`.
+async fn find_upgrade_cap(
+    client: &SuiClient,
+    sender: SuiAddress,
+    package_id: Option<ObjectID>,
+    upgrade_capability: Option<ObjectID>,
+) -> Result<(ObjectID, UpgradeCap), anyhow::Error> {
+    if let Some(cap_id) = upgrade_capability {
+        let object = client
+            .read_api()
+            .get_object_with_options(cap_id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let cap = match &object.bcs {
+            Some(SuiRawData::MoveObject(o)) if o.type_ == UpgradeCap::type_() => {
+                o.deserialize::<UpgradeCap>()?
+            }
+            _ => bail!("Object {cap_id} is not an UpgradeCap"),
+        };
+        if let Some(package_id) = package_id {
+            ensure!(
+                package_id == cap.package.bytes,
+                "UpgradeCap {cap_id} is for package {}, not {package_id}",
+                cap.package.bytes
+            );
+        }
+        return Ok((cap_id, cap));
+    }
+
+    let package_id = package_id.ok_or_else(|| {
+        CliError::InvalidInput(
+            "Either the package ID or the UpgradeCap ID must be provided".to_string(),
+        )
+    })?;
+    let query = SuiObjectResponseQuery::new(
+        Some(SuiObjectDataFilter::StructType(UpgradeCap::type_())),
+        Some(SuiObjectDataOptions::bcs_lossless()),
+    );
+    let mut cursor = None;
+    loop {
+        let page = client
+            .read_api()
+            .get_owned_objects(sender, Some(query.clone()), cursor, None, None)
+            .await?;
+        for object in page.data {
+            let object = object.into_object()?;
+            if let Some(SuiRawData::MoveObject(o)) = &object.bcs {
+                let cap: UpgradeCap = o.deserialize()?;
+                if cap.package.bytes == package_id {
+                    return Ok((object.object_id, cap));
+                }
+            }
+        }
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    bail!("No UpgradeCap for package {package_id} is owned by {sender}")
+}
+
+/// Check that `modules` are an upgrade of the on-chain package at `package_id` allowed by the
+/// upgrade `policy`, with the same rules as the validators, so that a disallowed upgrade fails
+/// before it is submitted.
+async fn check_upgrade_compatibility(
+    client: &SuiClient,
+    package_id: ObjectID,
+    policy: u8,
+    mut modules: Vec<CompiledModule>,
+) -> Result<(), anyhow::Error> {
+    ensure!(
+        is_valid_package_upgrade_policy(&policy),
+        "Unknown upgrade policy {policy}"
+    );
+    let protocol_config = current_protocol_config(client).await?;
+    let object = client
+        .read_api()
+        .get_object_with_options(package_id, SuiObjectDataOptions::bcs_lossless())
+        .await?
+        .into_object()?;
+    let Some(SuiRawData::Package(package)) = object.bcs else {
+        bail!("Object {package_id} is not a package");
+    };
+    let package = MovePackage::new(
+        package.id,
+        object.version,
+        package.module_map,
+        protocol_config.max_move_package_size(),
+        package.type_origin_table,
+        package.linkage_table,
+    )?;
+
+    // The modules are built at 0x0, compare them at the address of the original package, as
+    // they will be published.
+    substitute_package_id(&mut modules, package.original_package_id())?;
+
+    let check_struct_and_pub_function_linking = true;
+    let check_struct_layout = true;
+    let check_friend_linking = false;
+    let compatibility = Compatibility::new(
+        check_struct_and_pub_function_linking,
+        check_struct_layout,
+        check_friend_linking,
+    );
+
+    let mut new_normalized = normalize_deserialized_modules(modules.iter());
+    for (name, cur_module) in package.normalize()? {
+        let Some(new_module) = new_normalized.remove(&name) else {
+            bail!(CliError::InvalidInput(format!(
+                "Existing module {name} of package {package_id} not found in the upgraded package"
+            )));
+        };
+        compatibility.check(&cur_module, &new_module).map_err(|e| {
+            CliError::InvalidInput(format!(
+                "Module {name} is not compatible with package {package_id}: {e:?}"
+            ))
+        })?;
+        check_upgrade_policy(policy, &cur_module, &new_module).map_err(|e| {
+            CliError::InvalidInput(format!(
+                "Module {name} is not an upgrade of package {package_id} allowed by its \
+                 upgrade policy: {e}"
+            ))
+        })?;
+    }
+    if policy == UPGRADE_POLICY_DEP_ONLY {
+        if let Some(name) = new_normalized.keys().next() {
+            bail!(CliError::InvalidInput(format!(
+                "Module {name} cannot be added to package {package_id}, its upgrade policy only \
+                 allows changing dependencies"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check the rules of the stricter upgrade policies on top of compatibility: an additive upgrade
+/// can only add structs, functions and friends to a module, and a dependency-only upgrade cannot
+/// change the module at all.
+fn check_upgrade_policy(
+    policy: u8,
+    cur_module: &normalized::Module,
+    new_module: &normalized::Module,
+) -> Result<(), anyhow::Error> {
+    if policy == UPGRADE_POLICY_ADDITIVE {
+        for (name, cur_struct) in &cur_module.structs {
+            ensure!(
+                new_module.structs.get(name) == Some(cur_struct),
+                "struct {name} is changed"
+            );
+        }
+        for (name, cur_function) in &cur_module.exposed_functions {
+            ensure!(
+                new_module.exposed_functions.get(name) == Some(cur_function),
+                "function {name} is changed"
+            );
+        }
+        for friend in &cur_module.friends {
+            ensure!(
+                new_module.friends.contains(friend),
+                "friend {friend} is removed"
+            );
+        }
+    } else if policy == UPGRADE_POLICY_DEP_ONLY {
+        ensure!(
+            new_module.structs == cur_module.structs,
+            "structs are added or changed"
+        );
+        ensure!(
+            new_module.exposed_functions == cur_module.exposed_functions,
+            "functions are added or changed"
+        );
+        ensure!(
+            new_module.friends == cur_module.friends,
+            "friends are added or removed"
+        );
+    }
+    Ok(())
+}
+
+/// The protocol config of the network at its current protocol version.
+async fn current_protocol_config(client: &SuiClient) -> Result<ProtocolConfig, anyhow::Error> {
+    let version = client
+        .governance_api()
+        .get_latest_sui_system_state()
+        .await?
+        .protocol_version;
+    ensure!(
+        (ProtocolVersion::MIN.as_u64()..=ProtocolVersion::MAX.as_u64()).contains(&version),
+        "The network runs protocol version {version}, which is not supported by this client"
+    );
+    Ok(ProtocolConfig::get_for_version(ProtocolVersion::new(
+        version,
+    )))
+}
+
 pub async fn call_move(
     package: ObjectID,
     module: &str,
     function: &str,
     type_args: Vec<TypeTag>,
     gas: Option<ObjectID>,
-    gas_budget: u64,
+    gas_budget: Option<u64>,
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
-) -> Result<SuiTransactionResponse, anyhow::Error> {
+    mode: TransactionMode,
+) -> Result<SuiClientCommandResult, anyhow::Error> {
     // Convert all numeric input to String, this will allow number input from the CLI without failing SuiJSON's checks.
     let args = args
         .into_iter()
         .map(|value| SuiJsonValue::new(convert_number_to_string(value.to_json_value())))
-        .collect::<Result<_, _>>()?;
+        .collect::<Result<Vec<_>, _>>()?;
 
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
+    let type_args = type_args
+        .into_iter()
+        .map(|arg| arg.try_into())
+        .collect::<Result<Vec<SuiTypeTag>, _>>()?;
+
     let client = context.get_client().await?;
-    let data = client
-        .transaction_builder()
-        .move_call(
+    let data = build_transaction(&client, gas_budget, mode, |gas_budget| {
+        client.transaction_builder().move_call(
             sender,
             package,
             module,
             function,
-            type_args
-                .into_iter()
-                .map(|arg| arg.try_into())
-                .collect::<Result<Vec<_>, _>>()?,
-            args,
+            type_args.clone(),
+            args.clone(),
             gas,
             gas_budget,
         )
-        .await?;
+    })
+    .await?;
+    if let Some(result) = simulate_transaction(&client, &data, mode).await? {
+        return Ok(result);
+    }
     let signature = context
         .config
         .keystore
@@ -1529,9 +2229,89 @@ pub async fn call_move(
         .as_ref()
         .ok_or_else(|| anyhow!("Effects from SuiTransactionResult should not be empty"))?;
     if matches!(effects.status(), SuiExecutionStatus::Failure { .. }) {
-        return Err(anyhow!("Error calling module: {:#?}", effects.status()));
+        bail!(CliError::TransactionFailed(format!(
+            "Error calling module: {:#?}",
+            effects.status()
+        )));
     }
-    Ok(response)
+    Ok(SuiClientCommandResult::Call(response))
+}
+
+/// Build a transaction with `build` for `gas_budget` or, if it is not provided, for the budget
+/// estimated by dry-running the transaction.
+async fn build_transaction<F, Fut>(
+    client: &SuiClient,
+    gas_budget: Option<u64>,
+    mode: TransactionMode,
+    build: F,
+) -> Result<TransactionData, anyhow::Error>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<TransactionData, anyhow::Error>>,
+{
+    let gas_budget = match (gas_budget, mode) {
+        (Some(gas_budget), _) => gas_budget,
+        // Dev-inspect does not charge gas, there is nothing to estimate.
+        (None, TransactionMode::DevInspect) => 0,
+        // Any gas coin can pay for the dry run, which uses a mock coin.
+        (None, _) => estimate_gas_budget(client, &build(0).await?).await?,
+    };
+    build(gas_budget).await
+}
+
+/// Estimate the gas budget of `data` from the gas used by a dry run of the transaction, paid
+/// for by a mock gas coin large enough for any budget, plus a safety margin.
+async fn estimate_gas_budget(
+    client: &SuiClient,
+    data: &TransactionData,
+) -> Result<u64, anyhow::Error> {
+    let sender = data.sender();
+    let dry_run_data = TransactionData::new_with_gas_data(
+        data.kind().clone(),
+        sender,
+        GasData {
+            payment: vec![],
+            owner: data.gas_owner(),
+            price: data.gas_price(),
+            budget: ProtocolConfig::get_for_max_version().max_tx_gas(),
+        },
+    );
+    let response = client.read_api().dry_run_transaction(dry_run_data).await?;
+    if let SuiExecutionStatus::Failure { error } = response.effects.status() {
+        return Err(sui_sdk::error::Error::DryRunFailed(format!(
+            "cannot estimate the gas budget: {error}"
+        ))
+        .into());
+    }
+    let gas_used = response.effects.gas_used();
+    let cost = gas_used.computation_cost + gas_used.storage_cost;
+    Ok(cost + cost * GAS_BUDGET_ESTIMATE_MARGIN_PERCENT / 100)
+}
+
+/// Dry-run or dev-inspect `data` according to `mode`, or return None in
+/// `TransactionMode::Execute`, where the caller signs and executes the transaction.
+async fn simulate_transaction(
+    client: &SuiClient,
+    data: &TransactionData,
+    mode: TransactionMode,
+) -> Result<Option<SuiClientCommandResult>, anyhow::Error> {
+    Ok(match mode {
+        TransactionMode::Execute => None,
+        TransactionMode::DryRun => Some(SuiClientCommandResult::DryRun(
+            client.read_api().dry_run_transaction(data.clone()).await?,
+        )),
+        TransactionMode::DevInspect => Some(SuiClientCommandResult::DevInspect(
+            client
+                .read_api()
+                .dev_inspect_transaction(
+                    data.sender(),
+                    data.kind().clone(),
+                    Some(data.gas_price()),
+                    None,
+                )
+                .await?,
+        )),
+    })
 }
 
 fn convert_number_to_string(value: Value) -> Value {
@@ -1588,17 +2368,7 @@ pub fn write_transaction_response(response: &SuiTransactionResponse) -> Result<S
 
 impl Debug for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let s = unwrap_err_to_string(|| match self {
-            SuiClientCommandResult::Object(object_read) => {
-                let object = object_read.object()?;
-                Ok(serde_json::to_string_pretty(&object)?)
-            }
-            SuiClientCommandResult::RawObject(raw_object_read) => {
-                let raw_object = raw_object_read.object()?;
-                Ok(serde_json::to_string_pretty(&raw_object)?)
-            }
-            _ => Ok(serde_json::to_string_pretty(self)?),
-        });
+        let s = unwrap_err_to_string(|| Ok(serde_json::to_string_pretty(self)?));
         write!(f, "{}", s)
     }
 }
@@ -1626,41 +2396,87 @@ impl SuiClientCommandResult {
     }
 }
 
+/// The output of a client command. With `--json`, it is printed as `{"command": .., "result": ..}`,
+/// where `command` is the kebab-cased name of the variant, which is also the name of the subcommand
+/// when there is one, and `result` is the payload of the variant, omitted if it has none.
 #[derive(Serialize)]
-#[serde(untagged)]
+#[serde(tag = "command", content = "result", rename_all = "kebab-case")]
 pub enum SuiClientCommandResult {
     Publish(SuiTransactionResponse),
+    Upgrade(SuiTransactionResponse),
     VerifySource,
+    #[serde(serialize_with = "serialize_object_response")]
     Object(SuiObjectResponse),
+    #[serde(serialize_with = "serialize_object_response")]
     RawObject(SuiObjectResponse),
     Call(SuiTransactionResponse),
-    Transfer(
-        // Skipping serialisation for elapsed time.
-        #[serde(skip)] u128,
-        SuiTransactionResponse,
-    ),
+    // Skipping serialisation for elapsed time.
+    #[serde(serialize_with = "serialize_transfer")]
+    Transfer(u128, SuiTransactionResponse),
     TransferSui(SuiTransactionResponse),
     Pay(SuiTransactionResponse),
+    #[serde(rename = "pay_sui")]
     PaySui(SuiTransactionResponse),
+    #[serde(rename = "pay_all_sui")]
     PayAllSui(SuiTransactionResponse),
-    Addresses(Vec<SuiAddress>, Option<SuiAddress>),
+    #[serde(rename_all = "camelCase")]
+    Addresses {
+        addresses: Vec<SuiAddress>,
+        active_address: Option<SuiAddress>,
+    },
     Objects(Vec<SuiObjectResponse>),
+    #[serde(rename = "dynamic-field")]
     DynamicFieldQuery(DynamicFieldPage),
     SyncClientState,
-    NewAddress((SuiAddress, String, SignatureScheme)),
+    #[serde(rename_all = "camelCase")]
+    NewAddress {
+        address: SuiAddress,
+        recovery_phrase: String,
+        key_scheme: SignatureScheme,
+    },
     Gas(Vec<GasCoin>),
     SplitCoin(SuiTransactionResponse),
     MergeCoin(SuiTransactionResponse),
     Switch(SwitchResponse),
     ActiveAddress(Option<SuiAddress>),
     ActiveEnv(Option<String>),
-    Envs(Vec<SuiEnv>, Option<String>),
+    #[serde(rename_all = "camelCase")]
+    Envs {
+        envs: Vec<SuiEnv>,
+        active_env: Option<String>,
+    },
+    #[serde(rename = "create-example-nft")]
     CreateExampleNFT(SuiObjectResponse),
     SerializeTransferSui(String),
+    Ptb(SuiTransactionResponse),
+    DryRun(DryRunTransactionResponse),
+    DevInspect(DevInspectResults),
+    SerializeUnsignedTransaction(String),
+    SignTx(String),
     ExecuteSignedTx(SuiTransactionResponse),
     NewEnv(SuiEnv),
 }
 
+/// Serializes the object of the response, or the whole response, which carries the error, if there
+/// is no object.
+fn serialize_object_response<S: serde::Serializer>(
+    response: &SuiObjectResponse,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match response.object() {
+        Ok(object) => object.serialize(serializer),
+        Err(_) => response.serialize(serializer),
+    }
+}
+
+fn serialize_transfer<S: serde::Serializer>(
+    _time_elapsed: &u128,
+    response: &SuiTransactionResponse,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    response.serialize(serializer)
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct SwitchResponse {
     /// Active address
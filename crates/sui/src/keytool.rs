@@ -1,20 +1,29 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use bip32::DerivationPath;
 use clap::*;
+use colored::Colorize;
 use fastcrypto::encoding::{decode_bytes_hex, Base64, Encoding};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::KeyPair;
+use inquire::Password;
+use serde::Serialize;
 use shared_crypto::intent::{Intent, IntentMessage};
+use std::fmt::{Debug, Display, Formatter, Write};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
+use std::time::Duration;
+use sui_keys::encryption::KdfParams;
 use sui_keys::key_derive::generate_new_key;
 use sui_keys::keypair_file::{
-    read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
-    write_keypair_to_file,
+    read_authority_keypair_from_file, read_keypair_from_file, read_keystore_backup_from_file,
+    write_authority_keypair_to_file, write_keypair_to_file, write_keystore_backup_to_file,
+    KeystoreBackup,
+};
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedFileKeystore, Keystore, KEYSTORE_SESSION_ENV_VAR,
 };
-use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{get_authority_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
 use sui_types::crypto::{DefaultHash, PublicKey, Signature};
@@ -22,6 +31,9 @@ use sui_types::messages::TransactionData;
 use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
 use sui_types::signature::GenericSignature;
 use tracing::info;
+
+use crate::sui_commands::CliError;
+
 #[cfg(test)]
 #[path = "unit_tests/keytool_tests.rs"]
 mod keytool_tests;
@@ -104,11 +116,190 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+
+    /// Encrypt the plain sui.keystore with a password. The keypairs are encrypted with
+    /// AES-256-GCM under a key derived from the password with Argon2id. The encrypted keystore
+    /// is locked, it is unlocked for a session with `keytool unlock`, or with the password set
+    /// in the SUI_KEYSTORE_PASSWORD environment variable.
+    Encrypt,
+
+    /// Change the password of the encrypted sui.keystore, this ends the unlocked session.
+    ChangePassword,
+
+    /// Unlock the encrypted sui.keystore for the following commands, until the session
+    /// expires or `keytool lock` is called. The commands use the session when the printed
+    /// session token is set in the SUI_KEYSTORE_SESSION environment variable.
+    Unlock {
+        /// Duration of the session in seconds.
+        #[clap(long, default_value = "3600")]
+        duration: u64,
+    },
+
+    /// Lock the encrypted sui.keystore, ending the unlocked session.
+    Lock,
+
+    /// Export the keypair of the given address in sui.keystore as Base64 encoded
+    /// `flag || privkey`, the format of the keypair files of `keytool generate`. The keypair is
+    /// written to the given file, or printed if no file is given. The keystore does not keep
+    /// the mnemonic phrase the keypair was derived from, so it cannot be exported.
+    Export {
+        #[clap(long, parse(try_from_str = decode_bytes_hex))]
+        address: SuiAddress,
+        #[clap(long)]
+        file: Option<PathBuf>,
+    },
+
+    /// Back up all keypairs of sui.keystore to a versioned JSON file, which can be restored
+    /// with `keytool restore`. With `--encrypt`, the keypairs are encrypted with a password.
+    /// The keystore holds neither aliases nor the mnemonic phrases and derivation paths of its
+    /// keypairs, so the backup holds the keypairs only.
+    Backup {
+        file: PathBuf,
+        #[clap(long)]
+        encrypt: bool,
+    },
+
+    /// Restore the keypairs of a backup made with `keytool backup` into sui.keystore, prompting
+    /// for the password if the backup is encrypted.
+    Restore { file: PathBuf },
+}
+
+/// The output of a keytool command, printed as text or, with `--json`, as
+/// `{"command": .., "result": ..}`, where `command` is the name of the keytool subcommand and
+/// `result` is the content of the variant, omitted if it has none.
+#[derive(Serialize)]
+#[serde(tag = "command", content = "result", rename_all = "kebab-case")]
+pub enum KeyToolCommandResponse {
+    Generate(KeypairFile),
+    Show(Key),
+    ShowAuthority(AuthorityKey),
+    Unpack(KeypairFile),
+    List(Vec<Key>),
+    Sign(SignData),
+    Import(Key),
+    LoadKeypair(LoadedKeypair),
+    MultiSigAddress(MultiSigAddress),
+    MultiSigCombinePartialSig(MultiSigCombinePartialSig),
+    Encrypt { path: PathBuf },
+    ChangePassword,
+    Unlock { duration: u64, session: String },
+    Lock,
+    Export(ExportedKeypair),
+    Backup { file: PathBuf, keys: usize },
+    Restore { addresses: Vec<SuiAddress> },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Key {
+    pub sui_address: SuiAddress,
+    pub public_base64_key: String,
+    pub key_scheme: SignatureScheme,
+    pub flag: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+}
+
+impl From<&PublicKey> for Key {
+    fn from(key: &PublicKey) -> Self {
+        let peer_id = match key {
+            PublicKey::Ed25519(public_key) => Some(anemo::PeerId(public_key.0.into()).to_string()),
+            _ => None,
+        };
+        Self {
+            sui_address: key.into(),
+            public_base64_key: key.encode_base64(),
+            key_scheme: key.scheme(),
+            flag: key.flag(),
+            peer_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorityKey {
+    pub public_base64_key: String,
+    pub key_scheme: SignatureScheme,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeypairFile {
+    pub sui_address: SuiAddress,
+    pub key_scheme: SignatureScheme,
+    pub file: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignData {
+    pub sui_address: SuiAddress,
+    /// Base64 encoded BCS bytes of the transaction data.
+    pub raw_tx_data: String,
+    pub intent: Intent,
+    /// Base64 encoded BCS bytes of the intent message.
+    pub raw_intent_msg: String,
+    /// Base64 encoded digest of the intent message, which is what gets signed.
+    pub digest: String,
+    /// Base64 encoded `flag || sig || pk`.
+    pub sui_signature: String,
+}
+
+/// The keypairs of a keypair file, as Base64 encoded `flag || privkey` for the account keypair
+/// and Base64 encoded `privkey` for the others.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedKeypair {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worker_keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_keypair: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigAddress {
+    pub multisig_address: SuiAddress,
+    pub multisig: Vec<MultiSigParticipant>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigParticipant {
+    pub address: SuiAddress,
+    pub public_base64_key: String,
+    pub weight: WeightUnit,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigCombinePartialSig {
+    pub multisig_address: SuiAddress,
+    #[serde(skip)]
+    pub multisig_parsed: GenericSignature,
+    /// Base64 encoded multisig, to be used as a signature of `sui client execute-signed-tx`.
+    pub multisig_serialized: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedKeypair {
+    pub sui_address: SuiAddress,
+    /// Base64 encoded `flag || privkey`, only when the keypair is not written to a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keypair: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
 }
 
 impl KeyToolCommand {
-    pub fn execute(self, keystore: &mut Keystore) -> Result<(), anyhow::Error> {
-        match self {
+    pub fn execute(self, keystore: &mut Keystore) -> Result<KeyToolCommandResponse, anyhow::Error> {
+        let response = match self {
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -117,90 +308,72 @@ impl KeyToolCommand {
                     // Generate BLS12381 key for authority without key derivation.
                     // The saved keypair is encoded `privkey || pubkey` without the scheme flag.
                     let (address, keypair) = get_authority_key_pair();
-                    let file_name = format!("bls-{address}.key");
-                    write_authority_keypair_to_file(&keypair, file_name)?;
+                    let file = format!("bls-{address}.key");
+                    write_authority_keypair_to_file(&keypair, &file)?;
+                    KeyToolCommandResponse::Generate(KeypairFile {
+                        sui_address: address,
+                        key_scheme: SignatureScheme::BLS12381,
+                        file: file.into(),
+                    })
                 } else {
                     let (address, kp, scheme, _) = generate_new_key(key_scheme, derivation_path)?;
                     let file = format!("{address}.key");
                     write_keypair_to_file(&kp, &file)?;
-                    println!(
-                        "Keypair wrote to file path: {:?} with scheme: {:?}",
-                        file, scheme
-                    );
-                }
-            }
-            KeyToolCommand::Show { file } => {
-                let res = read_keypair_from_file(&file);
-                match res {
-                    Ok(keypair) => {
-                        println!("Public Key: {}", keypair.public().encode_base64());
-                        println!("Flag: {}", keypair.public().flag());
-                        if let PublicKey::Ed25519(public_key) = keypair.public() {
-                            let peer_id = anemo::PeerId(public_key.0.into());
-                            println!("PeerId: {}", peer_id);
-                        }
-                    }
-                    Err(_) => {
-                        let res = read_authority_keypair_from_file(&file);
-                        match res {
-                            Ok(keypair) => {
-                                println!("Public Key: {}", keypair.public().encode_base64());
-                                println!("Flag: {}", SignatureScheme::BLS12381);
-                            }
-                            Err(e) => {
-                                println!("Failed to read keypair at path {:?} err: {:?}", file, e)
-                            }
-                        }
-                    }
+                    KeyToolCommandResponse::Generate(KeypairFile {
+                        sui_address: address,
+                        key_scheme: scheme,
+                        file: file.into(),
+                    })
                 }
             }
+            KeyToolCommand::Show { file } => match read_keypair_from_file(&file) {
+                Ok(keypair) => KeyToolCommandResponse::Show((&keypair.public()).into()),
+                Err(_) => match read_authority_keypair_from_file(&file) {
+                    Ok(keypair) => KeyToolCommandResponse::ShowAuthority(AuthorityKey {
+                        public_base64_key: keypair.public().encode_base64(),
+                        key_scheme: SignatureScheme::BLS12381,
+                    }),
+                    Err(e) => bail!(CliError::InvalidInput(format!(
+                        "Failed to read keypair at path {:?} err: {:?}",
+                        file, e
+                    ))),
+                },
+            },
 
             KeyToolCommand::Unpack { keypair } => {
-                store_and_print_keypair((&keypair.public()).into(), keypair)
+                KeyToolCommandResponse::Unpack(store_keypair(keypair)?)
             }
             KeyToolCommand::List => {
-                println!(
-                    " {0: ^42} | {1: ^45} | {2: ^6}",
-                    "Sui Address", "Public Key (Base64)", "Scheme"
-                );
-                println!("{}", ["-"; 100].join(""));
-                for pub_key in keystore.keys() {
-                    println!(
-                        " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pub_key),
-                        pub_key.encode_base64(),
-                        pub_key.scheme().to_string()
-                    );
-                }
+                KeyToolCommandResponse::List(keystore.keys().iter().map(Key::from).collect())
             }
             KeyToolCommand::Sign {
                 address,
                 data,
                 intent,
             } => {
-                println!("Signer address: {}", address);
-                println!("Raw tx_bytes to execute: {}", data);
                 let intent = intent.unwrap_or_default();
-                println!("Intent: {:?}", intent);
                 let msg: TransactionData =
                     bcs::from_bytes(&Base64::decode(&data).map_err(|e| {
-                        anyhow!("Cannot deserialize data as TransactionData {:?}", e)
+                        CliError::InvalidInput(format!(
+                            "Cannot deserialize data as TransactionData {:?}",
+                            e
+                        ))
                     })?)?;
                 let intent_msg = IntentMessage::new(intent, msg);
-                println!(
-                    "Raw intent message: {:?}",
-                    Base64::encode(bcs::to_bytes(&intent_msg)?)
-                );
+                let raw_intent_msg = Base64::encode(bcs::to_bytes(&intent_msg)?);
                 let mut hasher = DefaultHash::default();
                 hasher.update(bcs::to_bytes(&intent_msg)?);
                 let digest = hasher.finalize().digest;
-                println!("Digest to sign: {:?}", Base64::encode(digest));
                 let sui_signature =
-                    keystore.sign_secure(&address, &intent_msg.value, intent_msg.intent)?;
-                println!(
-                    "Serialized signature (`flag || sig || pk` in Base64): {:?}",
-                    sui_signature.encode_base64()
-                );
+                    keystore.sign_secure(&address, &intent_msg.value, intent_msg.intent.clone())?;
+                KeyToolCommandResponse::Sign(SignData {
+                    sui_address: address,
+                    raw_tx_data: data,
+                    intent: intent_msg.intent,
+                    raw_intent_msg,
+                    digest: Base64::encode(digest),
+                    sui_signature: sui_signature.encode_base64(),
+                })
             }
             KeyToolCommand::Import {
                 mnemonic_phrase,
@@ -210,29 +383,48 @@ impl KeyToolCommand {
                 let address =
                     keystore.import_from_mnemonic(&mnemonic_phrase, key_scheme, derivation_path)?;
                 info!("Key imported for address [{address}]");
+                let key = keystore
+                    .keys()
+                    .into_iter()
+                    .find(|key| SuiAddress::from(key) == address)
+                    .ok_or_else(|| anyhow!("Imported key of address [{address}] not found"))?;
+                KeyToolCommandResponse::Import((&key).into())
             }
 
             KeyToolCommand::LoadKeypair { file } => {
-                match read_keypair_from_file(&file) {
+                let loaded = match read_keypair_from_file(&file) {
+                    // Account keypair is encoded with the key scheme flag {},
+                    // and network and worker keypair are not.
                     Ok(keypair) => {
-                        // Account keypair is encoded with the key scheme flag {},
-                        // and network and worker keypair are not.
-                        println!("Account Keypair: {}", keypair.encode_base64());
-                        if let SuiKeyPair::Ed25519(kp) = keypair {
-                            println!("Network Keypair: {}", kp.encode_base64());
-                            println!("Worker Keypair: {}", kp.encode_base64());
-                        };
+                        let account_keypair = Some(keypair.encode_base64());
+                        match keypair {
+                            SuiKeyPair::Ed25519(kp) => LoadedKeypair {
+                                account_keypair,
+                                network_keypair: Some(kp.encode_base64()),
+                                worker_keypair: Some(kp.encode_base64()),
+                                protocol_keypair: None,
+                            },
+                            _ => LoadedKeypair {
+                                account_keypair,
+                                ..Default::default()
+                            },
+                        }
                     }
                     Err(_) => {
                         // Authority keypair file is not stored with the flag, it will try read as BLS keypair..
                         match read_authority_keypair_from_file(&file) {
-                            Ok(kp) => println!("Protocol Keypair: {}", kp.encode_base64()),
-                            Err(e) => {
-                                println!("Failed to read keypair at path {:?} err: {:?}", file, e)
-                            }
+                            Ok(kp) => LoadedKeypair {
+                                protocol_keypair: Some(kp.encode_base64()),
+                                ..Default::default()
+                            },
+                            Err(e) => bail!(CliError::InvalidInput(format!(
+                                "Failed to read keypair at path {:?} err: {:?}",
+                                file, e
+                            ))),
                         }
                     }
-                }
+                };
+                KeyToolCommandResponse::LoadKeypair(loaded)
             }
             KeyToolCommand::MultiSigAddress {
                 threshold,
@@ -241,22 +433,18 @@ impl KeyToolCommand {
             } => {
                 let multisig_pk = MultiSigPublicKey::new(pks.clone(), weights.clone(), threshold)?;
                 let address: SuiAddress = multisig_pk.into();
-                println!("MultiSig address: {address}");
-
-                println!("Participating parties:");
-                println!(
-                    " {0: ^42} | {1: ^50} | {2: ^6}",
-                    "Sui Address", "Public Key (Base64)", "Weight"
-                );
-                println!("{}", ["-"; 100].join(""));
-                for (pk, w) in pks.into_iter().zip(weights.into_iter()) {
-                    println!(
-                        " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pk),
-                        pk.encode_base64(),
-                        w
-                    );
-                }
+                KeyToolCommandResponse::MultiSigAddress(MultiSigAddress {
+                    multisig_address: address,
+                    multisig: pks
+                        .into_iter()
+                        .zip(weights.into_iter())
+                        .map(|(pk, weight)| MultiSigParticipant {
+                            address: (&pk).into(),
+                            public_base64_key: pk.encode_base64(),
+                            weight,
+                        })
+                        .collect(),
+                })
             }
             KeyToolCommand::MultiSigCombinePartialSig {
                 sigs,
@@ -268,26 +456,308 @@ impl KeyToolCommand {
                 let address: SuiAddress = multisig_pk.clone().into();
                 let multisig = MultiSig::combine(sigs, multisig_pk)?;
                 let generic_sig: GenericSignature = multisig.into();
-                println!("MultiSig address: {address}");
-                println!("MultiSig parsed: {:?}", generic_sig);
-                println!("MultiSig serialized: {:?}", generic_sig.encode_base64());
+                KeyToolCommandResponse::MultiSigCombinePartialSig(MultiSigCombinePartialSig {
+                    multisig_address: address,
+                    multisig_serialized: generic_sig.encode_base64(),
+                    multisig_parsed: generic_sig,
+                })
+            }
+            KeyToolCommand::Encrypt => {
+                let encrypted = match keystore {
+                    Keystore::File(file) => {
+                        let password = read_new_password()?;
+                        EncryptedFileKeystore::encrypt(file, &password, KdfParams::default())?
+                    }
+                    Keystore::EncryptedFile(_) => bail!("Keystore is already encrypted"),
+                    Keystore::InMem(_) => bail!("Cannot encrypt an in-memory keystore"),
+                };
+                let path = encrypted.path().to_path_buf();
+                *keystore = Keystore::EncryptedFile(encrypted);
+                KeyToolCommandResponse::Encrypt { path }
+            }
+            KeyToolCommand::ChangePassword => {
+                let keystore = encrypted_keystore(keystore)?;
+                let current_password = Password::new("Current keystore password:")
+                    .without_confirmation()
+                    .prompt()?;
+                let new_password = read_new_password()?;
+                keystore.change_password(&current_password, &new_password)?;
+                KeyToolCommandResponse::ChangePassword
+            }
+            KeyToolCommand::Unlock { duration } => {
+                let keystore = encrypted_keystore(keystore)?;
+                if keystore.is_locked() {
+                    let password = Password::new("Keystore password:")
+                        .without_confirmation()
+                        .prompt()?;
+                    keystore.unlock(&password)?;
+                }
+                let session = keystore.start_session(Duration::from_secs(duration))?;
+                KeyToolCommandResponse::Unlock {
+                    duration,
+                    session: session.to_string(),
+                }
+            }
+            KeyToolCommand::Lock => {
+                let keystore = encrypted_keystore(keystore)?;
+                keystore.lock()?;
+                KeyToolCommandResponse::Lock
+            }
+            KeyToolCommand::Export { address, file } => {
+                let keypair = keystore.get_key(&address)?;
+                let keypair = match &file {
+                    Some(file) => {
+                        write_keypair_to_file(keypair, file)?;
+                        None
+                    }
+                    None => Some(keypair.encode_base64()),
+                };
+                KeyToolCommandResponse::Export(ExportedKeypair {
+                    sui_address: address,
+                    keypair,
+                    file,
+                })
+            }
+            KeyToolCommand::Backup { file, encrypt } => {
+                let password = if encrypt {
+                    Some(read_new_password()?)
+                } else {
+                    None
+                };
+                let addresses = keystore.addresses();
+                let keypairs = addresses
+                    .iter()
+                    .map(|address| keystore.get_key(address))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let backup =
+                    KeystoreBackup::new(&keypairs, password.as_deref(), KdfParams::default())?;
+                write_keystore_backup_to_file(&backup, &file)?;
+                KeyToolCommandResponse::Backup {
+                    file,
+                    keys: keypairs.len(),
+                }
+            }
+            KeyToolCommand::Restore { file } => {
+                let backup = read_keystore_backup_from_file(&file)?;
+                let password = if backup.is_encrypted() {
+                    Some(
+                        Password::new("Backup password:")
+                            .without_confirmation()
+                            .prompt()?,
+                    )
+                } else {
+                    None
+                };
+                let mut addresses = vec![];
+                for keypair in backup.key_pairs(password.as_deref())? {
+                    let address: SuiAddress = (&keypair.public()).into();
+                    keystore.add_key(keypair)?;
+                    addresses.push(address);
+                }
+                KeyToolCommandResponse::Restore { addresses }
+            }
+        };
+
+        Ok(response)
+    }
+}
+
+impl Display for KeyToolCommandResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        match self {
+            KeyToolCommandResponse::Generate(KeypairFile {
+                key_scheme, file, ..
+            }) => {
+                writeln!(
+                    writer,
+                    "Keypair wrote to file path: {:?} with scheme: {:?}",
+                    file, key_scheme
+                )?;
+            }
+            KeyToolCommandResponse::Show(key) => {
+                writeln!(writer, "Public Key: {}", key.public_base64_key)?;
+                writeln!(writer, "Flag: {}", key.flag)?;
+                if let Some(peer_id) = &key.peer_id {
+                    writeln!(writer, "PeerId: {}", peer_id)?;
+                }
+            }
+            KeyToolCommandResponse::ShowAuthority(key) => {
+                writeln!(writer, "Public Key: {}", key.public_base64_key)?;
+                writeln!(writer, "Flag: {}", key.key_scheme)?;
+            }
+            KeyToolCommandResponse::Unpack(KeypairFile { file, .. }) => {
+                writeln!(
+                    writer,
+                    "Address, keypair and key scheme written to {}",
+                    file.display()
+                )?;
+            }
+            KeyToolCommandResponse::List(keys) => {
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^45} | {2: ^6}",
+                    "Sui Address", "Public Key (Base64)", "Scheme"
+                )?;
+                writeln!(writer, "{}", ["-"; 100].join(""))?;
+                for key in keys {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^45} | {2: ^6}",
+                        key.sui_address,
+                        key.public_base64_key,
+                        key.key_scheme.to_string()
+                    )?;
+                }
+            }
+            KeyToolCommandResponse::Sign(data) => {
+                writeln!(writer, "Signer address: {}", data.sui_address)?;
+                writeln!(writer, "Raw tx_bytes to execute: {}", data.raw_tx_data)?;
+                writeln!(writer, "Intent: {:?}", data.intent)?;
+                writeln!(writer, "Raw intent message: {:?}", data.raw_intent_msg)?;
+                writeln!(writer, "Digest to sign: {:?}", data.digest)?;
+                writeln!(
+                    writer,
+                    "Serialized signature (`flag || sig || pk` in Base64): {:?}",
+                    data.sui_signature
+                )?;
+            }
+            KeyToolCommandResponse::Import(key) => {
+                writeln!(writer, "Key imported for address [{}]", key.sui_address)?;
+            }
+            KeyToolCommandResponse::LoadKeypair(loaded) => {
+                if let Some(kp) = &loaded.account_keypair {
+                    writeln!(writer, "Account Keypair: {}", kp)?;
+                }
+                if let Some(kp) = &loaded.network_keypair {
+                    writeln!(writer, "Network Keypair: {}", kp)?;
+                }
+                if let Some(kp) = &loaded.worker_keypair {
+                    writeln!(writer, "Worker Keypair: {}", kp)?;
+                }
+                if let Some(kp) = &loaded.protocol_keypair {
+                    writeln!(writer, "Protocol Keypair: {}", kp)?;
+                }
+            }
+            KeyToolCommandResponse::MultiSigAddress(multisig) => {
+                writeln!(writer, "MultiSig address: {}", multisig.multisig_address)?;
+                writeln!(writer, "Participating parties:")?;
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^50} | {2: ^6}",
+                    "Sui Address", "Public Key (Base64)", "Weight"
+                )?;
+                writeln!(writer, "{}", ["-"; 100].join(""))?;
+                for participant in &multisig.multisig {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^45} | {2: ^6}",
+                        participant.address, participant.public_base64_key, participant.weight
+                    )?;
+                }
+            }
+            KeyToolCommandResponse::MultiSigCombinePartialSig(multisig) => {
+                writeln!(writer, "MultiSig address: {}", multisig.multisig_address)?;
+                writeln!(writer, "MultiSig parsed: {:?}", multisig.multisig_parsed)?;
+                writeln!(
+                    writer,
+                    "MultiSig serialized: {:?}",
+                    multisig.multisig_serialized
+                )?;
+            }
+            KeyToolCommandResponse::Encrypt { path } => {
+                writeln!(writer, "Keystore encrypted at {:?}", path)?;
+            }
+            KeyToolCommandResponse::ChangePassword => {
+                writeln!(writer, "Keystore password changed")?;
+            }
+            KeyToolCommandResponse::Unlock { duration, session } => {
+                writeln!(
+                    writer,
+                    "Keystore unlocked for {duration} seconds, set the session token in the \
+                     environment to use the session:"
+                )?;
+                writeln!(writer, "export {KEYSTORE_SESSION_ENV_VAR}={session}")?;
+            }
+            KeyToolCommandResponse::Lock => {
+                writeln!(writer, "Keystore locked")?;
+            }
+            KeyToolCommandResponse::Export(exported) => match (&exported.keypair, &exported.file) {
+                (Some(keypair), _) => writeln!(writer, "{}", keypair)?,
+                (None, Some(file)) => writeln!(
+                    writer,
+                    "Keypair of address [{}] written to {:?}",
+                    exported.sui_address, file
+                )?,
+                (None, None) => {}
+            },
+            KeyToolCommandResponse::Backup { file, keys } => {
+                writeln!(writer, "Backed up {} keys to {:?}", keys, file)?;
+            }
+            KeyToolCommandResponse::Restore { addresses } => {
+                for address in addresses {
+                    writeln!(writer, "Restored key for address [{address}]")?;
+                }
             }
         }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+impl Debug for KeyToolCommandResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let string = serde_json::to_string_pretty(self);
+        let s = match string {
+            Ok(s) => s,
+            Err(err) => format!("{err}").red().to_string(),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl KeyToolCommandResponse {
+    pub fn print(&self, pretty: bool) {
+        if pretty {
+            println!("{self}");
+        } else {
+            println!("{:?}", self);
+        }
+    }
+}
+
+fn encrypted_keystore(
+    keystore: &mut Keystore,
+) -> Result<&mut EncryptedFileKeystore, anyhow::Error> {
+    match keystore {
+        Keystore::EncryptedFile(keystore) => Ok(keystore),
+        _ => bail!(CliError::InvalidInput(
+            "Keystore is not encrypted, encrypt it with `sui keytool encrypt`".to_string()
+        )),
+    }
+}
 
-        Ok(())
+fn read_new_password() -> Result<String, anyhow::Error> {
+    let password = Password::new("New keystore password:")
+        .with_custom_confirmation_message("Confirm new keystore password:")
+        .prompt()?;
+    if password.is_empty() {
+        bail!(CliError::InvalidInput(
+            "Keystore password cannot be empty".to_string()
+        ));
     }
+    Ok(password)
 }
 
-fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
-    let path_str = format!("{}.key", address).to_lowercase();
-    let path = Path::new(&path_str);
-    let address = format!("{}", address);
+fn store_keypair(keypair: SuiKeyPair) -> Result<KeypairFile, anyhow::Error> {
+    let address: SuiAddress = (&keypair.public()).into();
+    let path = PathBuf::from(format!("{}.key", address).to_lowercase());
     let kp = keypair.encode_base64();
     let flag = keypair.public().flag();
     let out_str = format!("address: {}\nkeypair: {}\nflag: {}", address, kp, flag);
-    fs::write(path, out_str).unwrap();
-    println!(
-        "Address, keypair and key scheme written to {}",
-        path.to_str().unwrap()
-    );
+    fs::write(&path, out_str)?;
+    Ok(KeypairFile {
+        sui_address: address,
+        key_scheme: keypair.public().scheme(),
+        file: path,
+    })
 }
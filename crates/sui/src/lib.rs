@@ -7,6 +7,7 @@ pub mod config;
 pub mod console;
 pub mod fire_drill;
 pub mod keytool;
+pub mod ptb;
 pub mod shell;
 pub mod sui_commands;
 pub mod validator_commands;
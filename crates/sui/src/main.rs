@@ -3,7 +3,7 @@
 
 use clap::*;
 use colored::Colorize;
-use sui::sui_commands::SuiCommand;
+use sui::sui_commands::{CommandError, SuiCommand};
 use sui_types::exit_main;
 use tracing::debug;
 
@@ -62,5 +62,12 @@ async fn main() {
 
     debug!("Sui CLI version: {VERSION}");
 
-    exit_main!(args.command.execute().await);
+    let json = args.command.json_output();
+    match args.command.execute().await {
+        Err(err) if json => {
+            CommandError::from(&err).print();
+            std::process::exit(1);
+        }
+        result => exit_main!(result),
+    }
 }
@@ -0,0 +1,469 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands of `sui client ptb`, composing a programmable transaction from a sequence of
+//! commands written one per `--command` flag or one per line of a file, e.g.
+//! ```text
+//! split-coins gas 1000 2000
+//! transfer-objects 0x4a2b...c1 %0.0 %0.1
+//! move-call 0x2::devnet_nft::mint "My NFT" "An NFT" "ipfs://..."
+//! ```
+//!
+//! Commands are one of `move-call <package>::<module>::<function>[<type args>] <args>`,
+//! `transfer-objects <recipient> <objects>`, `split-coins <coin> <amounts>`,
+//! `merge-coins <coin> <coins>` and `make-move-vec <type or _> <elements>`, and take the
+//! arguments:
+//! - `gas`, the gas coin;
+//! - `%i` and `%i.j`, the result of the `i`-th command (from 0) and the `j`-th value of that
+//!   result, only for commands earlier in the transaction;
+//! - `@<object id>`, an object, passed by reference or by value as the command requires;
+//! - pure values: `true` and `false`, addresses starting with `0x`, quoted strings, and integers
+//!   with an optional type suffix among `u8`, `u16`, `u32`, `u64` (the default) and `u128`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure, Context};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages::{Argument, Command, ObjectArg, ProgrammableTransaction};
+use sui_types::object::Owner;
+use sui_types::parse_sui_type_tag;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+use crate::sui_commands::CliError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtbArgument {
+    GasCoin,
+    Result(u16),
+    NestedResult(u16, u16),
+    Object(ObjectID),
+    /// BCS bytes of a pure value
+    Pure(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtbCommand {
+    MoveCall {
+        package: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<PtbArgument>,
+    },
+    TransferObjects {
+        recipient: PtbArgument,
+        objects: Vec<PtbArgument>,
+    },
+    SplitCoins {
+        coin: PtbArgument,
+        amounts: Vec<PtbArgument>,
+    },
+    MergeCoins {
+        coin: PtbArgument,
+        coins: Vec<PtbArgument>,
+    },
+    MakeMoveVec {
+        type_: Option<TypeTag>,
+        elements: Vec<PtbArgument>,
+    },
+}
+
+impl FromStr for PtbCommand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let (name, args) = tokens
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty command"))?;
+        let name = match name {
+            Token::Word(name) => name.as_str(),
+            Token::String(_) => bail!("Command name must not be quoted"),
+        };
+        Ok(match name {
+            "move-call" => {
+                let (function, args) = args
+                    .split_first()
+                    .ok_or_else(|| anyhow!("move-call expects a function"))?;
+                let (package, module, function, type_arguments) = match function {
+                    Token::Word(function) => parse_function(function)?,
+                    Token::String(_) => bail!("move-call function must not be quoted"),
+                };
+                PtbCommand::MoveCall {
+                    package,
+                    module,
+                    function,
+                    type_arguments,
+                    arguments: parse_arguments(args)?,
+                }
+            }
+            "transfer-objects" => {
+                let (recipient, objects) = split_first_argument(name, args)?;
+                ensure!(
+                    !objects.is_empty(),
+                    "transfer-objects expects at least one object"
+                );
+                PtbCommand::TransferObjects { recipient, objects }
+            }
+            "split-coins" => {
+                let (coin, amounts) = split_first_argument(name, args)?;
+                ensure!(
+                    !amounts.is_empty(),
+                    "split-coins expects at least one amount"
+                );
+                PtbCommand::SplitCoins { coin, amounts }
+            }
+            "merge-coins" => {
+                let (coin, coins) = split_first_argument(name, args)?;
+                ensure!(
+                    !coins.is_empty(),
+                    "merge-coins expects at least one coin to merge"
+                );
+                PtbCommand::MergeCoins { coin, coins }
+            }
+            "make-move-vec" => {
+                let (type_, elements) = args
+                    .split_first()
+                    .ok_or_else(|| anyhow!("make-move-vec expects a type or _"))?;
+                let type_ = match type_ {
+                    Token::Word(type_) if type_ == "_" => None,
+                    Token::Word(type_) => Some(parse_sui_type_tag(type_)?),
+                    Token::String(_) => bail!("make-move-vec type must not be quoted"),
+                };
+                PtbCommand::MakeMoveVec {
+                    type_,
+                    elements: parse_arguments(elements)?,
+                }
+            }
+            _ => bail!(
+                "Unknown command {name:?}, expected one of move-call, transfer-objects, \
+                 split-coins, merge-coins or make-move-vec"
+            ),
+        })
+    }
+}
+
+impl PtbCommand {
+    fn arguments(&self) -> impl Iterator<Item = &PtbArgument> {
+        let arguments: Vec<_> = match self {
+            PtbCommand::MoveCall { arguments, .. } => arguments.iter().collect(),
+            PtbCommand::TransferObjects { recipient, objects } => {
+                std::iter::once(recipient).chain(objects).collect()
+            }
+            PtbCommand::SplitCoins { coin, amounts } => {
+                std::iter::once(coin).chain(amounts).collect()
+            }
+            PtbCommand::MergeCoins { coin, coins } => std::iter::once(coin).chain(coins).collect(),
+            PtbCommand::MakeMoveVec { elements, .. } => elements.iter().collect(),
+        };
+        arguments.into_iter()
+    }
+}
+
+/// Parse the commands of a file, one per line, skipping empty lines and lines starting with `#`.
+pub fn parse_commands_file(content: &str) -> Result<Vec<PtbCommand>, anyhow::Error> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| {
+            line.parse()
+                .with_context(|| format!("Invalid command at line {}", i + 1))
+        })
+        .collect()
+}
+
+/// The objects passed to the commands, which cannot be used to pay for gas.
+pub fn input_objects(commands: &[PtbCommand]) -> BTreeSet<ObjectID> {
+    commands
+        .iter()
+        .flat_map(|command| command.arguments())
+        .filter_map(|argument| match argument {
+            PtbArgument::Object(id) => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Compose the programmable transaction running `commands` in order, fetching the objects they
+/// take as input.
+pub async fn build_programmable_transaction(
+    client: &SuiClient,
+    commands: Vec<PtbCommand>,
+) -> Result<ProgrammableTransaction, anyhow::Error> {
+    ensure!(
+        !commands.is_empty(),
+        CliError::InvalidInput("Expected at least one command".to_string())
+    );
+
+    let mut object_args = BTreeMap::new();
+    for id in input_objects(&commands) {
+        object_args.insert(id, get_object_arg(client, id).await?);
+    }
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for (i, command) in commands.into_iter().enumerate() {
+        let mut argument = |argument: PtbArgument| -> Result<Argument, anyhow::Error> {
+            Ok(match argument {
+                PtbArgument::GasCoin => Argument::GasCoin,
+                PtbArgument::Result(result) | PtbArgument::NestedResult(result, _)
+                    if result as usize >= i =>
+                {
+                    bail!(CliError::InvalidInput(format!(
+                        "Command {i} uses the result of command {result}, only the results of \
+                         earlier commands can be used"
+                    )))
+                }
+                PtbArgument::Result(result) => Argument::Result(result),
+                PtbArgument::NestedResult(result, value) => Argument::NestedResult(result, value),
+                PtbArgument::Object(id) => builder.obj(object_args[&id])?,
+                PtbArgument::Pure(bytes) => builder.pure_bytes(bytes, false),
+            })
+        };
+        let command = match command {
+            PtbCommand::MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            } => Command::move_call(
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments
+                    .into_iter()
+                    .map(&mut argument)
+                    .collect::<Result<_, _>>()?,
+            ),
+            PtbCommand::TransferObjects { recipient, objects } => {
+                let objects = objects
+                    .into_iter()
+                    .map(&mut argument)
+                    .collect::<Result<_, _>>()?;
+                Command::TransferObjects(objects, argument(recipient)?)
+            }
+            PtbCommand::SplitCoins { coin, amounts } => {
+                let coin = argument(coin)?;
+                Command::SplitCoins(
+                    coin,
+                    amounts
+                        .into_iter()
+                        .map(&mut argument)
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            PtbCommand::MergeCoins { coin, coins } => {
+                let coin = argument(coin)?;
+                Command::MergeCoins(
+                    coin,
+                    coins
+                        .into_iter()
+                        .map(&mut argument)
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            PtbCommand::MakeMoveVec { type_, elements } => Command::MakeMoveVec(
+                type_,
+                elements
+                    .into_iter()
+                    .map(&mut argument)
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        builder.command(command);
+    }
+    Ok(builder.finish())
+}
+
+async fn get_object_arg(client: &SuiClient, id: ObjectID) -> Result<ObjectArg, anyhow::Error> {
+    let object = client
+        .read_api()
+        .get_object_with_options(id, SuiObjectDataOptions::new().with_owner())
+        .await?
+        .into_object()?;
+    Ok(match object.owner {
+        // Shared objects are always passed mutably, as it is not known here whether the command
+        // taking them needs a mutable reference.
+        Some(Owner::Shared {
+            initial_shared_version,
+        }) => ObjectArg::SharedObject {
+            id,
+            initial_shared_version,
+            mutable: true,
+        },
+        _ => ObjectArg::ImmOrOwnedObject(object.object_ref()),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    /// A quoted string, with its quotes removed and escapes resolved.
+    String(String),
+}
+
+/// Split a command into whitespace separated words and quoted strings, in which `\"` and `\\`
+/// escape a quote and a backslash.
+fn tokenize(s: &str) -> Result<Vec<Token>, anyhow::Error> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if c == '"' {
+            let mut string = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(c @ ('"' | '\\')) => string.push(c),
+                        _ => bail!("Invalid escape in string {string:?}"),
+                    },
+                    Some(c) => string.push(c),
+                    None => bail!("Unterminated string {string:?}"),
+                }
+            }
+            tokens.push(Token::String(string));
+        } else {
+            let mut word = c.to_string();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                word.push(c);
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parse `<package>::<module>::<function>`, optionally followed by type arguments in angle
+/// brackets.
+fn parse_function(
+    s: &str,
+) -> Result<(ObjectID, Identifier, Identifier, Vec<TypeTag>), anyhow::Error> {
+    let (path, type_arguments) = match s.find('<') {
+        Some(start) => {
+            let type_arguments = s[start..]
+                .strip_prefix('<')
+                .and_then(|s| s.strip_suffix('>'))
+                .ok_or_else(|| anyhow!("Invalid type arguments in {s:?}"))?;
+            (&s[..start], split_type_arguments(type_arguments)?)
+        }
+        None => (s, vec![]),
+    };
+    let parts: Vec<_> = path.split("::").collect();
+    let [package, module, function] = parts[..] else {
+        bail!("Expected a function as <package>::<module>::<function>, got {s:?}");
+    };
+    Ok((
+        ObjectID::from_hex_literal(package)?,
+        Identifier::new(module)?,
+        Identifier::new(function)?,
+        type_arguments,
+    ))
+}
+
+/// Split a comma separated list of type tags, ignoring the commas nested in type arguments.
+fn split_type_arguments(s: &str) -> Result<Vec<TypeTag>, anyhow::Error> {
+    let mut type_arguments = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                type_arguments.push(parse_sui_type_tag(s[start..i].trim())?);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    if !s[start..].trim().is_empty() {
+        type_arguments.push(parse_sui_type_tag(s[start..].trim())?);
+    }
+    Ok(type_arguments)
+}
+
+fn split_first_argument(
+    command: &str,
+    tokens: &[Token],
+) -> Result<(PtbArgument, Vec<PtbArgument>), anyhow::Error> {
+    let (first, rest) = tokens
+        .split_first()
+        .ok_or_else(|| anyhow!("{command} expects arguments"))?;
+    Ok((parse_argument(first)?, parse_arguments(rest)?))
+}
+
+fn parse_arguments(tokens: &[Token]) -> Result<Vec<PtbArgument>, anyhow::Error> {
+    tokens.iter().map(parse_argument).collect()
+}
+
+fn parse_argument(token: &Token) -> Result<PtbArgument, anyhow::Error> {
+    let word = match token {
+        Token::String(string) => return Ok(PtbArgument::Pure(bcs::to_bytes(string)?)),
+        Token::Word(word) => word.as_str(),
+    };
+    Ok(if word == "gas" {
+        PtbArgument::GasCoin
+    } else if let Some(result) = word.strip_prefix('%') {
+        match result.split_once('.') {
+            Some((result, value)) => PtbArgument::NestedResult(
+                result
+                    .parse()
+                    .with_context(|| format!("Invalid result {word:?}"))?,
+                value
+                    .parse()
+                    .with_context(|| format!("Invalid result {word:?}"))?,
+            ),
+            None => PtbArgument::Result(
+                result
+                    .parse()
+                    .with_context(|| format!("Invalid result {word:?}"))?,
+            ),
+        }
+    } else if let Some(id) = word.strip_prefix('@') {
+        PtbArgument::Object(ObjectID::from_hex_literal(id)?)
+    } else if word == "true" || word == "false" {
+        PtbArgument::Pure(bcs::to_bytes(&(word == "true"))?)
+    } else if word.starts_with("0x") {
+        let address = SuiAddress::from(ObjectID::from_hex_literal(word)?);
+        PtbArgument::Pure(bcs::to_bytes(&address)?)
+    } else if word.starts_with(|c: char| c.is_ascii_digit()) {
+        PtbArgument::Pure(parse_integer(word)?)
+    } else {
+        bail!(
+            "Invalid argument {word:?}, expected gas, %<result>, @<object id>, a number, a bool, \
+             an address or a quoted string"
+        )
+    })
+}
+
+/// BCS bytes of an integer with an optional type suffix, `u64` by default.
+fn parse_integer(s: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '_')
+        .unwrap_or(s.len());
+    let (value, suffix) = s.split_at(split);
+    let value = value.replace('_', "");
+    let context = || format!("Invalid integer {s:?}");
+    Ok(match suffix {
+        "u8" => bcs::to_bytes(&value.parse::<u8>().with_context(context)?)?,
+        "u16" => bcs::to_bytes(&value.parse::<u16>().with_context(context)?)?,
+        "u32" => bcs::to_bytes(&value.parse::<u32>().with_context(context)?)?,
+        "" | "u64" => bcs::to_bytes(&value.parse::<u64>().with_context(context)?)?,
+        "u128" => bcs::to_bytes(&value.parse::<u128>().with_context(context)?)?,
+        _ => bail!("Invalid integer {s:?}, expected a u8, u16, u32, u64 or u128 suffix"),
+    })
+}
+
+#[cfg(test)]
+#[path = "unit_tests/ptb_tests.rs"]
+mod ptb_tests;
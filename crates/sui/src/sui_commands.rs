@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt::{Display, Formatter};
 use std::io::{stderr, stdout, Write};
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
@@ -10,6 +11,7 @@ use anyhow::{anyhow, bail};
 use clap::*;
 use fastcrypto::traits::KeyPair;
 use move_package::BuildConfig;
+use serde::Serialize;
 use sui_framework_build::compiled_package::SuiPackageHooks;
 use tracing::info;
 
@@ -19,11 +21,13 @@ use sui_config::{
     sui_config_dir, Config, PersistedConfig, FULL_NODE_DB_PATH, SUI_CLIENT_CONFIG,
     SUI_FULLNODE_CONFIG, SUI_NETWORK_CONFIG,
 };
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_sdk::error::Error as SdkError;
 use sui_swarm::memory::Swarm;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
+use sui_types::error::{SuiError, UserInputError};
 
-use crate::client_commands::{SuiClientCommands, WalletContext};
+use crate::client_commands::{SuiClientCommands, TransactionMode, WalletContext};
 use crate::config::{SuiClientConfig, SuiEnv};
 use crate::console::start_console;
 use crate::fire_drill::{run_fire_drill, FireDrill};
@@ -74,6 +78,9 @@ pub enum SuiCommand {
     KeyTool {
         #[clap(long)]
         keystore_path: Option<PathBuf>,
+        /// Return command outputs in json format.
+        #[clap(long, global = true)]
+        json: bool,
         /// Subcommands.
         #[clap(subcommand)]
         cmd: KeyToolCommand,
@@ -96,6 +103,14 @@ pub enum SuiCommand {
         /// Return command outputs in json format.
         #[clap(long, global = true)]
         json: bool,
+        /// Run the transaction of the command against the current state of the network without
+        /// executing it, and print its effects, gas summary, object and balance changes.
+        #[clap(long, global = true, conflicts_with = "dev_inspect")]
+        dry_run: bool,
+        /// Run the transaction of the command in dev-inspect mode without executing it, and print
+        /// its effects and the results of each of its commands.
+        #[clap(long, global = true)]
+        dev_inspect: bool,
         #[clap(short = 'y', long = "yes")]
         accept_defaults: bool,
     },
@@ -136,6 +151,16 @@ pub enum SuiCommand {
 }
 
 impl SuiCommand {
+    /// Whether the command prints its output, and its error if it fails, as JSON.
+    pub fn json_output(&self) -> bool {
+        matches!(
+            self,
+            SuiCommand::Client { json: true, .. }
+                | SuiCommand::KeyTool { json: true, .. }
+                | SuiCommand::Validator { json: true, .. }
+        )
+    }
+
     pub async fn execute(self) -> Result<(), anyhow::Error> {
         move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks {}));
         match self {
@@ -232,11 +257,16 @@ impl SuiCommand {
                 .await
             }
             SuiCommand::GenesisCeremony(cmd) => run(cmd),
-            SuiCommand::KeyTool { keystore_path, cmd } => {
+            SuiCommand::KeyTool {
+                keystore_path,
+                json,
+                cmd,
+            } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
-                cmd.execute(&mut keystore)
+                let mut keystore = Keystore::open(&keystore_path)?;
+                cmd.execute(&mut keystore)?.print(!json);
+                Ok(())
             }
             SuiCommand::Console { config } => {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
@@ -248,13 +278,24 @@ impl SuiCommand {
                 config,
                 cmd,
                 json,
+                dry_run,
+                dev_inspect,
                 accept_defaults,
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None).await?;
                 if let Some(cmd) = cmd {
-                    cmd.execute(&mut context).await?.print(!json);
+                    let mode = if dry_run {
+                        TransactionMode::DryRun
+                    } else if dev_inspect {
+                        TransactionMode::DevInspect
+                    } else {
+                        TransactionMode::Execute
+                    };
+                    cmd.execute_with_mode(&mut context, mode)
+                        .await?
+                        .print(!json);
                 } else {
                     // Print help
                     let mut app: Command = SuiCommand::command();
@@ -292,6 +333,108 @@ impl SuiCommand {
     }
 }
 
+/// The error of a command run with `--json`, printed as `{"error": {"code": .., "message": ..}}`
+/// so that scripts can tell the kind of failure apart without parsing the message.
+#[derive(Serialize, Debug)]
+pub struct CommandError {
+    pub code: CommandErrorCode,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandErrorCode {
+    /// The arguments of the command, or the transaction it builds, are invalid.
+    InvalidInput,
+    /// A request to the RPC server failed.
+    Rpc,
+    /// The transaction could not be signed with the keystore.
+    Signing,
+    /// The dry run of the transaction failed.
+    DryRunFailed,
+    /// The transaction was executed, but its execution failed.
+    TransactionFailed,
+    /// Any other failure.
+    Other,
+}
+
+/// A failure of a command that `--json` reports with its own code, raised with
+/// `bail!(CliError::..)` where the failure is detected.
+#[derive(Debug)]
+pub enum CliError {
+    /// An argument of the command is invalid, such as a PTB command that cannot be parsed.
+    InvalidInput(String),
+    /// The transaction was executed, but its execution failed.
+    TransactionFailed(String),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::InvalidInput(message) | CliError::TransactionFailed(message) => {
+                write!(f, "{message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<&anyhow::Error> for CommandError {
+    fn from(err: &anyhow::Error) -> Self {
+        let code = err
+            .chain()
+            .find_map(|cause| {
+                if let Some(err) = cause.downcast_ref::<SdkError>() {
+                    match err {
+                        SdkError::RpcError(_)
+                        | SdkError::Subscription(_)
+                        | SdkError::TransactionConfirmationError(..)
+                        | SdkError::FailToConfirmTransactionStatus(..)
+                        | SdkError::ServerVersionMismatch { .. } => Some(CommandErrorCode::Rpc),
+                        SdkError::UserInputError(_)
+                        | SdkError::SuiError(SuiError::UserInputError { .. }) => {
+                            Some(CommandErrorCode::InvalidInput)
+                        }
+                        SdkError::SigningError(_) => Some(CommandErrorCode::Signing),
+                        SdkError::DryRunFailed(_) => Some(CommandErrorCode::DryRunFailed),
+                        _ => None,
+                    }
+                } else if cause.is::<jsonrpsee::core::Error>() {
+                    Some(CommandErrorCode::Rpc)
+                } else if cause.is::<UserInputError>() {
+                    Some(CommandErrorCode::InvalidInput)
+                } else if let Some(SuiError::UserInputError { .. }) =
+                    cause.downcast_ref::<SuiError>()
+                {
+                    Some(CommandErrorCode::InvalidInput)
+                } else if cause.is::<signature::Error>() {
+                    Some(CommandErrorCode::Signing)
+                } else if let Some(err) = cause.downcast_ref::<CliError>() {
+                    Some(match err {
+                        CliError::InvalidInput(_) => CommandErrorCode::InvalidInput,
+                        CliError::TransactionFailed(_) => CommandErrorCode::TransactionFailed,
+                    })
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(CommandErrorCode::Other);
+        Self {
+            code,
+            // With its causes, as printed without `--json`.
+            message: format!("{err:#}"),
+        }
+    }
+}
+
+impl CommandError {
+    pub fn print(&self) {
+        let error = serde_json::json!({ "error": self });
+        println!("{}", serde_json::to_string_pretty(&error).unwrap());
+    }
+}
+
 async fn genesis(
     from_config: Option<PathBuf>,
     write_config: Option<PathBuf>,
@@ -327,7 +470,7 @@ async fn genesis(
     if write_config.is_none() && !files.is_empty() {
         if force {
             // check old keystore and client.yaml is compatible
-            let is_compatible = FileBasedKeystore::new(&keystore_path).is_ok()
+            let is_compatible = Keystore::open(&keystore_path).is_ok()
                 && PersistedConfig::<SuiClientConfig>::read(&client_path).is_ok();
             // Keep keystore and client.yaml if they are compatible
             if is_compatible {
@@ -366,7 +509,7 @@ async fn genesis(
         Some(path) => PersistedConfig::read(&path)?,
         None => {
             if keystore_path.exists() {
-                let existing_keys = FileBasedKeystore::new(&keystore_path)?.addresses();
+                let existing_keys = Keystore::open(&keystore_path)?.addresses();
                 GenesisConfig::for_local_testing_with_addresses(existing_keys)
             } else {
                 GenesisConfig::for_local_testing()
@@ -397,7 +540,7 @@ async fn genesis(
             .build()
     };
 
-    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    let mut keystore = Keystore::open(&keystore_path)?;
     for key in &network_config.account_keys {
         keystore.add_key(SuiKeyPair::Ed25519(key.copy()))?;
     }
@@ -435,7 +578,7 @@ async fn genesis(
     let mut client_config = if client_path.exists() {
         PersistedConfig::read(&client_path)?
     } else {
-        SuiClientConfig::new(keystore.into())
+        SuiClientConfig::new(keystore)
     };
 
     if client_config.active_address.is_none() {
@@ -517,7 +660,7 @@ async fn prompt_if_no_config(
                 .parent()
                 .unwrap_or(&sui_config_dir()?)
                 .join(SUI_KEYSTORE_FILENAME);
-            let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+            let mut keystore = Keystore::open(&keystore_path)?;
             let key_scheme = if accept_defaults {
                 SignatureScheme::ED25519
             } else {
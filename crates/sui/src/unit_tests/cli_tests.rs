@@ -11,9 +11,9 @@ use tokio::time::sleep;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
+    client_commands::{SuiClientCommandResult, SuiClientCommands, TransactionMode, WalletContext},
     config::SuiClientConfig,
-    sui_commands::SuiCommand,
+    sui_commands::{CliError, CommandError, CommandErrorCode, SuiCommand},
 };
 use sui_config::genesis_config::{AccountConfig, GenesisConfig, ObjectConfig};
 use sui_config::{
@@ -23,17 +23,21 @@ use sui_config::{
 use sui_framework_build::compiled_package::BuildConfig;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiObjectData, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiTransactionEffectsAPI,
+    ObjectChange, OwnedObjectRef, SuiObjectData, SuiObjectDataFilter, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionEffectsAPI,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_macros::sim_test;
+use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectType, SuiAddress};
 use sui_types::crypto::{
     Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
+use sui_types::move_package::UpgradeCap;
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
-use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
+use sui_types::{
+    sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID,
+};
 use test_utils::messages::make_transactions_with_wallet_context;
 use test_utils::network::TestClusterBuilder;
 
@@ -358,7 +362,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
         to: SuiAddress::random_for_testing_only(),
         object_id: object_to_send,
         gas: Some(object_id),
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(context)
     .await?;
@@ -405,7 +409,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
     }
@@ -482,7 +486,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args,
         gas: None,
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await?;
@@ -519,7 +523,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await;
@@ -543,7 +547,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await;
@@ -568,7 +572,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await?;
@@ -611,7 +615,7 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
     }
@@ -642,6 +646,211 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_package_upgrades_for_testing(true);
+        config
+    });
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new()
+                    .with_type()
+                    .with_owner()
+                    .with_previous_transaction(),
+            )),
+            None,
+            None,
+            None,
+        )
+        .await?
+        .data;
+
+    let gas_obj_id = object_refs.first().unwrap().object().unwrap().object_id;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let build_config = BuildConfig::new_for_testing().config;
+    let resp = SuiClientCommands::Publish {
+        package_path: package_path.clone(),
+        build_config,
+        gas: Some(gas_obj_id),
+        gas_budget: Some(20_000),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+
+    let package = if let SuiClientCommandResult::Publish(response) = resp {
+        response
+            .effects
+            .unwrap()
+            .created()
+            .iter()
+            .find(|refe| matches!(refe.owner, Owner::Immutable))
+            .unwrap()
+            .reference
+            .object_id
+    } else {
+        unreachable!("Invalid response");
+    };
+
+    // Upgrade the package to the same modules, looking up its UpgradeCap from the package ID.
+    let build_config = BuildConfig::new_for_testing().config;
+    let resp = SuiClientCommands::Upgrade {
+        package_path,
+        build_config,
+        package_id: Some(package),
+        upgrade_capability: None,
+        gas: Some(gas_obj_id),
+        gas_budget: Some(20_000),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+
+    // Print it out to CLI/logs
+    resp.print(true);
+
+    let upgraded_package = if let SuiClientCommandResult::Upgrade(response) = resp {
+        let effects = response.effects.unwrap();
+        assert!(effects.status().is_ok());
+        effects
+            .created()
+            .iter()
+            .find(|refe| matches!(refe.owner, Owner::Immutable))
+            .unwrap()
+            .reference
+            .object_id
+    } else {
+        unreachable!("Invalid response");
+    };
+    assert_ne!(upgraded_package, package);
+    get_parsed_object_assert_existence(upgraded_package, context).await;
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_package_upgrade_command_rejects_disallowed_upgrade() -> Result<(), anyhow::Error> {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_package_upgrades_for_testing(true);
+        config
+    });
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let build_config = BuildConfig::new_for_testing().config;
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config,
+        gas: None,
+        gas_budget: Some(20_000),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+
+    let package = if let SuiClientCommandResult::Publish(response) = resp {
+        response
+            .effects
+            .unwrap()
+            .created()
+            .iter()
+            .find(|refe| matches!(refe.owner, Owner::Immutable))
+            .unwrap()
+            .reference
+            .object_id
+    } else {
+        unreachable!("Invalid response");
+    };
+    let upgrade_cap = context
+        .get_client()
+        .await?
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new(
+                Some(SuiObjectDataFilter::StructType(UpgradeCap::type_())),
+                None,
+            )),
+            None,
+            None,
+            None,
+        )
+        .await?
+        .data
+        .first()
+        .unwrap()
+        .object()?
+        .object_id;
+
+    let upgrade = |package_dir: &str| {
+        let mut package_path = PathBuf::from(TEST_DATA_DIR);
+        package_path.push(package_dir);
+        SuiClientCommands::Upgrade {
+            package_path,
+            build_config: BuildConfig::new_for_testing().config,
+            package_id: Some(package),
+            upgrade_capability: Some(upgrade_cap),
+            gas: None,
+            gas_budget: Some(20_000),
+            skip_dependency_verification: false,
+            with_unpublished_dependencies: false,
+        }
+    };
+
+    // Changing the signature of a public function is rejected before the upgrade is submitted.
+    let err = upgrade("dummy_modules_upgrade_incompatible")
+        .execute(context)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Module trusted_coin is not compatible"),
+        "{err}"
+    );
+
+    // Adding a function is compatible, but not allowed once upgrades are restricted to
+    // dependencies.
+    SuiClientCommands::Call {
+        package: SUI_FRAMEWORK_OBJECT_ID,
+        module: "package".to_string(),
+        function: "only_dep_upgrades".to_string(),
+        type_args: vec![],
+        args: vec![SuiJsonValue::new(json!(upgrade_cap.to_string()))?],
+        gas: None,
+        gas_budget: Some(20_000),
+    }
+    .execute(context)
+    .await?;
+    let err = upgrade("dummy_modules_upgrade_additive")
+        .execute(context)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Module trusted_coin is not an upgrade of package"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_package_publish_command_with_unpublished_dependency_succeeds(
 ) -> Result<(), anyhow::Error> {
@@ -678,7 +887,7 @@ async fn test_package_publish_command_with_unpublished_dependency_succeeds(
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
     }
@@ -745,7 +954,7 @@ async fn test_package_publish_command_with_unpublished_dependency_fails(
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
     }
@@ -798,7 +1007,7 @@ async fn test_package_publish_command_failure_invalid() -> Result<(), anyhow::Er
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
     }
@@ -838,7 +1047,7 @@ async fn test_package_publish_nonexistent_dependency() -> Result<(), anyhow::Err
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
     }
@@ -885,7 +1094,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         gas: Some(gas_obj_id),
         to: recipient,
         object_id: obj_id,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(context)
     .await?;
@@ -976,7 +1185,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
         gas: None,
         to: recipient,
         object_id: obj_id,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(context)
     .await?;
@@ -1094,7 +1303,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     }
     .execute(context)
     .await?;
-    let new_addr = if let SuiClientCommandResult::NewAddress((a, _, _)) = os {
+    let new_addr = if let SuiClientCommandResult::NewAddress { address: a, .. } = os {
         a
     } else {
         panic!("Command failed")
@@ -1262,7 +1471,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         primary_coin,
         coin_to_merge,
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await?;
@@ -1315,7 +1524,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         primary_coin,
         coin_to_merge,
         gas: None,
-        gas_budget: 10_000,
+        gas_budget: Some(10_000),
     }
     .execute(context)
     .await?;
@@ -1376,7 +1585,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with gas specified
     let resp = SuiClientCommands::SplitCoin {
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
@@ -1442,7 +1651,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test split coin into equal parts
     let resp = SuiClientCommands::SplitCoin {
         gas: None,
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         coin_id: coin,
         amounts: None,
         count: Some(3),
@@ -1511,7 +1720,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with no gas specified
     let resp = SuiClientCommands::SplitCoin {
         gas: None,
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
@@ -1550,6 +1759,288 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new()
+                    .with_type()
+                    .with_owner()
+                    .with_previous_transaction(),
+            )),
+            None,
+            None,
+            None,
+        )
+        .await?;
+    let gas = object_refs.data.first().unwrap().object()?.object_id;
+    let coin = object_refs.data.get(1).unwrap().object()?.object_id;
+    let orig_value = get_gas_value(&get_object(coin, context).await.unwrap());
+
+    let commands = vec![
+        format!("split-coins @{coin} 1000 10"),
+        format!("transfer-objects {recipient} %0.0 %0.1"),
+    ];
+
+    // A dry run does not change the coin
+    let resp = SuiClientCommands::Ptb {
+        commands: commands.clone(),
+        file: None,
+        gas: Some(gas),
+        gas_budget: Some(20_000),
+        sponsor: None,
+        serialize_unsigned: false,
+    }
+    .execute_with_mode(context, TransactionMode::DryRun)
+    .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(response.effects.status().is_ok());
+    assert_eq!(response.effects.created().len(), 2);
+    assert_eq!(response.object_changes.len(), 4);
+    assert!(response
+        .balance_changes
+        .iter()
+        .any(|change| change.owner == Owner::AddressOwner(recipient) && change.amount == 1010));
+    assert_eq!(
+        get_gas_value(&get_object(coin, context).await.unwrap()),
+        orig_value
+    );
+
+    let resp = SuiClientCommands::Ptb {
+        commands,
+        file: None,
+        gas: Some(gas),
+        gas_budget: Some(20_000),
+        sponsor: None,
+        serialize_unsigned: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Ptb(response) = resp else {
+        panic!("Command failed")
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    let mut new_values = vec![];
+    for obj_ref in effects.created() {
+        assert_eq!(obj_ref.owner, Owner::AddressOwner(recipient));
+        let new_coin =
+            get_parsed_object_assert_existence(obj_ref.reference.object_id, context).await;
+        new_values.push(get_gas_value(&new_coin));
+    }
+    new_values.sort();
+    assert_eq!(new_values, vec![10, 1000]);
+    assert_eq!(
+        get_gas_value(&get_object(coin, context).await.unwrap()),
+        orig_value - 1010
+    );
+
+    // Later commands can only use the results of earlier ones
+    let resp = SuiClientCommands::Ptb {
+        commands: vec![
+            format!("transfer-objects {recipient} %1"),
+            format!("split-coins @{coin} 1000"),
+        ],
+        file: None,
+        gas: Some(gas),
+        gas_budget: Some(20_000),
+        sponsor: None,
+        serialize_unsigned: false,
+    }
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_sponsored_ptb() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    context.config.active_address = Some(sender);
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, None, None, None)
+        .await?
+        .data;
+    let coin = coins.first().unwrap().coin_object_id;
+    let commands = vec![
+        format!("split-coins @{coin} 1000"),
+        format!("transfer-objects {sponsor} %0"),
+    ];
+
+    // Build the transaction for the sponsor to sign
+    let resp = SuiClientCommands::Ptb {
+        commands: commands.clone(),
+        file: None,
+        gas: None,
+        gas_budget: Some(20_000),
+        sponsor: Some(sponsor),
+        serialize_unsigned: true,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::SerializeUnsignedTransaction(tx_bytes) = resp else {
+        panic!("Command failed")
+    };
+
+    let mut signatures = vec![];
+    for address in [None, Some(sponsor)] {
+        let resp = SuiClientCommands::SignTx {
+            tx_bytes: tx_bytes.clone(),
+            address,
+        }
+        .execute(context)
+        .await?;
+        let SuiClientCommandResult::SignTx(signature) = resp else {
+            panic!("Command failed")
+        };
+        signatures.push(signature);
+    }
+
+    // Only the sender and the sponsor can sign the transaction
+    let resp = SuiClientCommands::SignTx {
+        tx_bytes: tx_bytes.clone(),
+        address: Some(SuiAddress::random_for_testing_only()),
+    }
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    // The transaction needs the signatures of both the sender and the sponsor
+    let resp = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes: tx_bytes.clone(),
+        signatures: signatures[..1].to_vec(),
+    }
+    .execute(context)
+    .await;
+    assert!(resp.is_err());
+
+    // A dry run of the signed transaction does not execute it
+    let resp = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes: tx_bytes.clone(),
+        signatures: signatures.clone(),
+    }
+    .execute_with_mode(context, TransactionMode::DryRun)
+    .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(response.effects.status().is_ok());
+
+    let resp = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes,
+        signatures,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::ExecuteSignedTx(response) = resp else {
+        panic!("Command failed")
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().owner, Owner::AddressOwner(sponsor));
+    assert_eq!(effects.created().len(), 1);
+    assert_eq!(effects.created()[0].owner, Owner::AddressOwner(sponsor));
+
+    // Both keys are in the keystore, so the transaction can also be signed and executed at once
+    let resp = SuiClientCommands::Ptb {
+        commands,
+        file: None,
+        gas: None,
+        gas_budget: Some(20_000),
+        sponsor: Some(sponsor),
+        serialize_unsigned: false,
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Ptb(response) = resp else {
+        panic!("Command failed")
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().owner, Owner::AddressOwner(sponsor));
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_dry_run_and_dev_inspect_modes() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(
+            address,
+            Some(SuiObjectResponseQuery::new_with_options(
+                SuiObjectDataOptions::new().with_type().with_owner(),
+            )),
+            None,
+            None,
+            None,
+        )
+        .await?;
+    let gas = object_refs.data.first().unwrap().object()?.object_id;
+    let object_to_send = object_refs.data.get(1).unwrap().object()?.object_id;
+
+    // Neither mode transfers the object, and both estimate the omitted gas budget
+    let transfer = || SuiClientCommands::Transfer {
+        to: recipient,
+        object_id: object_to_send,
+        gas: Some(gas),
+        gas_budget: None,
+    };
+    let resp = transfer()
+        .execute_with_mode(context, TransactionMode::DryRun)
+        .await?;
+    let SuiClientCommandResult::DryRun(response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(response.effects.status().is_ok());
+    assert!(response.object_changes.iter().any(|change| matches!(
+        change,
+        ObjectChange::Mutated { object_id, owner, .. }
+            if *object_id == object_to_send && *owner == Owner::AddressOwner(recipient)
+    )));
+    assert_eq!(response.balance_changes.len(), 1);
+
+    let resp = transfer()
+        .execute_with_mode(context, TransactionMode::DevInspect)
+        .await?;
+    let SuiClientCommandResult::DevInspect(results) = resp else {
+        panic!("Command failed")
+    };
+    assert!(results.effects.status().is_ok());
+    assert_eq!(context.get_object_owner(&object_to_send).await?, address);
+
+    // The estimated gas budget is enough to execute the transfer
+    let resp = transfer().execute(context).await?;
+    let SuiClientCommandResult::Transfer(_, response) = resp else {
+        panic!("Command failed")
+    };
+    assert!(response.effects.unwrap().status().is_ok());
+    assert_eq!(context.get_object_owner(&object_to_send).await?, recipient);
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_signature_flag() -> Result<(), anyhow::Error> {
     let res = SignatureScheme::from_flag("0");
@@ -1726,6 +2217,124 @@ async fn test_stake_with_u64_amount() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_json_output() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let recipient = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    // Each output is tagged with its command, with the fields of its result named.
+    let resp = SuiClientCommands::Addresses.execute(context).await?;
+    let json: serde_json::Value = serde_json::from_str(&format!("{:?}", resp))?;
+    assert_eq!(json["command"], "addresses");
+    assert_eq!(
+        json["result"]["addresses"].as_array().unwrap().len(),
+        context.config.keystore.addresses().len()
+    );
+    assert_eq!(
+        json["result"]["activeAddress"],
+        serde_json::to_value(context.active_address()?)?
+    );
+
+    let resp = SuiClientCommands::Envs.execute(context).await?;
+    let json: serde_json::Value = serde_json::from_str(&format!("{:?}", resp))?;
+    assert_eq!(json["command"], "envs");
+    assert!(json["result"]["envs"].is_array());
+    assert!(json["result"].get("activeEnv").is_some());
+
+    // A command without a result only prints its name.
+    let json: serde_json::Value =
+        serde_json::from_str(&format!("{:?}", SuiClientCommandResult::SyncClientState))?;
+    assert_eq!(json, json!({ "command": "sync-client-state" }));
+
+    // The result of a transaction is its response, without the elapsed time of the transfer.
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_owned_objects(address, None, None, None, None)
+        .await?
+        .data;
+    let gas_obj_id = object_refs.first().unwrap().object()?.object_id;
+    let obj_id = object_refs.get(1).unwrap().object()?.object_id;
+    let resp = SuiClientCommands::Transfer {
+        to: recipient,
+        object_id: obj_id,
+        gas: Some(gas_obj_id),
+        gas_budget: Some(50000),
+    }
+    .execute(context)
+    .await?;
+    let json: serde_json::Value = serde_json::from_str(&format!("{:?}", resp))?;
+    assert_eq!(json["command"], "transfer");
+    assert!(json["result"]["effects"]["status"].is_object());
+    assert!(json["result"]["objectChanges"].is_array());
+    Ok(())
+}
+
+#[test]
+fn test_json_error_codes() {
+    let code = |err: anyhow::Error| CommandError::from(&err).code;
+
+    assert_eq!(
+        code(anyhow!(CliError::InvalidInput("invalid".to_string()))),
+        CommandErrorCode::InvalidInput
+    );
+    assert_eq!(
+        code(anyhow!(CliError::TransactionFailed("failed".to_string()))),
+        CommandErrorCode::TransactionFailed
+    );
+    assert_eq!(
+        code(sui_sdk::error::Error::DryRunFailed("failed".to_string()).into()),
+        CommandErrorCode::DryRunFailed
+    );
+    assert_eq!(
+        code(sui_sdk::error::Error::RpcError(jsonrpsee::core::Error::RequestTimeout).into()),
+        CommandErrorCode::Rpc
+    );
+    assert_eq!(code(anyhow!("unknown")), CommandErrorCode::Other);
+
+    // The code is found through the context added to the error, which is kept in the message.
+    let err = anyhow::Error::from(sui_sdk::error::Error::DryRunFailed("abort".to_string()))
+        .context("cannot estimate the gas budget");
+    let error = CommandError::from(&err);
+    assert_eq!(error.code, CommandErrorCode::DryRunFailed);
+    assert_eq!(
+        error.message,
+        "cannot estimate the gas budget: Transaction dry run failed: abort"
+    );
+}
+
+#[tokio::test]
+async fn test_json_error_output() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let config_path = test_cluster.swarm.dir().join(SUI_CLIENT_CONFIG);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("sui").unwrap();
+    cmd.args([
+        "client",
+        "--client.config",
+        config_path.to_str().unwrap(),
+        "--json",
+        "ptb",
+        "--command",
+        "unknown-command",
+    ]);
+    // test cluster will not response if this call is in the same thread
+    let out = thread::spawn(move || cmd.assert());
+    while !out.is_finished() {
+        sleep(Duration::from_millis(100)).await;
+    }
+    let output = out.join().unwrap().failure().code(1).get_output().clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["error"]["code"], "invalid_input");
+    let message = json["error"]["message"].as_str().unwrap();
+    assert!(message.contains("Invalid command 0"), "{message}");
+    assert!(message.contains("Unknown command"), "{message}");
+    Ok(())
+}
+
 async fn test_with_sui_binary(args: &[&str]) -> Result<(), anyhow::Error> {
     let mut cmd = assert_cmd::Command::cargo_bin("sui").unwrap();
     let args = args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
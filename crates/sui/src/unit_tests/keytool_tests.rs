@@ -7,7 +7,7 @@ use crate::keytool::read_authority_keypair_from_file;
 use crate::keytool::read_keypair_from_file;
 
 use super::write_keypair_to_file;
-use super::KeyToolCommand;
+use super::{KeyToolCommand, KeyToolCommandResponse, KeypairFile};
 use fastcrypto::encoding::Base64;
 use fastcrypto::encoding::Encoding;
 use rand::rngs::StdRng;
@@ -46,7 +46,55 @@ fn test_addresses_command() -> Result<(), anyhow::Error> {
     }
 
     // List all addresses with flag
-    KeyToolCommand::List.execute(&mut keystore).unwrap();
+    let response = KeyToolCommand::List.execute(&mut keystore).unwrap();
+    let KeyToolCommandResponse::List(keys) = &response else {
+        panic!("Command failed")
+    };
+    assert_eq!(
+        keys.iter().map(|key| key.sui_address).collect::<Vec<_>>(),
+        keystore.addresses()
+    );
+
+    // The JSON output lists the same keys
+    let json: serde_json::Value = serde_json::from_str(&format!("{:?}", response))?;
+    assert_eq!(json["command"], "list");
+    let json_keys = json["result"].as_array().unwrap();
+    assert_eq!(json_keys.len(), 6);
+    for (json_key, key) in json_keys.iter().zip(keys) {
+        assert_eq!(
+            json_key["suiAddress"],
+            serde_json::to_value(key.sui_address)?
+        );
+        assert_eq!(json_key["publicBase64Key"], key.public_base64_key);
+        assert_eq!(json_key["flag"], key.flag);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_json_output_is_tagged_with_command() -> Result<(), anyhow::Error> {
+    let keypair_file = || KeypairFile {
+        sui_address: SuiAddress::ZERO,
+        key_scheme: SignatureScheme::ED25519,
+        file: "0x0.key".into(),
+    };
+    let generate: serde_json::Value = serde_json::from_str(&format!(
+        "{:?}",
+        KeyToolCommandResponse::Generate(keypair_file())
+    ))?;
+    let unpack: serde_json::Value = serde_json::from_str(&format!(
+        "{:?}",
+        KeyToolCommandResponse::Unpack(keypair_file())
+    ))?;
+    assert_eq!(generate["command"], "generate");
+    assert_eq!(unpack["command"], "unpack");
+    assert_eq!(generate["result"], unpack["result"]);
+    assert_eq!(generate["result"]["file"], "0x0.key");
+
+    // A command without a result only prints its name, rather than `null`.
+    let lock: serde_json::Value =
+        serde_json::from_str(&format!("{:?}", KeyToolCommandResponse::Lock))?;
+    assert_eq!(lock, serde_json::json!({ "command": "lock" }));
     Ok(())
 }
 
@@ -380,3 +428,45 @@ fn test_sign_command() -> Result<(), anyhow::Error> {
     .execute(&mut keystore)?;
     Ok(())
 }
+
+#[test]
+fn test_export_backup_and_restore() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let mut keystore = Keystore::from(InMemKeystore::new(2));
+    keystore.add_key(SuiKeyPair::Secp256k1(get_key_pair().1))?;
+    let address = keystore.addresses()[0];
+
+    // Export a keypair to a keypair file.
+    let keypair_path = temp_dir.path().join("exported.key");
+    KeyToolCommand::Export {
+        address,
+        file: Some(keypair_path.clone()),
+    }
+    .execute(&mut keystore)?;
+    assert_eq!(
+        &read_keypair_from_file(&keypair_path)?,
+        keystore.get_key(&address)?
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&keypair_path)?.permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+
+    // Back up the keystore and restore it into an empty one.
+    let backup_path = temp_dir.path().join("sui.keystore.backup");
+    KeyToolCommand::Backup {
+        file: backup_path.clone(),
+        encrypt: false,
+    }
+    .execute(&mut keystore)?;
+
+    let mut restored = Keystore::from(InMemKeystore::new(0));
+    KeyToolCommand::Restore { file: backup_path }.execute(&mut restored)?;
+    assert_eq!(restored.addresses(), keystore.addresses());
+    for address in keystore.addresses() {
+        assert_eq!(restored.get_key(&address)?, keystore.get_key(&address)?);
+    }
+    Ok(())
+}
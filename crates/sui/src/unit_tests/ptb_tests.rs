@@ -0,0 +1,119 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::identifier::Identifier;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::parse_sui_type_tag;
+
+use super::{input_objects, parse_commands_file, PtbArgument, PtbCommand};
+
+fn pure<T: serde::Serialize>(value: T) -> PtbArgument {
+    PtbArgument::Pure(bcs::to_bytes(&value).unwrap())
+}
+
+#[test]
+fn test_parse_split_and_transfer() {
+    let recipient = SuiAddress::random_for_testing_only();
+    let commands = parse_commands_file(&format!(
+        "# Split two coins off the gas coin and send them\n\
+         split-coins gas 1000 2_000u64\n\
+         \n\
+         transfer-objects {recipient} %0.0 %0.1\n"
+    ))
+    .unwrap();
+    assert_eq!(
+        commands,
+        vec![
+            PtbCommand::SplitCoins {
+                coin: PtbArgument::GasCoin,
+                amounts: vec![pure(1000u64), pure(2000u64)],
+            },
+            PtbCommand::TransferObjects {
+                recipient: pure(recipient),
+                objects: vec![
+                    PtbArgument::NestedResult(0, 0),
+                    PtbArgument::NestedResult(0, 1)
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_move_call() {
+    let coin = ObjectID::random();
+    let command: PtbCommand = format!(
+        r#"move-call 0x2::example::call<0x2::coin::Coin<0x2::sui::SUI>,u8> @{coin} %1 "a \"quoted\" string" 7u8 true 0x2"#
+    )
+    .parse()
+    .unwrap();
+    assert_eq!(
+        command,
+        PtbCommand::MoveCall {
+            package: ObjectID::from_hex_literal("0x2").unwrap(),
+            module: Identifier::new("example").unwrap(),
+            function: Identifier::new("call").unwrap(),
+            type_arguments: vec![
+                parse_sui_type_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap(),
+                parse_sui_type_tag("u8").unwrap(),
+            ],
+            arguments: vec![
+                PtbArgument::Object(coin),
+                PtbArgument::Result(1),
+                pure("a \"quoted\" string"),
+                pure(7u8),
+                pure(true),
+                pure(SuiAddress::from(ObjectID::from_hex_literal("0x2").unwrap())),
+            ],
+        }
+    );
+    assert_eq!(
+        input_objects(&[command]).into_iter().collect::<Vec<_>>(),
+        vec![coin]
+    );
+}
+
+#[test]
+fn test_parse_make_move_vec() {
+    let command: PtbCommand = "make-move-vec u64 1 2 3".parse().unwrap();
+    assert_eq!(
+        command,
+        PtbCommand::MakeMoveVec {
+            type_: Some(parse_sui_type_tag("u64").unwrap()),
+            elements: vec![pure(1u64), pure(2u64), pure(3u64)],
+        }
+    );
+    let command: PtbCommand = "make-move-vec _ %0 %1".parse().unwrap();
+    assert_eq!(
+        command,
+        PtbCommand::MakeMoveVec {
+            type_: None,
+            elements: vec![PtbArgument::Result(0), PtbArgument::Result(1)],
+        }
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    for command in [
+        "",
+        "swap-coins gas",
+        "split-coins gas",
+        "merge-coins gas",
+        "transfer-objects 0x2",
+        "move-call 0x2::coin",
+        "move-call 0x2::coin::value<0x2::sui::SUI",
+        "split-coins gas 256u8",
+        "split-coins gas 10i64",
+        "split-coins gas -1",
+        "split-coins gas %x",
+        r#"move-call 0x2::example::call "unterminated"#,
+    ] {
+        assert!(
+            command.parse::<PtbCommand>().is_err(),
+            "{command:?} should not parse"
+        );
+    }
+    let err = parse_commands_file("split-coins gas 1\nsplit-coins gas\n").unwrap_err();
+    assert!(format!("{err}").contains("line 2"));
+}
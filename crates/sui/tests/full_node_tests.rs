@@ -289,6 +289,50 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     let ts = node.state().get_timestamp_ms(&digest).await?;
     assert!(ts.is_some());
 
+    let txes = node.state().get_transactions(
+        Some(
+            TransactionFilter::FromAddress(sender)
+                .and(TransactionFilter::InputObject(transferred_object)),
+        ),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes, vec![digest]);
+
+    let txes = node.state().get_transactions(
+        Some(TransactionFilter::FromAddress(receiver).or(TransactionFilter::FromAddress(sender))),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes, vec![digest]);
+
+    let ts = ts.unwrap();
+    let txes = node.state().get_transactions(
+        Some(
+            TransactionFilter::ToAddress(receiver).and(TransactionFilter::TimeRange {
+                start_time: ts,
+                end_time: ts + 1,
+            }),
+        ),
+        None,
+        None,
+        false,
+    )?;
+    assert_eq!(txes, vec![digest]);
+
+    let txes = node.state().get_transactions(
+        Some(TransactionFilter::TimeRange {
+            start_time: ts + 1,
+            end_time: u64::MAX,
+        }),
+        None,
+        None,
+        false,
+    )?;
+    assert!(txes.is_empty());
+
     // This is a poor substitute for the post processing taking some time
     // Unfortunately event store writes seem to add some latency so this wait is needed
     sleep(Duration::from_millis(1000)).await;
@@ -497,7 +541,7 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
                         count: None,
                         coin_id: object_to_split.0,
                         gas: Some(gas_object_id),
-                        gas_budget: 50000,
+                        gas_budget: Some(50000),
                     }
                     .execute(context)
                     .await
@@ -651,6 +695,62 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[sim_test]
+async fn test_full_node_sub_transaction_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+
+    // Start a new fullnode that is not on the write path
+    let fullnode = start_fullnode_from_config(
+        test_cluster
+            .fullnode_config_builder()
+            .with_event_store()
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+    let context = &mut test_cluster.wallet;
+    let sender = context.config.keystore.addresses()[0];
+
+    // Transactions are streamed before they are checkpointed, checkpoint ranges are rejected.
+    let checkpoint_range = TransactionFilter::CheckpointRange {
+        start_checkpoint: 0,
+        end_checkpoint: 100,
+    };
+    let result: Result<Subscription<SuiTransactionResponse>, _> = ws_client
+        .subscribe(
+            "sui_subscribeTransaction",
+            rpc_params![TransactionFilter::FromAddress(sender).and(checkpoint_range)],
+            "sui_unsubscribeTransaction",
+        )
+        .await;
+    assert!(result.is_err());
+
+    let mut sub: Subscription<SuiTransactionResponse> = ws_client
+        .subscribe(
+            "sui_subscribeTransaction",
+            rpc_params![TransactionFilter::FromAddress(sender)],
+            "sui_unsubscribeTransaction",
+        )
+        .await
+        .unwrap();
+
+    let (_, _, digest) = create_devnet_nft(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(response))) => assert_eq!(response.digest, digest),
+        other => panic!("Failed to get SuiTransactionResponse, but {:?}", other),
+    }
+    Ok(())
+}
+
 // Test fullnode has event read jsonrpc endpoints working
 #[sim_test]
 async fn test_full_node_event_read_api_ok() {
@@ -291,7 +291,7 @@ pub async fn transfer_sui(
         to: receiver,
         amount: None,
         sui_coin_object_id: gas_ref.0,
-        gas_budget: GAS_BUDGET,
+        gas_budget: Some(GAS_BUDGET),
     }
     .execute(context)
     .await?;
@@ -351,7 +351,7 @@ pub async fn transfer_coin(
         to: receiver,
         object_id: object_to_send,
         gas: None,
-        gas_budget: GAS_BUDGET,
+        gas_budget: Some(GAS_BUDGET),
     }
     .execute(context)
     .await?;
@@ -389,7 +389,7 @@ pub async fn split_coin_with_wallet_context(context: &mut WalletContext, coin_id
         amounts: None,
         count: Some(2),
         gas: None,
-        gas_budget: MAX_GAS,
+        gas_budget: Some(MAX_GAS),
     }
     .execute(context)
     .await
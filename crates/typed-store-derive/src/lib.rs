@@ -569,6 +569,22 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                 })
             }
 
+            /// Dump all key-value pairs in the page at the given table name, serialized as JSON
+            /// Tables must be opened in read only mode using `open_tables_read_only`
+            pub fn dump_json(&self, table_name: &str, page_size: u16,
+                page_number: usize) -> eyre::Result<Vec<typed_store::traits::DumpEntry>> {
+                match table_name {
+                    #(
+                        stringify!(#field_names) => {
+                            typed_store::traits::Map::try_catch_up_with_primary(&self.#field_names)?;
+                            self.#field_names.dump_json(page_size, page_number)
+                        }
+                    )*
+
+                    _ => eyre::bail!("No such table name: {}", table_name),
+                }
+            }
+
             /// Get key value sizes from the db
             /// Tables must be opened in read only mode using `open_tables_read_only`
             pub fn table_summary(&self, table_name: &str) -> eyre::Result<typed_store::traits::TableSummary> {
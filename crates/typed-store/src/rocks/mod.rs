@@ -8,7 +8,7 @@ pub(crate) mod values;
 
 use crate::{
     metrics::{DBMetrics, RocksDBPerfContext, SamplingInterval},
-    traits::{Map, TableSummary},
+    traits::{DumpEntry, Map, TableSummary},
 };
 use bincode::Options;
 use collectable::TryExtend;
@@ -922,6 +922,27 @@ impl<K, V> DBMap<K, V> {
     }
 }
 
+impl<K, V> DBMap<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// Serialize the key-value pairs in the page as JSON, unlike `dump` which formats them with
+    /// their Debug representation.
+    pub fn dump_json(&self, page_size: u16, page_number: usize) -> eyre::Result<Vec<DumpEntry>> {
+        self.iter()
+            .skip(page_number * page_size as usize)
+            .take(page_size as usize)
+            .map(|(key, value)| {
+                Ok(DumpEntry {
+                    key: serde_json::to_value(key)?,
+                    value: serde_json::to_value(value)?,
+                })
+            })
+            .collect()
+    }
+}
+
 /// Provides a mutable struct to form a collection of database write operations, and execute them.
 ///
 /// Batching write and delete operations is faster than performing them one by one and ensures their atomicity,
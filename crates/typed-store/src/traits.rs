@@ -140,6 +140,13 @@ where
     async fn try_catch_up_with_primary(&self) -> Result<(), Self::Error>;
 }
 
+/// A key-value pair of a table, with the serde representation of the key and value types.
+#[derive(Debug, Serialize)]
+pub struct DumpEntry {
+    pub key: serde_json::Value,
+    pub value: serde_json::Value,
+}
+
 pub struct TableSummary {
     pub num_keys: u64,
     pub key_bytes_total: usize,
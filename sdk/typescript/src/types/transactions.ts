@@ -238,12 +238,6 @@ export type TransactionEffects = Infer<typeof TransactionEffects>;
 export const TransactionEvents = array(SuiEvent);
 export type TransactionEvents = Infer<typeof TransactionEvents>;
 
-export const DryRunTransactionResponse = object({
-  effects: TransactionEffects,
-  events: TransactionEvents,
-});
-export type DryRunTransactionResponse = Infer<typeof DryRunTransactionResponse>;
-
 const ReturnValueType = tuple([array(number()), string()]);
 const MutableReferenceOutputType = tuple([
   SuiArgument,
@@ -376,6 +370,14 @@ export const BalanceChange = object({
   amount: string(),
 });
 
+export const DryRunTransactionResponse = object({
+  effects: TransactionEffects,
+  events: TransactionEvents,
+  objectChanges: optional(array(SuiObjectChange)),
+  balanceChanges: optional(array(BalanceChange)),
+});
+export type DryRunTransactionResponse = Infer<typeof DryRunTransactionResponse>;
+
 export const SuiTransactionResponse = object({
   digest: TransactionDigest,
   transaction: optional(SuiTransaction),
@@ -55,6 +55,20 @@ describe('Transaction Builders', () => {
     await validateTransaction(toolbox.signer, tx);
   });
 
+  it('Dry run SplitCoins + TransferObjects', async () => {
+    const tx = new Transaction();
+    const coin = tx.splitCoins(tx.gas, [tx.pure(10)]);
+    tx.transferObjects([coin], tx.pure(DEFAULT_RECIPIENT));
+    const result = await toolbox.signer.dryRunTransaction({ transaction: tx });
+    expect(result.effects.status.status).toEqual('success');
+    expect(
+      result.balanceChanges?.find((change) => change.amount === '10'),
+    ).toBeDefined();
+    expect(
+      result.objectChanges?.find((change) => change.type === 'created'),
+    ).toBeDefined();
+  });
+
   it('MergeCoins', async () => {
     const coins = await toolbox.getGasObjectsOwnedByAddress();
     const coin_0 = coins[0].details as SuiObjectData;